@@ -0,0 +1,93 @@
+//! Sparse-set vs archetype table storage iteration benchmark
+//!
+//! Run with `cargo bench -p rust_engine --bench ecs_storage`. Spawns the same
+//! entities into both storage backends, with a mix of optional components so
+//! the table backend has several archetypes, and times a position/velocity
+//! integration pass over them.
+
+use rust_engine::ecs::{Component, StorageType, World};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ENTITY_COUNTS: [usize; 3] = [10_000, 50_000, 100_000];
+const ITERATIONS: u32 = 100;
+
+macro_rules! bench_components {
+    ($position:ident, $velocity:ident, $health:ident, $storage:expr) => {
+        struct $position([f32; 3]);
+        impl Component for $position {
+            const STORAGE: StorageType = $storage;
+        }
+
+        struct $velocity([f32; 3]);
+        impl Component for $velocity {
+            const STORAGE: StorageType = $storage;
+        }
+
+        struct $health(#[allow(dead_code)] f32);
+        impl Component for $health {
+            const STORAGE: StorageType = $storage;
+        }
+    };
+}
+
+bench_components!(SparsePosition, SparseVelocity, SparseHealth, StorageType::SparseSet);
+bench_components!(TablePosition, TableVelocity, TableHealth, StorageType::Table);
+
+macro_rules! bench_storage {
+    ($name:literal, $count:expr, $position:ident, $velocity:ident, $health:ident) => {{
+        let mut world = World::new();
+        let mut churn = Vec::new();
+        for i in 0..$count {
+            let entity = world.create_entity();
+            world.add_component(entity, $position([i as f32, 0.0, 0.0]));
+            if i % 4 != 0 {
+                world.add_component(entity, $velocity([1.0, 0.5, 0.25]));
+            }
+            if i % 3 == 0 {
+                world.add_component(entity, $health(100.0));
+            }
+            if i % 5 == 0 {
+                churn.push(entity);
+            }
+        }
+        // Despawn and respawn some entities so both storages have seen churn
+        for entity in churn {
+            world.despawn(entity);
+            let entity = world.create_entity();
+            world.add_component(entity, $position([0.0; 3]));
+            world.add_component(entity, $velocity([1.0; 3]));
+        }
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            for (_, (mut position, velocity)) in world.query_mut::<(&mut $position, &$velocity)>() {
+                for axis in 0..3 {
+                    position.0[axis] += velocity.0[axis] * 0.016;
+                }
+            }
+        }
+        let elapsed = start.elapsed();
+
+        let sum: f32 = world.query::<&$position>().into_iter().map(|(_, position)| position.0[0]).sum();
+        black_box(sum);
+        report($name, $count, elapsed);
+        elapsed
+    }};
+}
+
+fn report(name: &str, count: usize, elapsed: Duration) {
+    let per_iteration = elapsed / ITERATIONS;
+    let per_entity = per_iteration.as_nanos() as f64 / count as f64;
+    println!("  {name:<8} {per_iteration:>12.3?}/iter  {per_entity:>7.2} ns/entity");
+}
+
+fn main() {
+    println!("Query (&mut Position, &Velocity), {ITERATIONS} iterations");
+    for count in ENTITY_COUNTS {
+        println!("{count} entities:");
+        let sparse = bench_storage!("sparse", count, SparsePosition, SparseVelocity, SparseHealth);
+        let table = bench_storage!("table", count, TablePosition, TableVelocity, TableHealth);
+        println!("  speedup  {:.2}x", sparse.as_secs_f64() / table.as_secs_f64());
+    }
+}
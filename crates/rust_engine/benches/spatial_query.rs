@@ -0,0 +1,175 @@
+//! Octree vs dynamic AABB tree vs hashed grid broad-phase benchmark
+//!
+//! Run with `cargo bench -p rust_engine --bench spatial_query`. Drives each
+//! `SpatialQuery` implementation through the same simulated frames of two
+//! workloads and times the calls the collision system makes:
+//!
+//! - fleet: squadrons of small ships flying in tight formations, plus a few
+//!   large capital ships
+//! - asteroid field: many spread-out rocks of varied size drifting slowly
+//!
+//! Each frame updates every body, queries its neighbours (the broad phase)
+//! and casts a batch of rays (weapons and picking).
+
+use rust_engine::foundation::math::Vec3;
+use rust_engine::scene::AABB;
+use rust_engine::spatial::{
+    AabbTreeConfig, DynamicAabbTree, HashGrid, HashGridConfig, Octree, OctreeConfig,
+    OctreeSpatialQuery, SpatialQuery,
+};
+use rust_engine::ecs::{Entity, World};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const FRAMES: u32 = 60;
+const RAYS_PER_FRAME: usize = 200;
+const DT: f32 = 1.0 / 60.0;
+
+/// Moving bounding sphere
+struct Body {
+    position: Vec3,
+    velocity: Vec3,
+    radius: f32,
+}
+
+struct Workload {
+    name: &'static str,
+    bodies: Vec<Body>,
+    /// Region the bodies stay in (octree world bounds)
+    bounds: AABB,
+    /// Grid cell size (about the diameter of the larger common bodies)
+    cell_size: f32,
+}
+
+/// Small deterministic generator so every structure sees the same scene
+struct Lcg(u32);
+
+impl Lcg {
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+        (self.0 >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next()
+    }
+
+    fn vector(&mut self, extent: f32) -> Vec3 {
+        Vec3::new(self.range(-extent, extent), self.range(-extent, extent), self.range(-extent, extent))
+    }
+}
+
+fn fleet() -> Workload {
+    let mut rng = Lcg(7);
+    let mut bodies = Vec::new();
+    for _ in 0..16 {
+        let center = rng.vector(1500.0);
+        let heading = rng.vector(1.0).try_normalize(1e-6).unwrap_or_else(Vec3::x) * 40.0;
+        for _ in 0..250 {
+            bodies.push(Body {
+                position: center + rng.vector(60.0),
+                velocity: heading + rng.vector(2.0),
+                radius: rng.range(2.0, 6.0),
+            });
+        }
+        bodies.push(Body { position: center, velocity: heading, radius: 80.0 });
+    }
+    Workload {
+        name: "fleet",
+        bodies,
+        bounds: AABB::from_center_extents(Vec3::zeros(), Vec3::new(2000.0, 2000.0, 2000.0)),
+        cell_size: 10.0,
+    }
+}
+
+fn asteroid_field() -> Workload {
+    let mut rng = Lcg(11);
+    let bodies = (0..20_000)
+        .map(|_| Body {
+            position: rng.vector(2000.0),
+            velocity: rng.vector(0.5),
+            radius: rng.range(1.0, 15.0),
+        })
+        .collect();
+    Workload {
+        name: "asteroids",
+        bodies,
+        bounds: AABB::from_center_extents(Vec3::zeros(), Vec3::new(2100.0, 2100.0, 2100.0)),
+        cell_size: 30.0,
+    }
+}
+
+#[derive(Default)]
+struct Timings {
+    insert: Duration,
+    update: Duration,
+    nearby: Duration,
+    rays: Duration,
+    pairs: usize,
+}
+
+fn run(workload: &Workload, spatial: &mut dyn SpatialQuery) -> Timings {
+    let mut timings = Timings::default();
+    let mut positions: Vec<Vec3> = workload.bodies.iter().map(|body| body.position).collect();
+    let mut world = World::new();
+    let entities: Vec<Entity> = positions.iter().map(|_| world.create_entity()).collect();
+
+    let start = Instant::now();
+    for (body, &entity) in workload.bodies.iter().zip(&entities) {
+        spatial.insert(entity, body.position, body.radius);
+    }
+    timings.insert = start.elapsed();
+
+    let mut rng = Lcg(3);
+    for _ in 0..FRAMES {
+        let start = Instant::now();
+        for ((body, position), &entity) in workload.bodies.iter().zip(&mut positions).zip(&entities) {
+            *position += body.velocity * DT;
+            spatial.update(entity, *position, body.radius);
+        }
+        timings.update += start.elapsed();
+
+        let start = Instant::now();
+        for &entity in &entities {
+            timings.pairs += spatial.query_nearby(entity).len() - 1;
+        }
+        timings.nearby += start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..RAYS_PER_FRAME {
+            let origin = positions[(rng.next() * positions.len() as f32) as usize % positions.len()];
+            let direction = rng.vector(1.0);
+            black_box(spatial.query_ray(origin, direction));
+        }
+        timings.rays += start.elapsed();
+    }
+    timings
+}
+
+fn report(name: &str, timings: &Timings) {
+    println!(
+        "  {name:<8} {:>10.3?} {:>12.3?} {:>12.3?} {:>12.3?}   {}",
+        timings.insert,
+        timings.update / FRAMES,
+        timings.nearby / FRAMES,
+        timings.rays / FRAMES,
+        timings.pairs / FRAMES as usize / 2,
+    );
+}
+
+fn main() {
+    println!("{FRAMES} frames, {RAYS_PER_FRAME} rays per frame (update/nearby/rays are per frame)");
+    for workload in [fleet(), asteroid_field()] {
+        println!("{} ({} bodies):", workload.name, workload.bodies.len());
+        println!("  {:<8} {:>10} {:>12} {:>12} {:>12}   pairs", "", "insert", "update", "nearby", "rays");
+
+        let mut octree = OctreeSpatialQuery::new(Octree::new(workload.bounds, OctreeConfig::default()));
+        report("octree", &run(&workload, &mut octree));
+
+        let mut tree = DynamicAabbTree::new(AabbTreeConfig { fat_margin: 1.0 });
+        report("aabbtree", &run(&workload, &mut tree));
+
+        let mut grid = HashGrid::new(HashGridConfig { cell_size: workload.cell_size, ..HashGridConfig::default() });
+        report("grid", &run(&workload, &mut grid));
+    }
+}
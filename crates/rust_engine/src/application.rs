@@ -22,6 +22,16 @@ pub trait Application {
     /// * `delta_time` - Time since last frame in seconds
     fn update(&mut self, engine: &mut Engine, delta_time: f32) -> Result<(), AppError>;
     
+    /// Advance the simulation by one fixed step
+    /// 
+    /// Called zero or more times per frame, after `update`, with a constant
+    /// `fixed_delta` (see `EngineConfig::time`), once the engine's `FixedUpdate`
+    /// systems (movement integration, collision and rigid bodies) have run for
+    /// the step. Put simulation logic here so it does not depend on the frame rate.
+    fn fixed_update(&mut self, _engine: &mut Engine, _fixed_delta: f32) -> Result<(), AppError> {
+        Ok(())
+    }
+    
     /// Render the application
     /// 
     /// Called after update. Implement your application-specific rendering workflow here.
@@ -141,6 +141,25 @@ impl CollisionDebugVisualizer {
                     0.0,
                 );
             }
+            CollisionShape::Capsule { half_height, radius } => {
+                // Spine along local Y (model space, at origin)
+                self.debug_draw.draw_capsule(
+                    Vec3::new(0.0, -half_height, 0.0),
+                    Vec3::new(0.0, *half_height, 0.0),
+                    *radius,
+                    color,
+                    0.0,
+                );
+            }
+            CollisionShape::Box { half_extents } => {
+                self.debug_draw.draw_box(Vec3::zeros(), *half_extents, color, 0.0);
+            }
+            CollisionShape::ConvexHull(template) => {
+                // Hull edges in model space
+                for (start, end) in template.edges() {
+                    self.debug_draw.draw_line(start, end, color, 0.0);
+                }
+            }
         }
     }
     
@@ -204,6 +204,21 @@ impl DebugDrawSystem {
         });
     }
     
+    /// Draw a capsule (temporary)
+    pub fn draw_capsule(&mut self, start: Vec3, end: Vec3, radius: f32, color: Vec4, duration: f32) {
+        if !self.enabled {
+            return;
+        }
+        
+        self.temporary_shapes.push(DebugShape::Capsule {
+            start,
+            end,
+            radius,
+            color,
+            duration,
+        });
+    }
+    
     /// Draw a point (temporary)
     pub fn draw_point(&mut self, position: Vec3, color: Vec4, size: f32, duration: f32) {
         if !self.enabled {
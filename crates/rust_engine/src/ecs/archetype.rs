@@ -0,0 +1,340 @@
+//! Archetype (table) component storage
+//!
+//! Components whose `Component::STORAGE` is `StorageType::Table` are grouped by
+//! archetype: the set of table components an entity has. Each archetype has one
+//! column per component type, and an entity sits in the same row of every column
+//! of its archetype, so iterating entities that share hot components (e.g.
+//! `TransformComponent` + `MovementComponent`) walks packed arrays in order.
+//!
+//! Columns are owned by the per-type `TableStorage<T>` rather than by the
+//! archetype, so queries and the scheduler still borrow each component type
+//! separately. Adding or removing a table component moves the entity's other
+//! table components to the columns of its new archetype.
+
+use super::Component;
+use super::world::{ComponentTicks, RowsMut};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// Index of an archetype in the world's `Archetypes` registry
+pub(crate) type ArchetypeId = usize;
+
+/// Registry of archetypes and the archetype each entity belongs to
+///
+/// Entities without table components belong to no archetype.
+#[derive(Default)]
+pub(crate) struct Archetypes {
+    types: Vec<Vec<TypeId>>,                   // Archetype -> sorted table component types
+    index: HashMap<Vec<TypeId>, ArchetypeId>,  // Sorted types -> archetype
+    entity_archetype: Vec<Option<ArchetypeId>>, // Entity ID -> archetype
+}
+
+impl Archetypes {
+    /// Number of archetypes created so far
+    pub(crate) fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Table component types of an archetype
+    pub(crate) fn types(&self, archetype: ArchetypeId) -> &[TypeId] {
+        &self.types[archetype]
+    }
+
+    /// Archetype of an entity, if it has any table components
+    pub(crate) fn archetype_of(&self, entity_id: u32) -> Option<ArchetypeId> {
+        self.entity_archetype.get(entity_id as usize).copied().flatten()
+    }
+
+    /// Table component types of an entity
+    pub(crate) fn types_of(&self, entity_id: u32) -> &[TypeId] {
+        self.archetype_of(entity_id).map_or(&[], |archetype| self.types(archetype))
+    }
+
+    /// Assign an entity to the archetype for `types`, creating it if needed
+    ///
+    /// Returns None (and clears the entity's archetype) when `types` is empty.
+    pub(crate) fn assign(&mut self, entity_id: u32, mut types: Vec<TypeId>) -> Option<ArchetypeId> {
+        types.sort();
+        types.dedup();
+        let archetype = if types.is_empty() {
+            None
+        } else if let Some(&archetype) = self.index.get(&types) {
+            Some(archetype)
+        } else {
+            let archetype = self.types.len();
+            self.types.push(types.clone());
+            self.index.insert(types, archetype);
+            Some(archetype)
+        };
+
+        let slot = entity_id as usize;
+        if slot >= self.entity_archetype.len() {
+            self.entity_archetype.resize(slot + 1, None);
+        }
+        self.entity_archetype[slot] = archetype;
+        archetype
+    }
+
+    /// Forget the archetype of a despawned entity
+    pub(crate) fn remove_entity(&mut self, entity_id: u32) {
+        if let Some(archetype) = self.entity_archetype.get_mut(entity_id as usize) {
+            *archetype = None;
+        }
+    }
+}
+
+/// Position of an entity's component in a table storage
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TableRow {
+    archetype: ArchetypeId,
+    row: usize,
+}
+
+/// One archetype's column of a table storage
+struct Column<T> {
+    components: Vec<T>,
+    entities: Vec<u32>,     // Row -> entity ID
+    generations: Vec<u64>,  // Generation counter per component (for dirty tracking)
+    ticks: Vec<ComponentTicks>,
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self {
+            components: Vec::new(),
+            entities: Vec::new(),
+            generations: Vec::new(),
+            ticks: Vec::new(),
+        }
+    }
+}
+
+/// Table storage for one component type: a column per archetype
+pub(crate) struct TableStorage<T: Component> {
+    columns: Vec<Column<T>>,            // Archetype -> column (empty if the archetype lacks T)
+    locations: Vec<Option<TableRow>>,   // Entity ID -> row
+    len: usize,
+}
+
+impl<T: Component> TableStorage<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            columns: Vec::new(),
+            locations: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn location(&self, entity_id: u32) -> Option<TableRow> {
+        self.locations.get(entity_id as usize).copied().flatten()
+    }
+
+    pub(crate) fn contains(&self, entity_id: u32) -> bool {
+        self.location(entity_id).is_some()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Add a component, or replace it in place if the entity already has one
+    ///
+    /// New components are appended to the column of `archetype`, which must be
+    /// the entity's archetype including `T`.
+    pub(crate) fn add(&mut self, entity_id: u32, component: T, tick: u64, archetype: Option<ArchetypeId>) {
+        if let Some(location) = self.location(entity_id) {
+            let column = &mut self.columns[location.archetype];
+            column.components[location.row] = component;
+            column.generations[location.row] = 1; // Reset generation
+            column.ticks[location.row].changed = tick;
+            return;
+        }
+        let archetype = archetype.expect("New table component needs the entity's archetype");
+        self.push(entity_id, archetype, component, 1, ComponentTicks { added: tick, changed: tick });
+    }
+
+    pub(crate) fn get(&self, entity_id: u32) -> Option<&T> {
+        let location = self.location(entity_id)?;
+        self.columns[location.archetype].components.get(location.row)
+    }
+
+    pub(crate) fn get_mut(&mut self, entity_id: u32, tick: u64) -> Option<&mut T> {
+        let location = self.location(entity_id)?;
+        let column = &mut self.columns[location.archetype];
+        column.generations[location.row] += 1;
+        column.ticks[location.row].changed = tick;
+        column.components.get_mut(location.row)
+    }
+
+    pub(crate) fn get_generation(&self, entity_id: u32) -> Option<u64> {
+        let location = self.location(entity_id)?;
+        Some(self.columns[location.archetype].generations[location.row])
+    }
+
+    pub(crate) fn ticks(&self, entity_id: u32) -> Option<ComponentTicks> {
+        let location = self.location(entity_id)?;
+        Some(self.columns[location.archetype].ticks[location.row])
+    }
+
+    pub(crate) fn remove(&mut self, entity_id: u32) -> Option<T> {
+        self.take(entity_id).map(|(component, _, _)| component)
+    }
+
+    /// Move an entity's component to the column of another archetype
+    pub(crate) fn move_to_archetype(&mut self, entity_id: u32, archetype: ArchetypeId) {
+        if self.location(entity_id).is_some_and(|location| location.archetype != archetype) {
+            let (component, generation, ticks) = self.take(entity_id).expect("location was just checked");
+            self.push(entity_id, archetype, component, generation, ticks);
+        }
+    }
+
+    /// Entity IDs in storage order: archetype by archetype, row by row
+    pub(crate) fn entity_ids(&self) -> Vec<u32> {
+        self.columns.iter().flat_map(|column| column.entities.iter().copied()).collect()
+    }
+
+    /// Mutable access to every component, walking each archetype's column in place
+    pub(crate) fn rows_mut(&mut self) -> TableRowsMut<'_, T> {
+        let columns = self.columns
+            .iter_mut()
+            .map(|column| RowsMut::new(&mut column.components, &mut column.ticks))
+            .collect();
+        TableRowsMut { locations: &self.locations, columns }
+    }
+
+    fn push(&mut self, entity_id: u32, archetype: ArchetypeId, component: T, generation: u64, ticks: ComponentTicks) {
+        if archetype >= self.columns.len() {
+            self.columns.resize_with(archetype + 1, Column::default);
+        }
+        let column = &mut self.columns[archetype];
+        let row = column.components.len();
+        column.components.push(component);
+        column.entities.push(entity_id);
+        column.generations.push(generation);
+        column.ticks.push(ticks);
+
+        let slot = entity_id as usize;
+        if slot >= self.locations.len() {
+            self.locations.resize(slot + 1, None);
+        }
+        self.locations[slot] = Some(TableRow { archetype, row });
+        self.len += 1;
+    }
+
+    /// Swap-remove an entity's row, fixing up the entity moved into its place
+    fn take(&mut self, entity_id: u32) -> Option<(T, u64, ComponentTicks)> {
+        let location = self.locations.get_mut(entity_id as usize)?.take()?;
+        let column = &mut self.columns[location.archetype];
+        let component = column.components.swap_remove(location.row);
+        column.entities.swap_remove(location.row);
+        let generation = column.generations.swap_remove(location.row);
+        let ticks = column.ticks.swap_remove(location.row);
+        if let Some(&moved_entity) = column.entities.get(location.row) {
+            self.locations[moved_entity as usize] = Some(location);
+        }
+        self.len -= 1;
+        Some((component, generation, ticks))
+    }
+}
+
+/// Rows of a table storage not yet handed out, returned by `TableStorage::rows_mut`
+pub(crate) struct TableRowsMut<'a, T> {
+    locations: &'a [Option<TableRow>],
+    columns: Vec<RowsMut<'a, T>>, // Archetype -> remaining rows of its column
+}
+
+impl<'a, T> TableRowsMut<'a, T> {
+    /// Hand out an entity's component, or None if absent or already taken
+    pub(crate) fn take(&mut self, entity_id: u32) -> Option<(&'a mut T, &'a mut ComponentTicks)> {
+        let location = self.locations.get(entity_id as usize).copied().flatten()?;
+        self.columns.get_mut(location.archetype)?.take(location.row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ecs::{Component, StorageType, World};
+
+    #[derive(Debug, PartialEq)]
+    struct Position(f32);
+    impl Component for Position {
+        const STORAGE: StorageType = StorageType::Table;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Velocity(f32);
+    impl Component for Velocity {
+        const STORAGE: StorageType = StorageType::Table;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Tag;
+    impl Component for Tag {}
+
+    #[test]
+    fn test_entities_move_between_archetypes() {
+        let mut world = World::new();
+        let a = world.create_entity();
+        let b = world.create_entity();
+        world.add_component(a, Position(1.0));
+        world.add_component(b, Position(2.0));
+        world.add_component(b, Velocity(20.0));
+        world.add_component(a, Tag); // sparse: no archetype change
+        assert_eq!(world.archetype_count(), 2);
+
+        // b moves from {Position} to {Position, Velocity}, a stays put
+        world.add_component(a, Velocity(10.0));
+        world.remove_component::<Velocity>(b);
+        assert_eq!(world.get_component::<Position>(a), Some(&Position(1.0)));
+        assert_eq!(world.get_component::<Velocity>(a), Some(&Velocity(10.0)));
+        assert_eq!(world.get_component::<Position>(b), Some(&Position(2.0)));
+        assert_eq!(world.get_component::<Velocity>(b), None);
+        assert_eq!(world.archetype_count(), 2);
+
+        let moving: Vec<_> = world.query::<(&Position, &Velocity)>().into_iter().map(|(e, _)| e).collect();
+        assert_eq!(moving, vec![a]);
+
+        // Despawning swap-removes the row; the remaining entity stays reachable
+        world.despawn(a);
+        world.add_component(b, Velocity(5.0));
+        for (_, (mut position, velocity)) in world.query_mut::<(&mut Position, &Velocity)>() {
+            position.0 += velocity.0;
+        }
+        assert_eq!(world.get_component::<Position>(b), Some(&Position(7.0)));
+    }
+
+    #[test]
+    fn test_table_components_keep_change_ticks_when_moved() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0.0));
+
+        let mut added = crate::ecs::Query::<&Position, crate::ecs::Added<Position>>::new();
+        assert_eq!(added.iter(&world).len(), 1);
+
+        // Moving to another archetype is not an addition
+        world.add_component(entity, Velocity(1.0));
+        assert!(added.iter(&world).is_empty());
+    }
+
+    #[test]
+    fn test_mutable_table_query_in_any_order() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..6).map(|i| {
+            let entity = world.create_entity();
+            world.add_component(entity, Position(i as f32));
+            entity
+        }).collect();
+        // The sparse Tag drives the query, visiting the table rows back to front
+        for &entity in entities.iter().rev().step_by(2) {
+            world.add_component(entity, Tag);
+        }
+
+        for (_, mut position) in world.query_filtered_mut::<&mut Position, crate::ecs::With<Tag>>() {
+            position.0 += 10.0;
+        }
+        let positions: Vec<f32> = entities.iter().map(|&e| world.get_component::<Position>(e).unwrap().0).collect();
+        assert_eq!(positions, vec![0.0, 11.0, 2.0, 13.0, 4.0, 15.0]);
+        assert_eq!(world.query_mut::<&mut Position>().len(), 6);
+    }
+}
@@ -0,0 +1,217 @@
+//! Command buffers for deferred structural changes
+//!
+//! Spawning, despawning and adding/removing components change the world's
+//! storages, so they cannot happen while a query is borrowing them. Systems
+//! record these operations in a `Commands` buffer instead; the buffer is applied
+//! at a sync point (the end of each scheduler phase), in recording order.
+//!
+//! ```ignore
+//! for (entity, lifecycle) in world.query_mut::<&mut LifecycleComponent>() {
+//!     if lifecycle.should_destroy() {
+//!         commands.despawn(entity);
+//!     }
+//! }
+//! commands.spawn().insert(TransformComponent::identity());
+//! commands.apply(&mut world);
+//! ```
+
+use super::{Component, Entity, Resource, World};
+
+/// Component insertion deferred until the target entity exists
+type InsertFn = Box<dyn FnOnce(&mut World, Entity) + Send>;
+
+/// A single recorded operation
+enum Command {
+    Spawn(Vec<InsertFn>),
+    Despawn(Entity),
+    Insert(Entity, InsertFn),
+    Remove(Entity, fn(&mut World, Entity)),
+    Custom(Box<dyn FnOnce(&mut World) + Send>),
+}
+
+/// Buffer of structural world changes, applied later in recording order
+#[derive(Default)]
+pub struct Commands {
+    commands: Vec<Command>,
+}
+
+impl Commands {
+    /// Create an empty command buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn a new entity; components are added with `insert` on the returned builder
+    pub fn spawn(&mut self) -> SpawnCommands<'_> {
+        self.commands.push(Command::Spawn(Vec::new()));
+        let Some(Command::Spawn(components)) = self.commands.last_mut() else {
+            unreachable!("spawn command was just pushed");
+        };
+        SpawnCommands { components }
+    }
+
+    /// Despawn an entity (ignored if it is already gone when applied)
+    pub fn despawn(&mut self, entity: Entity) {
+        self.commands.push(Command::Despawn(entity));
+    }
+
+    /// Add or replace a component on an existing entity
+    pub fn insert<T: Component>(&mut self, entity: Entity, component: T) {
+        self.commands.push(Command::Insert(entity, Self::insert_fn(component)));
+    }
+
+    /// Remove a component from an entity
+    pub fn remove<T: Component>(&mut self, entity: Entity) {
+        self.commands.push(Command::Remove(entity, |world, entity| {
+            world.remove_component::<T>(entity);
+        }));
+    }
+
+    /// Insert or replace a resource
+    pub fn insert_resource<R: Resource>(&mut self, resource: R) {
+        self.add(move |world| {
+            world.insert_resource(resource);
+        });
+    }
+
+    /// Remove a resource
+    pub fn remove_resource<R: Resource>(&mut self) {
+        self.add(|world| {
+            world.remove_resource::<R>();
+        });
+    }
+
+    /// Attach `child` to `parent` (logged and skipped if the link is invalid when applied)
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        self.add(move |world| {
+            if let Err(e) = world.set_parent(child, parent) {
+                log::warn!("Ignoring deferred set_parent: {}", e);
+            }
+        });
+    }
+
+    /// Despawn an entity and all of its descendants
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        self.add(move |world| {
+            world.despawn_recursive(entity);
+        });
+    }
+
+    /// Run arbitrary code with exclusive world access at the sync point
+    pub fn add(&mut self, command: impl FnOnce(&mut World) + Send + 'static) {
+        self.commands.push(Command::Custom(Box::new(command)));
+    }
+
+    /// Number of recorded commands
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Whether no commands are recorded
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Move all commands of another buffer to the end of this one
+    pub fn append(&mut self, other: &mut Commands) {
+        self.commands.append(&mut other.commands);
+    }
+
+    /// Apply all recorded commands to the world, leaving the buffer empty
+    pub fn apply(&mut self, world: &mut World) {
+        for command in self.commands.drain(..) {
+            match command {
+                Command::Spawn(components) => {
+                    let entity = world.create_entity();
+                    for insert in components {
+                        insert(world, entity);
+                    }
+                }
+                Command::Despawn(entity) => {
+                    world.despawn(entity);
+                }
+                Command::Insert(entity, insert) => {
+                    if world.is_alive(entity) {
+                        insert(world, entity);
+                    }
+                }
+                Command::Remove(entity, remove) => remove(world, entity),
+                Command::Custom(command) => command(world),
+            }
+        }
+    }
+
+    fn insert_fn<T: Component>(component: T) -> InsertFn {
+        Box::new(move |world, entity| world.add_component(entity, component))
+    }
+}
+
+/// Builder for the components of an entity spawned through `Commands`
+pub struct SpawnCommands<'a> {
+    components: &'a mut Vec<InsertFn>,
+}
+
+impl SpawnCommands<'_> {
+    /// Add a component to the entity being spawned
+    pub fn insert<T: Component>(&mut self, component: T) -> &mut Self {
+        self.components.push(Commands::insert_fn(component));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+    impl Component for Health {}
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+    impl Component for Name {}
+
+    #[test]
+    fn test_commands_apply_in_order() {
+        let mut world = World::new();
+        let doomed = world.create_entity();
+        let survivor = world.create_entity();
+        world.add_component(survivor, Health(10));
+
+        let mut commands = Commands::new();
+        for (entity, _) in world.query_mut::<&mut Health>() {
+            commands.insert(entity, Name("survivor"));
+        }
+        commands.despawn(doomed);
+        commands.spawn().insert(Health(5)).insert(Name("spawned"));
+        commands.remove::<Health>(survivor);
+        assert_eq!(commands.len(), 4);
+
+        commands.apply(&mut world);
+        assert!(commands.is_empty());
+        assert!(!world.is_alive(doomed));
+        assert_eq!(world.get_component::<Name>(survivor), Some(&Name("survivor")));
+        assert_eq!(world.get_component::<Health>(survivor), None);
+
+        let spawned: Vec<_> = world.query::<(&Health, &Name)>().into_iter().map(|(_, (h, n))| (h.0, n.0)).collect();
+        assert_eq!(spawned, vec![(5, "spawned")]);
+    }
+
+    #[test]
+    fn test_commands_on_despawned_entity_are_ignored() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+
+        let mut commands = Commands::new();
+        commands.despawn(entity);
+        commands.despawn(entity);
+        commands.insert(entity, Health(1));
+        commands.apply(&mut world);
+
+        // The recycled slot must not receive the stale insert
+        let recycled = world.create_entity();
+        assert_eq!(recycled.id(), entity.id());
+        assert_eq!(world.get_component::<Health>(recycled), None);
+        assert_eq!(world.entity_count(), 1);
+    }
+}
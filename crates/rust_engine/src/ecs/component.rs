@@ -1,7 +1,22 @@
 //! Component trait and implementations
 
+/// How a component type is stored in the `World`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageType {
+    /// Dense array indexed through a sparse entity map; cheap to add and remove
+    SparseSet,
+
+    /// Archetype tables; entities sharing the same table components are packed
+    /// together for cache-friendly iteration, at the cost of moving components
+    /// when the entity's set of table components changes
+    Table,
+}
+
 /// Marker trait for components
-pub trait Component: 'static + Send + Sync {}
+pub trait Component: 'static + Send + Sync {
+    /// Storage backend for this component type
+    const STORAGE: StorageType = StorageType::SparseSet;
+}
 
 // Implement Component for common types
 impl Component for crate::foundation::math::Transform {}
@@ -6,13 +6,14 @@
 
 use crate::physics::collision::CollisionShape;
 use crate::ecs::Entity;
+use serde::{Serialize, Deserialize};
 use std::collections::HashSet;
 
 /// Component that marks an entity as having collision detection enabled
 /// 
 /// Following GEA Section 16.2's component-based architecture, this component
 /// stores all configuration data for an entity's collision behavior.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ColliderComponent {
     /// The collision shape (sphere, mesh, etc.)
     pub shape: CollisionShape,
@@ -31,6 +32,11 @@ pub struct ColliderComponent {
     /// GEA 13.3.2: "Hierarchical bounding volumes" for broad-phase optimization
     pub bounding_radius: f32,
     
+    /// Sweep this collider between frames (continuous collision detection)
+    /// so fast movers such as projectiles cannot tunnel through thin targets
+    #[serde(default)]
+    pub ccd: bool,
+    
     /// Should this collider be visualized in debug mode?
     pub debug_draw: bool,
 }
@@ -46,6 +52,7 @@ impl ColliderComponent {
             mask: 0xFFFFFFFF,   // Default: collides with all layers
             is_trigger: false,
             bounding_radius,
+            ccd: false,
             debug_draw: false,
         }
     }
@@ -57,12 +64,29 @@ impl ColliderComponent {
         self
     }
     
+    /// Place the collider on a layer, colliding with every layer the
+    /// `CollisionMatrix` allows
+    pub fn with_layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self.mask = 0xFFFFFFFF;
+        self
+    }
+    
     /// Mark this as a trigger volume
+    ///
+    /// Overlaps produce `TriggerEntered`/`TriggerExited` events instead of
+    /// collision events and contact response.
     pub fn as_trigger(mut self) -> Self {
         self.is_trigger = true;
         self
     }
     
+    /// Enable continuous collision detection for fast-moving colliders
+    pub fn with_ccd(mut self, enabled: bool) -> Self {
+        self.ccd = enabled;
+        self
+    }
+    
     /// Enable debug visualization
     pub fn with_debug_draw(mut self, enabled: bool) -> Self {
         self.debug_draw = enabled;
@@ -75,7 +99,7 @@ impl ColliderComponent {
 /// Following GEA 13.3.10: "Collision event callbacks"
 /// This component is updated each frame by the collision system to reflect
 /// which entities are currently colliding with this entity.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct CollisionStateComponent {
     /// All entities we're currently colliding with
     pub colliding_with: HashSet<Entity>,
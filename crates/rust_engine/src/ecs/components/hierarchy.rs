@@ -0,0 +1,110 @@
+//! Parent/child hierarchy components
+//!
+//! `TransformComponent` is always local: relative to the entity's `Parent`, or
+//! to the world for root entities. The transform propagation system writes the
+//! resulting world-space transform of every hierarchy member to its
+//! `GlobalTransform`, following Game Engine Architecture scene graph transform
+//! concatenation.
+//!
+//! Use `World::set_parent` rather than adding `Parent`/`Children` by hand so both
+//! sides of the link stay consistent.
+
+use crate::ecs::{Component, Entity};
+use crate::ecs::components::TransformComponent;
+use crate::foundation::math::{Transform as MathTransform, Vec3, Mat4, Quat};
+use serde::{Serialize, Deserialize};
+
+/// Link from a child entity to its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Parent(pub(crate) Entity);
+
+impl Component for Parent {}
+
+impl Parent {
+    /// The parent entity
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Direct children of an entity, in attachment order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Children(pub(crate) Vec<Entity>);
+
+impl Component for Children {}
+
+impl Children {
+    /// Iterate over the direct children
+    pub fn iter(&self) -> std::slice::Iter<'_, Entity> {
+        self.0.iter()
+    }
+
+    /// Number of direct children
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the entity has no children
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `entity` is a direct child
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+}
+
+/// World-space transform of a hierarchy member
+///
+/// Written by the transform propagation system in `SystemPhase::PostUpdate`;
+/// treat it as read-only and move entities through their `TransformComponent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalTransform {
+    /// World space position
+    pub position: Vec3,
+
+    /// World space rotation
+    pub rotation: Quat,
+
+    /// World space scale factors
+    pub scale: Vec3,
+}
+
+impl Component for GlobalTransform {}
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self::from_math_transform(&MathTransform::identity())
+    }
+}
+
+impl GlobalTransform {
+    /// Create from foundation math Transform
+    pub fn from_math_transform(transform: &MathTransform) -> Self {
+        Self {
+            position: transform.position,
+            rotation: transform.rotation,
+            scale: transform.scale,
+        }
+    }
+
+    /// Convert to foundation math Transform for calculations
+    pub fn to_math_transform(&self) -> MathTransform {
+        MathTransform {
+            position: self.position,
+            rotation: self.rotation,
+            scale: self.scale,
+        }
+    }
+
+    /// Convert to a world-space `TransformComponent` (for code that consumes either)
+    pub fn to_transform_component(&self) -> TransformComponent {
+        TransformComponent::from_transform(self.position, self.rotation, self.scale)
+    }
+
+    /// Convert to transformation matrix (TRS order)
+    pub fn to_matrix(&self) -> Mat4 {
+        self.to_math_transform().to_matrix()
+    }
+}
@@ -0,0 +1,202 @@
+//! Joint component connecting two rigid bodies
+//!
+//! Based on Game Engine Architecture 3rd Edition, Section 13.4 (constraints):
+//! a joint removes degrees of freedom between two bodies, or between a body
+//! and the world. Joints live on their own entity and reference the bodies
+//! they connect, both of which need a `RigidBodyComponent` (use
+//! `RigidBodyComponent::fixed()` for immovable anchors).
+//!
+//! Anchors and axes are given in world space for the pose the bodies are in
+//! when the joint is created; the physics system converts them into each
+//! body's local frame on its first step.
+
+use crate::ecs::{Component, Entity, StorageType};
+use crate::foundation::math::{Quat, Vec3};
+use serde::{Serialize, Deserialize};
+
+/// Degrees of freedom removed by a joint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JointKind {
+    /// Locks relative position and rotation (welds)
+    Fixed,
+    /// Rotation about a single axis (doors, turret barrels)
+    Hinge,
+    /// Free rotation about a shared point (chains, ragdoll shoulders)
+    BallSocket,
+    /// Keeps the anchors within a distance range, optionally springy (tow cables)
+    Distance,
+    /// Translation along a single axis without rotation (pistons)
+    Slider,
+}
+
+/// Allowed range of a joint's free coordinate
+///
+/// Hinges limit the angle (radians), sliders the translation along the axis
+/// and distance joints the anchor separation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointLimits {
+    /// Lower bound
+    pub min: f32,
+    /// Upper bound
+    pub max: f32,
+}
+
+/// Drives a hinge or slider towards a target speed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointMotor {
+    /// Target speed (radians per second for hinges, units per second for sliders)
+    pub target_speed: f32,
+    /// Largest torque or force the motor may apply
+    pub max_force: f32,
+}
+
+/// Damped spring pulling a distance joint's anchors towards a rest length
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointSpring {
+    /// Separation at which the spring applies no force
+    pub rest_length: f32,
+    /// Force per unit of stretch
+    pub stiffness: f32,
+    /// Force per unit of stretching speed
+    pub damping: f32,
+}
+
+/// Joint anchors and axis in each body's local frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JointFrames {
+    /// Anchor relative to body a
+    pub anchor_a: Vec3,
+    /// Anchor relative to body b (world space without body b)
+    pub anchor_b: Vec3,
+    /// Joint axis in body a's frame
+    pub axis_a: Vec3,
+    /// Joint axis in body b's frame
+    pub axis_b: Vec3,
+    /// Rotation of body b relative to body a when the frames were captured
+    pub rest_rotation: Quat,
+    /// Anchor separation when the frames were captured
+    pub rest_length: f32,
+}
+
+/// Joint between `body_a` and `body_b` (or the world)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JointComponent {
+    /// Type of joint
+    pub kind: JointKind,
+
+    /// First connected body
+    pub body_a: Entity,
+
+    /// Second connected body; `None` attaches body a to the world
+    pub body_b: Option<Entity>,
+
+    /// World-space anchor on body a at creation
+    pub anchor_a: Vec3,
+
+    /// World-space anchor on body b at creation (same as `anchor_a` except for distance joints)
+    pub anchor_b: Vec3,
+
+    /// World-space hinge or slider axis at creation
+    pub axis: Vec3,
+
+    /// Range of the free coordinate
+    pub limits: Option<JointLimits>,
+
+    /// Hinge or slider motor
+    pub motor: Option<JointMotor>,
+
+    /// Distance joint spring
+    pub spring: Option<JointSpring>,
+
+    /// Let the connected bodies collide with each other
+    pub collide_connected: bool,
+
+    /// Local frames, captured by the physics system on its first step
+    #[serde(default)]
+    pub frames: Option<JointFrames>,
+}
+
+impl Component for JointComponent {
+    const STORAGE: StorageType = StorageType::Table;
+}
+
+impl JointComponent {
+    fn new(kind: JointKind, body_a: Entity, body_b: Option<Entity>, anchor: Vec3) -> Self {
+        Self {
+            kind,
+            body_a,
+            body_b,
+            anchor_a: anchor,
+            anchor_b: anchor,
+            axis: Vec3::y(),
+            limits: None,
+            motor: None,
+            spring: None,
+            collide_connected: false,
+            frames: None,
+        }
+    }
+
+    /// Weld two bodies together at their current relative pose
+    pub fn fixed(body_a: Entity, body_b: Option<Entity>, anchor: Vec3) -> Self {
+        Self::new(JointKind::Fixed, body_a, body_b, anchor)
+    }
+
+    /// Hinge two bodies about `axis` through `anchor`
+    pub fn hinge(body_a: Entity, body_b: Option<Entity>, anchor: Vec3, axis: Vec3) -> Self {
+        Self::new(JointKind::Hinge, body_a, body_b, anchor).with_axis(axis)
+    }
+
+    /// Connect two bodies at a shared pivot point
+    pub fn ball_socket(body_a: Entity, body_b: Option<Entity>, anchor: Vec3) -> Self {
+        Self::new(JointKind::BallSocket, body_a, body_b, anchor)
+    }
+
+    /// Keep two anchors at their current separation
+    ///
+    /// Use `with_limits` for a rope-like range and `with_spring` for a soft link.
+    pub fn distance(body_a: Entity, body_b: Option<Entity>, anchor_a: Vec3, anchor_b: Vec3) -> Self {
+        let mut joint = Self::new(JointKind::Distance, body_a, body_b, anchor_a);
+        joint.anchor_b = anchor_b;
+        joint
+    }
+
+    /// Let two bodies slide along `axis` without rotating relative to each other
+    pub fn slider(body_a: Entity, body_b: Option<Entity>, anchor: Vec3, axis: Vec3) -> Self {
+        Self::new(JointKind::Slider, body_a, body_b, anchor).with_axis(axis)
+    }
+
+    /// Set the world-space joint axis
+    pub fn with_axis(mut self, axis: Vec3) -> Self {
+        self.axis = axis.try_normalize(1e-6).unwrap_or_else(Vec3::y);
+        self
+    }
+
+    /// Limit the free coordinate to `[min, max]`
+    pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+        self.limits = Some(JointLimits { min: min.min(max), max: min.max(max) });
+        self
+    }
+
+    /// Drive the joint at `target_speed`, applying at most `max_force`
+    pub fn with_motor(mut self, target_speed: f32, max_force: f32) -> Self {
+        self.motor = Some(JointMotor { target_speed, max_force: max_force.max(0.0) });
+        self
+    }
+
+    /// Make a distance joint springy
+    pub fn with_spring(mut self, rest_length: f32, stiffness: f32, damping: f32) -> Self {
+        self.spring = Some(JointSpring {
+            rest_length: rest_length.max(0.0),
+            stiffness: stiffness.max(0.0),
+            damping: damping.max(0.0),
+        });
+        self
+    }
+
+    /// Allow or prevent contacts between the connected bodies
+    pub fn with_collide_connected(mut self, collide: bool) -> Self {
+        self.collide_connected = collide;
+        self
+    }
+}
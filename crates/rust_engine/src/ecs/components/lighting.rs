@@ -7,10 +7,11 @@
 
 use crate::foundation::math::Vec3;
 use crate::ecs::Component;
+use serde::{Serialize, Deserialize};
 
 /// Pure data component for lights - no logic, only data
 /// Following Game Engine Architecture principle: "Components should be pure data containers"
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LightComponent {
     /// The type of light (directional, point, or spot)
     pub light_type: LightType,
@@ -35,7 +36,7 @@ pub struct LightComponent {
 }
 
 /// Types of lights supported by the lighting system
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LightType {
     /// Directional light (like sunlight) with parallel rays
     Directional,
@@ -4,20 +4,26 @@
 
 pub mod lighting;
 pub mod transform;
+pub mod hierarchy;
 pub mod renderable;
 pub mod movement;
 pub mod lifecycle;
 pub mod pickable;
 pub mod selection;
 pub mod collision;
+pub mod rigid_body;
+pub mod joint;
 pub mod trail_emitter;
 
 pub use lighting::{LightComponent, LightType, LightFactory};
-pub use transform::{TransformComponent, TransformFactory};
+pub use transform::{TransformComponent, TransformFactory, PreviousTransform};
+pub use hierarchy::{Parent, Children, GlobalTransform};
 pub use renderable::{RenderableComponent, RenderableFactory};
 pub use movement::{MovementComponent, MovementFactory};
 pub use lifecycle::{LifecycleComponent, LifecycleFactory, EntityState};
 pub use pickable::PickableComponent;
 pub use selection::SelectionComponent;
 pub use collision::{ColliderComponent, CollisionStateComponent};
+pub use rigid_body::{RigidBodyComponent, RigidBodyType};
+pub use joint::{JointComponent, JointKind, JointLimits, JointMotor, JointSpring, JointFrames};
 pub use trail_emitter::{TrailEmitterComponent, TrailEmitterFactory, TrailSegment};
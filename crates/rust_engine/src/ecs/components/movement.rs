@@ -3,11 +3,12 @@
 //! This component provides velocity, acceleration, and rotational movement
 //! for dynamic objects in the scene.
 
-use crate::ecs::Component;
+use crate::ecs::{Component, StorageType};
 use crate::foundation::math::Vec3;
+use serde::{Serialize, Deserialize};
 
 /// Component for entities that can move
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MovementComponent {
     /// Linear velocity in units per second
     pub velocity: Vec3,
@@ -178,7 +179,9 @@ impl MovementComponent {
     }
 }
 
-impl Component for MovementComponent {}
+impl Component for MovementComponent {
+    const STORAGE: StorageType = StorageType::Table;
+}
 
 impl Default for MovementComponent {
     fn default() -> Self {
@@ -0,0 +1,322 @@
+//! Rigid body component for physically simulated entities
+//!
+//! Based on Game Engine Architecture 3rd Edition, Section 13.4 (rigid body
+//! dynamics): a body carries its mass properties, surface material and
+//! velocities, and the physics system integrates it and resolves its contacts.
+//!
+//! Rigid bodies are simulated in their `TransformComponent` and should be
+//! hierarchy roots. `MovementSystem` skips them, so a `MovementComponent` on a
+//! rigid body has no effect; set the body's velocity instead.
+//!
+//! Bodies that stay below the physics system's sleep thresholds fall asleep
+//! and are skipped until woken by a force, an impulse or an awake body in
+//! their contact island.
+
+use crate::ecs::{Component, StorageType};
+use crate::foundation::math::{Mat3, Quat, Vec3};
+use serde::{Serialize, Deserialize};
+
+/// How the physics system treats a body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RigidBodyType {
+    /// Never moves; infinite mass (level geometry, stations)
+    Static,
+    /// Moved by its velocity only; pushes dynamic bodies but is not pushed back
+    Kinematic,
+    /// Fully simulated: forces, gravity and contact impulses
+    Dynamic,
+}
+
+/// Rigid body mass properties, material and velocities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RigidBodyComponent {
+    /// Simulation mode
+    pub body_type: RigidBodyType,
+
+    /// Bounciness in [0, 1]; the larger value of a contact pair is used
+    pub restitution: f32,
+
+    /// Coulomb friction coefficient; a contact pair uses the geometric mean
+    pub friction: f32,
+
+    /// Linear velocity in units per second
+    pub linear_velocity: Vec3,
+
+    /// Angular velocity in radians per second (world space axis * rate)
+    pub angular_velocity: Vec3,
+
+    /// Fraction of linear velocity lost per second
+    pub linear_damping: f32,
+
+    /// Fraction of angular velocity lost per second
+    pub angular_damping: f32,
+
+    /// Multiplier for the physics system's gravity
+    pub gravity_scale: f32,
+
+    /// Ignore angular response to contacts (e.g. self-steering ships)
+    pub lock_rotation: bool,
+
+    /// Let the physics system put the body to sleep once it comes to rest
+    #[serde(default = "default_can_sleep")]
+    pub can_sleep: bool,
+
+    #[serde(default)]
+    sleeping: bool,
+    #[serde(skip)]
+    sleep_timer: f32,
+
+    mass: f32,
+    inverse_mass: f32,
+    inertia_tensor: Mat3,            // Body space
+    inverse_inertia_tensor: Mat3,    // Body space
+
+    #[serde(skip)]
+    force: Vec3,
+    #[serde(skip)]
+    torque: Vec3,
+}
+
+impl Component for RigidBodyComponent {
+    const STORAGE: StorageType = StorageType::Table;
+}
+
+impl RigidBodyComponent {
+    fn new(body_type: RigidBodyType, mass: f32) -> Self {
+        let mut body = Self {
+            body_type,
+            restitution: 0.5,
+            friction: 0.5,
+            linear_velocity: Vec3::zeros(),
+            angular_velocity: Vec3::zeros(),
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            gravity_scale: 1.0,
+            lock_rotation: false,
+            can_sleep: true,
+            sleeping: false,
+            sleep_timer: 0.0,
+            mass: 0.0,
+            inverse_mass: 0.0,
+            inertia_tensor: Mat3::zeros(),
+            inverse_inertia_tensor: Mat3::zeros(),
+            force: Vec3::zeros(),
+            torque: Vec3::zeros(),
+        };
+        body.set_mass(mass);
+        body
+    }
+
+    /// Create a dynamic body with the inertia of a solid unit sphere of `mass`
+    pub fn dynamic(mass: f32) -> Self {
+        Self::new(RigidBodyType::Dynamic, mass).with_sphere_inertia(1.0)
+    }
+
+    /// Create a kinematic body (moved by its velocity, unaffected by contacts)
+    pub fn kinematic() -> Self {
+        Self::new(RigidBodyType::Kinematic, 0.0)
+    }
+
+    /// Create a static body
+    pub fn fixed() -> Self {
+        Self::new(RigidBodyType::Static, 0.0)
+    }
+
+    /// Set restitution (bounciness)
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set friction coefficient
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction.max(0.0);
+        self
+    }
+
+    /// Set initial linear velocity
+    pub fn with_velocity(mut self, velocity: Vec3) -> Self {
+        self.linear_velocity = velocity;
+        self
+    }
+
+    /// Set initial angular velocity
+    pub fn with_angular_velocity(mut self, angular_velocity: Vec3) -> Self {
+        self.angular_velocity = angular_velocity;
+        self
+    }
+
+    /// Set linear and angular damping
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear.max(0.0);
+        self.angular_damping = angular.max(0.0);
+        self
+    }
+
+    /// Set gravity multiplier
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Disable angular response to contacts and torques
+    pub fn with_locked_rotation(mut self) -> Self {
+        self.lock_rotation = true;
+        self
+    }
+
+    /// Allow or prevent sleeping (e.g. for player-controlled bodies)
+    pub fn with_can_sleep(mut self, can_sleep: bool) -> Self {
+        self.can_sleep = can_sleep;
+        if !can_sleep {
+            self.wake_up();
+        }
+        self
+    }
+
+    /// Use the inertia tensor of a solid sphere (I = 2/5 m r^2)
+    pub fn with_sphere_inertia(self, radius: f32) -> Self {
+        let moment = 0.4 * self.mass * radius * radius;
+        self.with_inertia_tensor(Mat3::from_diagonal_element(moment))
+    }
+
+    /// Use the inertia tensor of a solid box with the given half extents
+    pub fn with_box_inertia(self, half_extents: Vec3) -> Self {
+        let size = half_extents * 2.0;
+        let (x2, y2, z2) = (size.x * size.x, size.y * size.y, size.z * size.z);
+        let k = self.mass / 12.0;
+        let diagonal = Vec3::new(k * (y2 + z2), k * (x2 + z2), k * (x2 + y2));
+        self.with_inertia_tensor(Mat3::from_diagonal(&diagonal))
+    }
+
+    /// Use an explicit body-space inertia tensor
+    pub fn with_inertia_tensor(mut self, inertia_tensor: Mat3) -> Self {
+        self.inertia_tensor = inertia_tensor;
+        self.inverse_inertia_tensor = inertia_tensor.try_inverse().unwrap_or_else(Mat3::zeros);
+        self
+    }
+
+    /// Mass (0 for static and kinematic bodies)
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    /// Set the mass, scaling the inertia tensor to match
+    pub fn set_mass(&mut self, mass: f32) {
+        let mass = mass.max(0.0);
+        if self.mass > 0.0 {
+            let scale = mass / self.mass;
+            self.inertia_tensor *= scale;
+            self.inverse_inertia_tensor = self.inertia_tensor.try_inverse().unwrap_or_else(Mat3::zeros);
+        }
+        self.mass = mass;
+        self.inverse_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+    }
+
+    /// Body-space inertia tensor
+    pub fn inertia_tensor(&self) -> Mat3 {
+        self.inertia_tensor
+    }
+
+    /// Whether contacts and forces move this body
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == RigidBodyType::Dynamic
+    }
+
+    /// Inverse mass used by the solver (0 unless dynamic)
+    pub fn inverse_mass(&self) -> f32 {
+        if self.is_dynamic() { self.inverse_mass } else { 0.0 }
+    }
+
+    /// World-space inverse inertia tensor for the given orientation (0 unless dynamic)
+    pub fn world_inverse_inertia(&self, rotation: &Quat) -> Mat3 {
+        if !self.is_dynamic() || self.lock_rotation {
+            return Mat3::zeros();
+        }
+        let rotation = rotation.to_rotation_matrix();
+        rotation.matrix() * self.inverse_inertia_tensor * rotation.matrix().transpose()
+    }
+
+    /// Whether the body is asleep (skipped by the solver and collision system)
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Wake the body; its contact island wakes with it on the next step
+    pub fn wake_up(&mut self) {
+        self.sleeping = false;
+        self.sleep_timer = 0.0;
+    }
+
+    /// Time the body has been resting below the sleep thresholds
+    pub(crate) fn sleep_timer(&self) -> f32 {
+        self.sleep_timer
+    }
+
+    /// Store the sleep state computed by the physics step
+    pub(crate) fn set_sleep_state(&mut self, sleeping: bool, sleep_timer: f32) {
+        self.sleeping = sleeping;
+        self.sleep_timer = sleep_timer;
+    }
+
+    /// Apply a force through the center of mass for the next physics step
+    pub fn apply_force(&mut self, force: Vec3) {
+        self.force += force;
+        self.wake_up();
+    }
+
+    /// Apply a force at a world-space offset from the center of mass
+    pub fn apply_force_at(&mut self, force: Vec3, offset: Vec3) {
+        self.force += force;
+        self.torque += offset.cross(&force);
+        self.wake_up();
+    }
+
+    /// Apply a torque for the next physics step
+    pub fn apply_torque(&mut self, torque: Vec3) {
+        self.torque += torque;
+        self.wake_up();
+    }
+
+    /// Apply an instantaneous impulse through the center of mass
+    pub fn apply_impulse(&mut self, impulse: Vec3) {
+        self.linear_velocity += impulse * self.inverse_mass();
+        self.wake_up();
+    }
+
+    /// Accumulated force and torque since the last step
+    pub(crate) fn accumulated_force(&self) -> (Vec3, Vec3) {
+        (self.force, self.torque)
+    }
+
+    /// Clear accumulated force and torque (after a physics step)
+    pub(crate) fn clear_forces(&mut self) {
+        self.force = Vec3::zeros();
+        self.torque = Vec3::zeros();
+    }
+}
+
+fn default_can_sleep() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_mass_depends_on_body_type() {
+        let mut body = RigidBodyComponent::dynamic(4.0).with_sphere_inertia(0.5);
+        assert_eq!(body.inverse_mass(), 0.25);
+        assert!((body.inertia_tensor()[(0, 0)] - 0.4).abs() < 1e-6);
+
+        // Scaling the mass scales the inertia
+        body.set_mass(8.0);
+        assert!((body.inertia_tensor()[(1, 1)] - 0.8).abs() < 1e-6);
+
+        body.body_type = RigidBodyType::Kinematic;
+        assert_eq!(body.inverse_mass(), 0.0);
+        assert_eq!(body.world_inverse_inertia(&Quat::identity()), Mat3::zeros());
+        assert_eq!(RigidBodyComponent::fixed().inverse_mass(), 0.0);
+    }
+}
@@ -6,14 +6,15 @@
 //! - Coordinate system consistency following Johannes Unterguggenberger guide
 
 use crate::foundation::math::{Transform as MathTransform, Vec3, Mat4, Quat};
-use crate::ecs::Component;
+use crate::ecs::{Component, StorageType};
+use serde::{Serialize, Deserialize};
 
 /// ECS Transform component
 /// 
 /// Pure data component representing spatial transformation in world space.
 /// All coordinates follow Y-up right-handed conventions consistent with 
 /// Johannes Unterguggenberger's academic standards.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransformComponent {
     /// World space position (Y-up right-handed)
     pub position: Vec3,
@@ -25,7 +26,9 @@ pub struct TransformComponent {
     pub scale: Vec3,
 }
 
-impl Component for TransformComponent {}
+impl Component for TransformComponent {
+    const STORAGE: StorageType = StorageType::Table;
+}
 
 impl Default for TransformComponent {
     fn default() -> Self {
@@ -133,6 +136,40 @@ impl TransformComponent {
         self.scale = scale;
         self
     }
+    
+    /// Blend from this transform towards `target` (alpha 0.0 = self, 1.0 = target)
+    /// 
+    /// Position and scale are interpolated linearly, rotation spherically.
+    pub fn interpolate(&self, target: &Self, alpha: f32) -> Self {
+        let rotation = self.rotation
+            .try_slerp(&target.rotation, alpha, 1e-6)
+            .unwrap_or(target.rotation);
+        Self {
+            position: self.position.lerp(&target.position, alpha),
+            rotation,
+            scale: self.scale.lerp(&target.scale, alpha),
+        }
+    }
+}
+
+/// Transform at the start of the latest fixed simulation step
+/// 
+/// Entities carrying this component are rendered between their previous and
+/// current `TransformComponent`, blended by `FixedTimestep::alpha`, so motion
+/// stays smooth when the frame rate differs from the simulation rate. The
+/// `TransformHistorySystem` refreshes it before every fixed step.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PreviousTransform(pub TransformComponent);
+
+impl Component for PreviousTransform {
+    const STORAGE: StorageType = StorageType::Table;
+}
+
+impl PreviousTransform {
+    /// Interpolated transform for rendering between the previous and `current` state
+    pub fn interpolate(&self, current: &TransformComponent, alpha: f32) -> TransformComponent {
+        self.0.interpolate(current, alpha)
+    }
 }
 
 /// Transform factory for creating common transform configurations
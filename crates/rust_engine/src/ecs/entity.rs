@@ -1,19 +1,31 @@
 //! Entity implementation
 
+use serde::{Serialize, Deserialize};
+
 /// Entity identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// An entity is a slot index plus a generation. When an entity is despawned its
+/// slot is recycled with an incremented generation, so stale handles to the old
+/// entity never alias the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Entity {
     id: u32,
+    generation: u32,
 }
 
 impl Entity {
-    /// Create a new entity with the given ID
-    pub(super) fn new(id: u32) -> Self {
-        Self { id }
+    /// Create a new entity with the given ID and generation
+    pub(crate) fn new(id: u32, generation: u32) -> Self {
+        Self { id, generation }
     }
-    
-    /// Get the entity ID
+
+    /// Get the entity ID (slot index, reused after despawn)
     pub fn id(&self) -> u32 {
         self.id
     }
+
+    /// Get the entity generation (incremented each time the slot is recycled)
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
 }
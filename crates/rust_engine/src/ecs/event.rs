@@ -0,0 +1,184 @@
+//! Double-buffered event queues with per-reader cursors
+//!
+//! An `Events<E>` resource collects events of one type. Each consumer keeps its
+//! own `EventReader<E>`, so several systems can read the same events
+//! independently without draining them for each other.
+//!
+//! The queue keeps two buffers: `update()` (called once per frame by whoever
+//! owns the queue) moves the current events into the previous buffer and drops
+//! the older ones. Readers therefore see every event as long as they read at
+//! least once every two updates.
+//!
+//! ```ignore
+//! let mut reader = EventReader::<CollisionStarted>::new();
+//! if let Some(events) = world.resource::<Events<CollisionStarted>>() {
+//!     for started in reader.read(events) {
+//!         log::info!("{:?} hit {:?}", started.entity_a, started.entity_b);
+//!     }
+//! }
+//! ```
+
+use super::Resource;
+use std::marker::PhantomData;
+
+/// Double-buffered queue of events of type `E`
+#[derive(Debug)]
+pub struct Events<E> {
+    previous: Vec<E>,
+    current: Vec<E>,
+    /// Sequence number of the first event in `previous`
+    previous_start: usize,
+    /// Sequence number of the first event in `current`
+    current_start: usize,
+}
+
+impl<E: Send + Sync + 'static> Resource for Events<E> {}
+
+impl<E> Default for Events<E> {
+    fn default() -> Self {
+        Self { previous: Vec::new(), current: Vec::new(), previous_start: 0, current_start: 0 }
+    }
+}
+
+impl<E> Events<E> {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue an event
+    pub fn send(&mut self, event: E) {
+        self.current.push(event);
+    }
+
+    /// Queue several events
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = E>) {
+        self.current.extend(events);
+    }
+
+    /// Swap buffers: events older than the previous update are dropped
+    pub fn update(&mut self) {
+        self.previous_start = self.current_start;
+        self.current_start += self.current.len();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Drop all queued events (readers skip past them)
+    pub fn clear(&mut self) {
+        self.update();
+        self.update();
+    }
+
+    /// Events still held by the queue
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Check whether the queue holds no events
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sequence number the next sent event will get
+    fn end(&self) -> usize {
+        self.current_start + self.current.len()
+    }
+}
+
+/// Cursor into an `Events<E>` queue
+///
+/// Each reader remembers the last event it saw; a new reader starts at the
+/// oldest event still held by the queue.
+#[derive(Debug)]
+pub struct EventReader<E> {
+    next: usize,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> Default for EventReader<E> {
+    fn default() -> Self {
+        Self { next: 0, _marker: PhantomData }
+    }
+}
+
+impl<E> Clone for EventReader<E> {
+    fn clone(&self) -> Self {
+        Self { next: self.next, _marker: PhantomData }
+    }
+}
+
+impl<E> EventReader<E> {
+    /// Create a reader that will see every event still held by the queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events sent since this reader last read, oldest first
+    pub fn read<'a>(&mut self, events: &'a Events<E>) -> impl Iterator<Item = &'a E> + 'a {
+        let start = self.next.max(events.previous_start);
+        self.next = events.end();
+
+        let previous = events.previous.get(start - events.previous_start..).unwrap_or_default();
+        let current = &events.current[start.saturating_sub(events.current_start).min(events.current.len())..];
+        previous.iter().chain(current)
+    }
+
+    /// Number of unread events still held by the queue
+    pub fn len(&self, events: &Events<E>) -> usize {
+        events.end() - self.next.max(events.previous_start)
+    }
+
+    /// Check whether there are no unread events
+    pub fn is_empty(&self, events: &Events<E>) -> bool {
+        self.len(events) == 0
+    }
+
+    /// Skip all events currently in the queue
+    pub fn clear(&mut self, events: &Events<E>) {
+        self.next = events.end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readers_consume_independently() {
+        let mut events = Events::new();
+        let mut first = EventReader::new();
+        let mut second = EventReader::new();
+
+        events.send(1);
+        events.send(2);
+        assert_eq!(first.read(&events).copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(first.is_empty(&events));
+
+        events.update();
+        events.send(3);
+        assert_eq!(first.read(&events).copied().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(second.len(&events), 3);
+        assert_eq!(second.read(&events).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(first.read(&events).count(), 0);
+    }
+
+    #[test]
+    fn test_events_expire_after_two_updates() {
+        let mut events = Events::new();
+        let mut reader = EventReader::new();
+
+        events.send("old");
+        events.update();
+        events.send("recent");
+        events.update();
+
+        // "old" was dropped before the reader got to it
+        assert_eq!(events.len(), 1);
+        assert_eq!(reader.read(&events).copied().collect::<Vec<_>>(), vec!["recent"]);
+
+        events.send("new");
+        events.clear();
+        assert!(events.is_empty());
+        assert_eq!(reader.read(&events).count(), 0);
+    }
+}
@@ -0,0 +1,390 @@
+//! Parent/child hierarchy operations and transform propagation
+//!
+//! Links are stored on both sides (`Parent` on the child, `Children` on the
+//! parent) and maintained by the `World` methods in this module. World-space
+//! transforms are propagated from the roots down, so a child always composes
+//! its local `TransformComponent` with its parent's up-to-date world transform.
+
+use super::components::{Children, GlobalTransform, Parent, PreviousTransform, TransformComponent};
+use super::{Entity, Without, World};
+use crate::foundation::math::Transform as MathTransform;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Hierarchy errors
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// Parent or child handle is stale
+    #[error("Entity {0:?} has been despawned")]
+    DespawnedEntity(Entity),
+
+    /// The link would make an entity its own ancestor
+    #[error("Parenting {child:?} to {parent:?} would create a cycle")]
+    Cycle {
+        /// Entity being attached
+        child: Entity,
+        /// Requested parent
+        parent: Entity,
+    },
+}
+
+impl World {
+    /// Attach `child` to `parent`, detaching it from its previous parent
+    ///
+    /// The child's `TransformComponent` is interpreted relative to the parent
+    /// from now on; it is not adjusted to keep the current world position.
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) -> Result<(), HierarchyError> {
+        for entity in [child, parent] {
+            if !self.is_alive(entity) {
+                return Err(HierarchyError::DespawnedEntity(entity));
+            }
+        }
+        if self.parent_of(child) == Some(parent) {
+            return Ok(());
+        }
+        if child == parent || self.ancestors(parent).contains(&child) {
+            return Err(HierarchyError::Cycle { child, parent });
+        }
+
+        self.detach_from_parent(child);
+        self.add_component(child, Parent(parent));
+        match self.get_component_mut::<Children>(parent) {
+            Some(children) => children.0.push(child),
+            None => self.add_component(parent, Children(vec![child])),
+        }
+        Ok(())
+    }
+
+    /// Detach an entity from its parent, making it a root
+    ///
+    /// Returns the previous parent, if it is still alive.
+    pub fn remove_parent(&mut self, child: Entity) -> Option<Entity> {
+        let parent = self.detach_from_parent(child);
+        self.remove_component::<Parent>(child)?;
+        // A detached leaf is no longer a hierarchy member; drop its stale world transform
+        if self.get_component::<Children>(child).is_none() {
+            self.remove_component::<GlobalTransform>(child);
+        }
+        parent
+    }
+
+    /// The entity's parent, if it has a live one
+    pub fn parent_of(&self, entity: Entity) -> Option<Entity> {
+        self.get_component::<Parent>(entity)
+            .map(Parent::get)
+            .filter(|&parent| self.is_alive(parent))
+    }
+
+    /// Despawn an entity together with all of its descendants
+    ///
+    /// Returns false if the entity was already despawned.
+    pub fn despawn_recursive(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        self.detach_from_parent(entity);
+
+        let mut stack = vec![entity];
+        let mut visited = HashSet::new();
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(children) = self.get_component::<Children>(current) {
+                stack.extend(children.iter().copied());
+            }
+            self.despawn(current);
+        }
+        true
+    }
+
+    /// World-space transform of an entity
+    ///
+    /// Roots return their local transform. Children return the `GlobalTransform`
+    /// written by the last propagation, or compose the parent chain if the child
+    /// has not been propagated yet.
+    pub fn world_transform(&self, entity: Entity) -> Option<TransformComponent> {
+        let local = self.get_component::<TransformComponent>(entity)?;
+        if self.parent_of(entity).is_none() {
+            return Some(local.clone());
+        }
+        if let Some(global) = self.get_component::<GlobalTransform>(entity) {
+            return Some(global.to_transform_component());
+        }
+        let world = self.ancestors(entity)
+            .iter()
+            .rev()
+            .filter_map(|&ancestor| self.get_component::<TransformComponent>(ancestor))
+            .fold(MathTransform::identity(), |acc, transform| acc.combine(&transform.to_math_transform()))
+            .combine(&local.to_math_transform());
+        Some(TransformComponent::from_math_transform(&world))
+    }
+
+    /// World-space transform blended from the previous fixed step (for rendering)
+    ///
+    /// Every level of the chain interpolates its local transform from its
+    /// `PreviousTransform` (levels without one use their current transform) and
+    /// the results are composed from the root down, so children follow their
+    /// interpolated parents. Returns `None` if no level has a `PreviousTransform`.
+    pub fn interpolated_world_transform(&self, entity: Entity, alpha: f32) -> Option<TransformComponent> {
+        let mut chain = self.ancestors(entity);
+        chain.reverse();
+        chain.push(entity);
+
+        let mut interpolated = false;
+        let mut world = MathTransform::identity();
+        for level in chain {
+            let Some(local) = self.get_component::<TransformComponent>(level) else {
+                continue;
+            };
+            let previous = self.get_component::<PreviousTransform>(level);
+            interpolated |= previous.is_some();
+            let local = previous.map_or_else(|| local.clone(), |previous| previous.interpolate(local, alpha));
+            world = world.combine(&local.to_math_transform());
+        }
+        interpolated.then(|| TransformComponent::from_math_transform(&world))
+    }
+
+    /// Recompute `GlobalTransform` for every entity in a hierarchy
+    ///
+    /// The engine runs this as `TransformPropagationSystem` in
+    /// `SystemPhase::PostUpdate`; applications driving a `World` directly call it
+    /// once per frame after moving entities. Only transforms that actually
+    /// changed are written, so `Changed<GlobalTransform>` stays meaningful.
+    pub fn propagate_transforms(&mut self) {
+        let roots = hierarchy_roots(
+            self.query::<(&Children, Option<&Parent>)>().into_iter().map(|(entity, (_, parent))| (entity, parent.map(Parent::get))),
+            self.query_filtered::<&Parent, Without<Children>>().into_iter().map(|(entity, parent)| (entity, Some(parent.get()))),
+            |entity| self.is_alive(entity),
+        );
+        let updates = compute_global_transforms(
+            roots,
+            |entity| self.is_alive(entity),
+            |entity| self.get_component::<TransformComponent>(entity).map(TransformComponent::to_math_transform),
+            |entity| self.get_component::<Children>(entity),
+        );
+
+        for (entity, global) in updates {
+            match self.get_component::<GlobalTransform>(entity).map(|current| *current == global) {
+                Some(true) => {}
+                Some(false) => {
+                    if let Some(current) = self.get_component_mut::<GlobalTransform>(entity) {
+                        *current = global;
+                    }
+                }
+                None => self.add_component(entity, global),
+            }
+        }
+    }
+
+    /// Remove `child` from its parent's `Children`, returning the parent
+    fn detach_from_parent(&mut self, child: Entity) -> Option<Entity> {
+        let parent = self.parent_of(child)?;
+        let now_empty = match self.get_component_mut::<Children>(parent) {
+            Some(children) => {
+                children.0.retain(|&entity| entity != child);
+                children.is_empty()
+            }
+            None => false,
+        };
+        if now_empty {
+            self.remove_component::<Children>(parent);
+        }
+        Some(parent)
+    }
+
+    /// Live ancestors of an entity, nearest first (stops at a cycle)
+    fn ancestors(&self, entity: Entity) -> Vec<Entity> {
+        let mut ancestors = Vec::new();
+        let mut current = entity;
+        while let Some(parent) = self.parent_of(current) {
+            if parent == entity || ancestors.contains(&parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+}
+
+/// Roots of all hierarchies: entities with children and no live parent, plus
+/// leaves whose parent has been despawned
+pub(crate) fn hierarchy_roots(
+    with_children: impl Iterator<Item = (Entity, Option<Entity>)>,
+    leaves: impl Iterator<Item = (Entity, Option<Entity>)>,
+    is_alive: impl Fn(Entity) -> bool,
+) -> Vec<Entity> {
+    with_children
+        .chain(leaves)
+        .filter(|&(_, parent)| !parent.is_some_and(&is_alive))
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// Walk each hierarchy depth-first, composing local transforms into world space
+///
+/// Entities without a `TransformComponent` act as identity (pure grouping
+/// nodes). A visited set guards against cycles created by editing `Parent`
+/// directly.
+pub(crate) fn compute_global_transforms<'w>(
+    roots: Vec<Entity>,
+    is_alive: impl Fn(Entity) -> bool,
+    local: impl Fn(Entity) -> Option<MathTransform>,
+    children: impl Fn(Entity) -> Option<&'w Children>,
+) -> Vec<(Entity, GlobalTransform)> {
+    let mut updates = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(Entity, Option<MathTransform>)> = roots.into_iter().map(|root| (root, None)).collect();
+
+    while let Some((entity, parent_global)) = stack.pop() {
+        if !is_alive(entity) || !visited.insert(entity) {
+            continue;
+        }
+        let local = local(entity).unwrap_or_default();
+        let global = match parent_global {
+            Some(parent) => parent.combine(&local),
+            None => local,
+        };
+        if let Some(children) = children(entity) {
+            stack.extend(children.iter().map(|&child| (child, Some(global.clone()))));
+        }
+        updates.push((entity, GlobalTransform::from_math_transform(&global)));
+    }
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::math::{Quat, Vec3};
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).magnitude() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_propagation_composes_parent_chain() {
+        let mut world = World::new();
+        let root = world.create_entity();
+        let middle = world.create_entity();
+        let leaf = world.create_entity();
+        world.add_component(root, TransformComponent::from_position_rotation(
+            Vec3::new(10.0, 0.0, 0.0),
+            Quat::from_euler_angles(0.0, std::f32::consts::FRAC_PI_2, 0.0),
+        ));
+        world.add_component(middle, TransformComponent::from_position(Vec3::new(0.0, 0.0, -2.0)).with_uniform_scale(2.0));
+        world.add_component(leaf, TransformComponent::from_position(Vec3::new(0.0, 1.0, 0.0)));
+        world.set_parent(middle, root).unwrap();
+        world.set_parent(leaf, middle).unwrap();
+
+        world.propagate_transforms();
+
+        // 90 degree yaw maps local -Z to world -X
+        let middle_global = world.get_component::<GlobalTransform>(middle).unwrap();
+        assert_near(middle_global.position, Vec3::new(8.0, 0.0, 0.0));
+        let leaf_global = world.get_component::<GlobalTransform>(leaf).unwrap();
+        assert_near(leaf_global.position, Vec3::new(8.0, 2.0, 0.0));
+        assert_near(leaf_global.scale, Vec3::new(2.0, 2.0, 2.0));
+        assert_near(world.world_transform(root).unwrap().position, Vec3::new(10.0, 0.0, 0.0));
+
+        // Moving the root moves the whole hierarchy on the next propagation
+        world.get_component_mut::<TransformComponent>(root).unwrap().position = Vec3::zeros();
+        world.propagate_transforms();
+        assert_near(world.world_transform(leaf).unwrap().position, Vec3::new(-2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_world_transform_before_propagation() {
+        let mut world = World::new();
+        let parent = world.create_entity();
+        let child = world.create_entity();
+        world.add_component(parent, TransformComponent::from_position(Vec3::new(1.0, 2.0, 3.0)));
+        world.add_component(child, TransformComponent::from_position(Vec3::new(1.0, 0.0, 0.0)));
+        world.set_parent(child, parent).unwrap();
+
+        assert!(world.get_component::<GlobalTransform>(child).is_none());
+        assert_near(world.world_transform(child).unwrap().position, Vec3::new(2.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_set_parent_rejects_cycles_and_reparents() {
+        let mut world = World::new();
+        let a = world.create_entity();
+        let b = world.create_entity();
+        let c = world.create_entity();
+        world.set_parent(b, a).unwrap();
+        world.set_parent(c, b).unwrap();
+
+        assert_eq!(world.set_parent(a, c), Err(HierarchyError::Cycle { child: a, parent: c }));
+        assert_eq!(world.set_parent(a, a), Err(HierarchyError::Cycle { child: a, parent: a }));
+
+        world.set_parent(c, a).unwrap();
+        assert_eq!(world.parent_of(c), Some(a));
+        assert!(world.get_component::<Children>(b).is_none());
+        assert_eq!(world.get_component::<Children>(a).unwrap().0, vec![b, c]);
+
+        assert_eq!(world.remove_parent(c), Some(a));
+        assert_eq!(world.parent_of(c), None);
+        assert_eq!(world.get_component::<Children>(a).unwrap().0, vec![b]);
+    }
+
+    #[test]
+    fn test_despawn_recursive() {
+        let mut world = World::new();
+        let root = world.create_entity();
+        let child = world.create_entity();
+        let grandchild = world.create_entity();
+        let sibling = world.create_entity();
+        world.set_parent(child, root).unwrap();
+        world.set_parent(grandchild, child).unwrap();
+        world.set_parent(sibling, root).unwrap();
+
+        assert!(world.despawn_recursive(child));
+        assert!(!world.is_alive(child));
+        assert!(!world.is_alive(grandchild));
+        assert!(world.is_alive(sibling));
+        assert_eq!(world.get_component::<Children>(root).unwrap().0, vec![sibling]);
+        assert!(!world.despawn_recursive(child));
+    }
+
+    #[test]
+    fn test_propagation_only_writes_changed_transforms() {
+        let mut world = World::new();
+        let parent = world.create_entity();
+        let child = world.create_entity();
+        world.add_component(parent, TransformComponent::identity());
+        world.add_component(child, TransformComponent::from_position(Vec3::new(0.0, 1.0, 0.0)));
+        world.set_parent(child, parent).unwrap();
+        world.propagate_transforms();
+
+        let mut changed = crate::ecs::Query::<&GlobalTransform, crate::ecs::Changed<GlobalTransform>>::new();
+        assert_eq!(changed.iter(&world).len(), 2);
+
+        world.propagate_transforms();
+        assert!(changed.iter(&world).is_empty());
+
+        world.get_component_mut::<TransformComponent>(parent).unwrap().position.x = 5.0;
+        world.propagate_transforms();
+        assert_eq!(changed.iter(&world).len(), 2);
+    }
+
+    #[test]
+    fn test_interpolation_follows_interpolated_parent() {
+        let mut world = World::new();
+        let parent = world.create_entity();
+        let child = world.create_entity();
+        let still = world.create_entity();
+        // Parent moved from x=0 to x=10 during the last step; the child is rigidly attached
+        world.add_component(parent, TransformComponent::from_position(Vec3::new(10.0, 0.0, 0.0)));
+        world.add_component(parent, PreviousTransform(TransformComponent::identity()));
+        world.add_component(child, TransformComponent::from_position(Vec3::new(0.0, 1.0, 0.0)));
+        world.add_component(still, TransformComponent::identity());
+        world.set_parent(child, parent).unwrap();
+        world.propagate_transforms();
+
+        assert_near(world.interpolated_world_transform(child, 0.25).unwrap().position, Vec3::new(2.5, 1.0, 0.0));
+        assert_near(world.interpolated_world_transform(parent, 1.0).unwrap().position, Vec3::new(10.0, 0.0, 0.0));
+        assert!(world.interpolated_world_transform(still, 0.5).is_none());
+    }
+}
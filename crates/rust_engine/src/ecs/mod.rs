@@ -5,9 +5,16 @@
 //! component purity, and structured update phases.
 
 pub mod world;
+pub mod archetype;
 pub mod entity;
 pub mod component;
+pub mod resource;
 pub mod system;
+pub mod scheduler;
+pub mod commands;
+pub mod event;
+pub mod hierarchy;
+pub mod serialization;
 pub mod query;
 pub mod components;
 pub mod systems;
@@ -18,14 +25,21 @@ pub mod tests;
 
 pub use world::World;
 pub use entity::Entity;
-pub use component::Component;
-pub use system::System;
-pub use query::Query;
+pub use component::{Component, StorageType};
+pub use resource::Resource;
+pub use system::{ExclusiveSystem, System, SystemAccess, SystemWorld};
+pub use commands::{Commands, SpawnCommands};
+pub use event::{EventReader, Events};
+pub use hierarchy::HierarchyError;
+pub use serialization::{ComponentRegistry, EntityMap, MapEntities, SceneError};
+pub use scheduler::{ExecutionMode, SchedulerError, SystemId, SystemPhase, SystemScheduler};
+pub use query::{Added, Changed, Mut, Query, QueryAccess, QueryData, QueryFilter, ReadOnlyQueryData, RemovedComponents, With, Without};
 
 // Re-export common components and systems
 pub use components::{
     LightComponent, LightType, LightFactory, 
-    TransformComponent, TransformFactory,
+    TransformComponent, TransformFactory, PreviousTransform,
+    Parent, Children, GlobalTransform,
     RenderableComponent, RenderableFactory,
     MovementComponent, MovementFactory,
     LifecycleComponent, LifecycleFactory, EntityState
@@ -1,15 +1,825 @@
 //! Query system for component access
+//!
+//! Queries join several component storages at once, so systems no longer need to
+//! iterate one component type and re-fetch the others with `get_component`:
+//!
+//! ```ignore
+//! for (entity, (transform, movement, collider)) in world.query_mut::<(
+//!     &TransformComponent,
+//!     &mut MovementComponent,
+//!     Option<&ColliderComponent>,
+//! )>() {
+//!     // ...
+//! }
+//! ```
+//!
+//! Filters narrow the matched entities without fetching data:
+//!
+//! ```ignore
+//! let lights = world.query_filtered::<&LightComponent, (With<TransformComponent>, Without<Disabled>)>();
+//! ```
+//!
+//! Change detection filters match components added or written since the query
+//! last ran. Mutable terms yield `Mut<T>`, which derefs to `T` and only marks
+//! the component changed when it is actually written through. A `Query` remembers its last run, so keep one around
+//! (e.g. in a system or manager struct) to only process what changed:
+//!
+//! ```ignore
+//! let mut moved = Query::<&TransformComponent, Changed<TransformComponent>>::new();
+//! for (entity, transform) in moved.iter(&world) {
+//!     // only transforms touched since the previous call
+//! }
+//! ```
+//!
+//! Queries run in two passes: the entities that match every required/excluded
+//! component (and change filter) are collected first, then the storages are
+//! split-borrowed so that each mutable term gets exclusive access to its own storage.
+
+use super::{Component, Entity, World};
+use super::world::{AnyStorage, ComponentStorage, ComponentTicks, EntityTable, StorageRowsMut};
+use std::any::{type_name, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+/// Component access declared by a query (used for conflict detection)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryAccess {
+    reads: HashSet<TypeId>,
+    writes: HashSet<TypeId>,
+    required: HashSet<TypeId>,
+    excluded: HashSet<TypeId>,
+    added: HashSet<TypeId>,
+    changed: HashSet<TypeId>,
+    names: HashMap<TypeId, &'static str>,
+}
+
+impl QueryAccess {
+    /// Create an empty access set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare read access to component `T`
+    pub fn add_read<T: Component>(&mut self) {
+        let type_id = TypeId::of::<T>();
+        if self.writes.contains(&type_id) {
+            panic!("Query accesses {} both mutably and immutably", type_name::<T>());
+        }
+        self.reads.insert(type_id);
+        self.names.insert(type_id, type_name::<T>());
+    }
+
+    /// Declare write access to component `T`
+    pub fn add_write<T: Component>(&mut self) {
+        let type_id = TypeId::of::<T>();
+        if self.writes.contains(&type_id) || self.reads.contains(&type_id) {
+            panic!("Query accesses {} mutably more than once", type_name::<T>());
+        }
+        self.writes.insert(type_id);
+        self.names.insert(type_id, type_name::<T>());
+    }
+
+    /// Require entities to have component `T`
+    pub fn add_required<T: Component>(&mut self) {
+        self.required.insert(TypeId::of::<T>());
+        self.names.insert(TypeId::of::<T>(), type_name::<T>());
+    }
+
+    /// Require entities to NOT have component `T`
+    pub fn add_excluded<T: Component>(&mut self) {
+        self.excluded.insert(TypeId::of::<T>());
+        self.names.insert(TypeId::of::<T>(), type_name::<T>());
+    }
+
+    /// Require component `T` to have been added since the query last ran
+    pub fn add_added<T: Component>(&mut self) {
+        self.add_required::<T>();
+        self.added.insert(TypeId::of::<T>());
+    }
+
+    /// Require component `T` to have been added or mutated since the query last ran
+    pub fn add_changed<T: Component>(&mut self) {
+        self.add_required::<T>();
+        self.changed.insert(TypeId::of::<T>());
+    }
+
+    /// Component types read by the query
+    pub fn reads(&self) -> &HashSet<TypeId> {
+        &self.reads
+    }
+
+    /// Component types written by the query
+    pub fn writes(&self) -> &HashSet<TypeId> {
+        &self.writes
+    }
+
+    /// Component types an entity must have to match
+    pub fn required(&self) -> &HashSet<TypeId> {
+        &self.required
+    }
+
+    /// Component types an entity must not have to match
+    pub fn excluded(&self) -> &HashSet<TypeId> {
+        &self.excluded
+    }
+
+    /// Check whether two access sets can run at the same time
+    pub fn is_compatible(&self, other: &QueryAccess) -> bool {
+        self.writes.is_disjoint(&other.writes)
+            && self.writes.is_disjoint(&other.reads)
+            && self.reads.is_disjoint(&other.writes)
+    }
+
+    /// Merge another access set into this one (no conflict checks)
+    pub fn extend(&mut self, other: &QueryAccess) {
+        self.reads.extend(other.reads.iter().copied());
+        self.writes.extend(other.writes.iter().copied());
+        self.required.extend(other.required.iter().copied());
+        self.excluded.extend(other.excluded.iter().copied());
+        self.added.extend(other.added.iter().copied());
+        self.changed.extend(other.changed.iter().copied());
+        self.names.extend(other.names.iter().map(|(k, v)| (*k, *v)));
+    }
+
+    /// Human readable component name for diagnostics
+    pub fn name_of(&self, type_id: TypeId) -> &'static str {
+        self.names.get(&type_id).copied().unwrap_or("<unknown component>")
+    }
+}
+
+/// Component storages split-borrowed out of a world for one query
+///
+/// Mutable terms take their storage exclusively; shared terms may borrow the
+/// same storage any number of times.
+#[doc(hidden)]
+pub struct StorageBorrows<'w> {
+    exclusive: HashMap<TypeId, &'w mut Box<dyn AnyStorage>>,
+    shared: HashMap<TypeId, &'w dyn AnyStorage>,
+    change_tick: u64,
+}
+
+impl<'w> StorageBorrows<'w> {
+    /// Borrow every storage with exclusive access available
+    ///
+    /// Mutably fetched components are stamped with `change_tick`.
+    pub(crate) fn exclusive(storages: &'w mut HashMap<TypeId, Box<dyn AnyStorage>>, change_tick: u64) -> Self {
+        Self {
+            exclusive: storages.iter_mut().map(|(type_id, storage)| (*type_id, storage)).collect(),
+            shared: HashMap::new(),
+            change_tick,
+        }
+    }
+
+    /// Borrow every storage read-only
+    pub(crate) fn shared(storages: &'w HashMap<TypeId, Box<dyn AnyStorage>>) -> Self {
+        Self {
+            exclusive: HashMap::new(),
+            shared: storages.iter().map(|(type_id, storage)| (*type_id, storage.as_ref())).collect(),
+            change_tick: 0,
+        }
+    }
+
+    /// Borrow exactly the given storages (used by system views)
+    pub(crate) fn new(
+        exclusive: HashMap<TypeId, &'w mut Box<dyn AnyStorage>>,
+        shared: HashMap<TypeId, &'w dyn AnyStorage>,
+        change_tick: u64,
+    ) -> Self {
+        Self { exclusive, shared, change_tick }
+    }
+
+    /// Type-erased look at a storage without taking it (used for matching)
+    fn storage(&self, type_id: &TypeId) -> Option<&dyn AnyStorage> {
+        self.exclusive
+            .get(type_id)
+            .map(|storage| storage.as_ref())
+            .or_else(|| self.shared.get(type_id).copied())
+    }
+
+    /// Get shared access to the storage for `T`
+    pub(crate) fn get<T: Component>(&mut self) -> Option<&'w ComponentStorage<T>> {
+        let type_id = TypeId::of::<T>();
+        if let Some(storage) = self.exclusive.remove(&type_id) {
+            let storage: &'w dyn AnyStorage = &**storage;
+            self.shared.insert(type_id, storage);
+        }
+        self.shared
+            .get(&type_id)
+            .map(|storage| storage.as_any().downcast_ref::<ComponentStorage<T>>()
+                .expect("Component storage type mismatch"))
+    }
+
+    /// Take exclusive access to the storage for `T`
+    pub(crate) fn get_mut<T: Component>(&mut self) -> Option<&'w mut ComponentStorage<T>> {
+        let type_id = TypeId::of::<T>();
+        if self.shared.contains_key(&type_id) {
+            panic!("{} is already borrowed immutably by this query", type_name::<T>());
+        }
+        self.exclusive
+            .remove(&type_id)
+            .map(|storage| storage.as_any_mut().downcast_mut::<ComponentStorage<T>>()
+                .expect("Component storage type mismatch"))
+    }
+}
+
+/// Data fetched by a query for each matching entity
+///
+/// Implemented for `&T`, `&mut T`, `Option<&T>`, `Option<&mut T>` and tuples of these.
+pub trait QueryData {
+    /// Item produced for each entity
+    type Item<'w>;
+
+    /// Per-query fetch state (borrowed storages)
+    #[doc(hidden)]
+    type Fetch<'w>;
+
+    /// Declare the component access of this term
+    fn add_access(access: &mut QueryAccess);
+
+    /// Borrow the storages this term needs
+    #[doc(hidden)]
+    fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w>;
+
+    /// Fetch the item for an entity (entities already match the access)
+    #[doc(hidden)]
+    fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>>;
+}
+
+/// Marker for queries that only read components (usable through `&World`)
+pub trait ReadOnlyQueryData: QueryData {}
+
+/// Filter restricting which entities a query matches, without fetching data
+pub trait QueryFilter {
+    /// Declare the required/excluded components of this filter
+    fn add_access(access: &mut QueryAccess);
+}
+
+/// Filter: entity must have component `T`
+pub struct With<T>(PhantomData<T>);
+
+/// Filter: entity must not have component `T`
+pub struct Without<T>(PhantomData<T>);
+
+impl<T: Component> QueryFilter for With<T> {
+    fn add_access(access: &mut QueryAccess) {
+        access.add_required::<T>();
+    }
+}
+
+impl<T: Component> QueryFilter for Without<T> {
+    fn add_access(access: &mut QueryAccess) {
+        access.add_excluded::<T>();
+    }
+}
+
+/// Filter: component `T` was added since the query last ran
+pub struct Added<T>(PhantomData<T>);
+
+/// Filter: component `T` was added or mutably accessed since the query last ran
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T: Component> QueryFilter for Added<T> {
+    fn add_access(access: &mut QueryAccess) {
+        access.add_added::<T>();
+    }
+}
+
+impl<T: Component> QueryFilter for Changed<T> {
+    fn add_access(access: &mut QueryAccess) {
+        access.add_changed::<T>();
+    }
+}
+
+impl QueryFilter for () {
+    fn add_access(_access: &mut QueryAccess) {}
+}
+
+impl<T: Component> QueryData for &T {
+    type Item<'w> = &'w T;
+    type Fetch<'w> = ReadFetch<'w, T>;
+
+    fn add_access(access: &mut QueryAccess) {
+        access.add_read::<T>();
+        access.add_required::<T>();
+    }
+
+    fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w> {
+        ReadFetch(borrows.get::<T>())
+    }
+
+    fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>> {
+        fetch.get(entity_id)
+    }
+}
+
+impl<T: Component> ReadOnlyQueryData for &T {}
+
+impl<T: Component> QueryData for &mut T {
+    type Item<'w> = Mut<'w, T>;
+    type Fetch<'w> = Option<MutFetch<'w, T>>;
+
+    fn add_access(access: &mut QueryAccess) {
+        access.add_write::<T>();
+        access.add_required::<T>();
+    }
+
+    fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w> {
+        let change_tick = borrows.change_tick;
+        borrows.get_mut::<T>().map(|storage| MutFetch::new(storage, change_tick))
+    }
+
+    fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>> {
+        fetch.as_mut().and_then(|fetch| fetch.take(entity_id))
+    }
+}
+
+impl<T: Component> QueryData for Option<&T> {
+    type Item<'w> = Option<&'w T>;
+    type Fetch<'w> = ReadFetch<'w, T>;
+
+    fn add_access(access: &mut QueryAccess) {
+        access.add_read::<T>();
+    }
+
+    fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w> {
+        ReadFetch(borrows.get::<T>())
+    }
+
+    fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>> {
+        Some(fetch.get(entity_id))
+    }
+}
+
+impl<T: Component> ReadOnlyQueryData for Option<&T> {}
+
+impl<T: Component> QueryData for Option<&mut T> {
+    type Item<'w> = Option<Mut<'w, T>>;
+    type Fetch<'w> = Option<MutFetch<'w, T>>;
+
+    fn add_access(access: &mut QueryAccess) {
+        access.add_write::<T>();
+    }
+
+    fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w> {
+        let change_tick = borrows.change_tick;
+        borrows.get_mut::<T>().map(|storage| MutFetch::new(storage, change_tick))
+    }
+
+    fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>> {
+        Some(fetch.as_mut().and_then(|fetch| fetch.take(entity_id)))
+    }
+}
+
+/// Shared fetch state: the borrowed storage, if it exists
+#[doc(hidden)]
+pub struct ReadFetch<'w, T: Component>(Option<&'w ComponentStorage<T>>);
+
+impl<'w, T: Component> ReadFetch<'w, T> {
+    fn get(&self, entity_id: u32) -> Option<&'w T> {
+        self.0.and_then(|storage| storage.get(entity_id))
+    }
+}
+
+/// Mutable access to a component fetched by a query
+///
+/// Reading through it leaves the component untouched; the first mutable
+/// dereference marks it changed for `Changed<T>` filters.
+pub struct Mut<'w, T: Component> {
+    value: &'w mut T,
+    ticks: &'w mut ComponentTicks,
+    change_tick: u64,
+}
+
+impl<'w, T: Component> Mut<'w, T> {
+    /// Unwrap into a plain reference, marking the component changed
+    pub fn into_inner(self) -> &'w mut T {
+        self.ticks.changed = self.change_tick;
+        self.value
+    }
+}
+
+impl<T: Component> Deref for Mut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: Component> DerefMut for Mut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.ticks.changed = self.change_tick;
+        self.value
+    }
+}
+
+impl<T: Component + std::fmt::Debug> std::fmt::Debug for Mut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Mutable fetch state: hands out each component at most once per query
+#[doc(hidden)]
+pub struct MutFetch<'w, T: Component> {
+    rows: StorageRowsMut<'w, T>,
+    change_tick: u64,
+}
+
+impl<'w, T: Component> MutFetch<'w, T> {
+    fn new(storage: &'w mut ComponentStorage<T>, change_tick: u64) -> Self {
+        Self { rows: storage.rows_mut(), change_tick }
+    }
+
+    fn take(&mut self, entity_id: u32) -> Option<Mut<'w, T>> {
+        let (value, ticks) = self.rows.take(entity_id)?;
+        Some(Mut { value, ticks, change_tick: self.change_tick })
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: QueryData),+> QueryData for ($($name,)+) {
+            type Item<'w> = ($($name::Item<'w>,)+);
+            type Fetch<'w> = ($($name::Fetch<'w>,)+);
+
+            fn add_access(access: &mut QueryAccess) {
+                $($name::add_access(access);)+
+            }
+
+            fn init_fetch<'w>(borrows: &mut StorageBorrows<'w>) -> Self::Fetch<'w> {
+                ($($name::init_fetch(borrows),)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn fetch<'w>(fetch: &mut Self::Fetch<'w>, entity_id: u32) -> Option<Self::Item<'w>> {
+                let ($($name,)+) = fetch;
+                Some(($($name::fetch($name, entity_id)?,)+))
+            }
+        }
+
+        impl<$($name: ReadOnlyQueryData),+> ReadOnlyQueryData for ($($name,)+) {}
+
+        impl<$($name: QueryFilter),+> QueryFilter for ($($name,)+) {
+            fn add_access(access: &mut QueryAccess) {
+                $($name::add_access(access);)+
+            }
+        }
+    };
+}
+
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+impl_query_tuple!(A, B, C, D, E);
+impl_query_tuple!(A, B, C, D, E, F);
+impl_query_tuple!(A, B, C, D, E, F, G);
+impl_query_tuple!(A, B, C, D, E, F, G, H);
 
 /// Query for accessing components
-pub struct Query<T> {
-    _phantom: std::marker::PhantomData<T>,
+///
+/// `Q` is the data fetched per entity and `F` an optional filter. The query
+/// validates its access once on creation and can then be run against any world.
+pub struct Query<Q: QueryData, F: QueryFilter = ()> {
+    access: QueryAccess,
+    last_run: u64, // World change tick of the previous run (for Added/Changed)
+    _phantom: PhantomData<fn() -> (Q, F)>,
 }
 
-impl<T> Query<T> {
+impl<Q: QueryData, F: QueryFilter> Query<Q, F> {
     /// Create a new query
+    ///
+    /// # Panics
+    /// Panics if the query accesses the same component mutably more than once,
+    /// or both mutably and immutably.
+    pub fn new() -> Self {
+        let mut access = QueryAccess::new();
+        Q::add_access(&mut access);
+        F::add_access(&mut access);
+        Self {
+            access,
+            last_run: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Component access of this query (for scheduling and conflict detection)
+    pub fn access(&self) -> &QueryAccess {
+        &self.access
+    }
+
+    /// Run a read-only query
+    pub fn iter<'w>(&mut self, world: &'w World) -> Vec<(Entity, Q::Item<'w>)>
+    where
+        Q: ReadOnlyQueryData,
+    {
+        let last_run = std::mem::replace(&mut self.last_run, world.increment_change_tick());
+        self.run(world.entity_table(), StorageBorrows::shared(world.storages()), last_run)
+    }
+
+    /// Run a query that may mutate components
+    pub fn iter_mut<'w>(&mut self, world: &'w mut World) -> Vec<(Entity, Q::Item<'w>)> {
+        let this_run = world.increment_change_tick();
+        let last_run = std::mem::replace(&mut self.last_run, this_run);
+        let (entities, _, storages, _) = world.split_mut();
+        self.run(entities, StorageBorrows::exclusive(storages, this_run), last_run)
+    }
+
+    /// Count matching entities without fetching components (does not count as a run)
+    pub fn count(&self, world: &World) -> usize {
+        self.matching_entities(world.entity_table(), &StorageBorrows::shared(world.storages()), self.last_run).len()
+    }
+
+    /// Run the query against already borrowed storages (worlds and system views)
+    pub(crate) fn run<'w>(
+        &self,
+        entities: &EntityTable,
+        mut borrows: StorageBorrows<'w>,
+        last_run: u64,
+    ) -> Vec<(Entity, Q::Item<'w>)> {
+        let matches = self.matching_entities(entities, &borrows, last_run);
+        let mut fetch = Q::init_fetch(&mut borrows);
+        matches
+            .into_iter()
+            .filter_map(|entity| Q::fetch(&mut fetch, entity.id()).map(|item| (entity, item)))
+            .collect()
+    }
+
+    /// First pass: collect entities that satisfy the required/excluded sets and change filters
+    fn matching_entities(&self, entities: &EntityTable, borrows: &StorageBorrows<'_>, last_run: u64) -> Vec<Entity> {
+        let mut required = Vec::with_capacity(self.access.required.len());
+        for type_id in &self.access.required {
+            match borrows.storage(type_id) {
+                Some(storage) => required.push(storage),
+                // A required component that was never added: nothing can match
+                None => return Vec::new(),
+            }
+        }
+        let excluded: Vec<&dyn AnyStorage> = self.access.excluded.iter()
+            .filter_map(|type_id| borrows.storage(type_id))
+            .collect();
+        // Change filters always require their component, so the storages exist
+        let added: Vec<&dyn AnyStorage> = self.access.added.iter()
+            .filter_map(|type_id| borrows.storage(type_id))
+            .collect();
+        let changed: Vec<&dyn AnyStorage> = self.access.changed.iter()
+            .filter_map(|type_id| borrows.storage(type_id))
+            .collect();
+
+        let is_match = |entity_id: u32| {
+            required.iter().all(|storage| storage.contains(entity_id))
+                && !excluded.iter().any(|storage| storage.contains(entity_id))
+                && added.iter().all(|storage| storage.ticks(entity_id).is_some_and(|ticks| ticks.added > last_run))
+                && changed.iter().all(|storage| storage.ticks(entity_id).is_some_and(|ticks| ticks.changed > last_run))
+        };
+
+        // Drive iteration from the smallest required storage
+        match required.iter().min_by_key(|storage| storage.len()) {
+            Some(driver) => driver.entity_ids()
+                .into_iter()
+                .filter(|&entity_id| is_match(entity_id))
+                .filter_map(|entity_id| entities.entity_from_id(entity_id))
+                .collect(),
+            None => entities.iter()
+                .filter(|entity| is_match(entity.id()))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl<Q: QueryData, F: QueryFilter> Default for Query<Q, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reader for entities whose component `T` was removed (or despawned)
+///
+/// Like a `Query`, it remembers its last read and only reports newer removals.
+/// Removals stay visible for two frames (see `World::update_trackers`), so read
+/// at least once per frame.
+pub struct RemovedComponents<T: Component> {
+    last_run: u64,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Component> RemovedComponents<T> {
+    /// Create a reader that has not seen any removals yet
     pub fn new() -> Self {
         Self {
-            _phantom: std::marker::PhantomData,
+            last_run: 0,
+            _phantom: PhantomData,
         }
     }
+
+    /// Entities that lost component `T` since the previous read
+    pub fn read(&mut self, world: &World) -> Vec<Entity> {
+        let last_run = std::mem::replace(&mut self.last_run, world.increment_change_tick());
+        world.removed_log().since(TypeId::of::<T>(), last_run)
+    }
+}
+
+impl<T: Component> Default for RemovedComponents<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Position(f32);
+    impl Component for Position {}
+
+    #[derive(Debug, PartialEq)]
+    struct Velocity(f32);
+    impl Component for Velocity {}
+
+    #[derive(Debug, PartialEq)]
+    struct Frozen;
+    impl Component for Frozen {}
+
+    fn setup() -> (World, Entity, Entity, Entity) {
+        let mut world = World::new();
+        let moving = world.create_entity();
+        world.add_component(moving, Position(0.0));
+        world.add_component(moving, Velocity(2.0));
+
+        let frozen = world.create_entity();
+        world.add_component(frozen, Position(10.0));
+        world.add_component(frozen, Velocity(5.0));
+        world.add_component(frozen, Frozen);
+
+        let static_only = world.create_entity();
+        world.add_component(static_only, Position(-1.0));
+
+        (world, moving, frozen, static_only)
+    }
+
+    #[test]
+    fn test_tuple_query_joins_storages() {
+        let (world, moving, frozen, _) = setup();
+
+        let mut results: Vec<_> = world.query::<(&Position, &Velocity)>()
+            .into_iter()
+            .map(|(entity, (p, v))| (entity, p.0, v.0))
+            .collect();
+        results.sort_by_key(|(entity, _, _)| entity.id());
+
+        assert_eq!(results, vec![(moving, 0.0, 2.0), (frozen, 10.0, 5.0)]);
+    }
+
+    #[test]
+    fn test_mutable_tuple_query() {
+        let (mut world, moving, frozen, _) = setup();
+
+        for (_, (mut position, velocity)) in world.query_mut::<(&mut Position, &Velocity)>() {
+            position.0 += velocity.0;
+        }
+
+        assert_eq!(world.get_component::<Position>(moving), Some(&Position(2.0)));
+        assert_eq!(world.get_component::<Position>(frozen), Some(&Position(15.0)));
+    }
+
+    #[test]
+    fn test_optional_terms() {
+        let (world, _, _, static_only) = setup();
+
+        let results = world.query::<(&Position, Option<&Velocity>)>();
+        assert_eq!(results.len(), 3);
+
+        let (_, (_, velocity)) = results.iter()
+            .find(|(entity, _)| *entity == static_only)
+            .unwrap();
+        assert!(velocity.is_none());
+    }
+
+    #[test]
+    fn test_with_without_filters() {
+        let (mut world, moving, frozen, _) = setup();
+
+        let with = world.query_filtered::<&Position, With<Frozen>>();
+        assert_eq!(with.len(), 1);
+        assert_eq!(with[0].0, frozen);
+
+        let without = world.query_filtered_mut::<&mut Velocity, Without<Frozen>>();
+        assert_eq!(without.len(), 1);
+        assert_eq!(without[0].0, moving);
+    }
+
+    #[test]
+    fn test_missing_required_storage_matches_nothing() {
+        let (world, _, _, _) = setup();
+
+        #[derive(Debug)]
+        struct Unused;
+        impl Component for Unused {}
+
+        assert!(world.query::<(&Position, &Unused)>().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_conflicting_access_panics() {
+        let _ = Query::<(&Position, &mut Position)>::new();
+    }
+
+    #[test]
+    fn test_access_compatibility() {
+        let read_position = Query::<&Position>::new();
+        let write_velocity = Query::<(&Position, &mut Velocity)>::new();
+        let write_position = Query::<&mut Position>::new();
+
+        assert!(read_position.access().is_compatible(write_velocity.access()));
+        assert!(!read_position.access().is_compatible(write_position.access()));
+    }
+
+    #[test]
+    fn test_added_and_changed_filters() {
+        let (mut world, moving, frozen, static_only) = setup();
+        let mut added = Query::<&Position, Added<Position>>::new();
+        let mut changed = Query::<&Position, Changed<Position>>::new();
+
+        // First run: everything is new
+        assert_eq!(added.iter(&world).len(), 3);
+        assert_eq!(changed.iter(&world).len(), 3);
+        assert!(added.iter(&world).is_empty());
+        assert!(changed.iter(&world).is_empty());
+
+        // Writing through a query counts as a change, not an addition
+        for (_, mut position) in world.query_filtered_mut::<&mut Position, Without<Frozen>>() {
+            position.0 += 1.0;
+        }
+        let spawned = world.create_entity();
+        world.add_component(spawned, Position(3.0));
+
+        let mut added_entities: Vec<_> = added.iter(&world).into_iter().map(|(entity, _)| entity).collect();
+        let mut changed_entities: Vec<_> = changed.iter(&world).into_iter().map(|(entity, _)| entity).collect();
+        added_entities.sort_by_key(|entity| entity.id());
+        changed_entities.sort_by_key(|entity| entity.id());
+        assert_eq!(added_entities, vec![spawned]);
+        assert_eq!(changed_entities, vec![moving, static_only, spawned]);
+
+        world.get_component_mut::<Position>(frozen).unwrap().0 = 0.0;
+        let changed_entities: Vec<_> = changed.iter(&world).into_iter().map(|(entity, _)| entity).collect();
+        assert_eq!(changed_entities, vec![frozen]);
+    }
+
+    #[test]
+    fn test_reading_through_mut_is_not_a_change() {
+        let (mut world, moving, frozen, _) = setup();
+        let mut changed = Query::<&Position, Changed<Position>>::new();
+        changed.iter(&world);
+
+        // Only entities actually written through `Mut` count as changed
+        for (_, (mut position, velocity)) in world.query_mut::<(&mut Position, &Velocity)>() {
+            if position.0 > 5.0 {
+                position.0 += velocity.0;
+            }
+        }
+        let changed_entities: Vec<_> = changed.iter(&world).into_iter().map(|(entity, _)| entity).collect();
+        assert_eq!(changed_entities, vec![frozen]);
+
+        let reborrowed = world.query_mut::<&mut Position>().into_iter()
+            .find(|(entity, _)| *entity == moving)
+            .map(|(_, position)| position.into_inner());
+        assert_eq!(reborrowed, Some(&mut Position(0.0)));
+        let changed_entities: Vec<_> = changed.iter(&world).into_iter().map(|(entity, _)| entity).collect();
+        assert_eq!(changed_entities, vec![moving]);
+    }
+
+    #[test]
+    fn test_query_does_not_see_its_own_writes() {
+        let (mut world, _, _, _) = setup();
+        let mut query = Query::<&mut Position, Changed<Position>>::new();
+
+        assert_eq!(query.iter_mut(&mut world).len(), 3);
+        assert!(query.iter_mut(&mut world).is_empty());
+    }
+
+    #[test]
+    fn test_removed_components_reader() {
+        let (mut world, moving, frozen, _) = setup();
+        let mut removed = RemovedComponents::<Velocity>::new();
+        assert!(removed.read(&world).is_empty());
+
+        world.remove_component::<Velocity>(moving);
+        world.despawn(frozen);
+        let mut entities = removed.read(&world);
+        entities.sort_by_key(|entity| entity.id());
+        assert_eq!(entities, vec![moving, frozen]);
+        assert!(removed.read(&world).is_empty());
+
+        // Removals survive one tracker update, then are dropped
+        let late_reader_sees = |world: &World| RemovedComponents::<Velocity>::new().read(world).len();
+        world.update_trackers();
+        assert_eq!(late_reader_sees(&world), 2);
+        world.update_trackers();
+        assert_eq!(late_reader_sees(&world), 0);
+    }
 }
@@ -0,0 +1,28 @@
+//! Resource trait and implementations
+//!
+//! Resources are global singletons stored in the `World` by type, for state that
+//! belongs to no particular entity (frame timing, mouse state, lighting).
+//! Systems declare resource access in their `SystemAccess` just like component
+//! access, so the scheduler can run them in parallel safely.
+//!
+//! Resources are shared with worker threads and must be `Send + Sync`; subsystems
+//! that own thread-bound handles (e.g. `AudioSystem`, `AssetManager`) stay on the
+//! `Engine`.
+
+use std::any::Any;
+
+/// Marker trait for resources
+pub trait Resource: 'static + Send + Sync {}
+
+/// Type-erased resource value
+pub(crate) type ResourceBox = Box<dyn Any + Send + Sync>;
+
+// Implement Resource for engine singletons
+impl Resource for crate::foundation::time::Timer {}
+impl Resource for crate::foundation::time::FixedTimestep {}
+impl Resource for crate::input::picking::MouseState {}
+impl Resource for crate::render::LightingEnvironment {}
+impl Resource for crate::render::MultiLightEnvironment {}
+impl Resource for crate::ecs::serialization::ComponentRegistry {}
+impl Resource for crate::physics::CollisionMatrix {}
+impl Resource for crate::events::EventBus {}
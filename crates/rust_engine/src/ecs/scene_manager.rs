@@ -166,12 +166,8 @@ impl SceneManager {
     
     /// Remove an entity from the scene
     pub fn remove_entity(&mut self, entity: Entity) {
-        // Remove all components for this entity
-        // Note: The World doesn't have a remove_entity method, so we'll need to remove components manually
-        // This is a simplified approach - in a full implementation, you'd extend World with proper entity removal
-        self.world.remove_component::<RenderableComponent>(entity);
-        self.world.remove_component::<MovementComponent>(entity);
-        self.world.remove_component::<LifecycleComponent>(entity);
+        // Despawn removes every component and invalidates the handle
+        self.world.despawn(entity);
     }
     
     /// Update the scene for one frame
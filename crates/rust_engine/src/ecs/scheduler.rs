@@ -1,12 +1,24 @@
 //! System Scheduling and Dependency Management
-//! 
+//!
 //! Provides deterministic execution order and parallel system execution
 //! while preventing race conditions and deadlocks.
-
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use crossbeam::channel::{bounded, Receiver, Sender};
+//!
+//! Systems are grouped by phase. Within a phase, systems whose component
+//! access does not conflict are packed into batches that run in parallel;
+//! conflicting systems keep their registration order. Commands recorded by the
+//! systems of a phase are applied at the end of that phase, in registration order.
+//! `ExclusiveSystem`s always form a batch of their own and run on the calling thread.
+//!
+//! `SystemPhase::FixedUpdate` is not part of the frame: the engine runs it zero
+//! or more times per frame with a constant step (see `FixedTimestep`), so the
+//! simulation does not depend on the frame rate.
+
+use super::{Commands, World};
+use super::component::Component;
+use super::system::{ExclusiveSystem, System, SystemAccess, SystemWorld};
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
+use thiserror::Error;
 
 /// Unique identifier for systems
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -14,189 +26,134 @@ pub struct SystemId(pub u64);
 
 /// Component type identifier for conflict detection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct ComponentType(pub u64);
+pub struct ComponentType(TypeId);
+
+impl ComponentType {
+    /// Component type of `T`
+    pub fn of<T: Component>() -> Self {
+        Self(TypeId::of::<T>())
+    }
+
+    pub(crate) fn from_type_id(type_id: TypeId) -> Self {
+        Self(type_id)
+    }
+}
 
 /// System execution phases with explicit ordering
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SystemPhase {
     /// Input processing, entity lifecycle management
     PreUpdate = 0,
-    /// Game logic, physics simulation
-    Update = 1,
+    /// Movement integration and collision, run at a fixed rate (`execute_fixed_step`)
+    FixedUpdate = 1,
+    /// Game logic
+    Update = 2,
     /// Animation, transform updates
-    PostUpdate = 2,
+    PostUpdate = 3,
     /// Render command generation (read-only)
-    Render = 3,
+    Render = 4,
     /// GPU command submission
-    Present = 4,
+    Present = 5,
 }
 
-/// System trait with dependency and conflict declaration
-pub trait System: Send + Sync {
-    /// Systems that must execute before this one
-    fn dependencies(&self) -> &[SystemId];
-    
-    /// Component types this system will modify (for conflict detection)
-    fn write_components(&self) -> &[ComponentType];
-    
-    /// Component types this system will read (for optimization)
-    fn read_components(&self) -> &[ComponentType];
-    
-    /// Which phase this system belongs to
-    fn phase(&self) -> SystemPhase;
-    
-    /// Execute the system
-    fn execute(&mut self, world: &crate::ecs::World, delta_time: f32);
-    
-    /// System identifier
-    fn id(&self) -> SystemId;
+impl SystemPhase {
+    /// All phases in execution order
+    pub const ALL: [SystemPhase; 6] = [
+        SystemPhase::PreUpdate,
+        SystemPhase::FixedUpdate,
+        SystemPhase::Update,
+        SystemPhase::PostUpdate,
+        SystemPhase::Render,
+        SystemPhase::Present,
+    ];
 }
 
-/// Dependency graph for system scheduling
-pub struct DependencyGraph {
-    nodes: HashMap<SystemId, SystemNode>,
-    phases: HashMap<SystemPhase, Vec<SystemId>>,
+/// How the scheduler executes batches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Run every system on the calling thread, in plan order (deterministic, for tests)
+    SingleThreaded,
+    /// Run the systems of a batch on rayon's global thread pool
+    Parallel,
 }
 
-#[derive(Debug)]
-struct SystemNode {
-    id: SystemId,
-    dependencies: HashSet<SystemId>,
-    dependents: HashSet<SystemId>,
-    phase: SystemPhase,
-    write_components: HashSet<ComponentType>,
-    read_components: HashSet<ComponentType>,
+/// Scheduler errors
+#[derive(Error, Debug)]
+pub enum SchedulerError {
+    /// Dependency was never registered with this scheduler
+    #[error("System '{system}' depends on unknown system {dependency:?}")]
+    UnknownDependency {
+        /// Name of the system being added
+        system: String,
+        /// The missing dependency
+        dependency: SystemId,
+    },
+
+    /// Dependency runs in a later phase, so the order can never be satisfied
+    #[error("System '{system}' ({phase:?}) depends on '{dependency}' in later phase {dependency_phase:?}")]
+    DependencyInLaterPhase {
+        /// Name of the system being added
+        system: String,
+        /// Phase of the system being added
+        phase: SystemPhase,
+        /// Name of the dependency
+        dependency: String,
+        /// Phase of the dependency
+        dependency_phase: SystemPhase,
+    },
 }
 
-impl DependencyGraph {
-    pub fn new() -> Self {
-        Self {
-            nodes: HashMap::new(),
-            phases: HashMap::new(),
-        }
-    }
-
-    /// Add a system to the dependency graph
-    pub fn add_system(&mut self, system: &dyn System) {
-        let id = system.id();
-        let phase = system.phase();
-        
-        let node = SystemNode {
-            id,
-            dependencies: system.dependencies().iter().copied().collect(),
-            dependents: HashSet::new(),
-            phase,
-            write_components: system.write_components().iter().copied().collect(),
-            read_components: system.read_components().iter().copied().collect(),
-        };
+/// A registered system of either kind
+enum SystemKind {
+    Shared(Box<dyn System>),
+    Exclusive(Box<dyn ExclusiveSystem>),
+}
 
-        // Add to phase group
-        self.phases.entry(phase).or_insert_with(Vec::new).push(id);
-        
-        // Update dependent relationships
-        for &dep_id in system.dependencies() {
-            if let Some(dep_node) = self.nodes.get_mut(&dep_id) {
-                dep_node.dependents.insert(id);
-            }
+impl SystemKind {
+    fn name(&self) -> &str {
+        match self {
+            SystemKind::Shared(system) => system.name(),
+            SystemKind::Exclusive(system) => system.name(),
         }
-
-        self.nodes.insert(id, node);
     }
 
-    /// Generate execution plan with parallel batches
-    pub fn generate_execution_plan(&self) -> ExecutionPlan {
-        let mut plan = ExecutionPlan::new();
-        
-        // Process each phase in order
-        let mut sorted_phases: Vec<_> = self.phases.keys().copied().collect();
-        sorted_phases.sort();
-        
-        for phase in sorted_phases {
-            if let Some(systems) = self.phases.get(&phase) {
-                let phase_plan = self.generate_phase_plan(systems);
-                plan.add_phase(phase, phase_plan);
-            }
+    fn phase(&self) -> SystemPhase {
+        match self {
+            SystemKind::Shared(system) => system.phase(),
+            SystemKind::Exclusive(system) => system.phase(),
         }
-        
-        plan
     }
 
-    /// Generate execution plan for a single phase
-    fn generate_phase_plan(&self, systems: &[SystemId]) -> PhasePlan {
-        let mut remaining: HashSet<SystemId> = systems.iter().copied().collect();
-        let mut batches = Vec::new();
-        
-        while !remaining.is_empty() {
-            let mut current_batch = Vec::new();
-            let mut conflicts = HashSet::new();
-            
-            // Find systems that can run in parallel
-            for &system_id in &remaining {
-                if let Some(node) = self.nodes.get(&system_id) {
-                    // Check if all dependencies are satisfied
-                    let deps_satisfied = node.dependencies.iter()
-                        .all(|dep| !remaining.contains(dep));
-                    
-                    // Check for component conflicts
-                    let has_conflicts = node.write_components.iter()
-                        .any(|comp| conflicts.contains(comp)) ||
-                        node.read_components.iter()
-                        .any(|comp| conflicts.contains(comp) && 
-                             self.component_has_writers(&remaining, *comp));
-                    
-                    if deps_satisfied && !has_conflicts {
-                        current_batch.push(system_id);
-                        // Mark components as in use
-                        conflicts.extend(&node.write_components);
-                        conflicts.extend(&node.read_components);
-                    }
-                }
-            }
-            
-            // Remove scheduled systems
-            for &system_id in &current_batch {
-                remaining.remove(&system_id);
-            }
-            
-            if current_batch.is_empty() {
-                panic!("Circular dependency detected or invalid system configuration");
-            }
-            
-            batches.push(current_batch);
+    fn access(&self) -> SystemAccess {
+        match self {
+            SystemKind::Shared(system) => system.access(),
+            SystemKind::Exclusive(_) => SystemAccess::exclusive(),
         }
-        
-        PhasePlan { batches }
     }
+}
 
-    /// Check if any remaining systems write to a component
-    fn component_has_writers(&self, remaining: &HashSet<SystemId>, component: ComponentType) -> bool {
-        remaining.iter().any(|&id| {
-            self.nodes.get(&id)
-                .map(|node| node.write_components.contains(&component))
-                .unwrap_or(false)
-        })
-    }
+/// Registered system with its cached scheduling data
+struct SystemEntry {
+    system: SystemKind,
+    phase: SystemPhase,
+    access: SystemAccess,
+    dependencies: HashSet<SystemId>,
+    last_run: u64, // World change tick of the previous run (for change detection)
 }
 
 /// Complete execution plan across all phases
+#[derive(Debug, Default)]
 pub struct ExecutionPlan {
     phases: Vec<(SystemPhase, PhasePlan)>,
 }
 
 /// Execution plan for a single phase
+#[derive(Debug)]
 pub struct PhasePlan {
     batches: Vec<Vec<SystemId>>,
 }
 
 impl ExecutionPlan {
-    fn new() -> Self {
-        Self { phases: Vec::new() }
-    }
-
-    fn add_phase(&mut self, phase: SystemPhase, plan: PhasePlan) {
-        self.phases.push((phase, plan));
-    }
-
     /// Get phases in execution order
     pub fn phases(&self) -> &[(SystemPhase, PhasePlan)] {
         &self.phases
@@ -210,117 +167,710 @@ impl PhasePlan {
     }
 }
 
-/// System scheduler with thread pool
+/// System scheduler
+///
+/// Dependencies must be registered before their dependents, so the
+/// dependency graph can never contain a cycle.
 pub struct SystemScheduler {
-    systems: HashMap<SystemId, Box<dyn System>>,
-    execution_plan: ExecutionPlan,
-    thread_pool: ThreadPool,
+    systems: Vec<SystemEntry>, // Indexed by SystemId
+    execution_plan: Option<ExecutionPlan>,
+    mode: ExecutionMode,
 }
 
 impl SystemScheduler {
-    pub fn new(thread_count: usize) -> Self {
+    /// Create a scheduler that runs batches in parallel
+    pub fn new() -> Self {
+        Self::with_mode(ExecutionMode::Parallel)
+    }
+
+    /// Create a scheduler with an explicit execution mode
+    pub fn with_mode(mode: ExecutionMode) -> Self {
         Self {
-            systems: HashMap::new(),
-            execution_plan: ExecutionPlan::new(),
-            thread_pool: ThreadPool::new(thread_count),
+            systems: Vec::new(),
+            execution_plan: None,
+            mode,
         }
     }
 
+    /// Current execution mode
+    pub fn mode(&self) -> ExecutionMode {
+        self.mode
+    }
+
+    /// Change the execution mode (the plan is unaffected)
+    pub fn set_mode(&mut self, mode: ExecutionMode) {
+        self.mode = mode;
+    }
+
+    /// Number of registered systems
+    pub fn system_count(&self) -> usize {
+        self.systems.len()
+    }
+
     /// Add a system to the scheduler
-    pub fn add_system(&mut self, system: Box<dyn System>) {
-        let id = system.id();
-        self.systems.insert(id, system);
+    pub fn add_system(&mut self, system: impl System + 'static) -> SystemId {
+        self.push(SystemKind::Shared(Box::new(system)), HashSet::new())
+    }
+
+    /// Add a system that must run after the given systems
+    ///
+    /// Dependencies may live in the same or an earlier phase.
+    pub fn add_system_after(
+        &mut self,
+        system: impl System + 'static,
+        dependencies: &[SystemId],
+    ) -> Result<SystemId, SchedulerError> {
+        self.push_after(SystemKind::Shared(Box::new(system)), dependencies)
+    }
+
+    /// Add a system that runs alone with full access to the world
+    pub fn add_exclusive_system(&mut self, system: impl ExclusiveSystem) -> SystemId {
+        self.push(SystemKind::Exclusive(Box::new(system)), HashSet::new())
     }
 
-    /// Build execution plan from current systems
-    pub fn build_plan(&mut self) {
-        let mut graph = DependencyGraph::new();
-        
-        for system in self.systems.values() {
-            graph.add_system(system.as_ref());
+    /// Add an exclusive system that must run after the given systems
+    pub fn add_exclusive_system_after(
+        &mut self,
+        system: impl ExclusiveSystem,
+        dependencies: &[SystemId],
+    ) -> Result<SystemId, SchedulerError> {
+        self.push_after(SystemKind::Exclusive(Box::new(system)), dependencies)
+    }
+
+    /// First registered exclusive system of type `S`
+    pub fn exclusive_system<S: ExclusiveSystem>(&self) -> Option<&S> {
+        self.systems.iter().find_map(|entry| match &entry.system {
+            SystemKind::Exclusive(system) => (&**system as &dyn Any).downcast_ref::<S>(),
+            SystemKind::Shared(_) => None,
+        })
+    }
+
+    /// First registered exclusive system of type `S`, mutably
+    pub fn exclusive_system_mut<S: ExclusiveSystem>(&mut self) -> Option<&mut S> {
+        self.systems.iter_mut().find_map(|entry| match &mut entry.system {
+            SystemKind::Exclusive(system) => (&mut **system as &mut dyn Any).downcast_mut::<S>(),
+            SystemKind::Shared(_) => None,
+        })
+    }
+
+    fn push_after(&mut self, system: SystemKind, dependencies: &[SystemId]) -> Result<SystemId, SchedulerError> {
+        let phase = system.phase();
+        for &dependency in dependencies {
+            let Some(entry) = self.entry(dependency) else {
+                return Err(SchedulerError::UnknownDependency {
+                    system: system.name().to_string(),
+                    dependency,
+                });
+            };
+            if entry.phase > phase {
+                return Err(SchedulerError::DependencyInLaterPhase {
+                    system: system.name().to_string(),
+                    phase,
+                    dependency: entry.system.name().to_string(),
+                    dependency_phase: entry.phase,
+                });
+            }
         }
-        
-        self.execution_plan = graph.generate_execution_plan();
+        Ok(self.push(system, dependencies.iter().copied().collect()))
+    }
+
+    /// Name of a registered system
+    pub fn system_name(&self, id: SystemId) -> Option<&str> {
+        self.entry(id).map(|entry| entry.system.name())
     }
 
-    /// Execute all systems for one frame
-    pub fn execute_frame(&mut self, world: &crate::ecs::World, delta_time: f32) {
-        for (phase, phase_plan) in self.execution_plan.phases() {
+    /// Execution plan for the registered systems (built on first use)
+    pub fn execution_plan(&mut self) -> &ExecutionPlan {
+        self.execution_plan.get_or_insert_with(|| Self::build_plan(&self.systems))
+    }
+
+    /// Execute all per-frame systems (every phase except `FixedUpdate`)
+    pub fn execute_frame(&mut self, world: &mut World, delta_time: f32) {
+        self.execute_phases(world, delta_time, |phase| phase != SystemPhase::FixedUpdate);
+        world.update_trackers();
+    }
+
+    /// Execute the `FixedUpdate` systems for one fixed step of `fixed_delta` seconds
+    pub fn execute_fixed_step(&mut self, world: &mut World, fixed_delta: f32) {
+        self.execute_phases(world, fixed_delta, |phase| phase == SystemPhase::FixedUpdate);
+    }
+
+    fn execute_phases(&mut self, world: &mut World, delta_time: f32, include: impl Fn(SystemPhase) -> bool) {
+        let plan = self.execution_plan.take().unwrap_or_else(|| Self::build_plan(&self.systems));
+
+        let mut commands = Commands::new();
+
+        for (_, phase_plan) in plan.phases().iter().filter(|(phase, _)| include(*phase)) {
             for batch in phase_plan.batches() {
-                if batch.len() == 1 {
-                    // Single system - execute directly
-                    let system_id = batch[0];
-                    if let Some(system) = self.systems.get_mut(&system_id) {
-                        system.execute(world, delta_time);
+                match self.mode {
+                    ExecutionMode::SingleThreaded => {
+                        for &system_id in batch {
+                            self.execute_batch(&[system_id], world, &mut commands, delta_time);
+                        }
                     }
+                    ExecutionMode::Parallel => self.execute_batch(batch, world, &mut commands, delta_time),
+                }
+            }
+
+            // Phase boundary: the sync point for structural changes
+            commands.apply(world);
+        }
+
+        self.execution_plan = Some(plan);
+    }
+
+    fn push(&mut self, system: SystemKind, dependencies: HashSet<SystemId>) -> SystemId {
+        let id = SystemId(self.systems.len() as u64);
+        self.systems.push(SystemEntry {
+            phase: system.phase(),
+            access: system.access(),
+            system,
+            dependencies,
+            last_run: 0,
+        });
+        self.execution_plan = None;
+        id
+    }
+
+    fn entry(&self, id: SystemId) -> Option<&SystemEntry> {
+        usize::try_from(id.0).ok().and_then(|index| self.systems.get(index))
+    }
+
+    /// Generate execution plan with parallel batches
+    fn build_plan(systems: &[SystemEntry]) -> ExecutionPlan {
+        let phases = SystemPhase::ALL
+            .iter()
+            .filter_map(|&phase| {
+                let ids: Vec<usize> = (0..systems.len()).filter(|&index| systems[index].phase == phase).collect();
+                (!ids.is_empty()).then(|| (phase, Self::build_phase_plan(systems, ids)))
+            })
+            .collect();
+        ExecutionPlan { phases }
+    }
+
+    /// Greedily pack a phase into batches, preserving registration order between conflicting systems
+    fn build_phase_plan(systems: &[SystemEntry], mut remaining: Vec<usize>) -> PhasePlan {
+        let mut batches = Vec::new();
+
+        while !remaining.is_empty() {
+            let mut batch: Vec<usize> = Vec::new();
+            let mut deferred: Vec<usize> = Vec::new();
+
+            for &index in &remaining {
+                let node = &systems[index];
+                // Dependencies must have run in an earlier batch
+                let deps_satisfied = node.dependencies
+                    .iter()
+                    .all(|dep| !remaining.contains(&(dep.0 as usize)));
+                // A system may not overtake an earlier conflicting one, nor share a batch with it
+                let has_conflicts = batch.iter()
+                    .chain(&deferred)
+                    .any(|&other| node.access.conflicts_with(&systems[other].access));
+
+                if deps_satisfied && !has_conflicts {
+                    batch.push(index);
                 } else {
-                    // Multiple systems - execute in parallel
-                    self.execute_parallel_batch(batch, world, delta_time);
+                    deferred.push(index);
                 }
             }
+
+            // The earliest remaining system has no remaining dependencies or earlier conflicts
+            assert!(!batch.is_empty(), "System dependencies must be registered first");
+
+            batches.push(batch.iter().map(|&index| SystemId(index as u64)).collect());
+            remaining = deferred;
         }
+
+        PhasePlan { batches }
     }
 
-    fn execute_parallel_batch(&mut self, batch: &[SystemId], world: &crate::ecs::World, delta_time: f32) {
-        // This is a simplified version - real implementation would need
-        // to safely share world access across threads
-        for &system_id in batch {
-            if let Some(system) = self.systems.get_mut(&system_id) {
-                system.execute(world, delta_time);
+    fn execute_batch(&mut self, batch: &[SystemId], world: &mut World, commands: &mut Commands, delta_time: f32) {
+        // Exclusive systems never share a batch (their access conflicts with everything)
+        if let [id] = batch {
+            if let SystemKind::Exclusive(system) = &mut self.systems[id.0 as usize].system {
+                // Sync point: the system sees the structural changes queued so far
+                commands.apply(world);
+                world.increment_change_tick();
+                system.run(world, delta_time);
+                return;
+            }
+        }
+
+        let mut systems: Vec<&mut Box<dyn System>> = Vec::with_capacity(batch.len());
+        let mut accesses: Vec<(&SystemAccess, u64)> = Vec::with_capacity(batch.len());
+        let mut last_runs: Vec<&mut u64> = Vec::with_capacity(batch.len());
+        for (index, entry) in self.systems.iter_mut().enumerate() {
+            if batch.contains(&SystemId(index as u64)) {
+                let SystemKind::Shared(system) = &mut entry.system else {
+                    unreachable!("exclusive systems run alone");
+                };
+                systems.push(system);
+                accesses.push((&entry.access, entry.last_run));
+                last_runs.push(&mut entry.last_run);
             }
         }
+        let mut views = SystemWorld::split(world, &accesses);
+        let mut buffers: Vec<Commands> = systems.iter().map(|_| Commands::new()).collect();
+        let mut jobs = systems.into_iter().zip(views.iter_mut()).zip(buffers.iter_mut());
+
+        // Single-system batches run inline; otherwise the first system runs on the
+        // calling thread and the rest on the persistent worker pool
+        if let Some(((first, first_view), first_commands)) = jobs.next() {
+            if jobs.len() == 0 {
+                first.execute(first_view, first_commands, delta_time);
+            } else {
+                rayon::in_place_scope(|scope| {
+                    for ((system, view), system_commands) in jobs {
+                        scope.spawn(move |_| system.execute(view, system_commands, delta_time));
+                    }
+                    first.execute(first_view, first_commands, delta_time);
+                });
+            }
+        }
+
+        for (last_run, view) in last_runs.into_iter().zip(&views) {
+            *last_run = view.change_tick();
+        }
+
+        // Keep registration order regardless of which thread finished first
+        for mut buffer in buffers {
+            commands.append(&mut buffer);
+        }
     }
 }
 
-/// Simple thread pool for parallel execution
-pub struct ThreadPool {
-    workers: Vec<Worker>,
-    sender: Sender<Job>,
+impl Default for SystemScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-type Job = Box<dyn FnOnce() + Send + 'static>;
-
-impl ThreadPool {
-    pub fn new(size: usize) -> Self {
-        let (sender, receiver) = bounded(100);
-        let receiver = Arc::new(Mutex::new(receiver));
-        
-        let mut workers = Vec::with_capacity(size);
-        
-        for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Changed, Entity, Resource, With};
+    use crate::foundation::time::FixedTimestep;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, PartialEq)]
+    struct Position(f32);
+    impl Component for Position {}
+
+    #[derive(Debug, PartialEq)]
+    struct Velocity(f32);
+    impl Component for Velocity {}
+
+    #[derive(Debug, PartialEq)]
+    struct Health(f32);
+    impl Component for Health {}
+
+    /// Test system: declared access plus a closure body, logging its name when run
+    struct TestSystem {
+        name: &'static str,
+        phase: SystemPhase,
+        access: SystemAccess,
+        log: Arc<Mutex<Vec<&'static str>>>,
+        body: fn(&mut SystemWorld<'_>, &mut Commands, f32),
+    }
+
+    impl TestSystem {
+        fn new(name: &'static str, access: SystemAccess, log: &Arc<Mutex<Vec<&'static str>>>) -> Self {
+            Self { name, phase: SystemPhase::Update, access, log: Arc::clone(log), body: |_, _, _| {} }
+        }
+
+        fn in_phase(mut self, phase: SystemPhase) -> Self {
+            self.phase = phase;
+            self
+        }
+
+        fn with_body(mut self, body: fn(&mut SystemWorld<'_>, &mut Commands, f32)) -> Self {
+            self.body = body;
+            self
+        }
+    }
+
+    impl System for TestSystem {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn phase(&self) -> SystemPhase {
+            self.phase
+        }
+
+        fn access(&self) -> SystemAccess {
+            self.access.clone()
+        }
+
+        fn execute(&mut self, world: &mut SystemWorld<'_>, commands: &mut Commands, delta_time: f32) {
+            (self.body)(world, commands, delta_time);
+            self.log.lock().unwrap().push(self.name);
         }
-        
-        ThreadPool { workers, sender }
     }
 
-    pub fn execute<F>(&self, f: F) 
-    where 
-        F: FnOnce() + Send + 'static,
-    {
-        let job = Box::new(f);
-        self.sender.send(job).unwrap();
+    fn batch_names(scheduler: &mut SystemScheduler) -> Vec<Vec<String>> {
+        let batches: Vec<Vec<SystemId>> = scheduler.execution_plan()
+            .phases()
+            .iter()
+            .flat_map(|(_, plan)| plan.batches().iter().cloned())
+            .collect();
+        batches.iter()
+            .map(|batch| batch.iter().map(|&id| scheduler.system_name(id).unwrap().to_string()).collect())
+            .collect()
     }
-}
 
-struct Worker {
-    id: usize,
-    thread: thread::JoinHandle<()>,
-}
+    #[test]
+    fn test_non_conflicting_systems_share_a_batch() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("move", SystemAccess::new().write::<Position>().read::<Velocity>(), &log));
+        scheduler.add_system(TestSystem::new("regen", SystemAccess::new().write::<Health>(), &log));
+        scheduler.add_system(TestSystem::new("read_vel", SystemAccess::new().read::<Velocity>(), &log));
+        scheduler.add_system(TestSystem::new("read_pos", SystemAccess::new().read::<Position>(), &log));
+
+        assert_eq!(batch_names(&mut scheduler), vec![
+            vec!["move", "regen", "read_vel"],
+            vec!["read_pos"],
+        ]);
+    }
+
+    #[test]
+    fn test_conflicting_systems_keep_registration_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("a", SystemAccess::new().write::<Position>(), &log));
+        scheduler.add_system(TestSystem::new("b", SystemAccess::new().write::<Position>().write::<Velocity>(), &log));
+        // Touches only Velocity, but must not overtake "b"
+        scheduler.add_system(TestSystem::new("c", SystemAccess::new().read::<Velocity>(), &log));
+
+        assert_eq!(batch_names(&mut scheduler), vec![vec!["a"], vec!["b"], vec!["c"]]);
+    }
 
-impl Worker {
-    fn new(id: usize, receiver: Arc<Mutex<Receiver<Job>>>) -> Worker {
-        let thread = thread::spawn(move || loop {
-            let receiver = receiver.lock().unwrap();
-            match receiver.recv() {
-                Ok(job) => {
-                    drop(receiver);
-                    job();
+    #[test]
+    fn test_query_access_declaration() {
+        let access = SystemAccess::new()
+            .query_filtered::<(&mut Position, &Velocity), With<Health>>();
+        assert!(access.writes().contains(&ComponentType::of::<Position>()));
+        assert!(access.reads().contains(&ComponentType::of::<Velocity>()));
+        assert!(access.reads().contains(&ComponentType::of::<Health>()));
+        assert!(access.conflicts_with(&SystemAccess::new().write::<Health>()));
+        assert!(!access.conflicts_with(&SystemAccess::new().read::<Velocity>()));
+    }
+
+    #[test]
+    fn test_dependencies_and_phases() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        let render = scheduler.add_system(TestSystem::new("render", SystemAccess::new(), &log).in_phase(SystemPhase::Render));
+        let input = scheduler.add_system(TestSystem::new("input", SystemAccess::new(), &log).in_phase(SystemPhase::PreUpdate));
+        let logic = scheduler.add_system(TestSystem::new("logic", SystemAccess::new(), &log));
+        scheduler.add_system_after(TestSystem::new("after_logic", SystemAccess::new(), &log), &[logic, input]).unwrap();
+
+        assert_eq!(batch_names(&mut scheduler), vec![
+            vec!["input"],
+            vec!["logic"],
+            vec!["after_logic"],
+            vec!["render"],
+        ]);
+
+        let mut world = World::new();
+        scheduler.execute_frame(&mut world, 0.016);
+        assert_eq!(*log.lock().unwrap(), vec!["input", "logic", "after_logic", "render"]);
+
+        let result = scheduler.add_system_after(TestSystem::new("too_early", SystemAccess::new(), &log), &[render]);
+        assert!(matches!(result, Err(SchedulerError::DependencyInLaterPhase { .. })));
+        let result = scheduler.add_system_after(TestSystem::new("orphan", SystemAccess::new(), &log), &[SystemId(99)]);
+        assert!(matches!(result, Err(SchedulerError::UnknownDependency { .. })));
+    }
+
+    fn integrate(world: &mut SystemWorld<'_>, _commands: &mut Commands, delta_time: f32) {
+        for (_, (mut position, velocity)) in world.query_mut::<(&mut Position, &Velocity)>() {
+            position.0 += velocity.0 * delta_time;
+        }
+    }
+
+    fn accelerate(world: &mut SystemWorld<'_>, _commands: &mut Commands, _delta_time: f32) {
+        for (_, mut velocity) in world.query_mut::<&mut Velocity>() {
+            velocity.0 += 1.0;
+        }
+    }
+
+    fn drain(world: &mut SystemWorld<'_>, _commands: &mut Commands, delta_time: f32) {
+        for (_, mut health) in world.query_mut::<&mut Health>() {
+            health.0 -= delta_time;
+        }
+    }
+
+    fn run_simulation(mode: ExecutionMode) -> Vec<(Entity, f32, f32, f32)> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::with_mode(mode);
+        scheduler.add_system(TestSystem::new("integrate", SystemAccess::new().query::<(&mut Position, &Velocity)>(), &log)
+            .with_body(integrate));
+        scheduler.add_system(TestSystem::new("drain", SystemAccess::new().query::<&mut Health>(), &log)
+            .with_body(drain));
+        scheduler.add_system(TestSystem::new("accelerate", SystemAccess::new().query::<&mut Velocity>(), &log)
+            .with_body(accelerate));
+
+        let mut world = World::new();
+        for i in 0..64 {
+            let entity = world.create_entity();
+            world.add_component(entity, Position(0.0));
+            world.add_component(entity, Velocity(i as f32));
+            world.add_component(entity, Health(100.0));
+        }
+        for _ in 0..10 {
+            scheduler.execute_frame(&mut world, 0.5);
+        }
+
+        let mut state: Vec<_> = world.query::<(&Position, &Velocity, &Health)>()
+            .into_iter()
+            .map(|(entity, (position, velocity, health))| (entity, position.0, velocity.0, health.0))
+            .collect();
+        state.sort_by_key(|(entity, ..)| entity.id());
+        state
+    }
+
+    /// Position after every fixed step when frames arrive with the given delta times
+    fn run_fixed_simulation(frame_deltas: &[f32]) -> Vec<f32> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("integrate", SystemAccess::new().query::<(&mut Position, &Velocity)>(), &log)
+            .in_phase(SystemPhase::FixedUpdate)
+            .with_body(integrate));
+        scheduler.add_system(TestSystem::new("accelerate", SystemAccess::new().query::<&mut Velocity>(), &log)
+            .in_phase(SystemPhase::FixedUpdate)
+            .with_body(accelerate));
+
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0.0));
+        world.add_component(entity, Velocity(1.0));
+
+        let mut fixed = FixedTimestep::new(60.0);
+        let mut trajectory = Vec::new();
+        for &delta_time in frame_deltas {
+            for _ in 0..fixed.accumulate(delta_time) {
+                scheduler.execute_fixed_step(&mut world, fixed.step());
+                trajectory.push(world.get_component::<Position>(entity).unwrap().0);
+            }
+            scheduler.execute_frame(&mut world, delta_time);
+        }
+        trajectory
+    }
+
+    #[test]
+    fn test_fixed_steps_are_independent_of_frame_rate() {
+        let steady = run_fixed_simulation(&[1.0 / 60.0; 120]);
+        let jittery: Vec<f32> = (0..200).map(|i| [1.0 / 144.0, 1.0 / 30.0, 0.011, 1.0 / 90.0][i % 4]).collect();
+        let jittery = run_fixed_simulation(&jittery);
+
+        assert_eq!(steady.len(), 120);
+        assert!(jittery.len() >= steady.len());
+        assert_eq!(steady[..], jittery[..steady.len()]);
+    }
+
+    #[test]
+    fn test_parallel_matches_single_threaded() {
+        let parallel = run_simulation(ExecutionMode::Parallel);
+        let single = run_simulation(ExecutionMode::SingleThreaded);
+        assert_eq!(parallel, single);
+        // Entity 1: velocity 1..=10 integrated at dt 0.5 before each acceleration
+        assert_eq!(parallel[1].1, 0.5 * (1..=10).sum::<i32>() as f32);
+        assert_eq!(parallel[1].3, 95.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "did not declare write access")]
+    fn test_undeclared_access_panics() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        scheduler.add_system(TestSystem::new("sneaky", SystemAccess::new().read::<Velocity>(), &log)
+            .with_body(accelerate));
+        scheduler.execute_frame(&mut World::new(), 0.016);
+    }
+
+    struct Gravity(f32);
+    impl Resource for Gravity {}
+
+    struct Stats {
+        moved: usize,
+    }
+    impl Resource for Stats {}
+
+    #[test]
+    fn test_resource_access() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("integrate", SystemAccess::new().write::<Position>().read_resource::<Gravity>(), &log)
+            .with_body(|world, _, delta_time| {
+                let gravity = world.resource::<Gravity>().unwrap().0;
+                for (_, mut position) in world.query_mut::<&mut Position>() {
+                    position.0 += gravity * delta_time;
                 }
-                Err(_) => break,
+            }));
+        scheduler.add_system(TestSystem::new("read_gravity", SystemAccess::new().read_resource::<Gravity>(), &log));
+        scheduler.add_system(TestSystem::new("count", SystemAccess::new().read::<Position>().write_resource::<Stats>(), &log)
+            .with_body(|world, _, _| {
+                let moved = world.query::<&Position>().len();
+                world.resource_mut::<Stats>().unwrap().moved += moved;
+            }));
+        scheduler.add_system(TestSystem::new("tune", SystemAccess::new().write_resource::<Gravity>(), &log)
+            .with_body(|world, _, _| world.resource_mut::<Gravity>().unwrap().0 *= 2.0));
+
+        // Shared resource reads run together; a resource writer waits for its readers
+        assert_eq!(batch_names(&mut scheduler), vec![
+            vec!["integrate", "read_gravity"],
+            vec!["count", "tune"],
+        ]);
+
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0.0));
+        world.insert_resource(Gravity(-10.0));
+        world.insert_resource(Stats { moved: 0 });
+        scheduler.execute_frame(&mut world, 0.5);
+        scheduler.execute_frame(&mut world, 0.5);
+
+        assert_eq!(world.get_component::<Position>(entity), Some(&Position(-15.0)));
+        assert_eq!(world.resource::<Stats>().unwrap().moved, 2);
+        assert_eq!(world.resource::<Gravity>().unwrap().0, -40.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "did not declare write access to resource")]
+    fn test_undeclared_resource_access_panics() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        scheduler.add_system(TestSystem::new("sneaky", SystemAccess::new().read_resource::<Gravity>(), &log)
+            .with_body(|world, _, _| world.resource_mut::<Gravity>().unwrap().0 = 0.0));
+        let mut world = World::new();
+        world.insert_resource(Gravity(-10.0));
+        scheduler.execute_frame(&mut world, 0.016);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Lifetime(u32);
+    impl Component for Lifetime {}
+
+    fn expire(world: &mut SystemWorld<'_>, commands: &mut Commands, _delta_time: f32) {
+        for (entity, mut lifetime) in world.query_mut::<&mut Lifetime>() {
+            lifetime.0 -= 1;
+            if lifetime.0 == 0 {
+                commands.despawn(entity);
+                commands.spawn().insert(Health(1.0));
             }
+        }
+    }
+
+    fn count_health(world: &mut SystemWorld<'_>, commands: &mut Commands, _delta_time: f32) {
+        let count = world.query::<&Health>().len();
+        commands.add(move |world| {
+            let entity = world.create_entity();
+            world.add_component(entity, Position(count as f32));
         });
+    }
+
+    #[test]
+    fn test_commands_apply_at_phase_boundary() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("expire", SystemAccess::new().write::<Lifetime>(), &log)
+            .with_body(expire));
+        // Same phase: must not see the entity spawned by "expire" yet
+        scheduler.add_system(TestSystem::new("same_phase", SystemAccess::new().read::<Health>(), &log)
+            .with_body(count_health));
+        // Later phase: runs after the sync point
+        scheduler.add_system(TestSystem::new("next_phase", SystemAccess::new().read::<Health>(), &log)
+            .in_phase(SystemPhase::PostUpdate)
+            .with_body(count_health));
+
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Lifetime(1));
+        scheduler.execute_frame(&mut world, 0.016);
+
+        assert!(!world.is_alive(entity));
+        assert_eq!(world.query::<&Health>().len(), 1);
+        let mut counts: Vec<f32> = world.query::<&Position>().into_iter().map(|(_, p)| p.0).collect();
+        counts.sort_by(f32::total_cmp);
+        assert_eq!(counts, vec![0.0, 1.0]);
+    }
+
+    fn count_velocity_changes(world: &mut SystemWorld<'_>, _commands: &mut Commands, _delta_time: f32) {
+        for (_, mut position) in world.query_filtered_mut::<&mut Position, Changed<Velocity>>() {
+            position.0 += 1.0;
+        }
+    }
+
+    #[test]
+    fn test_systems_see_each_change_once() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("observer", SystemAccess::new()
+            .query_filtered::<&mut Position, Changed<Velocity>>(), &log)
+            .with_body(count_velocity_changes));
+
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..2).map(|_| {
+            let entity = world.create_entity();
+            world.add_component(entity, Position(0.0));
+            world.add_component(entity, Velocity(1.0));
+            entity
+        }).collect();
+        let positions = |world: &World| -> Vec<f32> {
+            entities.iter().map(|&entity| world.get_component::<Position>(entity).unwrap().0).collect()
+        };
+
+        scheduler.execute_frame(&mut world, 0.016);
+        assert_eq!(positions(&world), vec![1.0, 1.0]);
+
+        world.get_component_mut::<Velocity>(entities[0]).unwrap().0 = 2.0;
+        scheduler.execute_frame(&mut world, 0.016);
+        assert_eq!(positions(&world), vec![2.0, 1.0]);
+
+        scheduler.execute_frame(&mut world, 0.016);
+        assert_eq!(positions(&world), vec![2.0, 1.0]);
+    }
+
+    /// Exclusive test system: counts the `Position`s it can see and spawns one more
+    struct Census {
+        seen: Vec<usize>,
+    }
+
+    impl ExclusiveSystem for Census {
+        fn run(&mut self, world: &mut World, _delta_time: f32) {
+            self.seen.push(world.query::<&Position>().len());
+            let entity = world.create_entity();
+            world.add_component(entity, Position(0.0));
+        }
+    }
 
-        Worker { id, thread }
+    #[test]
+    fn test_exclusive_systems_run_alone() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TestSystem::new("expire", SystemAccess::new().write::<Lifetime>(), &log)
+            .with_body(expire));
+        scheduler.add_exclusive_system(Census { seen: Vec::new() });
+        scheduler.add_system(TestSystem::new("regen", SystemAccess::new().write::<Health>(), &log));
+
+        // Nothing may share a batch with (or overtake) the exclusive system
+        let batches = batch_names(&mut scheduler);
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0], vec!["expire"]);
+        assert_eq!(batches[2], vec!["regen"]);
+
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Lifetime(1));
+        world.add_component(entity, Position(0.0));
+        scheduler.execute_frame(&mut world, 0.016);
+        scheduler.execute_frame(&mut world, 0.016);
+
+        // Commands queued earlier in the phase (the despawn) are applied before it runs
+        assert_eq!(scheduler.exclusive_system::<Census>().unwrap().seen, vec![0, 1]);
+        scheduler.exclusive_system_mut::<Census>().unwrap().seen.clear();
+        assert!(scheduler.exclusive_system::<Census>().unwrap().seen.is_empty());
+        assert_eq!(world.query::<&Position>().len(), 2);
     }
 }
@@ -0,0 +1,533 @@
+//! Scene serialization: saving and loading a `World` as RON
+//!
+//! Components are written through a `ComponentRegistry`, which maps a stable
+//! name to serialize/deserialize hooks for each persistable component type.
+//! The registry is a world resource; engine components are registered by
+//! default and applications add their own with `World::register_scene_component`.
+//!
+//! Each component is stored as its own RON string keyed by the registered name,
+//! so scenes stay readable and unknown types are reported by name on load.
+//! Loaded entities get fresh handles: components holding `Entity` references
+//! implement `MapEntities` so those references are remapped to the new handles.
+//! `GlobalTransform` is not saved; the transform propagation pass rebuilds it.
+
+use super::{Component, Entity, World};
+use super::components::{
+    Children, ColliderComponent, CollisionStateComponent, JointComponent, LightComponent,
+    MovementComponent, Parent, RigidBodyComponent, TransformComponent,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Scene serialization errors
+#[derive(thiserror::Error, Debug)]
+pub enum SceneError {
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Parse error
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    /// Serialization error
+    #[error("Serialization error: {0}")]
+    Serialize(String),
+
+    /// The scene contains a component name that is not registered
+    #[error("Unknown scene component: {0}")]
+    UnknownComponent(String),
+
+    /// A component's data could not be deserialized
+    #[error("Invalid {component} data: {message}")]
+    InvalidComponent {
+        /// Registered component name
+        component: String,
+        /// Deserializer message
+        message: String,
+    },
+}
+
+/// Mapping from entities as saved in a scene to the entities spawned on load
+#[derive(Debug, Clone, Default)]
+pub struct EntityMap {
+    map: HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    /// Entity spawned for a saved entity, if it was part of the scene
+    pub fn get(&self, saved: Entity) -> Option<Entity> {
+        self.map.get(&saved).copied()
+    }
+
+    /// Number of mapped entities
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether no entities were mapped
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterate over `(saved, spawned)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.map.iter().map(|(saved, spawned)| (*saved, *spawned))
+    }
+}
+
+/// Components that store `Entity` references
+///
+/// Called after loading a scene so references point at the spawned entities.
+/// References to entities that were not part of the scene must be dropped:
+/// the saved handle may name an unrelated entity in the target world.
+pub trait MapEntities {
+    /// Rewrite every stored entity through `map`
+    ///
+    /// Returns `false` if the component is meaningless without a reference
+    /// that could not be mapped; it is then not added to the loaded entity.
+    fn map_entities(&mut self, map: &EntityMap) -> bool;
+}
+
+impl MapEntities for Parent {
+    fn map_entities(&mut self, map: &EntityMap) -> bool {
+        // Every live entity is saved, so only an already-despawned parent is
+        // unmapped; the child is loaded as a root
+        match map.get(self.0) {
+            Some(parent) => {
+                self.0 = parent;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl MapEntities for Children {
+    fn map_entities(&mut self, map: &EntityMap) -> bool {
+        self.0 = self.0.iter().filter_map(|&child| map.get(child)).collect();
+        true
+    }
+}
+
+impl MapEntities for CollisionStateComponent {
+    fn map_entities(&mut self, map: &EntityMap) -> bool {
+        self.colliding_with = self.colliding_with.iter().filter_map(|&entity| map.get(entity)).collect();
+        self.collision_entered = self.collision_entered.iter().filter_map(|&entity| map.get(entity)).collect();
+        self.collision_exited = self.collision_exited.iter().filter_map(|&entity| map.get(entity)).collect();
+        self.nearby_entities = self.nearby_entities.iter().filter_map(|&entity| map.get(entity)).collect();
+        true
+    }
+}
+
+impl MapEntities for JointComponent {
+    fn map_entities(&mut self, map: &EntityMap) -> bool {
+        // A joint missing one of its bodies is dropped rather than silently
+        // re-anchored to the world
+        let Some(body_a) = map.get(self.body_a) else { return false };
+        let body_b = match self.body_b {
+            Some(body) => match map.get(body) {
+                Some(mapped) => Some(mapped),
+                None => return false,
+            },
+            None => None,
+        };
+        self.body_a = body_a;
+        self.body_b = body_b;
+        true
+    }
+}
+
+/// Serialize/deserialize hooks for one component type
+struct ComponentRegistration {
+    name: String,
+    serialize: fn(&World, Entity) -> Option<Result<String, ron::Error>>,
+    deserialize: fn(&mut World, Entity, &str, &EntityMap) -> Result<(), ron::error::SpannedError>,
+}
+
+/// Registry of components that can be saved to and loaded from scenes
+///
+/// `ComponentRegistry::default()` registers the engine components
+/// (`TransformComponent`, `MovementComponent`, `LightComponent`,
+/// `ColliderComponent`, `RigidBodyComponent`, `CollisionStateComponent`,
+/// `JointComponent`, `Parent` and `Children`).
+pub struct ComponentRegistry {
+    registrations: Vec<ComponentRegistration>,
+    by_name: HashMap<String, usize>,
+    by_type: HashMap<TypeId, usize>,
+}
+
+impl ComponentRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            registrations: Vec::new(),
+            by_name: HashMap::new(),
+            by_type: HashMap::new(),
+        }
+    }
+
+    /// Register a component without entity references under a stable name
+    ///
+    /// Names must be unique; re-registering a type replaces its previous name.
+    pub fn register<T: Component + Serialize + DeserializeOwned>(&mut self, name: impl Into<String>) -> &mut Self {
+        self.insert::<T>(name.into(), deserialize_component::<T>)
+    }
+
+    /// Register a component whose `Entity` references are remapped on load
+    pub fn register_mapped<T: Component + Serialize + DeserializeOwned + MapEntities>(&mut self, name: impl Into<String>) -> &mut Self {
+        self.insert::<T>(name.into(), deserialize_mapped_component::<T>)
+    }
+
+    /// Whether a component name is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    /// Number of registered components
+    pub fn len(&self) -> usize {
+        self.registrations.len()
+    }
+
+    /// Whether no components are registered
+    pub fn is_empty(&self) -> bool {
+        self.registrations.is_empty()
+    }
+
+    fn insert<T: Component + Serialize>(
+        &mut self,
+        name: String,
+        deserialize: fn(&mut World, Entity, &str, &EntityMap) -> Result<(), ron::error::SpannedError>,
+    ) -> &mut Self {
+        let registration = ComponentRegistration { name: name.clone(), serialize: serialize_component::<T>, deserialize };
+        match self.by_type.get(&TypeId::of::<T>()) {
+            Some(&index) => {
+                self.by_name.remove(&self.registrations[index].name);
+                self.registrations[index] = registration;
+                self.by_name.insert(name, index);
+            }
+            None => {
+                let index = self.registrations.len();
+                self.registrations.push(registration);
+                self.by_name.insert(name, index);
+                self.by_type.insert(TypeId::of::<T>(), index);
+            }
+        }
+        self
+    }
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register::<TransformComponent>("TransformComponent")
+            .register::<MovementComponent>("MovementComponent")
+            .register::<LightComponent>("LightComponent")
+            .register::<ColliderComponent>("ColliderComponent")
+            .register::<RigidBodyComponent>("RigidBodyComponent")
+            .register_mapped::<CollisionStateComponent>("CollisionStateComponent")
+            .register_mapped::<JointComponent>("JointComponent")
+            .register_mapped::<Parent>("Parent")
+            .register_mapped::<Children>("Children");
+        registry
+    }
+}
+
+fn serialize_component<T: Component + Serialize>(world: &World, entity: Entity) -> Option<Result<String, ron::Error>> {
+    world.get_component::<T>(entity).map(ron::to_string)
+}
+
+fn deserialize_component<T: Component + DeserializeOwned>(
+    world: &mut World,
+    entity: Entity,
+    data: &str,
+    _map: &EntityMap,
+) -> Result<(), ron::error::SpannedError> {
+    let component: T = ron::from_str(data)?;
+    world.add_component(entity, component);
+    Ok(())
+}
+
+fn deserialize_mapped_component<T: Component + DeserializeOwned + MapEntities>(
+    world: &mut World,
+    entity: Entity,
+    data: &str,
+    map: &EntityMap,
+) -> Result<(), ron::error::SpannedError> {
+    let mut component: T = ron::from_str(data)?;
+    if component.map_entities(map) {
+        world.add_component(entity, component);
+    }
+    Ok(())
+}
+
+/// On-disk scene layout
+#[derive(Serialize, Deserialize)]
+struct SceneFile {
+    entities: Vec<SceneEntity>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SceneEntity {
+    entity: Entity,
+    components: BTreeMap<String, String>, // Registered name -> component RON
+}
+
+impl World {
+    /// Register a user component for scene saving/loading
+    ///
+    /// Adds the default `ComponentRegistry` resource first if the world has none.
+    pub fn register_scene_component<T: Component + Serialize + DeserializeOwned>(&mut self, name: impl Into<String>) {
+        self.scene_registry_mut().register::<T>(name);
+    }
+
+    /// Register a user component holding `Entity` references for scene saving/loading
+    pub fn register_scene_component_mapped<T: Component + Serialize + DeserializeOwned + MapEntities>(&mut self, name: impl Into<String>) {
+        self.scene_registry_mut().register_mapped::<T>(name);
+    }
+
+    /// Serialize all live entities and their registered components to a RON string
+    ///
+    /// Components that are not registered are skipped.
+    pub fn to_scene_string(&self) -> Result<String, SceneError> {
+        let default_registry;
+        let registry = match self.resource::<ComponentRegistry>() {
+            Some(registry) => registry,
+            None => {
+                default_registry = ComponentRegistry::default();
+                &default_registry
+            }
+        };
+
+        let mut entities = Vec::with_capacity(self.entity_count());
+        for &entity in self.entities() {
+            let mut components = BTreeMap::new();
+            for registration in &registry.registrations {
+                if let Some(data) = (registration.serialize)(self, entity) {
+                    let data = data.map_err(|e| SceneError::Serialize(format!("{}: {}", registration.name, e)))?;
+                    components.insert(registration.name.clone(), data);
+                }
+            }
+            entities.push(SceneEntity { entity, components });
+        }
+
+        ron::ser::to_string_pretty(&SceneFile { entities }, Default::default())
+            .map_err(|e| SceneError::Serialize(e.to_string()))
+    }
+
+    /// Spawn the entities of a RON scene string, returning the saved -> spawned mapping
+    ///
+    /// Loaded entities are added alongside existing ones. Nothing is spawned if
+    /// the scene names an unregistered component, and entities spawned so far
+    /// are despawned again if a component fails to deserialize.
+    pub fn load_scene_str(&mut self, scene: &str) -> Result<EntityMap, SceneError> {
+        let scene: SceneFile = ron::from_str(scene).map_err(|e| SceneError::Parse(e.to_string()))?;
+        match self.remove_resource::<ComponentRegistry>() {
+            Some(registry) => {
+                let result = self.spawn_scene(&registry, scene);
+                self.insert_resource(registry);
+                result
+            }
+            None => self.spawn_scene(&ComponentRegistry::default(), scene),
+        }
+    }
+
+    /// Save all live entities and their registered components to a RON file
+    pub fn save_scene(&self, path: impl AsRef<Path>) -> Result<(), SceneError> {
+        let contents = self.to_scene_string()?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Load a RON scene file saved by `save_scene`, returning the saved -> spawned mapping
+    pub fn load_scene(&mut self, path: impl AsRef<Path>) -> Result<EntityMap, SceneError> {
+        let contents = std::fs::read_to_string(path)?;
+        self.load_scene_str(&contents)
+    }
+
+    fn scene_registry_mut(&mut self) -> &mut ComponentRegistry {
+        if !self.contains_resource::<ComponentRegistry>() {
+            self.insert_resource(ComponentRegistry::default());
+        }
+        self.resource_mut::<ComponentRegistry>().expect("registry was just inserted")
+    }
+
+    fn spawn_scene(&mut self, registry: &ComponentRegistry, scene: SceneFile) -> Result<EntityMap, SceneError> {
+        let unknown = scene.entities.iter()
+            .flat_map(|saved| saved.components.keys())
+            .find(|name| !registry.contains(name));
+        if let Some(name) = unknown {
+            return Err(SceneError::UnknownComponent(name.clone()));
+        }
+
+        let mut map = EntityMap::default();
+        for saved in &scene.entities {
+            let spawned = self.create_entity();
+            map.map.insert(saved.entity, spawned);
+        }
+
+        for saved in &scene.entities {
+            let entity = map.map[&saved.entity];
+            for (name, data) in &saved.components {
+                let registration = &registry.registrations[registry.by_name[name]];
+                if let Err(e) = (registration.deserialize)(self, entity, data, &map) {
+                    for (_, spawned) in map.iter() {
+                        self.despawn(spawned);
+                    }
+                    return Err(SceneError::InvalidComponent { component: name.clone(), message: e.to_string() });
+                }
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::LightFactory;
+    use crate::foundation::math::Vec3;
+    use crate::physics::collision::CollisionShape;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Health(u32);
+    impl Component for Health {}
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Target(Entity);
+    impl Component for Target {}
+    impl MapEntities for Target {
+        fn map_entities(&mut self, map: &EntityMap) -> bool {
+            map.get(self.0).map(|entity| self.0 = entity).is_some()
+        }
+    }
+
+    #[test]
+    fn test_scene_round_trip_remaps_entities() {
+        let mut world = World::new();
+        let ship = world.create_entity();
+        let asteroid = world.create_entity();
+        let turret = world.create_entity();
+        world.add_component(ship, TransformComponent::from_position(Vec3::new(1.0, 2.0, 3.0)));
+        world.add_component(ship, MovementComponent::with_velocity(Vec3::new(0.0, 0.0, -4.0)));
+        world.add_component(ship, ColliderComponent::new(CollisionShape::sphere(1.5)).with_layers(2, 6));
+        let mut state = CollisionStateComponent::default();
+        state.colliding_with.insert(asteroid);
+        world.add_component(ship, state);
+        world.add_component(asteroid, LightFactory::point(Vec3::new(0.0, 5.0, 0.0), Vec3::new(1.0, 0.5, 0.0), 2.0, 10.0));
+        world.add_component(turret, TransformComponent::from_position(Vec3::new(0.0, 1.0, 0.0)));
+        world.set_parent(turret, ship).unwrap();
+        world.register_scene_component::<Health>("Health");
+        world.register_scene_component_mapped::<Target>("Target");
+        world.add_component(turret, Health(75));
+        world.add_component(turret, Target(asteroid));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scene.ron");
+        world.save_scene(&path).unwrap();
+
+        // Load into a world with existing entities so the handles differ
+        let mut loaded = World::new();
+        loaded.register_scene_component::<Health>("Health");
+        loaded.register_scene_component_mapped::<Target>("Target");
+        let existing = loaded.create_entity();
+        let map = loaded.load_scene(&path).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(loaded.entity_count(), 4);
+        let (new_ship, new_asteroid, new_turret) = (map.get(ship).unwrap(), map.get(asteroid).unwrap(), map.get(turret).unwrap());
+        assert_ne!(new_ship, ship);
+        assert!(!map.iter().any(|(_, spawned)| spawned == existing));
+
+        let transform = loaded.get_component::<TransformComponent>(new_ship).unwrap();
+        assert_eq!(transform, world.get_component::<TransformComponent>(ship).unwrap());
+        let movement = loaded.get_component::<MovementComponent>(new_ship).unwrap();
+        assert_eq!(movement.velocity, Vec3::new(0.0, 0.0, -4.0));
+        let collider = loaded.get_component::<ColliderComponent>(new_ship).unwrap();
+        assert_eq!((collider.layer, collider.mask), (2, 6));
+        assert!(matches!(collider.shape, CollisionShape::Sphere(radius) if radius == 1.5));
+        let light = loaded.get_component::<LightComponent>(new_asteroid).unwrap();
+        assert_eq!(light.position, Vec3::new(0.0, 5.0, 0.0));
+
+        // Entity references point at the spawned entities
+        assert!(loaded.get_component::<CollisionStateComponent>(new_ship).unwrap().is_colliding_with(new_asteroid));
+        assert_eq!(loaded.parent_of(new_turret), Some(new_ship));
+        assert!(loaded.get_component::<Children>(new_ship).unwrap().contains(new_turret));
+        assert_eq!(loaded.get_component::<Health>(new_turret), Some(&Health(75)));
+        assert_eq!(loaded.get_component::<Target>(new_turret), Some(&Target(new_asteroid)));
+        assert_eq!(loaded.world_transform(new_turret).unwrap().position, Vec3::new(1.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_unsaved_parent_is_dropped() {
+        let mut world = World::new();
+        let parent = world.create_entity();
+        let child = world.create_entity();
+        world.add_component(child, TransformComponent::from_position(Vec3::new(1.0, 0.0, 0.0)));
+        world.set_parent(child, parent).unwrap();
+        // Despawning leaves the child's Parent pointing at a dead handle
+        world.despawn(parent);
+        let scene = world.to_scene_string().unwrap();
+
+        // The target world already has a live entity with that same handle
+        let mut loaded = World::new();
+        let existing = loaded.create_entity();
+        assert_eq!(existing, parent);
+        let map = loaded.load_scene_str(&scene).unwrap();
+        let new_child = map.get(child).unwrap();
+
+        assert!(loaded.get_component::<Parent>(new_child).is_none());
+        assert_eq!(loaded.parent_of(new_child), None);
+        assert!(loaded.get_component::<Children>(existing).is_none());
+        assert_eq!(loaded.world_transform(new_child).unwrap().position, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_joint_to_unsaved_body_is_dropped() {
+        let mut world = World::new();
+        let gone = world.create_entity();
+        let door = world.create_entity();
+        let frame = world.create_entity();
+        let anchored = world.create_entity();
+        world.add_component(door, JointComponent::hinge(door, Some(frame), Vec3::zeros(), Vec3::y()));
+        world.add_component(frame, JointComponent::fixed(frame, Some(gone), Vec3::zeros()));
+        world.add_component(anchored, JointComponent::ball_socket(anchored, None, Vec3::zeros()));
+        world.despawn(gone);
+        let scene = world.to_scene_string().unwrap();
+
+        // `gone`'s handle names a live, unrelated entity in the target world
+        let mut loaded = World::new();
+        let existing = loaded.create_entity();
+        assert_eq!(existing, gone);
+        let map = loaded.load_scene_str(&scene).unwrap();
+
+        let hinge = loaded.get_component::<JointComponent>(map.get(door).unwrap()).unwrap();
+        assert_eq!((hinge.body_a, hinge.body_b), (map.get(door).unwrap(), map.get(frame)));
+        assert!(loaded.get_component::<JointComponent>(map.get(frame).unwrap()).is_none());
+        let world_joint = loaded.get_component::<JointComponent>(map.get(anchored).unwrap()).unwrap();
+        assert_eq!((world_joint.body_a, world_joint.body_b), (map.get(anchored).unwrap(), None));
+    }
+
+    #[test]
+    fn test_unknown_component_spawns_nothing() {
+        let mut world = World::new();
+        world.register_scene_component::<Health>("Health");
+        let entity = world.create_entity();
+        world.add_component(entity, Health(10));
+        let scene = world.to_scene_string().unwrap();
+
+        let mut loaded = World::new();
+        assert!(matches!(loaded.load_scene_str(&scene), Err(SceneError::UnknownComponent(name)) if name == "Health"));
+        assert_eq!(loaded.entity_count(), 0);
+
+        // Unregistered components are skipped on save
+        let mut plain = World::new();
+        let entity = plain.create_entity();
+        plain.add_component(entity, Health(10));
+        let map = loaded.load_scene_str(&plain.to_scene_string().unwrap()).unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(loaded.get_component::<Health>(map.get(entity).unwrap()), None);
+    }
+}
@@ -1,7 +1,413 @@
-//! System trait and implementations
+//! System trait and per-system world access
+//!
+//! Systems declare the components and resources they read and write up front. The scheduler
+//! uses these declarations to run non-conflicting systems in parallel, and hands
+//! each system a `SystemWorld` that only exposes the storages and resources it declared.
+//! Structural changes (spawn/despawn/insert/remove) are recorded in `Commands`
+//! and applied by the scheduler at the end of the phase.
+
+use super::{Commands, Component, Entity, Resource, World};
+use super::query::{Query, QueryAccess, QueryData, QueryFilter, ReadOnlyQueryData, StorageBorrows};
+use super::scheduler::{ComponentType, SystemPhase};
+use super::resource::ResourceBox;
+use super::world::{AnyStorage, ComponentStorage, EntityTable, RemovedComponentsLog};
+use std::any::{type_name, Any, TypeId};
+use std::collections::{HashMap, HashSet};
 
 /// System trait for processing entities and components
-pub trait System {
-    /// Run the system
-    fn run(&mut self, world: &mut crate::ecs::World);
+///
+/// ```ignore
+/// struct Movement;
+///
+/// impl System for Movement {
+///     fn access(&self) -> SystemAccess {
+///         SystemAccess::new().query::<(&mut TransformComponent, &MovementComponent)>()
+///     }
+///
+///     fn execute(&mut self, world: &mut SystemWorld, commands: &mut Commands, delta_time: f32) {
+///         for (entity, (transform, movement)) in world.query_mut::<(&mut TransformComponent, &MovementComponent)>() {
+///             transform.position += movement.velocity * delta_time;
+///             if transform.position.y < KILL_PLANE {
+///                 commands.despawn(entity);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait System: Send {
+    /// Human readable name for diagnostics
+    fn name(&self) -> &str {
+        type_name::<Self>()
+    }
+
+    /// Which phase this system belongs to
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::Update
+    }
+
+    /// Components this system reads and writes (queried once, on registration)
+    fn access(&self) -> SystemAccess;
+
+    /// Execute the system, recording structural changes in `commands`
+    fn execute(&mut self, world: &mut SystemWorld<'_>, commands: &mut Commands, delta_time: f32);
+}
+
+/// System that needs the whole `World` for the duration of its run
+///
+/// For subsystems built on `&World`/`&mut World` (collision detection, scene
+/// sync) that cannot work through a `SystemWorld`. Exclusive systems run alone
+/// on the calling thread, after the commands recorded earlier in their phase
+/// have been applied.
+pub trait ExclusiveSystem: Any + Send {
+    /// Human readable name for diagnostics
+    fn name(&self) -> &str {
+        type_name::<Self>()
+    }
+
+    /// Which phase this system belongs to
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::Update
+    }
+
+    /// Run the system with full access to the world
+    fn run(&mut self, world: &mut World, delta_time: f32);
+}
+
+/// Component and resource access declared by a system (used for conflict detection)
+///
+/// A component or resource that is both read and written counts as written.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAccess {
+    exclusive: bool, // Whole-world access: conflicts with every other system
+    reads: HashSet<ComponentType>,
+    writes: HashSet<ComponentType>,
+    resource_reads: HashSet<TypeId>,
+    resource_writes: HashSet<TypeId>,
+}
+
+impl SystemAccess {
+    /// Create an empty access set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Access of an `ExclusiveSystem`, which conflicts with every other system
+    pub fn exclusive() -> Self {
+        Self { exclusive: true, ..Self::default() }
+    }
+
+    /// Declare read access to component `T`
+    #[must_use]
+    pub fn read<T: Component>(mut self) -> Self {
+        self.add_read(ComponentType::of::<T>());
+        self
+    }
+
+    /// Declare write access to component `T`
+    #[must_use]
+    pub fn write<T: Component>(mut self) -> Self {
+        self.add_write(ComponentType::of::<T>());
+        self
+    }
+
+    /// Declare read access to resource `R`
+    #[must_use]
+    pub fn read_resource<R: Resource>(mut self) -> Self {
+        let type_id = TypeId::of::<R>();
+        if !self.resource_writes.contains(&type_id) {
+            self.resource_reads.insert(type_id);
+        }
+        self
+    }
+
+    /// Declare write access to resource `R`
+    #[must_use]
+    pub fn write_resource<R: Resource>(mut self) -> Self {
+        let type_id = TypeId::of::<R>();
+        self.resource_reads.remove(&type_id);
+        self.resource_writes.insert(type_id);
+        self
+    }
+
+    /// Declare the access of a query the system runs
+    #[must_use]
+    pub fn query<Q: QueryData>(self) -> Self {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Declare the access of a filtered query the system runs
+    ///
+    /// Filter components (`With`/`Without`) count as reads.
+    #[must_use]
+    pub fn query_filtered<Q: QueryData, F: QueryFilter>(mut self) -> Self {
+        let query = Query::<Q, F>::new();
+        let access = query.access();
+        for &type_id in access.writes() {
+            self.add_write(ComponentType::from_type_id(type_id));
+        }
+        for &type_id in access.reads().iter().chain(access.required()).chain(access.excluded()) {
+            self.add_read(ComponentType::from_type_id(type_id));
+        }
+        self
+    }
+
+    /// Whether the system needs the whole world
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Component types read (but not written) by the system
+    pub fn reads(&self) -> &HashSet<ComponentType> {
+        &self.reads
+    }
+
+    /// Component types written by the system
+    pub fn writes(&self) -> &HashSet<ComponentType> {
+        &self.writes
+    }
+
+    /// Resource types read (but not written) by the system
+    pub fn resource_reads(&self) -> &HashSet<TypeId> {
+        &self.resource_reads
+    }
+
+    /// Resource types written by the system
+    pub fn resource_writes(&self) -> &HashSet<TypeId> {
+        &self.resource_writes
+    }
+
+    /// Check whether two systems touch the same component or resource with at least one writer
+    /// (exclusive access conflicts with everything)
+    pub fn conflicts_with(&self, other: &SystemAccess) -> bool {
+        self.exclusive
+            || other.exclusive
+            || !self.writes.is_disjoint(&other.writes)
+            || !self.writes.is_disjoint(&other.reads)
+            || !self.reads.is_disjoint(&other.writes)
+            || !self.resource_writes.is_disjoint(&other.resource_writes)
+            || !self.resource_writes.is_disjoint(&other.resource_reads)
+            || !self.resource_reads.is_disjoint(&other.resource_writes)
+    }
+
+    fn add_read(&mut self, component: ComponentType) {
+        if !self.writes.contains(&component) {
+            self.reads.insert(component);
+        }
+    }
+
+    fn add_write(&mut self, component: ComponentType) {
+        self.reads.remove(&component);
+        self.writes.insert(component);
+    }
+
+    fn can_read(&self, type_id: TypeId) -> bool {
+        let component = ComponentType::from_type_id(type_id);
+        self.reads.contains(&component) || self.writes.contains(&component)
+    }
+
+    fn can_write(&self, type_id: TypeId) -> bool {
+        self.writes.contains(&ComponentType::from_type_id(type_id))
+    }
+}
+
+/// A system's view of the world while it executes
+///
+/// Only the storages and resources declared in the system's `SystemAccess` are
+/// visible: written ones are borrowed exclusively, read ones are shared with the
+/// other systems of the batch.
+///
+/// `Added<T>`/`Changed<T>` filters and `removed` compare against the tick of the
+/// system's previous run, so each system sees every change exactly once.
+///
+/// # Panics
+/// Accessing a component that was not declared panics, so missing declarations
+/// surface immediately instead of as silently empty queries.
+pub struct SystemWorld<'w> {
+    entities: &'w EntityTable,
+    removed: &'w RemovedComponentsLog,
+    exclusive: HashMap<TypeId, &'w mut Box<dyn AnyStorage>>,
+    shared: HashMap<TypeId, &'w dyn AnyStorage>,
+    exclusive_resources: HashMap<TypeId, &'w mut ResourceBox>,
+    shared_resources: HashMap<TypeId, &'w (dyn Any + Send + Sync)>,
+    access: &'w SystemAccess,
+    last_run: u64,
+    this_run: u64,
+}
+
+impl<'w> SystemWorld<'w> {
+    /// Split a world into one view per system
+    ///
+    /// Each system comes with the tick of its previous run; the accesses must be
+    /// pairwise conflict-free.
+    pub(crate) fn split(world: &'w mut World, systems: &[(&'w SystemAccess, u64)]) -> Vec<Self> {
+        // Every system gets its own tick, so writes are ordered even within a batch
+        let this_runs: Vec<u64> = systems.iter().map(|_| world.increment_change_tick()).collect();
+        let (entities, removed, storages, resources) = world.split_mut();
+        let mut views: Vec<Self> = systems
+            .iter()
+            .zip(this_runs)
+            .map(|(&(access, last_run), this_run)| Self {
+                entities,
+                removed,
+                exclusive: HashMap::new(),
+                shared: HashMap::new(),
+                exclusive_resources: HashMap::new(),
+                shared_resources: HashMap::new(),
+                access,
+                last_run,
+                this_run,
+            })
+            .collect();
+
+        for (&type_id, storage) in storages.iter_mut() {
+            let component = ComponentType::from_type_id(type_id);
+            if let Some(writer) = systems.iter().position(|(access, _)| access.writes.contains(&component)) {
+                views[writer].exclusive.insert(type_id, storage);
+            } else {
+                let storage: &'w dyn AnyStorage = &**storage;
+                for view in views.iter_mut().filter(|view| view.access.reads.contains(&component)) {
+                    view.shared.insert(type_id, storage);
+                }
+            }
+        }
+
+        for (&type_id, resource) in resources.iter_mut() {
+            if let Some(writer) = systems.iter().position(|(access, _)| access.resource_writes.contains(&type_id)) {
+                views[writer].exclusive_resources.insert(type_id, resource);
+            } else {
+                let resource: &'w ResourceBox = resource;
+                for view in views.iter_mut().filter(|view| view.access.resource_reads.contains(&type_id)) {
+                    view.shared_resources.insert(type_id, resource.as_ref());
+                }
+            }
+        }
+        views
+    }
+
+    /// Get an iterator over all entities
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter()
+    }
+
+    /// Check whether an entity handle still refers to a live entity
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    /// Number of live entities
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// World change tick of this run (stamped on components mutated by the system)
+    pub fn change_tick(&self) -> u64 {
+        self.this_run
+    }
+
+    /// Entities that lost component `T` (or were despawned) since the system last ran
+    pub fn removed<T: Component>(&self) -> Vec<Entity> {
+        self.removed.since(TypeId::of::<T>(), self.last_run)
+    }
+
+    /// Get a component from an entity (requires read or write access)
+    pub fn get_component<T: Component>(&self, entity: Entity) -> Option<&T> {
+        let type_id = TypeId::of::<T>();
+        assert!(self.access.can_read(type_id), "System did not declare access to {}", type_name::<T>());
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let storage = self.exclusive
+            .get(&type_id)
+            .map(|storage| storage.as_ref())
+            .or_else(|| self.shared.get(&type_id).copied())?;
+        storage.as_any()
+            .downcast_ref::<ComponentStorage<T>>()
+            .expect("Component storage type mismatch")
+            .get(entity.id())
+    }
+
+    /// Get a mutable component from an entity (requires write access)
+    pub fn get_component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        let type_id = TypeId::of::<T>();
+        assert!(self.access.can_write(type_id), "System did not declare write access to {}", type_name::<T>());
+        if !self.entities.is_alive(entity) {
+            return None;
+        }
+        self.exclusive
+            .get_mut(&type_id)?
+            .as_any_mut()
+            .downcast_mut::<ComponentStorage<T>>()
+            .expect("Component storage type mismatch")
+            .get_mut(entity.id(), self.this_run)
+    }
+
+    /// Get a resource (requires read or write access)
+    pub fn resource<R: Resource>(&self) -> Option<&R> {
+        let type_id = TypeId::of::<R>();
+        assert!(
+            self.access.resource_reads.contains(&type_id) || self.access.resource_writes.contains(&type_id),
+            "System did not declare access to resource {}", type_name::<R>()
+        );
+        self.exclusive_resources
+            .get(&type_id)
+            .map(|resource| resource.as_ref())
+            .or_else(|| self.shared_resources.get(&type_id).copied())?
+            .downcast_ref::<R>()
+    }
+
+    /// Get a mutable resource (requires write access)
+    pub fn resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
+        let type_id = TypeId::of::<R>();
+        assert!(
+            self.access.resource_writes.contains(&type_id),
+            "System did not declare write access to resource {}", type_name::<R>()
+        );
+        self.exclusive_resources.get_mut(&type_id)?.downcast_mut::<R>()
+    }
+
+    /// Query all entities matching a read-only query
+    pub fn query<Q: ReadOnlyQueryData>(&self) -> Vec<(Entity, Q::Item<'_>)> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Query all entities matching a query that may mutate components
+    pub fn query_mut<Q: QueryData>(&mut self) -> Vec<(Entity, Q::Item<'_>)> {
+        self.query_filtered_mut::<Q, ()>()
+    }
+
+    /// Read-only query with a filter (`With`, `Without`, `Added`, `Changed`)
+    pub fn query_filtered<Q: ReadOnlyQueryData, F: QueryFilter>(&self) -> Vec<(Entity, Q::Item<'_>)> {
+        let query = Query::<Q, F>::new();
+        self.check_access(query.access());
+        let shared = self.exclusive
+            .iter()
+            .map(|(&type_id, storage)| (type_id, storage.as_ref()))
+            .chain(self.shared.iter().map(|(&type_id, &storage)| (type_id, storage)))
+            .collect();
+        query.run(self.entities, StorageBorrows::new(HashMap::new(), shared, self.this_run), self.last_run)
+    }
+
+    /// Mutable query with a filter (`With`, `Without`, `Added`, `Changed`)
+    pub fn query_filtered_mut<Q: QueryData, F: QueryFilter>(&mut self) -> Vec<(Entity, Q::Item<'_>)> {
+        let query = Query::<Q, F>::new();
+        self.check_access(query.access());
+        let exclusive = self.exclusive
+            .iter_mut()
+            .map(|(&type_id, storage)| (type_id, &mut **storage))
+            .collect();
+        let shared = self.shared
+            .iter()
+            .map(|(&type_id, &storage)| (type_id, storage))
+            .collect();
+        query.run(self.entities, StorageBorrows::new(exclusive, shared, self.this_run), self.last_run)
+    }
+
+    fn check_access(&self, query: &QueryAccess) {
+        for &type_id in query.writes() {
+            assert!(self.access.can_write(type_id), "System did not declare write access to {}", query.name_of(type_id));
+        }
+        for &type_id in query.reads().iter().chain(query.required()).chain(query.excluded()) {
+            assert!(self.access.can_read(type_id), "System did not declare access to {}", query.name_of(type_id));
+        }
+    }
 }
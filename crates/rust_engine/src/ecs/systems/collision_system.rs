@@ -6,9 +6,11 @@
 //!
 //! This module provides an ECS-aware wrapper around the core collision system,
 //! integrating it with World, Components, and providing automatic updates.
+//! Each update also publishes `CollisionStarted`/`CollisionEnded` and
+//! `TriggerEntered`/`TriggerExited` to the world's `Events` queues.
 
-use crate::ecs::{World, Entity};
-use crate::ecs::components::{ColliderComponent, CollisionStateComponent, TransformComponent};
+use crate::ecs::{World, Entity, ExclusiveSystem, Query, Changed};
+use crate::ecs::components::{ColliderComponent, CollisionStateComponent, GlobalTransform, TransformComponent};
 use crate::physics::collision_system::PhysicsCollisionSystem;
 use crate::spatial::spatial_query::SpatialQuery;
 use crate::debug::collision_debug::CollisionDebugVisualizer;
@@ -19,11 +21,15 @@ use std::collections::HashSet;
 /// This system:
 /// - Automatically syncs ColliderComponents with the PhysicsCollisionSystem
 /// - Updates CollisionStateComponents each frame
+/// - Publishes collision and trigger events
 /// - Optionally provides debug visualization
 pub struct EcsCollisionSystem {
     collision_system: PhysicsCollisionSystem,
     debug_visualizer: Option<CollisionDebugVisualizer>,
     selected_entity: Option<Entity>,
+    moved_colliders: Query<&'static ColliderComponent, Changed<TransformComponent>>,
+    moved_child_colliders: Query<&'static ColliderComponent, Changed<GlobalTransform>>,
+    changed_colliders: Query<&'static ColliderComponent, Changed<ColliderComponent>>,
 }
 
 impl EcsCollisionSystem {
@@ -33,6 +39,9 @@ impl EcsCollisionSystem {
             collision_system: PhysicsCollisionSystem::new(spatial_query),
             debug_visualizer: None,
             selected_entity: None,
+            moved_colliders: Query::new(),
+            moved_child_colliders: Query::new(),
+            changed_colliders: Query::new(),
         }
     }
     
@@ -63,8 +72,8 @@ impl EcsCollisionSystem {
     
     /// Register a new collider (called when ColliderComponent is added)
     pub fn register_collider(&mut self, entity: Entity, collider: &ColliderComponent, world: &World) {
-        // Get initial position from TransformComponent
-        let initial_position = world.get_component::<TransformComponent>(entity)
+        // Get initial world-space position (colliders may be parented)
+        let initial_position = world.world_transform(entity)
             .map(|t| t.position)
             .unwrap_or(crate::foundation::math::Vec3::zeros());
         
@@ -77,6 +86,7 @@ impl EcsCollisionSystem {
             collider.bounding_radius,
             initial_position,
         );
+        self.collision_system.set_ccd(entity, collider.ccd);
     }
     
     /// Unregister a collider (called when ColliderComponent is removed)
@@ -97,13 +107,16 @@ impl EcsCollisionSystem {
         self.sync_positions_for_broad_phase(world);
         
         // Step 2: Perform collision detection (broad + narrow phase)
-        // PhysicsCollisionSystem will query TransformComponent directly for narrow-phase
+        // PhysicsCollisionSystem resolves world transforms directly for narrow-phase
         self.collision_system.detect_collisions(world);
         
         // Step 3: Update CollisionStateComponents with results
         self.update_collision_states(world);
         
-        // Step 4: Update debug visualization if enabled
+        // Step 4: Publish enter/exit events
+        self.collision_system.send_events(world);
+        
+        // Step 5: Update debug visualization if enabled
         if self.debug_visualizer.is_some() {
             // Capture values before borrowing to avoid borrow conflicts
             let selected_entity = self.selected_entity;
@@ -115,34 +128,38 @@ impl EcsCollisionSystem {
     
     /// Sync spatial query positions for broad-phase (no shape updates needed)
     /// With model-space shapes, we only update positions in spatial structure
+    /// 
+    /// Sleeping rigid bodies aren't written back by the physics step, so their
+    /// colliders drop out of this sync (and the broad phase) until woken.
     fn sync_positions_for_broad_phase(&mut self, world: &World) {
-        // Query all entities with ColliderComponent
-        let colliders = world.query::<ColliderComponent>();
+        // Only colliders whose (local or propagated) transform moved or whose
+        // collider changed since the last sync
+        let mut colliders = self.moved_colliders.iter(world);
+        colliders.extend(self.moved_child_colliders.iter(world));
+        colliders.extend(self.changed_colliders.iter(world));
         
         for (entity, collider) in colliders {
-            // Get transform for this entity
-            if let Some(transform) = world.get_component::<TransformComponent>(entity) {
-                // Update spatial query position (bounding_radius is already in world-space)
-                self.collision_system.update_collider_position(
-                    entity,
-                    transform.position,
-                    collider.bounding_radius,
-                );
-            }
+            let Some(transform) = world.world_transform(entity) else {
+                continue;
+            };
+            // Update spatial query position (bounding_radius is already in world-space)
+            self.collision_system.update_collider_position(
+                entity,
+                transform.position,
+                collider.bounding_radius,
+            );
         }
     }
     /// Update CollisionStateComponents with current collision data
     fn update_collision_states(&mut self, world: &mut World) {
         // Get collision enter/exit events
-        let entered = self.collision_system.get_collision_entered();
-        let exited = self.collision_system.get_collision_exited();
         let current = self.collision_system.get_current_collisions();
         
         // Build sets for quick lookup
         let mut colliding_entities: std::collections::HashMap<Entity, HashSet<Entity>> = 
             std::collections::HashMap::new();
         
-        for pair in current {
+        for pair in current.keys() {
             colliding_entities.entry(pair.entity_a)
                 .or_insert_with(HashSet::new)
                 .insert(pair.entity_b);
@@ -153,7 +170,7 @@ impl EcsCollisionSystem {
         
         let mut entered_map: std::collections::HashMap<Entity, Vec<Entity>> = 
             std::collections::HashMap::new();
-        for pair in &entered {
+        for pair in self.collision_system.get_collision_entered() {
             entered_map.entry(pair.entity_a)
                 .or_insert_with(Vec::new)
                 .push(pair.entity_b);
@@ -164,7 +181,7 @@ impl EcsCollisionSystem {
         
         let mut exited_map: std::collections::HashMap<Entity, Vec<Entity>> = 
             std::collections::HashMap::new();
-        for pair in &exited {
+        for pair in self.collision_system.get_collision_exited() {
             exited_map.entry(pair.entity_a)
                 .or_insert_with(Vec::new)
                 .push(pair.entity_b);
@@ -173,11 +190,19 @@ impl EcsCollisionSystem {
                 .push(pair.entity_a);
         }
         
-        // Pre-collect collider data to avoid borrow conflicts
-        let entity_colliders: std::collections::HashMap<Entity, (u32, u32)> = world
-            .query::<ColliderComponent>()
+        // Nearby colliders, filtered exactly like the broad phase (masks and
+        // collision matrix); collected up front to avoid borrow conflicts
+        let nearby_map: std::collections::HashMap<Entity, HashSet<Entity>> = world
+            .query::<&CollisionStateComponent>()
             .into_iter()
-            .map(|(entity, collider)| (entity, (collider.layer, collider.mask)))
+            .filter(|(entity, _)| self.collision_system.has_collider(*entity))
+            .map(|(entity, _)| {
+                let nearby = self.collision_system.query_nearby(entity)
+                    .into_iter()
+                    .filter(|&other| self.collision_system.can_collide(world, entity, other))
+                    .collect();
+                (entity, nearby)
+            })
             .collect();
         
         // Get selected entity's bounding radius for debug
@@ -188,15 +213,15 @@ impl EcsCollisionSystem {
         };
         
         let _selected_transform_position = if let Some(selected) = self.selected_entity {
-            world.get_component::<TransformComponent>(selected).map(|t| t.position)
+            world.world_transform(selected).map(|t| t.position)
         } else {
             None
         };
         
         // Update all CollisionStateComponents
-        let states = world.query_mut::<CollisionStateComponent>();
+        let states = world.query_mut::<&mut CollisionStateComponent>();
         
-        for (entity, state) in states {
+        for (entity, mut state) in states {
             // Clear per-frame data
             state.clear_frame_data();
             
@@ -218,25 +243,7 @@ impl EcsCollisionSystem {
             }
             
             // Update nearby entities (with layer filtering)
-            let mut nearby_filtered = Vec::new();
-            if let Some((layer, mask)) = entity_colliders.get(&entity) {
-                let all_nearby = self.collision_system.query_nearby(entity);
-                
-                for nearby_entity in all_nearby {
-                    if let Some((nearby_layer, nearby_mask)) = entity_colliders.get(&nearby_entity) {
-                        // Apply same layer filtering as broad-phase
-                        if crate::physics::CollisionLayers::should_collide(
-                            *layer,
-                            *mask,
-                            *nearby_layer,
-                            *nearby_mask,
-                        ) {
-                            nearby_filtered.push(nearby_entity);
-                        }
-                    }
-                }
-            }
-            state.nearby_entities = nearby_filtered.into_iter().collect();
+            state.nearby_entities = nearby_map.get(&entity).cloned().unwrap_or_default();
         }
     }
     
@@ -264,17 +271,15 @@ impl EcsCollisionSystem {
         }
         
         // Visualize all collision shapes
-        let colliders = world.query::<ColliderComponent>();
+        let colliders = world.query::<(&ColliderComponent, &CollisionStateComponent)>();
         
-        for (entity, collider) in colliders {
+        for (entity, (collider, state)) in colliders {
             if collider.debug_draw {
-                if let Some(state) = world.get_component::<CollisionStateComponent>(entity) {
-                    viz.draw_collision_shape(
-                        entity,
-                        &collider.shape,
-                        state.is_colliding(),
-                    );
-                }
+                viz.draw_collision_shape(
+                    entity,
+                    &collider.shape,
+                    state.is_colliding(),
+                );
             }
         }
     }
@@ -309,3 +314,59 @@ impl EcsCollisionSystem {
         self.debug_visualizer.as_mut()
     }
 }
+
+impl ExclusiveSystem for EcsCollisionSystem {
+    fn name(&self) -> &str {
+        "EcsCollisionSystem"
+    }
+
+    fn run(&mut self, world: &mut World, delta_time: f32) {
+        self.update(world, delta_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::math::Vec3;
+    use crate::physics::{CollisionLayers, CollisionMatrix, CollisionShape};
+    use crate::scene::AABB;
+    use crate::spatial::{Octree, OctreeConfig, OctreeSpatialQuery};
+
+    #[test]
+    fn test_nearby_entities_respect_collision_matrix() {
+        let bounds = AABB::new(Vec3::new(-50.0, -50.0, -50.0), Vec3::new(50.0, 50.0, 50.0));
+        let octree = Octree::new(bounds, OctreeConfig::default());
+        let mut system = EcsCollisionSystem::new(Box::new(OctreeSpatialQuery::new(octree)));
+
+        let mut world = World::new();
+        let mut matrix = CollisionMatrix::default();
+        matrix.set_collides("player", "pickup", false).unwrap();
+        world.insert_resource(matrix);
+
+        let mut spawn = |position: Vec3, layer: u32| {
+            let entity = world.create_entity();
+            world.add_component(entity, TransformComponent::from_position(position));
+            world.add_component(entity, ColliderComponent::new(CollisionShape::sphere(1.0)).with_layer(layer));
+            world.add_component(entity, CollisionStateComponent::default());
+            entity
+        };
+        let player = spawn(Vec3::zeros(), CollisionLayers::PLAYER);
+        let pickup = spawn(Vec3::new(1.5, 0.0, 0.0), CollisionLayers::PICKUP);
+        let enemy = spawn(Vec3::new(-1.5, 0.0, 0.0), CollisionLayers::ENEMY);
+        for entity in [player, pickup, enemy] {
+            let collider = world.get_component::<ColliderComponent>(entity).unwrap().clone();
+            system.register_collider(entity, &collider, &world);
+        }
+
+        system.update(&mut world, 1.0 / 60.0);
+
+        // The masks allow every pair; the matrix rules out player/pickup
+        let nearby = &world.get_component::<CollisionStateComponent>(player).unwrap().nearby_entities;
+        assert!(nearby.contains(&enemy));
+        assert!(!nearby.contains(&pickup));
+        let state = world.get_component::<CollisionStateComponent>(pickup).unwrap();
+        assert!(!state.nearby_entities.contains(&player));
+        assert!(!state.is_colliding_with(player));
+    }
+}
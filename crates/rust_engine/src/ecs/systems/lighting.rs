@@ -2,18 +2,28 @@
 //!
 //! Following Game Engine Architecture principles: systems contain logic, components contain data
 
-use crate::ecs::{World, LightComponent, LightType};
-use crate::ecs::components::TransformComponent;
+use crate::ecs::{Commands, LightComponent, LightType, System, SystemAccess, SystemPhase, SystemWorld, World};
+use crate::ecs::components::{GlobalTransform, Parent, TransformComponent};
 use crate::render::systems::lighting::{
     MultiLightEnvironment, DirectionalLightData, PointLightData, SpotLightData,
     MAX_DIRECTIONAL_LIGHTS, MAX_POINT_LIGHTS, MAX_SPOT_LIGHTS
 };
 use crate::foundation::math::Vec3;
 
+/// Components read for each light entity
+type LightQuery = (&'static LightComponent, Option<&'static TransformComponent>, Option<&'static Parent>, Option<&'static GlobalTransform>);
+
 /// Lighting system that processes light entities and produces rendering data
+///
+/// Either call `build_multi_light_environment` directly, or register it with a
+/// `SystemScheduler`: it then runs in `SystemPhase::Render` (after transform
+/// propagation) and stores the result in the `MultiLightEnvironment` resource.
 pub struct LightingSystem {
     /// Cached multi-light environment to avoid allocations
     cached_environment: MultiLightEnvironment,
+    /// Ambient light used when running as a scheduled system
+    ambient_color: Vec3,
+    ambient_intensity: f32,
 }
 
 impl LightingSystem {
@@ -21,8 +31,17 @@ impl LightingSystem {
     pub fn new() -> Self {
         Self {
             cached_environment: MultiLightEnvironment::new(),
+            ambient_color: Vec3::zeros(),
+            ambient_intensity: 0.0,
         }
     }
+
+    /// Set the ambient light used when running as a scheduled system
+    pub fn with_ambient(mut self, color: Vec3, intensity: f32) -> Self {
+        self.ambient_color = color;
+        self.ambient_intensity = intensity;
+        self
+    }
     
     /// Extract lighting data from ECS world and build MultiLightEnvironment
     /// CRITICAL: Must produce identical lighting to hardcoded system for validation
@@ -32,40 +51,41 @@ impl LightingSystem {
         ambient_color: Vec3,
         ambient_intensity: f32
     ) -> &MultiLightEnvironment {
+        let lights = world.query::<LightQuery>();
+        self.cached_environment = Self::environment_from(
+            lights.into_iter().map(|(_, light)| light),
+            ambient_color,
+            ambient_intensity,
+        );
+        &self.cached_environment
+    }
 
-        // Reset environment
-        self.cached_environment = MultiLightEnvironment::new();
+    /// Build the environment from the lights' components
+    fn environment_from<'a>(
+        lights: impl IntoIterator<Item = (&'a LightComponent, Option<&'a TransformComponent>, Option<&'a Parent>, Option<&'a GlobalTransform>)>,
+        ambient_color: Vec3,
+        ambient_intensity: f32,
+    ) -> MultiLightEnvironment {
+        let mut environment = MultiLightEnvironment::new();
         
         // Set ambient lighting
-        self.cached_environment.header.ambient_color = [
+        environment.header.ambient_color = [
             ambient_color.x, 
             ambient_color.y, 
             ambient_color.z, 
             ambient_intensity
         ];
         
-        // Query all light entities
-        let light_entities = world.query::<LightComponent>();
-        let transform_entities = world.query::<TransformComponent>();
-        
-        // Build entity -> transform lookup for efficiency
-        let mut entity_transforms = std::collections::HashMap::new();
-        for (entity, transform) in transform_entities {
-            entity_transforms.insert(entity.id(), transform);
-        }
-        
         // Process all light entities
         let mut dir_count = 0;
         let mut point_count = 0;
         let mut spot_count = 0;
         
-        for (entity, light_comp) in light_entities {
+        for (light_comp, transform, parent, global) in lights {
             if !light_comp.enabled {
                 continue; // Skip disabled lights
             }
             
-            let transform = entity_transforms.get(&entity.id());
-            
             match light_comp.light_type {
                 LightType::Directional => {
                     if dir_count < MAX_DIRECTIONAL_LIGHTS {
@@ -73,7 +93,7 @@ impl LightingSystem {
                         log::trace!("LightingSystem: Directional light direction: {:?}, intensity: {}", 
                             light_comp.direction, light_comp.intensity);
                         
-                        self.cached_environment.directional_lights[dir_count] = DirectionalLightData {
+                        environment.directional_lights[dir_count] = DirectionalLightData {
                             direction: [
                                 light_comp.direction.x,
                                 light_comp.direction.y,
@@ -93,19 +113,12 @@ impl LightingSystem {
                 LightType::Point => {
                     log::trace!("LightingSystem: Processing point light #{}", point_count);
                     if point_count < MAX_POINT_LIGHTS {
-                        // Use position from light component or transform
-                        let position = if light_comp.position != Vec3::new(0.0, 0.0, 0.0) {
-                            light_comp.position
-                        } else if let Some(transform) = transform {
-                            transform.position
-                        } else {
-                            Vec3::new(0.0, 0.0, 0.0)
-                        };
+                        let position = Self::light_position(light_comp, transform, parent, global);
                         
                         log::trace!("LightingSystem: Point light position: {:?}, color: {:?}, intensity: {}", 
                             position, light_comp.color, light_comp.intensity);
                         
-                        self.cached_environment.point_lights[point_count] = PointLightData {
+                        environment.point_lights[point_count] = PointLightData {
                             position: [
                                 position.x,
                                 position.y,
@@ -125,16 +138,9 @@ impl LightingSystem {
                 }
                 LightType::Spot => {
                     if spot_count < MAX_SPOT_LIGHTS {
-                        // Use position from light component or transform
-                        let position = if light_comp.position != Vec3::new(0.0, 0.0, 0.0) {
-                            light_comp.position
-                        } else if let Some(transform) = transform {
-                            transform.position
-                        } else {
-                            Vec3::new(0.0, 0.0, 0.0)
-                        };
+                        let position = Self::light_position(light_comp, transform, parent, global);
                         
-                        self.cached_environment.spot_lights[spot_count] = SpotLightData {
+                        environment.spot_lights[spot_count] = SpotLightData {
                             position: [
                                 position.x,
                                 position.y,
@@ -167,14 +173,36 @@ impl LightingSystem {
         }
         
         // Set light counts
-        self.cached_environment.header.directional_light_count = dir_count as u32;
-        self.cached_environment.header.point_light_count = point_count as u32;
-        self.cached_environment.header.spot_light_count = spot_count as u32;
+        environment.header.directional_light_count = dir_count as u32;
+        environment.header.point_light_count = point_count as u32;
+        environment.header.spot_light_count = spot_count as u32;
         
         log::trace!("LightingSystem: Final counts - Dir: {}, Point: {}, Spot: {}", 
             dir_count, point_count, spot_count);
         
-        &self.cached_environment
+        environment
+    }
+
+    /// World position of a point or spot light
+    ///
+    /// Lights parented to another entity follow it through their propagated
+    /// `GlobalTransform`; others use the light component's position, falling
+    /// back to their transform.
+    fn light_position(
+        light_comp: &LightComponent,
+        transform: Option<&TransformComponent>,
+        parent: Option<&Parent>,
+        global: Option<&GlobalTransform>,
+    ) -> Vec3 {
+        if let (Some(_), Some(global)) = (parent, global) {
+            global.position
+        } else if light_comp.position != Vec3::new(0.0, 0.0, 0.0) {
+            light_comp.position
+        } else if let Some(transform) = transform {
+            transform.position
+        } else {
+            Vec3::new(0.0, 0.0, 0.0)
+        }
     }
 }
 
@@ -184,6 +212,34 @@ impl Default for LightingSystem {
     }
 }
 
+impl System for LightingSystem {
+    fn name(&self) -> &str {
+        "LightingSystem"
+    }
+
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::Render
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new()
+            .query::<LightQuery>()
+            .write_resource::<MultiLightEnvironment>()
+    }
+
+    fn execute(&mut self, world: &mut SystemWorld<'_>, commands: &mut Commands, _delta_time: f32) {
+        let environment = Self::environment_from(
+            world.query::<LightQuery>().into_iter().map(|(_, light)| light),
+            self.ambient_color,
+            self.ambient_intensity,
+        );
+        match world.resource_mut::<MultiLightEnvironment>() {
+            Some(current) => *current = environment,
+            None => commands.insert_resource(environment),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +302,34 @@ mod tests {
         assert!((multi_light_env.header.ambient_color[2] - 0.18).abs() < EPSILON);
         assert!((multi_light_env.header.ambient_color[3] - 0.1).abs() < EPSILON);
     }
+    
+    #[test]
+    fn test_scheduled_lighting_follows_parent() {
+        use crate::ecs::{ExecutionMode, SystemScheduler};
+        use crate::ecs::systems::TransformPropagationSystem;
+        
+        let mut world = World::new();
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.add_system(LightingSystem::new().with_ambient(Vec3::new(0.1, 0.1, 0.1), 0.2));
+        
+        // Point light mounted 2 units in front of a parent that moves
+        let parent = world.create_entity();
+        world.add_component(parent, TransformComponent::from_position(Vec3::new(5.0, 0.0, 0.0)));
+        let light = world.create_entity();
+        world.add_component(light, TransformComponent::from_position(Vec3::new(0.0, 0.0, -2.0)));
+        world.add_component(light, LightFactory::point(Vec3::new(5.0, 0.0, -2.0), Vec3::new(1.0, 0.5, 0.0), 2.0, 10.0));
+        world.set_parent(light, parent).unwrap();
+        
+        scheduler.execute_frame(&mut world, 0.016);
+        let environment = world.resource::<MultiLightEnvironment>().unwrap();
+        assert_eq!(environment.header.point_light_count, 1);
+        assert_vec3_approx_eq(environment.point_lights[0].position[..3].try_into().unwrap(), Vec3::new(5.0, 0.0, -2.0));
+        assert!((environment.header.ambient_color[3] - 0.2).abs() < EPSILON);
+        
+        world.get_component_mut::<TransformComponent>(parent).unwrap().position = Vec3::new(0.0, 3.0, 0.0);
+        scheduler.execute_frame(&mut world, 0.016);
+        let environment = world.resource::<MultiLightEnvironment>().unwrap();
+        assert_vec3_approx_eq(environment.point_lights[0].position[..3].try_into().unwrap(), Vec3::new(0.0, 3.0, -2.0));
+    }
 }
@@ -6,6 +6,10 @@ pub mod renderable_collector;
 pub mod picking_system;
 pub mod collision_system;
 pub mod trail_system;
+pub mod transform_propagation;
+pub mod transform_history;
+pub mod movement;
+pub mod physics_step;
 
 pub use lighting::LightingSystem;
 pub use coordinate_validation_simple::CoordinateSystemValidator;
@@ -13,3 +17,7 @@ pub use renderable_collector::RenderableCollector;
 pub use picking_system::PickingSystem;
 pub use collision_system::EcsCollisionSystem;
 pub use trail_system::TrailSystem;
+pub use transform_propagation::TransformPropagationSystem;
+pub use transform_history::TransformHistorySystem;
+pub use movement::MovementSystem;
+pub use physics_step::PhysicsStepSystem;
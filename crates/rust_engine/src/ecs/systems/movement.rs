@@ -0,0 +1,112 @@
+//! Movement integration system
+//!
+//! Integrates `MovementComponent` velocities into `TransformComponent`s once
+//! per fixed step, so kinematic motion does not depend on the frame rate.
+//! Entities with a `RigidBodyComponent` are left to the physics step.
+
+use crate::ecs::{Commands, System, SystemAccess, SystemPhase, SystemWorld, Without};
+use crate::ecs::components::{MovementComponent, RigidBodyComponent, TransformComponent};
+use crate::foundation::math::{Quat, Vec3};
+
+/// Scheduler system moving entities by their `MovementComponent`
+///
+/// Runs in `SystemPhase::FixedUpdate`: accelerations and damping are applied
+/// first, then the new velocities move the transform. Disabled components,
+/// entities at rest and rigid bodies are left untouched, so
+/// `Changed<TransformComponent>` only reports entities this system moved.
+#[derive(Debug, Default)]
+pub struct MovementSystem;
+
+impl MovementSystem {
+    /// Create the movement system
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl System for MovementSystem {
+    fn name(&self) -> &str {
+        "MovementSystem"
+    }
+
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::FixedUpdate
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new()
+            .query_filtered::<(&mut TransformComponent, &mut MovementComponent), Without<RigidBodyComponent>>()
+    }
+
+    fn execute(&mut self, world: &mut SystemWorld<'_>, _commands: &mut Commands, delta_time: f32) {
+        let movers = world.query_filtered_mut::<(&mut TransformComponent, &mut MovementComponent), Without<RigidBodyComponent>>();
+        for (_, (mut transform, mut movement)) in movers {
+            if !movement.enabled {
+                continue;
+            }
+            movement.integrate(delta_time);
+
+            let translation = movement.get_position_delta(delta_time);
+            if translation != Vec3::zeros() {
+                transform.position += translation;
+            }
+            let rotation = movement.get_rotation_delta(delta_time);
+            if rotation != Vec3::zeros() {
+                transform.rotation = Quat::from_scaled_axis(rotation) * transform.rotation;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{ExecutionMode, SystemScheduler, World};
+    use crate::ecs::systems::PhysicsStepSystem;
+    use crate::spatial::DynamicAabbTree;
+
+    #[test]
+    fn test_movement_integrates_velocity_and_acceleration() {
+        let mut world = World::new();
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        scheduler.add_system(MovementSystem::new());
+
+        let falling = world.create_entity();
+        world.add_component(falling, TransformComponent::identity());
+        let mut movement = MovementComponent::with_velocity(Vec3::new(1.0, 0.0, 0.0));
+        movement.set_acceleration(Vec3::new(0.0, -2.0, 0.0));
+        world.add_component(falling, movement);
+
+        let parked = world.create_entity();
+        world.add_component(parked, TransformComponent::identity());
+        world.add_component(parked, MovementComponent::new());
+
+        for _ in 0..2 {
+            scheduler.execute_fixed_step(&mut world, 0.5);
+        }
+
+        // Semi-implicit Euler: velocity first, then position
+        let position = world.get_component::<TransformComponent>(falling).unwrap().position;
+        assert!((position - Vec3::new(1.0, -1.5, 0.0)).magnitude() < 1e-5, "{:?}", position);
+        assert_eq!(world.get_component::<TransformComponent>(parked).unwrap().position, Vec3::zeros());
+    }
+
+    #[test]
+    fn test_rigid_bodies_are_left_to_the_physics_step() {
+        let mut world = World::new();
+        let mut scheduler = SystemScheduler::with_mode(ExecutionMode::SingleThreaded);
+        scheduler.add_system(MovementSystem::new());
+        scheduler.add_exclusive_system(PhysicsStepSystem::new(Box::new(DynamicAabbTree::default())));
+
+        let body = world.create_entity();
+        world.add_component(body, TransformComponent::identity());
+        world.add_component(body, MovementComponent::with_velocity(Vec3::new(1.0, 0.0, 0.0)));
+        world.add_component(body, RigidBodyComponent::dynamic(1.0).with_velocity(Vec3::new(1.0, 0.0, 0.0)));
+
+        scheduler.execute_fixed_step(&mut world, 0.5);
+
+        // Moved once by the physics step, not a second time by MovementSystem
+        let position = world.get_component::<TransformComponent>(body).unwrap().position;
+        assert!((position - Vec3::new(0.5, 0.0, 0.0)).magnitude() < 1e-5, "{:?}", position);
+    }
+}
@@ -0,0 +1,71 @@
+//! Fixed-step physics system
+//!
+//! Runs collision detection and the rigid body step back to back once per
+//! fixed step, so contacts, bounces and collision events come out the same at
+//! any frame rate.
+
+use crate::ecs::{ExclusiveSystem, SystemPhase, World};
+use crate::ecs::systems::EcsCollisionSystem;
+use crate::physics::{PhysicsConfig, PhysicsSystem};
+use crate::spatial::SpatialQuery;
+
+/// Scheduler system detecting collisions and stepping rigid bodies
+///
+/// Runs exclusively in `SystemPhase::FixedUpdate`: `EcsCollisionSystem` finds
+/// the contacts at the current positions, then `PhysicsSystem` resolves them
+/// and integrates the bodies. Colliders are registered through `collision_mut`.
+pub struct PhysicsStepSystem {
+    collision: EcsCollisionSystem,
+    physics: PhysicsSystem,
+}
+
+impl PhysicsStepSystem {
+    /// Create the physics step with the given broad-phase structure
+    pub fn new(spatial_query: Box<dyn SpatialQuery>) -> Self {
+        Self {
+            collision: EcsCollisionSystem::new(spatial_query),
+            physics: PhysicsSystem::new(),
+        }
+    }
+
+    /// Use custom solver settings
+    pub fn with_config(mut self, config: PhysicsConfig) -> Self {
+        self.physics = PhysicsSystem::with_config(config);
+        self
+    }
+
+    /// Collision detection half of the step
+    pub fn collision(&self) -> &EcsCollisionSystem {
+        &self.collision
+    }
+
+    /// Mutable collision detection (collider registration, debug settings)
+    pub fn collision_mut(&mut self) -> &mut EcsCollisionSystem {
+        &mut self.collision
+    }
+
+    /// Rigid body half of the step
+    pub fn physics(&self) -> &PhysicsSystem {
+        &self.physics
+    }
+
+    /// Mutable rigid body simulation
+    pub fn physics_mut(&mut self) -> &mut PhysicsSystem {
+        &mut self.physics
+    }
+}
+
+impl ExclusiveSystem for PhysicsStepSystem {
+    fn name(&self) -> &str {
+        "PhysicsStepSystem"
+    }
+
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::FixedUpdate
+    }
+
+    fn run(&mut self, world: &mut World, delta_time: f32) {
+        self.collision.update(world, delta_time);
+        self.physics.step(world, self.collision.collision_system(), delta_time);
+    }
+}
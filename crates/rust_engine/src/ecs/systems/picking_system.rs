@@ -8,7 +8,7 @@ use crate::render::Camera;
 use crate::physics::CollisionShape;
 use crate::physics::collision::WorldSpaceShape;
 use crate::spatial::Octree;
-use crate::ecs::components::{PickableComponent, SelectionComponent, TransformComponent};
+use crate::ecs::components::{PickableComponent, SelectionComponent};
 use crate::foundation::math::Vec3;
 use std::collections::HashSet;
 
@@ -254,6 +254,12 @@ impl PickingSystem {
         shape_provider: &dyn Fn(Entity) -> Option<CollisionShape>,
         current_frame: u64,
     ) {
+        // Forget despawned entities so stale handles are never reported as selected
+        self.selected_entities.retain(|entity| world.is_alive(*entity));
+        if self.hovered_entity.is_some_and(|entity| !world.is_alive(entity)) {
+            self.hovered_entity = None;
+        }
+        
         // For now, only handle single-click selection
         // Box selection will be handled when mouse is released after dragging
         
@@ -404,7 +410,7 @@ impl PickingSystem {
             // Check if entity has required components
             if let (Some(pickable), Some(transform)) = (
                 world.get_component::<PickableComponent>(*entity),
-                world.get_component::<TransformComponent>(*entity),
+                world.world_transform(*entity),
             ) {
                 // Skip if not enabled
                 if !pickable.enabled {
@@ -439,7 +445,7 @@ impl PickingSystem {
                                     candidates.push((*entity, shape));
                                 }
                             }
-                            CollisionShape::Sphere(_) => {
+                            _ => {
                                 candidates.push((*entity, shape));
                             }
                         }
@@ -467,7 +473,7 @@ impl PickingSystem {
 
         for (entity, model_shape) in shapes {
             // Get transform to convert model-space shape to world-space
-            if let Some(transform) = world.get_component::<TransformComponent>(*entity) {
+            if let Some(transform) = world.world_transform(*entity) {
                 // Transform shape to world-space
                 let world_shape = model_shape.to_world_space(
                     transform.position,
@@ -637,7 +643,7 @@ impl PickingSystem {
             // Check if entity has required components
             if let (Some(pickable), Some(transform)) = (
                 world.get_component::<PickableComponent>(*entity),
-                world.get_component::<TransformComponent>(*entity),
+                world.world_transform(*entity),
             ) {
                 // Skip if not enabled or doesn't match layer
                 if !pickable.enabled || !pickable.matches_layer_mask(self.layer_mask) {
@@ -742,6 +748,11 @@ impl PickingSystem {
                 
                 false
             }
+            WorldSpaceShape::Capsule(_) | WorldSpaceShape::Box(_) | WorldSpaceShape::ConvexHull(_) => {
+                // Conservative: bounding sphere against the frustum planes
+                let bounds = world_shape.bounding_sphere();
+                frustum.intersects_sphere(bounds.center, bounds.radius)
+            }
         }
     }
 }
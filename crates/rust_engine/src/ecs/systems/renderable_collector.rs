@@ -4,8 +4,9 @@
 //! for efficient GPU submission. It replaces the single-object rendering approach
 //! with batch processing for better performance.
 
-use crate::ecs::{Entity, World};
-use crate::ecs::components::{RenderableComponent, MovementComponent};
+use crate::ecs::World;
+use crate::ecs::components::{RenderableComponent, TransformComponent, GlobalTransform, PreviousTransform};
+use crate::foundation::time::FixedTimestep;
 use crate::render::{
     RenderQueue, 
     RenderCommand, 
@@ -70,62 +71,76 @@ impl RenderableCollector {
     
     /// Collect all entities that have renderable components
     fn collect_renderables(&mut self, world: &World) {
-        for entity in world.entities() {
-            if let Some(renderable) = world.get_component::<RenderableComponent>(*entity) {
-                if !renderable.visible {
-                    continue; // Skip invisible entities
-                }
-                
-                // Calculate transform matrix for this entity
-                let transform = self.calculate_entity_transform(world, *entity);
-                
-                // Determine command type based on transparency
-                let command_type = if renderable.is_transparent {
-                    CommandType::Transparent
-                } else {
-                    CommandType::Opaque
-                };
-                
-                // Create render command using the factory methods
-                let command = match command_type {
-                    CommandType::Opaque => RenderCommand::opaque(
-                        *entity,
-                        renderable.material.id,
-                        transform,
-                        transform.m34, // Use Z component for depth sorting
-                    ),
-                    CommandType::Transparent => RenderCommand::transparent(
-                        *entity,
-                        renderable.material.id,
-                        transform,
-                        transform.m34,
-                    ),
-                };
-                
-                // Add to render queue
-                self.render_queue.add_command(command);
+        let renderables = world.query::<(
+            &RenderableComponent,
+            Option<&TransformComponent>,
+            Option<&GlobalTransform>,
+            Option<&PreviousTransform>,
+        )>();
+        let alpha = world.resource::<FixedTimestep>().map(FixedTimestep::alpha);
+        
+        for (entity, (renderable, transform, global, previous)) in renderables {
+            if !renderable.visible {
+                continue; // Skip invisible entities
             }
+            
+            // Calculate transform matrix for this entity; hierarchy members blend
+            // every level of their chain so children follow their parents
+            let transform = global
+                .zip(alpha)
+                .and_then(|(_, alpha)| world.interpolated_world_transform(entity, alpha))
+                .map_or_else(
+                    || Self::calculate_entity_transform(transform, global, previous.zip(alpha)),
+                    |interpolated| interpolated.to_matrix(),
+                );
+            
+            // Determine command type based on transparency
+            let command_type = if renderable.is_transparent {
+                CommandType::Transparent
+            } else {
+                CommandType::Opaque
+            };
+            
+            // Create render command using the factory methods
+            let command = match command_type {
+                CommandType::Opaque => RenderCommand::opaque(
+                    entity,
+                    renderable.material.id,
+                    transform,
+                    transform.m34, // Use Z component for depth sorting
+                ),
+                CommandType::Transparent => RenderCommand::transparent(
+                    entity,
+                    renderable.material.id,
+                    transform,
+                    transform.m34,
+                ),
+            };
+            
+            // Add to render queue
+            self.render_queue.add_command(command);
         }
     }
     
     /// Calculate the world transform matrix for an entity
-    fn calculate_entity_transform(&self, world: &World, entity: Entity) -> Matrix4<f32> {
-        // Start with identity transform
-        let mut transform = Matrix4::identity();
-        
-        // Check if the entity has movement component for position
-        if let Some(movement) = world.get_component::<MovementComponent>(entity) {
-            // Apply translation based on current position
-            // For now, we assume position is stored in the velocity component as current position
-            // This is a simplified approach - in a full implementation, you'd have a Transform component
-            let position = movement.velocity; // Placeholder - should be actual position
-            transform = Matrix4::new_translation(&position) * transform;
+    ///
+    /// Hierarchy members without interpolation data use their propagated
+    /// `GlobalTransform`; for everything else the local transform already is the
+    /// world transform, blended from the previous fixed step when the entity has
+    /// a `PreviousTransform`.
+    fn calculate_entity_transform(
+        transform: Option<&TransformComponent>,
+        global: Option<&GlobalTransform>,
+        previous: Option<(&PreviousTransform, f32)>,
+    ) -> Matrix4<f32> {
+        if let Some(global) = global {
+            return global.to_matrix();
         }
-        
-        // TODO: Add support for rotation and scale when Transform component is added
-        // TODO: Add support for parent-child relationships for hierarchical transforms
-        
-        transform
+        if let (Some(current), Some((previous, alpha))) = (transform, previous) {
+            return previous.interpolate(current, alpha).to_matrix();
+        }
+        // Entities without a transform render at the origin
+        transform.map_or_else(Matrix4::identity, TransformComponent::to_matrix)
     }
     
     /// Get rendering statistics from the batch renderer
@@ -1,6 +1,6 @@
 //! Trail system for updating trail emitters and generating trail geometry
 
-use crate::ecs::{World, Entity};
+use crate::ecs::{World, With};
 use crate::ecs::components::{TransformComponent, TrailEmitterComponent};
 use crate::foundation::math::{Vec3, Vec2};
 use crate::render::systems::billboard::BillboardQuad;
@@ -21,31 +21,20 @@ impl TrailSystem {
     
     /// Update all trail emitters in the world
     pub fn update(&mut self, world: &mut World, delta_time: f32) {
-        // Get all entities with trail emitters
-        let entities: Vec<Entity> = world
-            .query::<TrailEmitterComponent>()
-            .iter()
-            .map(|(entity, _)| *entity)
-            .collect();
+        // Get all entities with trail emitters and their transforms
+        let trails = world.query_mut::<(&TransformComponent, &mut TrailEmitterComponent)>();
         
-        for entity in entities {
-            self.update_entity_trail(world, entity, delta_time);
+        for (_entity, (transform, mut trail_emitter)) in trails {
+            Self::update_entity_trail(transform, &mut trail_emitter, delta_time);
         }
     }
     
     /// Update trail for a single entity
-    fn update_entity_trail(&mut self, world: &mut World, entity: Entity, delta_time: f32) {
-        // Get components
-        let transform = match world.get_component::<TransformComponent>(entity) {
-            Some(t) => t.clone(),
-            None => return,
-        };
-        
-        let trail_emitter = match world.get_component_mut::<TrailEmitterComponent>(entity) {
-            Some(t) => t,
-            None => return,
-        };
-        
+    fn update_entity_trail(
+        transform: &TransformComponent,
+        trail_emitter: &mut TrailEmitterComponent,
+        delta_time: f32,
+    ) {
         // Update segment ages and remove expired segments
         trail_emitter.update_segments(delta_time);
         
@@ -75,19 +64,14 @@ impl TrailSystem {
     pub fn generate_billboard_quads(&mut self, world: &World) -> &Vec<BillboardQuad> {
         self.billboard_quads.clear();
         
-        // Query all entities with trail emitters
-        let trail_entities = world.query::<TrailEmitterComponent>();
+        // Query all entities with trail emitters (transform needed for rendering)
+        let trail_entities = world.query_filtered::<&TrailEmitterComponent, With<TransformComponent>>();
         
-        for (entity, trail_emitter) in trail_entities.iter() {
+        for (_entity, trail_emitter) in trail_entities {
             if !trail_emitter.enabled {
                 continue;
             }
             
-            // Check if entity has transform (needed for rendering)
-            if world.get_component::<TransformComponent>(*entity).is_none() {
-                continue;
-            }
-            
             // Generate quads for this trail
             self.generate_trail_quads(trail_emitter);
         }
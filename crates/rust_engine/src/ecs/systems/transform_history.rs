@@ -0,0 +1,98 @@
+//! Transform history system
+//!
+//! Records each entity's `TransformComponent` into its `PreviousTransform`
+//! at the start of every fixed step, so rendering can interpolate between the
+//! last two simulation states.
+
+use crate::ecs::{Commands, System, SystemAccess, SystemPhase, SystemWorld};
+use crate::ecs::components::{PreviousTransform, TransformComponent};
+
+/// Scheduler system snapshotting transforms before each fixed step
+///
+/// Runs in `SystemPhase::FixedUpdate`. Register it before the systems that
+/// move entities (the engine registers it first) so the snapshot is taken
+/// before they run. Only entities that already have a `PreviousTransform`
+/// are tracked.
+#[derive(Debug, Default)]
+pub struct TransformHistorySystem;
+
+impl TransformHistorySystem {
+    /// Create the history system
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl System for TransformHistorySystem {
+    fn name(&self) -> &str {
+        "TransformHistorySystem"
+    }
+
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::FixedUpdate
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new().query::<(&TransformComponent, &mut PreviousTransform)>()
+    }
+
+    fn execute(&mut self, world: &mut SystemWorld<'_>, _commands: &mut Commands, _delta_time: f32) {
+        for (_, (transform, mut previous)) in world.query_mut::<(&TransformComponent, &mut PreviousTransform)>() {
+            if previous.0 != *transform {
+                previous.0 = transform.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{SystemScheduler, World};
+    use crate::foundation::math::Vec3;
+
+    struct Slide;
+
+    impl System for Slide {
+        fn name(&self) -> &str {
+            "Slide"
+        }
+
+        fn phase(&self) -> SystemPhase {
+            SystemPhase::FixedUpdate
+        }
+
+        fn access(&self) -> SystemAccess {
+            SystemAccess::new().query::<&mut TransformComponent>()
+        }
+
+        fn execute(&mut self, world: &mut SystemWorld<'_>, _commands: &mut Commands, delta_time: f32) {
+            for (_, mut transform) in world.query_mut::<&mut TransformComponent>() {
+                transform.position.x += 10.0 * delta_time;
+            }
+        }
+    }
+
+    #[test]
+    fn test_previous_transform_is_recorded_before_each_step() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::identity());
+        world.add_component(entity, PreviousTransform::default());
+
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TransformHistorySystem::new());
+        scheduler.add_system(Slide);
+
+        scheduler.execute_fixed_step(&mut world, 0.1);
+        scheduler.execute_fixed_step(&mut world, 0.1);
+
+        let current = world.get_component::<TransformComponent>(entity).unwrap();
+        let previous = world.get_component::<PreviousTransform>(entity).unwrap();
+        assert!((previous.0.position.x - 1.0).abs() < 1e-6);
+        assert!((current.position.x - 2.0).abs() < 1e-6);
+
+        let halfway = previous.interpolate(current, 0.5);
+        assert!((halfway.position - Vec3::new(1.5, 0.0, 0.0)).magnitude() < 1e-6);
+    }
+}
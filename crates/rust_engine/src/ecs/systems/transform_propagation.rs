@@ -0,0 +1,101 @@
+//! Transform propagation system
+//!
+//! Writes the world-space `GlobalTransform` of every hierarchy member after
+//! gameplay systems have moved entities, so rendering, picking and collision in
+//! the same frame see consistent parent/child positions.
+
+use crate::ecs::{Commands, System, SystemAccess, SystemPhase, SystemWorld, Without};
+use crate::ecs::components::{Children, GlobalTransform, Parent, TransformComponent};
+use crate::ecs::hierarchy::{compute_global_transforms, hierarchy_roots};
+
+/// Scheduler system propagating local transforms down the entity hierarchy
+///
+/// Runs in `SystemPhase::PostUpdate`. Children that do not have a
+/// `GlobalTransform` yet receive one when the phase's commands are applied.
+/// The engine registers this system automatically; `World::propagate_transforms`
+/// does the same work for applications without a scheduler.
+#[derive(Debug, Default)]
+pub struct TransformPropagationSystem;
+
+impl TransformPropagationSystem {
+    /// Create the propagation system
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl System for TransformPropagationSystem {
+    fn name(&self) -> &str {
+        "TransformPropagationSystem"
+    }
+
+    fn phase(&self) -> SystemPhase {
+        SystemPhase::PostUpdate
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new()
+            .read::<TransformComponent>()
+            .read::<Parent>()
+            .read::<Children>()
+            .write::<GlobalTransform>()
+    }
+
+    fn execute(&mut self, world: &mut SystemWorld<'_>, commands: &mut Commands, _delta_time: f32) {
+        let updates = {
+            let world = &*world;
+            let roots = hierarchy_roots(
+                world.query::<(&Children, Option<&Parent>)>().into_iter().map(|(entity, (_, parent))| (entity, parent.map(Parent::get))),
+                world.query_filtered::<&Parent, Without<Children>>().into_iter().map(|(entity, parent)| (entity, Some(parent.get()))),
+                |entity| world.is_alive(entity),
+            );
+            compute_global_transforms(
+                roots,
+                |entity| world.is_alive(entity),
+                |entity| world.get_component::<TransformComponent>(entity).map(TransformComponent::to_math_transform),
+                |entity| world.get_component::<Children>(entity),
+            )
+        };
+
+        for (entity, global) in updates {
+            match world.get_component::<GlobalTransform>(entity).map(|current| *current == global) {
+                Some(true) => {}
+                Some(false) => {
+                    if let Some(current) = world.get_component_mut::<GlobalTransform>(entity) {
+                        *current = global;
+                    }
+                }
+                None => commands.insert(entity, global),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{SystemScheduler, World};
+    use crate::foundation::math::Vec3;
+
+    #[test]
+    fn test_propagation_system_matches_world_propagation() {
+        let mut world = World::new();
+        let parent = world.create_entity();
+        let child = world.create_entity();
+        world.add_component(parent, TransformComponent::from_position(Vec3::new(0.0, 3.0, 0.0)));
+        world.add_component(child, TransformComponent::from_position(Vec3::new(1.0, 0.0, 0.0)));
+        world.set_parent(child, parent).unwrap();
+
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.execute_frame(&mut world, 0.016);
+
+        let global = world.get_component::<GlobalTransform>(child).unwrap();
+        assert_eq!(global.position, Vec3::new(1.0, 3.0, 0.0));
+
+        world.get_component_mut::<TransformComponent>(parent).unwrap().position.y = 5.0;
+        scheduler.execute_frame(&mut world, 0.016);
+        let global = world.get_component::<GlobalTransform>(child).unwrap();
+        assert_eq!(global.position, Vec3::new(1.0, 5.0, 0.0));
+    }
+}
@@ -46,8 +46,8 @@ mod tests {
         world.add_component(point_entity, point_light);
         
         // Test that we can query both component types
-        let transforms: Vec<_> = world.query::<TransformComponent>();
-        let lights: Vec<_> = world.query::<LightComponent>();
+        let transforms: Vec<_> = world.query::<&TransformComponent>();
+        let lights: Vec<_> = world.query::<&LightComponent>();
         
         assert_eq!(transforms.len(), 2);
         assert_eq!(lights.len(), 2);
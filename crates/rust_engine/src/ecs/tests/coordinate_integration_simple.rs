@@ -32,8 +32,8 @@ mod tests {
         world.add_component(directional_entity, directional_light);
         
         // Test that we can query both component types
-        let transforms: Vec<_> = world.query::<TransformComponent>();
-        let lights: Vec<_> = world.query::<LightComponent>();
+        let transforms: Vec<_> = world.query::<&TransformComponent>();
+        let lights: Vec<_> = world.query::<&LightComponent>();
         
         assert_eq!(transforms.len(), 1);
         assert_eq!(lights.len(), 1);
@@ -1,47 +1,89 @@
 //! ECS World implementation with proper component storage
 //! Based on Game Engine Architecture principles: cache-friendly data layout and component purity
 
-use super::{Entity, Component};
+use super::{Entity, Component, Resource, StorageType};
+use super::archetype::{ArchetypeId, Archetypes, TableRowsMut, TableStorage};
+use super::query::{Query, QueryData, QueryFilter, ReadOnlyQueryData};
+use super::resource::ResourceBox;
 use std::collections::HashMap;
 use std::any::{TypeId, Any};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-/// Dense component storage for a specific component type
+/// Type-erased view of a component storage
+///
+/// Lets the world and queries inspect storages without knowing the component type.
+pub(crate) trait AnyStorage: Any + Send + Sync {
+    /// Does this storage hold a component for the entity?
+    fn contains(&self, entity_id: u32) -> bool;
+    
+    /// Number of live components
+    fn len(&self) -> usize;
+    
+    /// Entity IDs of all live components
+    fn entity_ids(&self) -> Vec<u32>;
+    
+    /// Change ticks of an entity's component, if present
+    fn ticks(&self, entity_id: u32) -> Option<ComponentTicks>;
+    
+    /// Drop the component of an entity, returning whether it was present (used by despawn)
+    fn remove_entity(&mut self, entity_id: u32) -> bool;
+    
+    /// Move a table component to the column of the entity's new archetype (no-op for sparse sets)
+    fn move_to_archetype(&mut self, entity_id: u32, archetype: ArchetypeId);
+    
+    /// Downcast support
+    fn as_any(&self) -> &dyn Any;
+    
+    /// Mutable downcast support
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// World ticks at which a component was added and last mutably accessed
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ComponentTicks {
+    pub(crate) added: u64,
+    pub(crate) changed: u64,
+}
+
+/// Sparse-set component storage for a specific component type
 /// Uses packed arrays for cache-friendly iteration
-struct ComponentStorage<T: Component> {
+///
+/// Components stay contiguous: removal swaps the last component into the freed
+/// slot, so iteration always costs O(alive) regardless of spawn/despawn churn.
+pub(crate) struct SparseSet<T: Component> {
     components: Vec<T>,
     entity_to_index: HashMap<u32, usize>, // Entity ID -> component index
     index_to_entity: Vec<u32>,            // Component index -> Entity ID
-    free_indices: Vec<usize>,             // Free slots for reuse
     generations: Vec<u64>,                // Generation counter per component (for dirty tracking)
+    ticks: Vec<ComponentTicks>,           // Added/changed world ticks (for Added/Changed filters)
 }
 
-impl<T: Component> ComponentStorage<T> {
+impl<T: Component> SparseSet<T> {
     fn new() -> Self {
         Self {
             components: Vec::new(),
             entity_to_index: HashMap::new(),
             index_to_entity: Vec::new(),
-            free_indices: Vec::new(),
             generations: Vec::new(),
+            ticks: Vec::new(),
         }
     }
     
-    fn add(&mut self, entity_id: u32, component: T) {
-        let index = if let Some(free_idx) = self.free_indices.pop() {
-            // Reuse free slot
-            self.components[free_idx] = component;
-            self.index_to_entity[free_idx] = entity_id;
-            self.generations[free_idx] = 1; // Reset generation
-            free_idx
-        } else {
-            // Append new component
-            let idx = self.components.len();
-            self.components.push(component);
-            self.index_to_entity.push(entity_id);
-            self.generations.push(1); // Start at generation 1
-            idx
-        };
+    fn add(&mut self, entity_id: u32, component: T, tick: u64) {
+        if let Some(&index) = self.entity_to_index.get(&entity_id) {
+            // Replace existing component in place (a change, not an addition)
+            self.components[index] = component;
+            self.generations[index] = 1; // Reset generation
+            self.ticks[index].changed = tick;
+            return;
+        }
         
+        // Append new component
+        let index = self.components.len();
+        self.components.push(component);
+        self.index_to_entity.push(entity_id);
+        self.generations.push(1); // Start at generation 1
+        self.ticks.push(ComponentTicks { added: tick, changed: tick });
         self.entity_to_index.insert(entity_id, index);
     }
     
@@ -50,10 +92,11 @@ impl<T: Component> ComponentStorage<T> {
             .and_then(|&index| self.components.get(index))
     }
     
-    fn get_mut(&mut self, entity_id: u32) -> Option<&mut T> {
+    fn get_mut(&mut self, entity_id: u32, tick: u64) -> Option<&mut T> {
         if let Some(&index) = self.entity_to_index.get(&entity_id) {
             // Increment generation when mutably accessing (marks as dirty)
             self.generations[index] += 1;
+            self.ticks[index].changed = tick;
             self.components.get_mut(index)
         } else {
             None
@@ -67,59 +110,328 @@ impl<T: Component> ComponentStorage<T> {
     }
     
     fn remove(&mut self, entity_id: u32) -> Option<T> {
-        if let Some(index) = self.entity_to_index.remove(&entity_id) {
-            // Mark slot as free
-            self.free_indices.push(index);
-            // Note: We don't actually remove from Vec to maintain indices
-            // This is a simplified implementation - production ECS would handle this better
-            None
-        } else {
-            None
+        let index = self.entity_to_index.remove(&entity_id)?;
+        
+        // Swap-remove keeps the arrays packed; fix up the entity moved into `index`
+        let component = self.components.swap_remove(index);
+        self.index_to_entity.swap_remove(index);
+        self.generations.swap_remove(index);
+        self.ticks.swap_remove(index);
+        if let Some(&moved_entity) = self.index_to_entity.get(index) {
+            self.entity_to_index.insert(moved_entity, index);
+        }
+        
+        Some(component)
+    }
+    
+    fn rows_mut(&mut self) -> (&HashMap<u32, usize>, RowsMut<'_, T>) {
+        (&self.entity_to_index, RowsMut::new(&mut self.components, &mut self.ticks))
+    }
+}
+
+/// Rows of a packed component array not yet handed out by a mutable query
+///
+/// Starts as a single chunk over the whole array. Taking a row splits its
+/// chunk with `split_at_mut`, so each component is handed out at most once
+/// without building a slot per row. Rows skipped over stay available as their
+/// own chunk; taking every row in storage order keeps a single chunk.
+pub(crate) struct RowsMut<'a, T> {
+    chunks: Vec<RowChunk<'a, T>>, // Non-empty, sorted by first row
+}
+
+struct RowChunk<'a, T> {
+    start: usize,
+    components: &'a mut [T],
+    ticks: &'a mut [ComponentTicks],
+}
+
+impl<'a, T> RowsMut<'a, T> {
+    pub(crate) fn new(components: &'a mut [T], ticks: &'a mut [ComponentTicks]) -> Self {
+        let mut chunks = Vec::new();
+        if !components.is_empty() {
+            chunks.push(RowChunk { start: 0, components, ticks });
+        }
+        Self { chunks }
+    }
+
+    /// Hand out a row, or None if it is out of range or was already taken
+    pub(crate) fn take(&mut self, row: usize) -> Option<(&'a mut T, &'a mut ComponentTicks)> {
+        let i = self.chunks.partition_point(|chunk| chunk.start + chunk.components.len() <= row);
+        let chunk = self.chunks.get_mut(i).filter(|chunk| chunk.start <= row)?;
+        let start = chunk.start;
+        let (before, rest) = std::mem::take(&mut chunk.components).split_at_mut(row - start);
+        let (ticks_before, ticks_rest) = std::mem::take(&mut chunk.ticks).split_at_mut(row - start);
+        let (component, after) = rest.split_first_mut().expect("row is inside the chunk");
+        let (ticks, ticks_after) = ticks_rest.split_first_mut().expect("row is inside the chunk");
+
+        let remaining = [
+            RowChunk { start, components: before, ticks: ticks_before },
+            RowChunk { start: row + 1, components: after, ticks: ticks_after },
+        ];
+        self.chunks.splice(i..=i, remaining.into_iter().filter(|chunk| !chunk.components.is_empty()));
+        Some((component, ticks))
+    }
+}
+
+/// Component storage for a specific component type
+///
+/// The backend is chosen by `Component::STORAGE`: sparse sets for most
+/// components, archetype tables for hot components iterated together every frame.
+pub(crate) enum ComponentStorage<T: Component> {
+    SparseSet(SparseSet<T>),
+    Table(TableStorage<T>),
+}
+
+impl<T: Component> ComponentStorage<T> {
+    fn new() -> Self {
+        match T::STORAGE {
+            StorageType::SparseSet => Self::SparseSet(SparseSet::new()),
+            StorageType::Table => Self::Table(TableStorage::new()),
+        }
+    }
+    
+    /// Add or replace a component; `archetype` places new table components
+    fn add(&mut self, entity_id: u32, component: T, tick: u64, archetype: Option<ArchetypeId>) {
+        match self {
+            Self::SparseSet(storage) => storage.add(entity_id, component, tick),
+            Self::Table(storage) => storage.add(entity_id, component, tick, archetype),
         }
     }
     
-    /// Iterator over all components with their entity IDs (cache-friendly)
-    fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
-        self.index_to_entity.iter()
-            .zip(self.components.iter())
-            .enumerate()
-            .filter(|(idx, _)| !self.free_indices.contains(idx))
-            .map(|(_, (entity_id, component))| (*entity_id, component))
+    pub(crate) fn get(&self, entity_id: u32) -> Option<&T> {
+        match self {
+            Self::SparseSet(storage) => storage.get(entity_id),
+            Self::Table(storage) => storage.get(entity_id),
+        }
+    }
+    
+    pub(crate) fn get_mut(&mut self, entity_id: u32, tick: u64) -> Option<&mut T> {
+        match self {
+            Self::SparseSet(storage) => storage.get_mut(entity_id, tick),
+            Self::Table(storage) => storage.get_mut(entity_id, tick),
+        }
     }
     
-    /// Mutable iterator over all components with their entity IDs
-    fn iter_mut(&mut self) -> Vec<(u32, &mut T)> {
-        let mut results = Vec::new();
-        for (idx, (entity_id, component)) in self.index_to_entity.iter()
-            .zip(self.components.iter_mut())
-            .enumerate() {
-            if !self.free_indices.contains(&idx) {
-                results.push((*entity_id, component));
+    /// Get the generation counter for a component (for change detection)
+    fn get_generation(&self, entity_id: u32) -> Option<u64> {
+        match self {
+            Self::SparseSet(storage) => storage.get_generation(entity_id),
+            Self::Table(storage) => storage.get_generation(entity_id),
+        }
+    }
+    
+    fn remove(&mut self, entity_id: u32) -> Option<T> {
+        match self {
+            Self::SparseSet(storage) => storage.remove(entity_id),
+            Self::Table(storage) => storage.remove(entity_id),
+        }
+    }
+    
+    /// Mutable access to every component (and its ticks), each handed out at most once
+    pub(crate) fn rows_mut(&mut self) -> StorageRowsMut<'_, T> {
+        match self {
+            Self::SparseSet(storage) => {
+                let (index, rows) = storage.rows_mut();
+                StorageRowsMut::Sparse(index, rows)
             }
+            Self::Table(storage) => StorageRowsMut::Table(storage.rows_mut()),
         }
-        results
+    }
+}
+
+/// Rows of a component storage not yet handed out, returned by `ComponentStorage::rows_mut`
+pub(crate) enum StorageRowsMut<'a, T> {
+    Sparse(&'a HashMap<u32, usize>, RowsMut<'a, T>),
+    Table(TableRowsMut<'a, T>),
+}
+
+impl<'a, T> StorageRowsMut<'a, T> {
+    /// Hand out an entity's component, or None if absent or already taken
+    pub(crate) fn take(&mut self, entity_id: u32) -> Option<(&'a mut T, &'a mut ComponentTicks)> {
+        match self {
+            Self::Sparse(index, rows) => rows.take(*index.get(&entity_id)?),
+            Self::Table(rows) => rows.take(entity_id),
+        }
+    }
+}
+
+impl<T: Component> AnyStorage for ComponentStorage<T> {
+    fn contains(&self, entity_id: u32) -> bool {
+        match self {
+            Self::SparseSet(storage) => storage.entity_to_index.contains_key(&entity_id),
+            Self::Table(storage) => storage.contains(entity_id),
+        }
+    }
+    
+    fn len(&self) -> usize {
+        match self {
+            Self::SparseSet(storage) => storage.components.len(),
+            Self::Table(storage) => storage.len(),
+        }
+    }
+    
+    fn entity_ids(&self) -> Vec<u32> {
+        match self {
+            Self::SparseSet(storage) => storage.index_to_entity.clone(),
+            Self::Table(storage) => storage.entity_ids(),
+        }
+    }
+    
+    fn ticks(&self, entity_id: u32) -> Option<ComponentTicks> {
+        match self {
+            Self::SparseSet(storage) => storage.entity_to_index.get(&entity_id).map(|&index| storage.ticks[index]),
+            Self::Table(storage) => storage.ticks(entity_id),
+        }
+    }
+    
+    fn remove_entity(&mut self, entity_id: u32) -> bool {
+        self.remove(entity_id).is_some()
+    }
+    
+    fn move_to_archetype(&mut self, entity_id: u32, archetype: ArchetypeId) {
+        if let Self::Table(storage) = self {
+            storage.move_to_archetype(entity_id, archetype);
+        }
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Per-slot entity bookkeeping
+#[derive(Debug, Clone, Copy)]
+struct EntityMeta {
+    /// Current generation of this slot
+    generation: u32,
+    /// Position in the dense alive list (None when the slot is free)
+    alive_index: Option<usize>,
+}
+
+/// Entity allocator: generations, liveness and ID recycling
+///
+/// Kept separate from component storage so that system views can share it
+/// while component storages are split-borrowed.
+#[derive(Default)]
+pub(crate) struct EntityTable {
+    meta: Vec<EntityMeta>,               // Entity ID -> generation/liveness
+    free_ids: Vec<u32>,                  // Despawned IDs ready for reuse
+    alive: Vec<Entity>,                  // Dense list of alive entities
+}
+
+impl EntityTable {
+    fn allocate(&mut self) -> Entity {
+        let alive_index = self.alive.len();
+        let entity = if let Some(id) = self.free_ids.pop() {
+            let meta = &mut self.meta[id as usize];
+            meta.alive_index = Some(alive_index);
+            Entity::new(id, meta.generation)
+        } else {
+            let id = u32::try_from(self.meta.len()).expect("Entity ID space exhausted");
+            self.meta.push(EntityMeta { generation: 0, alive_index: Some(alive_index) });
+            Entity::new(id, 0)
+        };
+        self.alive.push(entity);
+        entity
+    }
+    
+    fn free(&mut self, entity: Entity) {
+        // Remove from the dense alive list, fixing up the entity moved into its place
+        let meta = &mut self.meta[entity.id() as usize];
+        let alive_index = meta.alive_index.take().expect("alive entity has an index");
+        meta.generation = meta.generation.wrapping_add(1);
+        self.alive.swap_remove(alive_index);
+        if let Some(moved) = self.alive.get(alive_index) {
+            self.meta[moved.id() as usize].alive_index = Some(alive_index);
+        }
+        
+        self.free_ids.push(entity.id());
+    }
+    
+    pub(crate) fn is_alive(&self, entity: Entity) -> bool {
+        self.meta
+            .get(entity.id() as usize)
+            .is_some_and(|meta| meta.alive_index.is_some() && meta.generation == entity.generation())
+    }
+    
+    pub(crate) fn entity_from_id(&self, entity_id: u32) -> Option<Entity> {
+        self.meta
+            .get(entity_id as usize)
+            .filter(|meta| meta.alive_index.is_some())
+            .map(|meta| Entity::new(entity_id, meta.generation))
+    }
+    
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, Entity> {
+        self.alive.iter()
+    }
+    
+    pub(crate) fn len(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+/// Log of removed components, double-buffered per frame
+///
+/// Readers see removals from the current and the previous frame, so anything
+/// that reads at least once per frame never misses one.
+#[derive(Default)]
+pub(crate) struct RemovedComponentsLog {
+    current: HashMap<TypeId, Vec<(Entity, u64)>>,
+    previous: HashMap<TypeId, Vec<(Entity, u64)>>,
+}
+
+impl RemovedComponentsLog {
+    fn record(&mut self, type_id: TypeId, entity: Entity, tick: u64) {
+        self.current.entry(type_id).or_default().push((entity, tick));
+    }
+    
+    /// Entities whose component of `type_id` was removed after `last_run`
+    pub(crate) fn since(&self, type_id: TypeId, last_run: u64) -> Vec<Entity> {
+        [&self.previous, &self.current]
+            .into_iter()
+            .filter_map(|log| log.get(&type_id))
+            .flatten()
+            .filter(|(_, tick)| *tick > last_run)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+    
+    fn update(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
     }
 }
 
 /// ECS World containing all entities and components with type-safe storage
+///
+/// Components use sparse-set storage unless their `Component::STORAGE` asks for
+/// archetype tables (see `ecs::archetype`).
 pub struct World {
-    next_entity_id: u32,
-    entities: Vec<Entity>,
-    alive_entities: HashMap<u32, bool>, // Track which entities are alive
-    component_storages: HashMap<TypeId, Box<dyn Any>>,
+    entities: EntityTable,
+    component_storages: HashMap<TypeId, Box<dyn AnyStorage>>,
+    archetypes: Archetypes,
     // Change tracking: entity_id -> (component_type, generation)
     changed_components: HashMap<u32, HashMap<TypeId, u64>>,
+    // Tick stamped on component additions/mutations (for Added/Changed filters)
+    change_tick: AtomicU64,
+    removed_components: RemovedComponentsLog,
+    resources: HashMap<TypeId, ResourceBox>,
 }
 
 impl World {
     /// Create a new world
     pub fn new() -> Self {
         Self {
-            next_entity_id: 0,
-            entities: Vec::new(),
-            alive_entities: HashMap::new(),
+            entities: EntityTable::default(),
             component_storages: HashMap::new(),
+            archetypes: Archetypes::default(),
             changed_components: HashMap::new(),
+            change_tick: AtomicU64::new(1),
+            removed_components: RemovedComponentsLog::default(),
+            resources: HashMap::new(),
         }
     }
     
@@ -133,6 +445,7 @@ impl World {
         
         self.component_storages.get_mut(&type_id)
             .unwrap()
+            .as_any_mut()
             .downcast_mut::<ComponentStorage<T>>()
             .expect("Component storage type mismatch")
     }
@@ -141,22 +454,112 @@ impl World {
     fn get_storage_ref<T: Component>(&self) -> Option<&ComponentStorage<T>> {
         let type_id = TypeId::of::<T>();
         self.component_storages.get(&type_id)
-            .and_then(|storage| storage.downcast_ref::<ComponentStorage<T>>())
+            .and_then(|storage| storage.as_any().downcast_ref::<ComponentStorage<T>>())
+    }
+    
+    /// All component storages (used by queries)
+    pub(crate) fn storages(&self) -> &HashMap<TypeId, Box<dyn AnyStorage>> {
+        &self.component_storages
+    }
+    
+    /// Entity bookkeeping (used by queries and system views)
+    pub(crate) fn entity_table(&self) -> &EntityTable {
+        &self.entities
+    }
+    
+    /// Split-borrow the entity table, component storages and resources (used by queries and the scheduler)
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn split_mut(&mut self) -> (&EntityTable, &RemovedComponentsLog, &mut HashMap<TypeId, Box<dyn AnyStorage>>, &mut HashMap<TypeId, ResourceBox>) {
+        (&self.entities, &self.removed_components, &mut self.component_storages, &mut self.resources)
+    }
+    
+    /// Removed component log (used by `RemovedComponents` readers)
+    pub(crate) fn removed_log(&self) -> &RemovedComponentsLog {
+        &self.removed_components
+    }
+    
+    /// Current change tick, stamped on component additions and mutations
+    pub fn change_tick(&self) -> u64 {
+        self.change_tick.load(Ordering::Relaxed)
+    }
+    
+    /// Advance the change tick, returning the tick before the increment
+    ///
+    /// Each query/system run takes its own tick, so changes made after the run
+    /// are newer than it and show up the next time it runs.
+    pub fn increment_change_tick(&self) -> u64 {
+        self.change_tick.fetch_add(1, Ordering::Relaxed)
+    }
+    
+    /// Rotate the removed component log
+    ///
+    /// Call once per frame (the scheduler does this after running all systems).
+    /// Removals are visible to readers for two frames.
+    pub fn update_trackers(&mut self) {
+        self.removed_components.update();
     }
     
     /// Create a new entity
+    ///
+    /// IDs of despawned entities are recycled with a new generation.
     pub fn create_entity(&mut self) -> Entity {
-        let entity = Entity::new(self.next_entity_id);
-        self.next_entity_id += 1;
-        self.entities.push(entity);
-        self.alive_entities.insert(entity.id(), true);
-        entity
+        self.entities.allocate()
+    }
+    
+    /// Despawn an entity, removing all of its components
+    ///
+    /// Returns false if the entity was already despawned (stale handle).
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        
+        let tick = self.change_tick();
+        for (type_id, storage) in &mut self.component_storages {
+            if storage.remove_entity(entity.id()) {
+                self.removed_components.record(*type_id, entity, tick);
+            }
+        }
+        self.archetypes.remove_entity(entity.id());
+        self.changed_components.remove(&entity.id());
+        self.entities.free(entity);
+        true
+    }
+    
+    /// Check whether an entity handle still refers to a live entity
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+    
+    /// Get the live entity currently occupying an ID slot
+    pub(crate) fn entity_from_id(&self, entity_id: u32) -> Option<Entity> {
+        self.entities.entity_from_id(entity_id)
+    }
+    
+    /// Number of live entities
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
     }
     
     /// Add a component to an entity
+    ///
+    /// Ignored (with a warning) if the entity has been despawned.
     pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
-        let storage = self.get_storage::<T>();
-        storage.add(entity.id(), component);
+        if !self.is_alive(entity) {
+            log::warn!("Ignoring add_component on despawned entity {:?}", entity);
+            return;
+        }
+        
+        let tick = self.change_tick();
+        let archetype = match T::STORAGE {
+            StorageType::Table if !self.get_storage::<T>().contains(entity.id()) => {
+                let mut types = self.archetypes.types_of(entity.id()).to_vec();
+                types.push(TypeId::of::<T>());
+                self.set_archetype(entity.id(), types)
+            }
+            _ => None,
+        };
+        self.get_storage::<T>().add(entity.id(), component, tick, archetype);
         
         // Track this change
         let type_id = TypeId::of::<T>();
@@ -168,11 +571,17 @@ impl World {
     
     /// Get a component from an entity
     pub fn get_component<T: Component>(&self, entity: Entity) -> Option<&T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
         self.get_storage_ref::<T>()?.get(entity.id())
     }
     
     /// Get a mutable component from an entity
     pub fn get_component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
         let entity_id = entity.id();
         let type_id = TypeId::of::<T>();
         
@@ -194,46 +603,107 @@ impl World {
         }
         
         // Now get the mutable reference (this increments generation internally)
-        self.get_storage::<T>().get_mut(entity_id)
+        let tick = self.change_tick();
+        self.get_storage::<T>().get_mut(entity_id, tick)
     }
     
     /// Remove a component from an entity
     pub fn remove_component<T: Component>(&mut self, entity: Entity) -> Option<T> {
-        let storage = self.get_storage::<T>();
-        storage.remove(entity.id())
-    }
-    
-    /// Query all entities with a specific component type
-    pub fn query<T: Component>(&self) -> Vec<(Entity, &T)> {
-        if let Some(storage) = self.get_storage_ref::<T>() {
-            storage.iter()
-                .map(|(entity_id, component)| (Entity::new(entity_id), component))
-                .collect()
-        } else {
-            Vec::new()
+        if !self.is_alive(entity) {
+            return None;
         }
+        let removed = self.get_storage::<T>().remove(entity.id());
+        if removed.is_some() && T::STORAGE == StorageType::Table {
+            let type_id = TypeId::of::<T>();
+            let types = self.archetypes.types_of(entity.id()).iter().copied().filter(|&other| other != type_id).collect();
+            self.set_archetype(entity.id(), types);
+        }
+        
+        // Stop reporting changes for a component that no longer exists
+        if removed.is_some() {
+            let tick = self.change_tick();
+            self.removed_components.record(TypeId::of::<T>(), entity, tick);
+            if let Some(changes) = self.changed_components.get_mut(&entity.id()) {
+                changes.remove(&TypeId::of::<T>());
+                if changes.is_empty() {
+                    self.changed_components.remove(&entity.id());
+                }
+            }
+        }
+        removed
     }
     
-    /// Query all entities with a specific component type (mutable)
-    pub fn query_mut<T: Component>(&mut self) -> Vec<(Entity, &mut T)> {
-        let type_id = TypeId::of::<T>();
-        if let Some(storage) = self.component_storages.get_mut(&type_id) {
-            if let Some(storage) = storage.downcast_mut::<ComponentStorage<T>>() {
-                storage.iter_mut()
-                    .into_iter()
-                    .map(|(entity_id, component)| (Entity::new(entity_id), component))
-                    .collect()
-            } else {
-                Vec::new()
+    /// Move an entity to the archetype of `types`, relocating its table components
+    fn set_archetype(&mut self, entity_id: u32, types: Vec<TypeId>) -> Option<ArchetypeId> {
+        let archetype = self.archetypes.assign(entity_id, types)?;
+        for type_id in self.archetypes.types(archetype) {
+            if let Some(storage) = self.component_storages.get_mut(type_id) {
+                storage.move_to_archetype(entity_id, archetype);
             }
-        } else {
-            Vec::new()
         }
+        Some(archetype)
+    }
+    
+    /// Number of archetypes (distinct sets of table components) seen so far
+    pub fn archetype_count(&self) -> usize {
+        self.archetypes.len()
     }
     
-    /// Update the world (run systems)
-    pub fn update(&mut self, _delta_time: f32) {
-        // TODO: Implement system execution
+    /// Insert a resource, returning the previous value of the same type
+    pub fn insert_resource<R: Resource>(&mut self, resource: R) -> Option<R> {
+        self.resources
+            .insert(TypeId::of::<R>(), Box::new(resource))
+            .map(|previous| *previous.downcast::<R>().expect("Resource type mismatch"))
+    }
+    
+    /// Get a resource
+    pub fn resource<R: Resource>(&self) -> Option<&R> {
+        self.resources.get(&TypeId::of::<R>())?.downcast_ref::<R>()
+    }
+    
+    /// Get a mutable resource
+    pub fn resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
+        self.resources.get_mut(&TypeId::of::<R>())?.downcast_mut::<R>()
+    }
+    
+    /// Remove a resource, returning it
+    pub fn remove_resource<R: Resource>(&mut self) -> Option<R> {
+        self.resources
+            .remove(&TypeId::of::<R>())
+            .map(|resource| *resource.downcast::<R>().expect("Resource type mismatch"))
+    }
+    
+    /// Check whether a resource of type `R` is present
+    pub fn contains_resource<R: Resource>(&self) -> bool {
+        self.resources.contains_key(&TypeId::of::<R>())
+    }
+    
+    /// Query all entities matching a read-only query
+    ///
+    /// `Q` can be a single term or a tuple, e.g.
+    /// `world.query::<(&TransformComponent, Option<&ColliderComponent>)>()`.
+    pub fn query<Q: ReadOnlyQueryData>(&self) -> Vec<(Entity, Q::Item<'_>)> {
+        Query::<Q>::new().iter(self)
+    }
+    
+    /// Query all entities matching a query that may mutate components
+    ///
+    /// e.g. `world.query_mut::<(&TransformComponent, &mut MovementComponent)>()`.
+    pub fn query_mut<Q: QueryData>(&mut self) -> Vec<(Entity, Q::Item<'_>)> {
+        Query::<Q>::new().iter_mut(self)
+    }
+    
+    /// Read-only query with a `With<T>`/`Without<T>` filter
+    ///
+    /// `Added<T>`/`Changed<T>` treat everything as new here, since a one-shot
+    /// query has never run before; keep a `Query` to see changes since its last run.
+    pub fn query_filtered<Q: ReadOnlyQueryData, F: QueryFilter>(&self) -> Vec<(Entity, Q::Item<'_>)> {
+        Query::<Q, F>::new().iter(self)
+    }
+    
+    /// Mutable query with a `With<T>`/`Without<T>` filter
+    pub fn query_filtered_mut<Q: QueryData, F: QueryFilter>(&mut self) -> Vec<(Entity, Q::Item<'_>)> {
+        Query::<Q, F>::new().iter_mut(self)
     }
     
     /// Get an iterator over all entities
@@ -242,6 +712,8 @@ impl World {
     }
     
     /// Get entities with Transform or Renderable components that changed
+    ///
+    /// Prefer `Changed<T>` query filters, which work for any component.
     pub fn get_changed_renderable_entities(&self) -> Vec<Entity> {
         use crate::ecs::components::{TransformComponent, RenderableComponent};
         
@@ -253,7 +725,7 @@ impl World {
             .filter_map(|(entity_id, changes)| {
                 // Include if Transform or Renderable changed
                 if changes.contains_key(&transform_type) || changes.contains_key(&renderable_type) {
-                    Some(Entity::new(*entity_id))
+                    self.entity_from_id(*entity_id)
                 } else {
                     None
                 }
@@ -263,7 +735,9 @@ impl World {
     
     /// Clear change tracking for a specific entity
     pub fn clear_entity_changes(&mut self, entity: Entity) {
-        self.changed_components.remove(&entity.id());
+        if self.is_alive(entity) {
+            self.changed_components.remove(&entity.id());
+        }
     }
     
     /// Clear all change tracking
@@ -427,4 +901,141 @@ mod tests {
         assert_ne!(gen1, gen2);
         assert!(gen2 > gen1);
     }
+    
+    #[test]
+    fn test_despawn_removes_components() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(Vec3::new(1.0, 2.0, 3.0)));
+        
+        assert!(world.despawn(entity));
+        assert!(!world.is_alive(entity));
+        assert_eq!(world.entity_count(), 0);
+        assert!(world.query::<&TransformComponent>().is_empty());
+        
+        // Despawning twice is a no-op
+        assert!(!world.despawn(entity));
+    }
+    
+    #[test]
+    fn test_stale_handle_does_not_alias_recycled_id() {
+        let mut world = World::new();
+        let old = world.create_entity();
+        world.add_component(old, TransformComponent::from_position(Vec3::new(1.0, 0.0, 0.0)));
+        world.despawn(old);
+        
+        // The slot is recycled with a new generation
+        let new = world.create_entity();
+        assert_eq!(new.id(), old.id());
+        assert_ne!(new.generation(), old.generation());
+        world.add_component(new, TransformComponent::from_position(Vec3::new(2.0, 0.0, 0.0)));
+        
+        assert!(world.is_alive(new));
+        assert!(world.get_component::<TransformComponent>(old).is_none());
+        assert!(world.get_component_mut::<TransformComponent>(old).is_none());
+        assert!(world.remove_component::<TransformComponent>(old).is_none());
+        assert_eq!(
+            world.get_component::<TransformComponent>(new).map(|t| t.position.x),
+            Some(2.0)
+        );
+        
+        // Queries report the live handle
+        let results = world.query::<&TransformComponent>();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, new);
+    }
+    
+    #[test]
+    fn test_despawn_keeps_other_entities() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..4).map(|_| world.create_entity()).collect();
+        
+        world.despawn(entities[1]);
+        
+        let mut alive: Vec<_> = world.entities().copied().collect();
+        alive.sort_by_key(|e| e.id());
+        assert_eq!(alive, vec![entities[0], entities[2], entities[3]]);
+        assert!(entities.iter().enumerate().all(|(i, e)| world.is_alive(*e) == (i != 1)));
+    }
+    
+    #[test]
+    fn test_remove_component_returns_value() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(Vec3::new(4.0, 5.0, 6.0)));
+        
+        let removed = world.remove_component::<TransformComponent>(entity);
+        assert_eq!(removed.map(|t| t.position), Some(Vec3::new(4.0, 5.0, 6.0)));
+        assert!(world.get_component::<TransformComponent>(entity).is_none());
+        assert!(world.remove_component::<TransformComponent>(entity).is_none());
+        assert!(world.get_changed_renderable_entities().is_empty());
+    }
+    
+    #[test]
+    fn test_swap_remove_keeps_other_components() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..5)
+            .map(|i| {
+                let entity = world.create_entity();
+                world.add_component(entity, TransformComponent::from_position(Vec3::new(i as f32, 0.0, 0.0)));
+                entity
+            })
+            .collect();
+        
+        // Remove from the middle: the last component is moved into the hole
+        world.remove_component::<TransformComponent>(entities[1]);
+        
+        for (i, entity) in entities.iter().enumerate().filter(|(i, _)| *i != 1) {
+            let transform = world.get_component::<TransformComponent>(*entity).unwrap();
+            assert_eq!(transform.position.x, i as f32);
+        }
+        assert_eq!(world.query::<&TransformComponent>().len(), 4);
+    }
+    
+    #[test]
+    fn test_add_component_twice_replaces() {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(Vec3::new(1.0, 0.0, 0.0)));
+        world.add_component(entity, TransformComponent::from_position(Vec3::new(2.0, 0.0, 0.0)));
+        
+        let transforms = world.query::<&TransformComponent>();
+        assert_eq!(transforms.len(), 1);
+        assert_eq!(transforms[0].1.position.x, 2.0);
+    }
+    
+    #[test]
+    fn test_storage_stays_dense_after_churn() {
+        let mut world = World::new();
+        let keep = world.create_entity();
+        world.add_component(keep, TransformComponent::identity());
+        
+        for _ in 0..1000 {
+            let entity = world.create_entity();
+            world.add_component(entity, TransformComponent::identity());
+            world.despawn(entity);
+        }
+        
+        let storage = world.get_storage_ref::<TransformComponent>().unwrap();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.entity_ids(), vec![keep.id()]);
+    }
+    
+    #[test]
+    fn test_resources() {
+        struct Score(u32);
+        impl Resource for Score {}
+        
+        let mut world = World::new();
+        assert!(world.resource::<Score>().is_none());
+        assert!(world.insert_resource(Score(1)).is_none());
+        assert!(world.contains_resource::<Score>());
+        
+        world.resource_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(world.resource::<Score>().map(|s| s.0), Some(2));
+        
+        assert_eq!(world.insert_resource(Score(10)).map(|s| s.0), Some(2));
+        assert_eq!(world.remove_resource::<Score>().map(|s| s.0), Some(10));
+        assert!(!world.contains_resource::<Score>());
+    }
 }
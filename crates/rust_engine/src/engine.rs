@@ -2,10 +2,14 @@
 
 use crate::{
     application::{Application, AppError, AppEvent},
-    foundation::time::Timer,
-    ecs::World,
+    foundation::time::{FixedTimestep, Timer},
+    ecs::{
+        World, System, ExclusiveSystem, SystemId, SystemScheduler, ExecutionMode,
+        systems::{MovementSystem, PhysicsStepSystem, TransformHistorySystem, TransformPropagationSystem},
+    },
     assets::AssetManager,
     render::GraphicsEngine,
+    spatial::DynamicAabbTree,
     input::InputManager,
     audio::AudioSystem,
 };
@@ -15,29 +19,29 @@ use thiserror::Error;
 /// 
 /// The engine coordinates all subsystems and manages the main loop.
 pub struct Engine {
-    /// ECS world containing all entities, components, and systems
+    /// ECS world containing all entities, components, and resources (including the frame
+    /// `Timer` and the `FixedTimestep` simulation clock)
     pub world: World,
     
+    /// Scheduler running registered ECS systems each frame
+    pub scheduler: SystemScheduler,
+    
     /// Asset management system
     pub assets: AssetManager,
     
     /// Audio system
     pub audio: Option<AudioSystem>,
     
-    /// Graphics engine
-    pub graphics_engine: GraphicsEngine,
+    /// Graphics engine (`None` when running headless)
+    pub graphics_engine: Option<GraphicsEngine>,
     
-    /// Window handle
-    pub window: crate::render::WindowHandle,
+    /// Window handle (`None` when running headless)
+    pub window: Option<crate::render::WindowHandle>,
     
     /// Input handling system
     pub input: InputManager,
     
-    /// Frame timing
-    timer: Timer,
-    
     /// Engine configuration
-    #[allow(dead_code)] // Will be used for runtime engine configuration
     config: EngineConfig,
     
     /// Whether the engine should continue running
@@ -50,21 +54,41 @@ impl Engine {
         log::info!("Initializing engine...");
         
         // Initialize subsystems
-        let world = World::new();
+        let mut world = World::new();
+        world.insert_resource(Timer::new());
+        world.insert_resource(
+            FixedTimestep::new(config.time.fixed_hz).with_max_substeps(config.time.max_substeps),
+        );
+        let mut scheduler = SystemScheduler::with_mode(if config.features.parallel_systems {
+            ExecutionMode::Parallel
+        } else {
+            ExecutionMode::SingleThreaded
+        });
+        // Snapshot transforms before any fixed-step system moves them
+        scheduler.add_system(TransformHistorySystem::new());
+        scheduler.add_system(MovementSystem::new());
+        if config.features.physics {
+            scheduler.add_exclusive_system(PhysicsStepSystem::new(Box::new(DynamicAabbTree::default())));
+        }
+        scheduler.add_system(TransformPropagationSystem::new());
         let assets = AssetManager::new(&config.assets)
             .map_err(|e| EngineError::InitializationFailed(format!("Asset manager: {}", e)))?;
         
         // Create window and graphics engine using the cleaner API
-        let mut window = crate::render::WindowHandle::new(
-            config.window.width,
-            config.window.height,
-            &config.window.title,
-        );
-        let graphics_engine = GraphicsEngine::new_from_window(&mut window, &crate::render::VulkanRendererConfig::default())
-            .map_err(|e| EngineError::InitializationFailed(format!("Graphics engine: {}", e)))?;
+        let (window, graphics_engine) = if config.window.headless {
+            (None, None)
+        } else {
+            let mut window = crate::render::WindowHandle::new(
+                config.window.width,
+                config.window.height,
+                &config.window.title,
+            );
+            let graphics_engine = GraphicsEngine::new_from_window(&mut window, &crate::render::VulkanRendererConfig::default())
+                .map_err(|e| EngineError::InitializationFailed(format!("Graphics engine: {}", e)))?;
+            (Some(window), Some(graphics_engine))
+        };
         
         let input = InputManager::new();
-        let timer = Timer::new();
         
         // Initialize audio if enabled
         let audio = if config.features.audio {
@@ -84,12 +108,12 @@ impl Engine {
         
         Ok(Self {
             world,
+            scheduler,
             assets,
             audio,
             graphics_engine,
             window,
             input,
-            timer,
             config,
             running: true,
         })
@@ -106,12 +130,20 @@ impl Engine {
         log::info!("Starting main loop...");
         
         while engine.running {
-            let delta_time = engine.timer.delta_time();
+            let delta_time = engine.tick_timer();
             
             // Update application
             app.update(&mut engine, delta_time)
                 .map_err(|e| EngineError::ApplicationError(format!("App update: {}", e)))?;
             
+            // Fixed-rate simulation (movement integration, collision)
+            for _ in 0..engine.accumulate_fixed_time(delta_time) {
+                let fixed_delta = engine.fixed_delta_time();
+                engine.scheduler.execute_fixed_step(&mut engine.world, fixed_delta);
+                app.fixed_update(&mut engine, fixed_delta)
+                    .map_err(|e| EngineError::ApplicationError(format!("App fixed update: {}", e)))?;
+            }
+            
             // Update engine systems
             engine.update(delta_time)?;
             
@@ -132,7 +164,7 @@ impl Engine {
         // Update subsystems
         self.input.update();
         self.assets.update().map_err(|e| EngineError::AssetError(e.to_string()))?;
-        self.world.update(delta_time);
+        self.scheduler.execute_frame(&mut self.world, delta_time);
         
         // Update audio system
         if let Some(audio) = &mut self.audio {
@@ -187,6 +219,26 @@ impl Engine {
         &mut self.world
     }
     
+    /// Register a system to run every frame in its phase
+    pub fn add_system(&mut self, system: impl System + 'static) -> SystemId {
+        self.scheduler.add_system(system)
+    }
+    
+    /// Register a system that runs alone with full access to the world
+    pub fn add_exclusive_system(&mut self, system: impl ExclusiveSystem) -> SystemId {
+        self.scheduler.add_exclusive_system(system)
+    }
+    
+    /// Collision detection and rigid body step (`None` unless `features.physics` is enabled)
+    pub fn physics(&self) -> Option<&PhysicsStepSystem> {
+        self.scheduler.exclusive_system()
+    }
+    
+    /// Mutable physics step, e.g. to register colliders
+    pub fn physics_mut(&mut self) -> Option<&mut PhysicsStepSystem> {
+        self.scheduler.exclusive_system_mut()
+    }
+    
     /// Get the asset manager
     pub fn assets(&self) -> &AssetManager {
         &self.assets
@@ -197,14 +249,14 @@ impl Engine {
         &mut self.assets
     }
     
-    /// Get the graphics engine
-    pub fn graphics_engine(&self) -> &GraphicsEngine {
-        &self.graphics_engine
+    /// Get the graphics engine (`None` when running headless)
+    pub fn graphics_engine(&self) -> Option<&GraphicsEngine> {
+        self.graphics_engine.as_ref()
     }
     
-    /// Get mutable access to the graphics engine
-    pub fn graphics_engine_mut(&mut self) -> &mut GraphicsEngine {
-        &mut self.graphics_engine
+    /// Get mutable access to the graphics engine (`None` when running headless)
+    pub fn graphics_engine_mut(&mut self) -> Option<&mut GraphicsEngine> {
+        self.graphics_engine.as_mut()
     }
     
     /// Get the input manager
@@ -214,7 +266,33 @@ impl Engine {
     
     /// Get the current frame delta time
     pub fn delta_time(&self) -> f32 {
-        self.timer.delta_time()
+        self.world.resource::<Timer>().map_or(0.0, Timer::delta_time)
+    }
+    
+    /// Length of one fixed simulation step in seconds
+    pub fn fixed_delta_time(&self) -> f32 {
+        self.world.resource::<FixedTimestep>().map_or(1.0 / self.config.time.fixed_hz, FixedTimestep::step)
+    }
+    
+    /// Fraction of a fixed step not yet simulated, for interpolating rendered transforms
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.world.resource::<FixedTimestep>().map_or(1.0, FixedTimestep::alpha)
+    }
+    
+    /// Feed the frame time to the fixed-step clock, returning the steps to run this frame
+    fn accumulate_fixed_time(&mut self, delta_time: f32) -> u32 {
+        self.world.resource_mut::<FixedTimestep>().map_or(0, |fixed| fixed.accumulate(delta_time))
+    }
+    
+    /// Advance the frame timer resource, returning the new delta time
+    fn tick_timer(&mut self) -> f32 {
+        let frame_delta = self.config.time.frame_delta;
+        let timer = self.world.resource_mut::<Timer>().expect("Timer resource removed from the world");
+        match frame_delta {
+            Some(delta_time) => timer.advance(delta_time),
+            None => timer.update(),
+        }
+        timer.delta_time()
     }
     
     /// Request engine shutdown
@@ -237,6 +315,9 @@ pub struct EngineConfig {
     
     /// Engine features to enable
     pub features: EngineFeatures,
+    
+    /// Simulation timing
+    pub time: TimeConfig,
 }
 
 /// Window configuration
@@ -259,6 +340,9 @@ pub struct WindowConfig {
     
     /// VSync setting
     pub vsync: bool,
+    
+    /// Run without a window or renderer (headless tests, servers)
+    pub headless: bool,
 }
 
 /// Renderer configuration
@@ -290,13 +374,26 @@ pub struct AssetConfig {
     pub cache_size_mb: u32,
 }
 
+/// Simulation timing configuration
+#[derive(Debug, Clone)]
+pub struct TimeConfig {
+    /// Fixed simulation rate for `SystemPhase::FixedUpdate`, in steps per second
+    pub fixed_hz: f32,
+    
+    /// Maximum fixed steps per frame (time beyond this is dropped)
+    pub max_substeps: u32,
+    
+    /// Constant frame time in seconds instead of the wall clock (replays, headless tests)
+    pub frame_delta: Option<f32>,
+}
+
 /// Engine features
 #[derive(Debug, Clone)]
 pub struct EngineFeatures {
     /// Enable audio system
     pub audio: bool,
     
-    /// Enable physics system
+    /// Run collision detection and rigid bodies in the fixed step (`PhysicsStepSystem`)
     pub physics: bool,
     
     /// Enable profiling
@@ -304,6 +401,9 @@ pub struct EngineFeatures {
     
     /// Enable debug rendering
     pub debug_rendering: bool,
+    
+    /// Run non-conflicting ECS systems in parallel (disable for deterministic runs)
+    pub parallel_systems: bool,
 }
 
 impl Default for EngineConfig {
@@ -316,6 +416,7 @@ impl Default for EngineConfig {
                 resizable: true,
                 fullscreen: false,
                 vsync: true,
+                headless: false,
             },
             renderer: RendererConfig {
                 validation: cfg!(debug_assertions),
@@ -330,9 +431,15 @@ impl Default for EngineConfig {
             },
             features: EngineFeatures {
                 audio: true,
-                physics: false,
+                physics: true,
                 profiling: cfg!(debug_assertions),
                 debug_rendering: cfg!(debug_assertions),
+                parallel_systems: true,
+            },
+            time: TimeConfig {
+                fixed_hz: 60.0,
+                max_substeps: FixedTimestep::DEFAULT_MAX_SUBSTEPS,
+                frame_delta: None,
             },
         }
     }
@@ -365,3 +472,99 @@ pub enum EngineError {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Entity, components::{ColliderComponent, MovementComponent, RigidBodyComponent, TransformComponent}};
+    use crate::foundation::math::Vec3;
+    use crate::physics::CollisionShape;
+
+    /// Headless app recording positions after every fixed step
+    struct Recorder {
+        frames: u32,
+        duration: f32,
+        mover: Option<Entity>,
+        balls: Vec<Entity>,
+        trajectory: Vec<Vec<Vec3>>,
+    }
+
+    impl Recorder {
+        fn new(duration: f32) -> Self {
+            Self { frames: 0, duration, mover: None, balls: Vec::new(), trajectory: Vec::new() }
+        }
+    }
+
+    impl Application for Recorder {
+        fn initialize(&mut self, engine: &mut Engine) -> Result<(), AppError> {
+            let world = &mut engine.world;
+            let mover = world.create_entity();
+            world.add_component(mover, TransformComponent::identity());
+            let mut movement = MovementComponent::with_velocity(Vec3::new(3.0, 0.0, 0.0));
+            movement.set_acceleration(Vec3::new(0.0, -9.81, 0.0));
+            world.add_component(mover, movement);
+            self.mover = Some(mover);
+
+            // Two balls on a collision course
+            for (x, speed) in [(-3.0, 4.0), (3.0, -4.0)] {
+                let ball = engine.world.create_entity();
+                let collider = ColliderComponent::new(CollisionShape::sphere(1.0));
+                engine.world.add_component(ball, TransformComponent::from_position(Vec3::new(x, 10.0, 0.0)));
+                engine.world.add_component(ball, RigidBodyComponent::dynamic(1.0).with_restitution(1.0).with_velocity(Vec3::new(speed, 0.0, 0.0)));
+                engine.scheduler.exclusive_system_mut::<PhysicsStepSystem>()
+                    .expect("physics is enabled")
+                    .collision_mut()
+                    .register_collider(ball, &collider, &engine.world);
+                engine.world.add_component(ball, collider);
+                self.balls.push(ball);
+            }
+            Ok(())
+        }
+
+        fn update(&mut self, engine: &mut Engine, delta_time: f32) -> Result<(), AppError> {
+            self.frames += 1;
+            if self.frames as f32 * delta_time >= self.duration {
+                engine.quit();
+            }
+            Ok(())
+        }
+
+        fn fixed_update(&mut self, engine: &mut Engine, _fixed_delta: f32) -> Result<(), AppError> {
+            let step = self.mover.iter()
+                .chain(&self.balls)
+                .map(|&entity| engine.world.get_component::<TransformComponent>(entity).unwrap().position)
+                .collect();
+            self.trajectory.push(step);
+            Ok(())
+        }
+
+        fn cleanup(&mut self, _engine: &mut Engine) {}
+    }
+
+    fn run_headless(frame_delta: f32) -> Recorder {
+        let mut config = EngineConfig::default();
+        config.window.headless = true;
+        config.features.audio = false;
+        config.time.frame_delta = Some(frame_delta);
+        let mut app = Recorder::new(2.0);
+        Engine::run(config, &mut app).unwrap();
+        app
+    }
+
+    #[test]
+    fn test_trajectories_are_independent_of_frame_rate() {
+        let slow = run_headless(1.0 / 30.0);
+        let steady = run_headless(1.0 / 60.0);
+        let fast = run_headless(1.0 / 144.0);
+
+        let steps = slow.trajectory.len().min(steady.trajectory.len()).min(fast.trajectory.len());
+        assert!(steps >= 100, "only {} fixed steps ran", steps);
+        assert_eq!(slow.trajectory[..steps], steady.trajectory[..steps]);
+        assert_eq!(fast.trajectory[..steps], steady.trajectory[..steps]);
+
+        // The mover was integrated and the balls bounced off each other
+        let last = &steady.trajectory[steps - 1];
+        assert!(last[0].x > 4.0 && last[0].y < -4.0, "mover at {:?}", last[0]);
+        assert!(last[1].x < -3.0 && last[2].x > 3.0, "balls at {:?} and {:?}", last[1], last[2]);
+    }
+}
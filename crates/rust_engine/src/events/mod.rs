@@ -1,205 +1,129 @@
-//! Event system following Game Engine Architecture Ch 16.8
-//! Key principles:
-//! - Key-value arguments (no order dependency)
-//! - Handler returns bool (true = consumed, stops forwarding)
-//! - Registration system (only notify interested handlers)
-//! - Queuing support (immediate + deferred delivery)
-
+//! Typed event bus following Game Engine Architecture Ch 16.8
+//!
+//! Any `Send + Sync + 'static` type can be an event, so game code defines its
+//! own (`ShipDestroyed`, `WaveCleared`, ...) next to the systems that raise
+//! them instead of extending a shared enum. Arguments are ordinary struct
+//! fields, which keeps the order-independence of GEA's key-value arguments
+//! while making them checked at compile time.
+//!
+//! `EventBus` holds one double-buffered `Events<E>` queue per event type,
+//! created on first use. Consumers keep an `EventReader<E>` each, so several
+//! systems read the same events without draining them for each other.
+//! `update()` is called once per frame by the bus owner; readers see every
+//! event as long as they read at least once every two updates.
+//!
+//! ```ignore
+//! struct ShipDestroyed { ship: Entity }
+//!
+//! bus.send(ShipDestroyed { ship });
+//!
+//! let mut reader = EventReader::<ShipDestroyed>::new();
+//! for destroyed in bus.read(&mut reader) {
+//!     log::info!("{:?} destroyed", destroyed.ship);
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
-/// Event type identification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum EventType {
-    /// Button was clicked
-    ButtonClicked,
-    /// Button hover state changed
-    ButtonHoverChanged,
-    /// Mouse cursor moved
-    MouseMoved,
-    /// Mouse button was pressed
-    MouseButtonPressed,
-    /// Mouse button was released
-    MouseButtonReleased,
-}
+pub use crate::ecs::{EventReader, Events};
 
-/// Variant for type-safe event arguments
-/// Uses key-value pairs to avoid order dependency problems
-#[derive(Debug, Clone)]
-pub enum EventArg {
-    /// Button identifier
-    ButtonId(u32),
-    /// Hover state
-    Hovered(bool),
-    /// Position coordinates
-    Position(f32, f32),
-    /// Mouse button index
-    MouseButton(u32),
-}
-
-/// Event with type ID and key-value arguments
-#[derive(Debug, Clone)]
-pub struct Event {
-    /// Type of event
-    pub event_type: EventType,
-    /// Timestamp when event was created (seconds)
-    pub timestamp: f64,
-    args: HashMap<&'static str, EventArg>,
-}
+/// Marker for types that can be sent through an `EventBus`
+pub trait Event: Send + Sync + 'static {}
 
-impl Event {
-    /// Create a new event with the given type and timestamp
-    pub fn new(event_type: EventType, timestamp: f64) -> Self {
-        Self {
-            event_type,
-            timestamp,
-            args: HashMap::new(),
-        }
-    }
-
-    /// Add an argument to the event (builder pattern)
-    pub fn with_arg(mut self, key: &'static str, value: EventArg) -> Self {
-        self.args.insert(key, value);
-        self
-    }
+impl<T: Send + Sync + 'static> Event for T {}
 
-    /// Get an argument by key
-    pub fn get_arg(&self, key: &str) -> Option<&EventArg> {
-        self.args.get(key)
-    }
+/// Type-erased access to an `Events<E>` queue
+trait EventQueue: Send + Sync {
+    fn update(&mut self);
+    fn clear(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
 
-    /// Get button_id argument if present
-    pub fn get_button_id(&self) -> Option<u32> {
-        if let Some(EventArg::ButtonId(id)) = self.get_arg("button_id") {
-            Some(*id)
-        } else {
-            None
-        }
+impl<E: Event> EventQueue for Events<E> {
+    fn update(&mut self) {
+        Events::update(self);
     }
 
-    /// Get hovered argument if present
-    pub fn get_hovered(&self) -> Option<bool> {
-        if let Some(EventArg::Hovered(hovered)) = self.get_arg("hovered") {
-            Some(*hovered)
-        } else {
-            None
-        }
+    fn clear(&mut self) {
+        Events::clear(self);
     }
 
-    /// Get position argument if present
-    pub fn get_position(&self) -> Option<(f32, f32)> {
-        if let Some(EventArg::Position(x, y)) = self.get_arg("position") {
-            Some((*x, *y))
-        } else {
-            None
-        }
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 
-    /// Get mouse button argument if present
-    pub fn get_mouse_button(&self) -> Option<u32> {
-        if let Some(EventArg::MouseButton(button)) = self.get_arg("button") {
-            Some(*button)
-        } else {
-            None
-        }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
     }
 }
 
-/// Event handler trait
-/// Returns true if event was consumed (stops forwarding)
-/// Returns false to allow forwarding to other handlers
-pub trait EventHandler {
-    /// Handle an event, return true if consumed
-    fn on_event(&mut self, event: &Event) -> bool;
+/// Collection of typed event queues, updated together once per frame
+#[derive(Default)]
+pub struct EventBus {
+    queues: HashMap<TypeId, Box<dyn EventQueue>>,
 }
 
-/// Event system with registration and queuing
-/// Follows chain of responsibility pattern
-pub struct EventSystem {
-    immediate_queue: Vec<Event>,
-    deferred_queue: Vec<(f64, Event)>,
-    handlers: HashMap<EventType, Vec<Box<dyn EventHandler>>>,
-    current_time: f64,
-}
-
-impl EventSystem {
-    /// Create a new empty event system
+impl EventBus {
+    /// Create an empty bus
     pub fn new() -> Self {
-        Self {
-            immediate_queue: Vec::new(),
-            deferred_queue: Vec::new(),
-            handlers: HashMap::new(),
-            current_time: 0.0,
-        }
+        Self::default()
     }
 
-    /// Update current time (seconds since start)
-    pub fn update_time(&mut self, time: f64) {
-        self.current_time = time;
+    /// Queue an event
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.events_mut::<E>().send(event);
     }
 
-    /// Register a handler for a specific event type
-    /// Only handlers registered for this type will be notified
-    pub fn register_handler(&mut self, event_type: EventType, handler: Box<dyn EventHandler>) {
-        self.handlers
-            .entry(event_type)
-            .or_insert_with(Vec::new)
-            .push(handler);
+    /// Queue several events of one type
+    pub fn send_batch<E: Event>(&mut self, events: impl IntoIterator<Item = E>) {
+        self.events_mut::<E>().send_batch(events);
     }
 
-    /// Send event for immediate handling this frame
-    pub fn send(&mut self, event: Event) {
-        self.immediate_queue.push(event);
+    /// Events of type `E` sent since `reader` last read, oldest first
+    pub fn read<'a, E: Event>(&'a self, reader: &mut EventReader<E>) -> impl Iterator<Item = &'a E> + 'a {
+        self.events::<E>().map(|events| reader.read(events)).into_iter().flatten()
     }
 
-    /// Post event for deferred delivery at specified time
-    pub fn post(&mut self, delivery_time: f64, event: Event) {
-        self.deferred_queue.push((delivery_time, event));
+    /// Queue of events of type `E`, if any have been sent
+    pub fn events<E: Event>(&self) -> Option<&Events<E>> {
+        self.queues.get(&TypeId::of::<E>())?.as_any().downcast_ref::<Events<E>>()
     }
 
-    /// Dispatch all pending events
-    /// Processes immediate queue first, then due deferred events
-    pub fn dispatch(&mut self) {
-        // Process immediate events
-        let immediate = std::mem::take(&mut self.immediate_queue);
-        for event in immediate {
-            self.dispatch_event(&event);
-        }
+    /// Queue of events of type `E`, created if needed
+    pub fn events_mut<E: Event>(&mut self) -> &mut Events<E> {
+        self.queues
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(Events::<E>::new()))
+            .as_any_mut()
+            .downcast_mut::<Events<E>>()
+            .expect("event queue is keyed by its type")
+    }
 
-        // Process due deferred events
-        let mut i = 0;
-        while i < self.deferred_queue.len() {
-            if self.deferred_queue[i].0 <= self.current_time {
-                let (_, event) = self.deferred_queue.remove(i);
-                self.dispatch_event(&event);
-            } else {
-                i += 1;
-            }
+    /// Swap the buffers of every queue (call once per frame)
+    pub fn update(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.update();
         }
     }
 
-    /// Dispatch single event to registered handlers
-    /// Stops on first handler that returns true (consumed)
-    fn dispatch_event(&mut self, event: &Event) {
-        if let Some(handlers) = self.handlers.get_mut(&event.event_type) {
-            for handler in handlers.iter_mut() {
-                if handler.on_event(event) {
-                    // Event consumed, stop forwarding
-                    break;
-                }
-            }
+    /// Drop all queued events (useful for state transitions)
+    pub fn clear(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.clear();
         }
     }
 
-    /// Clear all queued events (useful for state transitions)
-    pub fn clear(&mut self) {
-        self.immediate_queue.clear();
-        self.deferred_queue.clear();
+    /// Number of event types that have queues
+    pub fn queue_count(&self) -> usize {
+        self.queues.len()
     }
 }
 
-impl Default for EventSystem {
-    fn default() -> Self {
-        Self::new()
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus").field("queues", &self.queues.len()).finish()
     }
 }
 
@@ -207,82 +131,61 @@ impl Default for EventSystem {
 mod tests {
     use super::*;
 
-    struct TestHandler {
-        events_received: Vec<EventType>,
-        consume: bool,
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct ShipDestroyed {
+        ship: u32,
     }
 
-    impl TestHandler {
-        fn new(consume: bool) -> Self {
-            Self {
-                events_received: Vec::new(),
-                consume,
-            }
-        }
-    }
-
-    impl EventHandler for TestHandler {
-        fn on_event(&mut self, event: &Event) -> bool {
-            self.events_received.push(event.event_type);
-            self.consume
-        }
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct WaveCleared {
+        wave: u32,
     }
 
     #[test]
-    fn test_immediate_dispatch() {
-        let mut system = EventSystem::new();
-        let handler = Box::new(TestHandler::new(false));
-        system.register_handler(EventType::ButtonClicked, handler);
-
-        let event = Event::new(EventType::ButtonClicked, 0.0)
-            .with_arg("button_id", EventArg::ButtonId(42));
-        system.send(event);
-        system.dispatch();
-
-        // Handler should have received the event
-        if let Some(handlers) = system.handlers.get(&EventType::ButtonClicked) {
-            assert_eq!(handlers.len(), 1);
-        }
-    }
+    fn test_events_are_routed_by_type() {
+        let mut bus = EventBus::new();
+        let mut destroyed = EventReader::<ShipDestroyed>::new();
+        let mut cleared = EventReader::<WaveCleared>::new();
 
-    #[test]
-    fn test_deferred_dispatch() {
-        let mut system = EventSystem::new();
-        system.update_time(0.0);
-
-        let event = Event::new(EventType::ButtonClicked, 1.0);
-        system.post(1.0, event);
-
-        // Event should not dispatch at t=0.5
-        system.update_time(0.5);
-        system.dispatch();
-        assert_eq!(system.deferred_queue.len(), 1);
-
-        // Event should dispatch at t=1.0
-        system.update_time(1.0);
-        system.dispatch();
-        assert_eq!(system.deferred_queue.len(), 0);
+        // Reading a type nobody has sent yet is fine
+        assert_eq!(bus.read(&mut cleared).count(), 0);
+
+        bus.send(ShipDestroyed { ship: 7 });
+        bus.send_batch([ShipDestroyed { ship: 8 }, ShipDestroyed { ship: 9 }]);
+        bus.send(WaveCleared { wave: 1 });
+        assert_eq!(bus.queue_count(), 2);
+
+        let ships: Vec<u32> = bus.read(&mut destroyed).map(|event| event.ship).collect();
+        assert_eq!(ships, vec![7, 8, 9]);
+        assert_eq!(bus.read(&mut cleared).copied().collect::<Vec<_>>(), vec![WaveCleared { wave: 1 }]);
+        assert_eq!(bus.read(&mut destroyed).count(), 0);
     }
 
     #[test]
-    fn test_event_consumption() {
-        let mut system = EventSystem::new();
-        
-        // First handler consumes
-        let handler1 = Box::new(TestHandler::new(true));
-        system.register_handler(EventType::ButtonClicked, handler1);
-        
-        // Second handler should not receive
-        let handler2 = Box::new(TestHandler::new(false));
-        system.register_handler(EventType::ButtonClicked, handler2);
-
-        let event = Event::new(EventType::ButtonClicked, 0.0);
-        system.send(event);
-        system.dispatch();
-
-        // Only first handler should have received event
-        if let Some(handlers) = system.handlers.get(&EventType::ButtonClicked) {
-            assert_eq!(handlers.len(), 2);
-        }
+    fn test_update_double_buffers_every_queue() {
+        let mut bus = EventBus::new();
+        let mut early = EventReader::<ShipDestroyed>::new();
+        let mut late = EventReader::<ShipDestroyed>::new();
+        let mut waves = EventReader::<WaveCleared>::new();
+
+        bus.send(ShipDestroyed { ship: 1 });
+        bus.send(WaveCleared { wave: 1 });
+        assert_eq!(bus.read(&mut early).count(), 1);
+
+        // Still readable one frame later...
+        bus.update();
+        bus.send(ShipDestroyed { ship: 2 });
+        assert_eq!(bus.read(&mut early).map(|event| event.ship).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(bus.read(&mut late).count(), 2);
+
+        // ...but gone after two
+        bus.update();
+        bus.update();
+        assert_eq!(bus.read(&mut waves).count(), 0);
+
+        bus.send(WaveCleared { wave: 2 });
+        bus.clear();
+        assert_eq!(bus.read(&mut waves).count(), 0);
+        assert!(bus.events::<WaveCleared>().unwrap().is_empty());
     }
 }
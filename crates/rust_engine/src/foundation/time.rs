@@ -38,6 +38,14 @@ impl Timer {
         self.frame_count += 1;
     }
     
+    /// Advance by a fixed amount instead of the wall clock (replays, headless tests)
+    pub fn advance(&mut self, delta_time: f32) {
+        self.delta_time = delta_time;
+        self.total_time += delta_time;
+        self.last_frame = Instant::now();
+        self.frame_count += 1;
+    }
+    
     /// Get the time since the last frame in seconds
     pub fn delta_time(&self) -> f32 {
         self.delta_time
@@ -150,3 +158,124 @@ impl Stopwatch {
         self.start_time.is_some()
     }
 }
+
+/// Fixed-rate simulation clock
+///
+/// Accumulates variable frame time and hands it out in fixed steps, so
+/// movement integration and collision produce the same results regardless of
+/// frame rate. The leftover fraction of a step is exposed as `alpha` for
+/// interpolating rendered transforms between the last two simulation states.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step: f32,
+    max_substeps: u32,
+    accumulator: f64,
+    total_steps: u64,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(60.0)
+    }
+}
+
+impl FixedTimestep {
+    /// Default cap on steps run for a single frame
+    pub const DEFAULT_MAX_SUBSTEPS: u32 = 8;
+
+    /// Create a clock stepping at `hz` steps per second
+    pub fn new(hz: f32) -> Self {
+        assert!(hz > 0.0, "Fixed timestep rate must be positive");
+        Self {
+            step: 1.0 / hz,
+            max_substeps: Self::DEFAULT_MAX_SUBSTEPS,
+            accumulator: 0.0,
+            total_steps: 0,
+        }
+    }
+
+    /// Limit the steps run per frame; time beyond the limit is dropped so a slow
+    /// frame cannot make the next one even slower
+    pub fn with_max_substeps(mut self, max_substeps: u32) -> Self {
+        self.max_substeps = max_substeps.max(1);
+        self
+    }
+
+    /// Length of one step in seconds
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Steps per second
+    pub fn hz(&self) -> f32 {
+        1.0 / self.step
+    }
+
+    /// Maximum steps run per frame
+    pub fn max_substeps(&self) -> u32 {
+        self.max_substeps
+    }
+
+    /// Add a frame's delta time, returning how many fixed steps to run now
+    pub fn accumulate(&mut self, delta_time: f32) -> u32 {
+        let step = f64::from(self.step);
+        self.accumulator += f64::from(delta_time.max(0.0));
+
+        let steps = ((self.accumulator / step).floor() as u32).min(self.max_substeps);
+        self.accumulator -= f64::from(steps) * step;
+        if self.accumulator >= step {
+            // Over the substep budget: keep only the partial step
+            self.accumulator %= step;
+        }
+
+        self.total_steps += u64::from(steps);
+        steps
+    }
+
+    /// Fraction of a step accumulated but not yet simulated (0.0..1.0)
+    ///
+    /// Render with `previous.interpolate(&current, alpha)` to smooth motion
+    /// between fixed steps.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / f64::from(self.step)) as f32
+    }
+
+    /// Total number of fixed steps handed out
+    pub fn total_steps(&self) -> u64 {
+        self.total_steps
+    }
+
+    /// Simulated time in seconds (total steps times the step length)
+    pub fn elapsed(&self) -> f64 {
+        self.total_steps as f64 * f64::from(self.step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_timestep_accumulates_partial_steps() {
+        let mut fixed = FixedTimestep::new(60.0);
+
+        // Two 120 Hz frames make one step
+        assert_eq!(fixed.accumulate(1.0 / 120.0), 0);
+        assert!((fixed.alpha() - 0.5).abs() < 1e-4);
+        assert_eq!(fixed.accumulate(1.0 / 120.0), 1);
+        assert!(fixed.alpha() < 1e-4);
+
+        // A 30 Hz frame makes two
+        assert_eq!(fixed.accumulate(1.0 / 30.0), 2);
+        assert_eq!(fixed.total_steps(), 3);
+    }
+
+    #[test]
+    fn test_fixed_timestep_drops_time_beyond_substep_limit() {
+        let mut fixed = FixedTimestep::new(100.0).with_max_substeps(4);
+
+        assert_eq!(fixed.accumulate(1.005), 4);
+        assert!((fixed.alpha() - 0.5).abs() < 1e-3, "only the partial step should remain, got {}", fixed.alpha());
+        assert_eq!(fixed.accumulate(0.0), 0);
+    }
+}
@@ -84,13 +84,13 @@ pub mod prelude {
         Engine, EngineConfig, EngineError,
         foundation::{
             math::{Vec3, Mat4, Transform},
-            time::{Timer, Stopwatch},
+            time::{Timer, Stopwatch, FixedTimestep},
         },
-        ecs::{World, Entity, Component, System, Query},
+        ecs::{World, Entity, Component, Resource, System, SystemAccess, SystemWorld, SystemPhase, Query, Events, EventReader},
         assets::{Asset, AssetHandle, AssetManager},
         render::{GraphicsEngine, Camera, Mesh, Material},
         input::{InputManager, KeyCode, MouseButton},
-        events::{Event, EventType, EventArg, EventHandler, EventSystem},
+        events::{Event, EventBus},
         // Scene management
         scene::{SceneManager, SceneRenderer, SceneRendererConfig, RenderQueue},
         // New unified config system
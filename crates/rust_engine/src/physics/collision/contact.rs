@@ -0,0 +1,169 @@
+//! Contact data produced by the narrow phase
+//!
+//! GEA 13.4.3: collision response needs more than a yes/no answer; it needs
+//! the contact point, the separating normal and how deep the shapes overlap.
+//! Resting and sliding contacts between flat surfaces need several points (a
+//! contact manifold) so the solver doesn't rock the bodies around one corner.
+
+use crate::foundation::math::Vec3;
+
+/// Most points kept in a contact manifold (enough to support a face)
+pub const MAX_MANIFOLD_POINTS: usize = 4;
+
+/// Points closer than this are merged when building a manifold
+const MERGE_DISTANCE: f32 = 1.0e-4;
+
+/// Contact between two intersecting world-space shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Contact point in world space
+    pub point: Vec3,
+    /// Unit normal pointing from the first shape towards the second
+    pub normal: Vec3,
+    /// Penetration depth along the normal (>= 0)
+    pub depth: f32,
+}
+
+impl Contact {
+    /// Creates a new contact
+    pub fn new(point: Vec3, normal: Vec3, depth: f32) -> Self {
+        Self { point, normal, depth }
+    }
+
+    /// The same contact seen from the second shape (normal reversed)
+    pub fn flipped(&self) -> Self {
+        Self { normal: -self.normal, ..*self }
+    }
+}
+
+/// A single point of a contact manifold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactPoint {
+    /// Contact point in world space
+    pub point: Vec3,
+    /// Penetration depth at this point along the manifold normal (>= 0)
+    pub depth: f32,
+}
+
+/// All contact points between two shapes, sharing one normal
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactManifold {
+    /// Unit normal pointing from the first shape towards the second
+    pub normal: Vec3,
+    /// Contact points (1 to `MAX_MANIFOLD_POINTS`)
+    pub points: Vec<ContactPoint>,
+}
+
+impl ContactManifold {
+    /// Creates a manifold, merging duplicate points and keeping at most
+    /// `MAX_MANIFOLD_POINTS` that span the largest area
+    pub fn new(normal: Vec3, points: impl IntoIterator<Item = ContactPoint>) -> Self {
+        let mut merged: Vec<ContactPoint> = Vec::new();
+        for candidate in points {
+            match merged.iter_mut().find(|p| (p.point - candidate.point).magnitude_squared() < MERGE_DISTANCE * MERGE_DISTANCE) {
+                Some(existing) => existing.depth = existing.depth.max(candidate.depth),
+                None => merged.push(candidate),
+            }
+        }
+        Self { normal, points: reduce(merged) }
+    }
+
+    /// Number of contact points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// True if the manifold has no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Largest penetration depth of all points
+    pub fn max_depth(&self) -> f32 {
+        self.points.iter().fold(0.0, |depth, p| depth.max(p.depth))
+    }
+
+    /// The deepest point as a single contact
+    pub fn deepest(&self) -> Option<Contact> {
+        self.points
+            .iter()
+            .max_by(|a, b| a.depth.total_cmp(&b.depth))
+            .map(|p| Contact::new(p.point, self.normal, p.depth))
+    }
+
+    /// Iterate over the points as individual contacts
+    pub fn contacts(&self) -> impl Iterator<Item = Contact> + '_ {
+        self.points.iter().map(|p| Contact::new(p.point, self.normal, p.depth))
+    }
+
+    /// The same manifold seen from the second shape (normal reversed)
+    pub fn flipped(&self) -> Self {
+        Self { normal: -self.normal, points: self.points.clone() }
+    }
+}
+
+impl From<Contact> for ContactManifold {
+    fn from(contact: Contact) -> Self {
+        Self {
+            normal: contact.normal,
+            points: vec![ContactPoint { point: contact.point, depth: contact.depth }],
+        }
+    }
+}
+
+/// Keep the deepest point plus the points spanning the largest area
+fn reduce(mut points: Vec<ContactPoint>) -> Vec<ContactPoint> {
+    if points.len() <= MAX_MANIFOLD_POINTS {
+        return points;
+    }
+
+    let pick = |points: &[ContactPoint], score: &dyn Fn(Vec3) -> f32| {
+        (0..points.len())
+            .max_by(|&i, &j| score(points[i].point).total_cmp(&score(points[j].point)))
+            .unwrap()
+    };
+
+    let mut kept = Vec::with_capacity(MAX_MANIFOLD_POINTS);
+    let first = (0..points.len()).max_by(|&i, &j| points[i].depth.total_cmp(&points[j].depth)).unwrap();
+    kept.push(points.swap_remove(first));
+
+    let a = kept[0].point;
+    let second = pick(&points, &|p| (p - a).magnitude_squared());
+    kept.push(points.swap_remove(second));
+
+    let b = kept[1].point;
+    let third = pick(&points, &|p| (p - a).cross(&(b - a)).magnitude_squared());
+    kept.push(points.swap_remove(third));
+
+    // Points inside triangle abc all score twice its area; outside points score more
+    let c = kept[2].point;
+    let area = |p: Vec3, q: Vec3, r: Vec3| (q - p).cross(&(r - p)).magnitude();
+    let fourth = pick(&points, &|p| area(p, a, b) + area(p, b, c) + area(p, c, a));
+    kept.push(points.swap_remove(fourth));
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifold_keeps_deepest_and_widest_points() {
+        let point = |x: f32, z: f32, depth: f32| ContactPoint { point: Vec3::new(x, 0.0, z), depth };
+        let manifold = ContactManifold::new(Vec3::y(), [
+            point(0.0, 0.0, 0.1),   // Interior
+            point(-1.0, -1.0, 0.2),
+            point(1.0, -1.0, 0.3),
+            point(1.0, 1.0, 0.5),   // Deepest
+            point(-1.0, 1.0, 0.2),
+            point(0.5, 0.0, 0.1),   // Interior
+            point(1.0, 1.0, 0.4),   // Duplicate of the deepest
+        ]);
+
+        assert_eq!(manifold.len(), MAX_MANIFOLD_POINTS);
+        assert_eq!(manifold.max_depth(), 0.5);
+        assert_eq!(manifold.deepest().unwrap().point, Vec3::new(1.0, 0.0, 1.0));
+        assert!(manifold.points.iter().all(|p| p.point.x.abs() == 1.0 && p.point.z.abs() == 1.0));
+    }
+}
@@ -0,0 +1,586 @@
+//! GJK/EPA narrow phase for convex shapes
+//!
+//! GEA 13.3.5: the Gilbert-Johnson-Keerthi algorithm tests any two convex
+//! shapes using only their support functions, by checking whether the origin
+//! lies inside their Minkowski difference. When it does, the expanding
+//! polytope algorithm (EPA) grows GJK's final simplex towards the surface of
+//! the difference to find the penetration normal and depth.
+//!
+//! A single EPA point is enough for round shapes, but flat contacts (a box
+//! resting on a box) need several points, so the contact features of both
+//! shapes are clipped against each other to build the manifold.
+
+use crate::foundation::math::Vec3;
+use super::contact::{Contact, ContactManifold, ContactPoint};
+use super::hull::WorldSpaceConvexHull;
+use super::primitives::{BoundingSphere, Capsule, OrientedBox, Triangle};
+use std::collections::HashSet;
+
+/// Iteration limit for GJK and EPA
+const MAX_ITERATIONS: usize = 64;
+
+/// EPA stops when the polytope grows less than this towards the surface
+const EPA_TOLERANCE: f32 = 1.0e-4;
+
+/// Points closer than this are treated as the same point
+const POINT_EPSILON: f32 = 1.0e-5;
+
+/// Cosine below which an edge or face counts as perpendicular to the contact normal
+const FEATURE_TOLERANCE: f32 = 0.05;
+
+/// A convex shape described by its support function
+pub trait SupportMap {
+    /// Farthest point of the shape in `direction` (need not be normalized)
+    fn support(&self, direction: Vec3) -> Vec3;
+
+    /// Points of the vertex, edge or face farthest in `direction`, in order around the face
+    fn support_feature(&self, direction: Vec3) -> Vec<Vec3> {
+        vec![self.support(direction)]
+    }
+}
+
+impl SupportMap for BoundingSphere {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.center + unit_or_x(direction) * self.radius
+    }
+}
+
+impl SupportMap for Capsule {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let end = if (self.end - self.start).dot(&direction) >= 0.0 { self.end } else { self.start };
+        end + unit_or_x(direction) * self.radius
+    }
+
+    fn support_feature(&self, direction: Vec3) -> Vec<Vec3> {
+        let normal = unit_or_x(direction);
+        let axis = self.end - self.start;
+        let length = axis.magnitude();
+        if length > POINT_EPSILON && (axis.dot(&normal) / length).abs() < FEATURE_TOLERANCE {
+            // Lying on its side: the whole spine touches
+            vec![self.start + normal * self.radius, self.end + normal * self.radius]
+        } else {
+            vec![self.support(direction)]
+        }
+    }
+}
+
+impl SupportMap for OrientedBox {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.axes()
+            .iter()
+            .enumerate()
+            .fold(self.center, |point, (i, axis)| {
+                let sign = if axis.dot(&direction) >= 0.0 { 1.0 } else { -1.0 };
+                point + axis * (sign * self.half_extents[i])
+            })
+    }
+
+    fn support_feature(&self, direction: Vec3) -> Vec<Vec3> {
+        let normal = unit_or_x(direction);
+        let mut base = self.center;
+        let mut free = Vec::new();
+        for (i, axis) in self.axes().iter().enumerate() {
+            let alignment = axis.dot(&normal);
+            if alignment.abs() < FEATURE_TOLERANCE {
+                free.push(axis * self.half_extents[i]);
+            } else {
+                base += axis * (alignment.signum() * self.half_extents[i]);
+            }
+        }
+
+        match free.as_slice() {
+            [] => vec![base],
+            [u] => vec![base - u, base + u],
+            [u, v] => vec![base + u + v, base - u + v, base - u - v, base + u - v],
+            _ => vec![self.support(direction)],
+        }
+    }
+}
+
+impl SupportMap for WorldSpaceConvexHull {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.vertices
+            .iter()
+            .copied()
+            .max_by(|a, b| a.dot(&direction).total_cmp(&b.dot(&direction)))
+            .unwrap_or(self.center)
+    }
+
+    fn support_feature(&self, direction: Vec3) -> Vec<Vec3> {
+        let normal = unit_or_x(direction);
+        let tolerance = FEATURE_TOLERANCE * self.bounding_radius;
+        farthest_points(&self.vertices, normal, tolerance)
+    }
+}
+
+impl SupportMap for Triangle {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        [self.v0, self.v1, self.v2]
+            .into_iter()
+            .max_by(|a, b| a.dot(&direction).total_cmp(&b.dot(&direction)))
+            .unwrap()
+    }
+
+    fn support_feature(&self, direction: Vec3) -> Vec<Vec3> {
+        let normal = unit_or_x(direction);
+        let size = (self.v1 - self.v0).magnitude().max((self.v2 - self.v0).magnitude());
+        farthest_points(&[self.v0, self.v1, self.v2], normal, FEATURE_TOLERANCE * size)
+    }
+}
+
+/// Whether two convex shapes overlap (GJK only, no contact data)
+pub fn intersects(a: &dyn SupportMap, b: &dyn SupportMap) -> bool {
+    gjk(a, b).is_some()
+}
+
+/// Contact manifold between two convex shapes, normal pointing from `a` towards `b`
+pub fn contact_manifold(a: &dyn SupportMap, b: &dyn SupportMap) -> Option<ContactManifold> {
+    let simplex = gjk(a, b)?;
+    let (normal, depth, on_a, on_b) = epa(a, b, simplex)?;
+    let deepest = ContactManifold::from(Contact::new((on_a + on_b) * 0.5, normal, depth));
+
+    // Clip the feature with fewer points (incident) against the other (reference)
+    let feature_a = a.support_feature(normal);
+    let feature_b = b.support_feature(-normal);
+    if feature_a.len() < 2 || feature_b.len() < 2 {
+        return Some(deepest);
+    }
+    let (reference, incident, reference_normal) = if feature_a.len() >= feature_b.len() {
+        (feature_a, feature_b, normal)
+    } else {
+        (feature_b, feature_a, -normal)
+    };
+
+    let reference_offset = reference[0].dot(&reference_normal);
+    let points: Vec<ContactPoint> = clip_feature(&reference, &incident, reference_normal)
+        .into_iter()
+        .filter_map(|point| {
+            let point_depth = reference_offset - point.dot(&reference_normal);
+            (point_depth >= -EPA_TOLERANCE).then(|| ContactPoint {
+                point: point + reference_normal * (point_depth * 0.5),
+                depth: point_depth.max(0.0),
+            })
+        })
+        .collect();
+
+    if points.is_empty() {
+        Some(deepest)
+    } else {
+        Some(ContactManifold::new(normal, points))
+    }
+}
+
+/// Point of the Minkowski difference a - b, with the points it came from
+#[derive(Debug, Clone, Copy)]
+struct SupportPoint {
+    point: Vec3,
+    on_a: Vec3,
+    on_b: Vec3,
+}
+
+fn support_point(a: &dyn SupportMap, b: &dyn SupportMap, direction: Vec3) -> SupportPoint {
+    let on_a = a.support(direction);
+    let on_b = b.support(-direction);
+    SupportPoint { point: on_a - on_b, on_a, on_b }
+}
+
+/// GJK: returns the final simplex if the origin is inside the Minkowski difference
+fn gjk(a: &dyn SupportMap, b: &dyn SupportMap) -> Option<Vec<SupportPoint>> {
+    let first = support_point(a, b, Vec3::x());
+    let mut simplex = vec![first];
+    let mut closest = first.point;
+
+    for _ in 0..MAX_ITERATIONS {
+        if closest.magnitude_squared() < POINT_EPSILON * POINT_EPSILON {
+            return Some(simplex); // Origin on the simplex
+        }
+
+        let next = support_point(a, b, -closest);
+        if next.point.dot(&closest) > 0.0 {
+            return None; // Nothing lies beyond the origin: separating direction found
+        }
+        if simplex.iter().any(|p| (p.point - next.point).magnitude_squared() < POINT_EPSILON * POINT_EPSILON) {
+            return None; // No progress: the shapes only touch
+        }
+
+        simplex.push(next);
+        let (point, reduced) = closest_on_simplex(&simplex);
+        closest = point;
+        simplex = reduced;
+        if simplex.len() == 4 {
+            return Some(simplex); // Origin inside the tetrahedron
+        }
+    }
+
+    None
+}
+
+/// Closest point of a simplex to the origin, with the smallest sub-simplex containing it
+/// (Johnson's sub-algorithm, checking every face of the simplex)
+fn closest_on_simplex(simplex: &[SupportPoint]) -> (Vec3, Vec<SupportPoint>) {
+    let mut best: Option<(f32, Vec3, Vec<SupportPoint>)> = None;
+
+    for mask in 1u32..(1 << simplex.len()) {
+        let subset: Vec<SupportPoint> = (0..simplex.len())
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| simplex[i])
+            .collect();
+        let Some(weights) = affine_weights(&subset) else {
+            continue;
+        };
+        if weights.iter().any(|&w| w < -1.0e-7) {
+            continue; // Closest point of the affine hull lies outside this face
+        }
+
+        let point = subset.iter().zip(&weights).fold(Vec3::zeros(), |sum, (p, w)| sum + p.point * *w);
+        let distance = point.magnitude_squared();
+        let better = match &best {
+            None => true,
+            Some((best_distance, _, best_subset)) => {
+                distance < *best_distance - 1.0e-9
+                    || (distance <= *best_distance + 1.0e-9 && subset.len() < best_subset.len())
+            }
+        };
+        if better {
+            best = Some((distance, point, subset));
+        }
+    }
+
+    // Every non-empty simplex has at least its vertices as candidates
+    let (_, point, subset) = best.expect("simplex is not empty");
+    (point, subset)
+}
+
+/// Barycentric weights of the point of the subset's affine hull closest to the origin
+fn affine_weights(subset: &[SupportPoint]) -> Option<Vec<f32>> {
+    let origin = subset[0].point;
+    let edges: Vec<Vec3> = subset[1..].iter().map(|p| p.point - origin).collect();
+    if edges.is_empty() {
+        return Some(vec![1.0]);
+    }
+
+    // Normal equations: minimize |origin + sum(mu_i * edge_i)|^2
+    let n = edges.len();
+    let gram = nalgebra::DMatrix::<f32>::from_fn(n, n, |i, j| edges[i].dot(&edges[j]));
+    let rhs = nalgebra::DVector::<f32>::from_fn(n, |i, _| -edges[i].dot(&origin));
+    if gram.determinant().abs() < 1.0e-12 {
+        return None; // Degenerate face
+    }
+    let mu = gram.lu().solve(&rhs)?;
+
+    let mut weights = Vec::with_capacity(n + 1);
+    weights.push(1.0 - mu.sum());
+    weights.extend(mu.iter());
+    Some(weights)
+}
+
+/// Grow a touching simplex into a tetrahedron so EPA has a volume to expand
+fn complete_tetrahedron(a: &dyn SupportMap, b: &dyn SupportMap, simplex: &mut Vec<SupportPoint>) -> bool {
+    let axes = [Vec3::x(), Vec3::y(), Vec3::z()];
+    while simplex.len() < 4 {
+        let p0 = simplex[0].point;
+        let directions: Vec<Vec3> = match simplex.len() {
+            1 => axes.iter().flat_map(|&axis| [axis, -axis]).collect(),
+            2 => {
+                let line = simplex[1].point - p0;
+                axes.iter()
+                    .map(|axis| line.cross(axis))
+                    .filter(|perpendicular| perpendicular.magnitude_squared() > POINT_EPSILON)
+                    .flat_map(|perpendicular| {
+                        let other = line.cross(&perpendicular);
+                        [perpendicular, -perpendicular, other, -other]
+                    })
+                    .collect()
+            }
+            _ => {
+                let normal = (simplex[1].point - p0).cross(&(simplex[2].point - p0));
+                vec![normal, -normal]
+            }
+        };
+
+        let new_point = directions.into_iter()
+            .map(|direction| support_point(a, b, direction))
+            .find(|candidate| spans_new_dimension(simplex, candidate.point));
+        match new_point {
+            Some(point) => simplex.push(point),
+            None => return false, // Flat Minkowski difference
+        }
+    }
+    true
+}
+
+/// Whether `point` is off the point, line or plane spanned by the simplex
+fn spans_new_dimension(simplex: &[SupportPoint], point: Vec3) -> bool {
+    let p0 = simplex[0].point;
+    let offset = point - p0;
+    match simplex.len() {
+        1 => offset.magnitude() > POINT_EPSILON,
+        2 => {
+            let line = (simplex[1].point - p0).normalize();
+            offset.cross(&line).magnitude() > POINT_EPSILON
+        }
+        _ => {
+            let normal = (simplex[1].point - p0).cross(&(simplex[2].point - p0)).normalize();
+            normal.dot(&offset).abs() > POINT_EPSILON
+        }
+    }
+}
+
+/// Face of the EPA polytope, oriented away from its interior
+struct EpaFace {
+    vertices: [usize; 3],
+    normal: Vec3,
+    distance: f32,
+}
+
+fn epa_face(points: &[SupportPoint], mut vertices: [usize; 3], interior: Vec3) -> Option<EpaFace> {
+    let [a, b, c] = vertices.map(|i| points[i].point);
+    let normal = (b - a).cross(&(c - a));
+    let length = normal.magnitude();
+    if length < 1.0e-12 {
+        return None;
+    }
+
+    let mut normal = normal / length;
+    if normal.dot(&(a - interior)) < 0.0 {
+        normal = -normal;
+        vertices.swap(1, 2);
+    }
+    Some(EpaFace { vertices, normal, distance: normal.dot(&a) })
+}
+
+/// EPA: returns (normal from a to b, depth, deepest point on a, deepest point on b)
+fn epa(a: &dyn SupportMap, b: &dyn SupportMap, mut points: Vec<SupportPoint>) -> Option<(Vec3, f32, Vec3, Vec3)> {
+    if !complete_tetrahedron(a, b, &mut points) {
+        return None;
+    }
+
+    // The polytope only grows, so the first tetrahedron's centroid stays inside
+    let interior = points.iter().fold(Vec3::zeros(), |sum, p| sum + p.point) / 4.0;
+    let mut faces: Vec<EpaFace> = [[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]]
+        .into_iter()
+        .filter_map(|vertices| epa_face(&points, vertices, interior))
+        .collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let closest = faces.iter().min_by(|x, y| x.distance.total_cmp(&y.distance))?;
+        let next = support_point(a, b, closest.normal);
+        if next.point.dot(&closest.normal) - closest.distance < EPA_TOLERANCE {
+            return Some(epa_result(&points, closest));
+        }
+
+        // Replace the faces the new point can see with a fan to their horizon
+        points.push(next);
+        let index = points.len() - 1;
+        let (visible, hidden): (Vec<EpaFace>, Vec<EpaFace>) = faces
+            .into_iter()
+            .partition(|face| face.normal.dot(&(next.point - points[face.vertices[0]].point)) > 0.0);
+        faces = hidden;
+
+        let visible_edges: HashSet<(usize, usize)> = visible
+            .iter()
+            .flat_map(|face| {
+                let [a, b, c] = face.vertices;
+                [(a, b), (b, c), (c, a)]
+            })
+            .collect();
+        for &(start, end) in &visible_edges {
+            if !visible_edges.contains(&(end, start)) {
+                faces.extend(epa_face(&points, [start, end, index], interior));
+            }
+        }
+    }
+
+    let closest = faces.iter().min_by(|x, y| x.distance.total_cmp(&y.distance))?;
+    Some(epa_result(&points, closest))
+}
+
+fn epa_result(points: &[SupportPoint], face: &EpaFace) -> (Vec3, f32, Vec3, Vec3) {
+    let [a, b, c] = face.vertices.map(|i| points[i]);
+    let [u, v, w] = barycentric(face.normal * face.distance, a.point, b.point, c.point);
+    let on_a = a.on_a * u + b.on_a * v + c.on_a * w;
+    let on_b = a.on_b * u + b.on_b * v + c.on_b * w;
+    (face.normal, face.distance.max(0.0), on_a, on_b)
+}
+
+/// Barycentric coordinates of `p` in triangle abc
+fn barycentric(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> [f32; 3] {
+    let (v0, v1, v2) = (b - a, c - a, p - a);
+    let (d00, d01, d11) = (v0.dot(&v0), v0.dot(&v1), v1.dot(&v1));
+    let (d20, d21) = (v2.dot(&v0), v2.dot(&v1));
+    // Gram determinant of (v0, v1); d01 is shared by both off-diagonal entries
+    let denominator = d00 * d11 - d01.powi(2);
+    if denominator.abs() < 1.0e-12 {
+        return [1.0, 0.0, 0.0];
+    }
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    [1.0 - v - w, v, w]
+}
+
+/// Clip the incident feature against the side planes of the reference feature
+fn clip_feature(reference: &[Vec3], incident: &[Vec3], normal: Vec3) -> Vec<Vec3> {
+    let mut planes = Vec::new();
+    if reference.len() == 2 {
+        // An edge bounds the contact only along its length
+        let edge = reference[1] - reference[0];
+        planes.push((reference[0], -edge));
+        planes.push((reference[1], edge));
+    } else {
+        let centroid = reference.iter().sum::<Vec3>() / reference.len() as f32;
+        for (i, &start) in reference.iter().enumerate() {
+            let end = reference[(i + 1) % reference.len()];
+            let mut side = (end - start).cross(&normal);
+            if side.dot(&(centroid - start)) > 0.0 {
+                side = -side;
+            }
+            planes.push((start, side));
+        }
+    }
+
+    planes.into_iter().fold(incident.to_vec(), |polygon, (origin, side)| clip_polygon(&polygon, origin, side))
+}
+
+/// Sutherland-Hodgman: keep the part of the polygon behind the plane
+fn clip_polygon(polygon: &[Vec3], origin: Vec3, side: Vec3) -> Vec<Vec3> {
+    let distance = |p: Vec3| side.dot(&(p - origin));
+    if polygon.len() == 1 {
+        return polygon.iter().copied().filter(|p| distance(*p) <= 0.0).collect();
+    }
+
+    let mut clipped = Vec::new();
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (d_current, d_next) = (distance(current), distance(next));
+        if d_current <= 0.0 {
+            clipped.push(current);
+        }
+        if (d_current <= 0.0) != (d_next <= 0.0) {
+            clipped.push(current + (next - current) * (d_current / (d_current - d_next)));
+        }
+    }
+    clipped
+}
+
+/// Points within `tolerance` of the farthest along `normal`, sorted around their centroid
+fn farthest_points(points: &[Vec3], normal: Vec3, tolerance: f32) -> Vec<Vec3> {
+    let max = points.iter().map(|p| p.dot(&normal)).fold(f32::MIN, f32::max);
+    let mut feature: Vec<Vec3> = points.iter().copied().filter(|p| max - p.dot(&normal) <= tolerance).collect();
+    if feature.len() < 3 {
+        return feature;
+    }
+
+    let centroid = feature.iter().sum::<Vec3>() / feature.len() as f32;
+    let u = (feature[0] - centroid).normalize();
+    let v = normal.cross(&u);
+    let angle = |p: &Vec3| (p - centroid).dot(&v).atan2((p - centroid).dot(&u));
+    feature.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+    feature
+}
+
+fn unit_or_x(direction: Vec3) -> Vec3 {
+    let length = direction.magnitude();
+    if length > f32::EPSILON { direction / length } else { Vec3::x() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::hull::ConvexHullTemplate;
+    use crate::foundation::math::{Mat4, Quat};
+
+    const SHAPES: [&str; 4] = ["sphere", "capsule", "box", "hull"];
+
+    /// Convex shape centered on `center` whose surface is 1 unit away along +-X
+    fn shape_at(kind: &str, center: Vec3) -> Box<dyn SupportMap> {
+        match kind {
+            "sphere" => Box::new(BoundingSphere::new(center, 1.0)),
+            "capsule" => Box::new(Capsule::new(center - Vec3::y(), center + Vec3::y(), 1.0)),
+            "box" => Box::new(OrientedBox::new(center, Vec3::new(1.0, 1.0, 1.0), Quat::identity())),
+            "hull" => {
+                // Hexagonal prism along X, flat caps at x = +-1
+                let points: Vec<Vec3> = (0..12)
+                    .map(|i| {
+                        let angle = (i % 6) as f32 * std::f32::consts::FRAC_PI_3;
+                        Vec3::new(if i < 6 { -1.0 } else { 1.0 }, angle.cos(), angle.sin())
+                    })
+                    .collect();
+                Box::new(ConvexHullTemplate::from_points(&points)
+                    .unwrap()
+                    .to_world_space(&Mat4::new_translation(&center), center, 1.0))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_gjk_epa_sphere_capsule() {
+        let sphere = BoundingSphere::new(Vec3::new(1.5, 0.0, 0.0), 1.0);
+        let capsule = Capsule::new(Vec3::new(0.0, -2.0, 0.0), Vec3::new(0.0, 2.0, 0.0), 1.0);
+
+        let manifold = contact_manifold(&capsule, &sphere).unwrap();
+        assert!((manifold.normal - Vec3::x()).magnitude() < 1e-2, "normal {:?}", manifold.normal);
+        assert!((manifold.max_depth() - 0.5).abs() < 1e-2, "depth {}", manifold.max_depth());
+        assert!(!intersects(&capsule, &BoundingSphere::new(Vec3::new(2.1, 0.0, 0.0), 1.0)));
+    }
+
+    #[test]
+    fn test_box_resting_on_box_has_face_manifold() {
+        let ground = OrientedBox::new(Vec3::zeros(), Vec3::new(5.0, 1.0, 5.0), Quat::identity());
+        let crate_box = OrientedBox::new(Vec3::new(0.5, 1.9, -0.5), Vec3::new(1.0, 1.0, 1.0), Quat::identity());
+
+        let manifold = contact_manifold(&ground, &crate_box).unwrap();
+        assert!((manifold.normal - Vec3::y()).magnitude() < 1e-4, "normal {:?}", manifold.normal);
+        assert_eq!(manifold.len(), 4);
+        for point in &manifold.points {
+            assert!((point.depth - 0.1).abs() < 1e-3);
+            assert!((point.point.x - 0.5).abs() <= 1.0 + 1e-4 && (point.point.z + 0.5).abs() <= 1.0 + 1e-4);
+        }
+
+        // Rotated 45 degrees about Y still rests on its face
+        let rotated = OrientedBox::new(Vec3::new(0.0, 1.9, 0.0), Vec3::new(1.0, 1.0, 1.0),
+            Quat::from_axis_angle(&Vec3::y_axis(), std::f32::consts::FRAC_PI_4));
+        assert_eq!(contact_manifold(&ground, &rotated).unwrap().len(), 4);
+
+        let apart = OrientedBox::new(Vec3::new(0.0, 2.5, 0.0), Vec3::new(1.0, 1.0, 1.0), Quat::identity());
+        assert!(contact_manifold(&ground, &apart).is_none());
+    }
+
+    #[test]
+    fn test_capsule_lying_on_box_touches_along_spine() {
+        let ground = OrientedBox::new(Vec3::zeros(), Vec3::new(5.0, 1.0, 5.0), Quat::identity());
+        let capsule = Capsule::new(Vec3::new(-2.0, 1.4, 0.0), Vec3::new(2.0, 1.4, 0.0), 0.5);
+
+        let manifold = contact_manifold(&ground, &capsule).unwrap();
+        assert_eq!(manifold.len(), 2);
+        assert!((manifold.max_depth() - 0.1).abs() < 1e-3);
+        let xs: Vec<f32> = manifold.points.iter().map(|p| p.point.x).collect();
+        assert!(xs.iter().any(|x| (x + 2.0).abs() < 1e-3) && xs.iter().any(|x| (x - 2.0).abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_gjk_epa_every_shape_pair() {
+        for a in SHAPES {
+            for b in SHAPES {
+                let first = shape_at(a, Vec3::zeros());
+
+                // Surfaces overlap by 0.2 along X
+                let overlapping = shape_at(b, Vec3::new(1.8, 0.0, 0.0));
+                assert!(intersects(first.as_ref(), overlapping.as_ref()), "{} vs {} should intersect", a, b);
+                let manifold = contact_manifold(first.as_ref(), overlapping.as_ref())
+                    .unwrap_or_else(|| panic!("{} vs {}: no manifold", a, b));
+                assert!((manifold.normal - Vec3::x()).magnitude() < 1e-2, "{} vs {}: normal {:?}", a, b, manifold.normal);
+                assert!((manifold.max_depth() - 0.2).abs() < 1e-2, "{} vs {}: depth {}", a, b, manifold.max_depth());
+
+                // Reversed order flips the normal
+                let flipped = contact_manifold(overlapping.as_ref(), first.as_ref()).unwrap();
+                assert!((flipped.normal + Vec3::x()).magnitude() < 1e-2, "{} vs {}: flipped normal {:?}", b, a, flipped.normal);
+
+                // Surfaces 0.3 apart
+                let separated = shape_at(b, Vec3::new(2.3, 0.0, 0.0));
+                assert!(!intersects(first.as_ref(), separated.as_ref()), "{} vs {} should be apart", a, b);
+                assert!(contact_manifold(first.as_ref(), separated.as_ref()).is_none(), "{} vs {}: unexpected manifold", a, b);
+            }
+        }
+    }
+}
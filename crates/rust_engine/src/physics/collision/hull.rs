@@ -0,0 +1,373 @@
+//! Convex hull collision shapes
+//!
+//! A convex hull wraps a model's vertices in the smallest convex polyhedron.
+//! Unlike triangle-soup meshes, hulls are convex, so the GJK/EPA narrow phase
+//! can test them against every other convex shape. Like meshes, the template
+//! is stored in model space and transformed on demand (GEA 13.3.4).
+
+use crate::assets::obj_loader::{ObjError, ObjLoader};
+use crate::foundation::math::{Mat4, Vec3};
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use thiserror::Error;
+use super::primitives::{Ray, Triangle};
+
+/// Errors that can occur while building a convex hull
+#[derive(Error, Debug)]
+pub enum ConvexHullError {
+    /// The OBJ file could not be loaded
+    #[error("OBJ error: {0}")]
+    Obj(#[from] ObjError),
+    /// The points don't span a volume (fewer than 4, or all coplanar)
+    #[error("Degenerate point set: {0}")]
+    Degenerate(String),
+}
+
+/// A convex hull template stored in MODEL SPACE
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvexHullTemplate {
+    /// Hull vertices in model space
+    pub vertices: Vec<Vec3>,
+    /// Triangular faces (indices into `vertices`), counter-clockwise seen from outside
+    pub faces: Vec<[u32; 3]>,
+    /// Local bounding sphere radius (model space)
+    pub local_bounding_radius: f32,
+}
+
+impl ConvexHullTemplate {
+    /// Build the convex hull of a point cloud (incremental quickhull)
+    ///
+    /// Interior and coplanar points are dropped, so the hull of a render mesh
+    /// usually has far fewer vertices than the mesh itself.
+    pub fn from_points(points: &[Vec3]) -> Result<Self, ConvexHullError> {
+        let (vertices, faces) = quickhull(points)?;
+        let local_bounding_radius = vertices
+            .iter()
+            .map(|v| v.magnitude())
+            .fold(0.0, f32::max);
+
+        Ok(Self { vertices, faces, local_bounding_radius })
+    }
+
+    /// Build the convex hull of the vertices in an OBJ file (model space)
+    pub fn from_obj<P: AsRef<Path>>(path: P) -> Result<Self, ConvexHullError> {
+        let mesh = ObjLoader::load_obj(path)?;
+        let points: Vec<Vec3> = mesh.vertices
+            .iter()
+            .map(|vertex| Vec3::new(vertex.position[0], vertex.position[1], vertex.position[2]))
+            .collect();
+        Self::from_points(&points)
+    }
+
+    /// Hull edges for debug drawing (edges between coplanar faces are skipped)
+    pub fn edges(&self) -> Vec<(Vec3, Vec3)> {
+        let mut edge_faces: HashMap<(u32, u32), Vec<Vec3>> = HashMap::new();
+        for face in &self.faces {
+            let normal = face_normal(&self.vertices, face);
+            for (a, b) in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                edge_faces.entry((a.min(b), a.max(b))).or_default().push(normal);
+            }
+        }
+
+        edge_faces
+            .into_iter()
+            .filter(|(_, normals)| normals.len() < 2 || normals[0].dot(&normals[1]) < 0.999)
+            .map(|((a, b), _)| (self.vertices[a as usize], self.vertices[b as usize]))
+            .collect()
+    }
+
+    /// Transform this template to world space using a transformation matrix
+    pub fn to_world_space(&self, matrix: &Mat4, center: Vec3, scale_factor: f32) -> WorldSpaceConvexHull {
+        let vertices: Vec<Vec3> = self.vertices
+            .iter()
+            .map(|v| {
+                let p = matrix.transform_point(&nalgebra::Point3::new(v.x, v.y, v.z));
+                Vec3::new(p.x, p.y, p.z)
+            })
+            .collect();
+        let triangles = self.faces
+            .iter()
+            .map(|[a, b, c]| Triangle::new(vertices[*a as usize], vertices[*b as usize], vertices[*c as usize]))
+            .collect();
+
+        WorldSpaceConvexHull {
+            vertices,
+            triangles,
+            center,
+            bounding_radius: self.local_bounding_radius * scale_factor,
+        }
+    }
+}
+
+/// World-space convex hull (temporary, created on-demand for collision tests)
+#[derive(Debug)]
+pub struct WorldSpaceConvexHull {
+    /// Hull vertices in world space
+    pub vertices: Vec<Vec3>,
+    /// Hull faces in world space (outward normals)
+    pub triangles: Vec<Triangle>,
+    /// Center position in world space
+    pub center: Vec3,
+    /// Bounding sphere radius in world space
+    pub bounding_radius: f32,
+}
+
+impl WorldSpaceConvexHull {
+    /// Test ray intersection by clipping the ray against every face plane
+    /// Returns (distance, hit_point, normal) of the closest positive hit
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(f32, Vec3, Vec3)> {
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+        let mut enter_normal = Vec3::zeros();
+        let mut exit_normal = Vec3::zeros();
+
+        for triangle in &self.triangles {
+            let normal = triangle.normal();
+            if !normal.iter().all(|c| c.is_finite()) {
+                continue; // Sliver face
+            }
+
+            let distance = triangle.distance_to_point(ray.origin); // > 0 outside
+            let speed = normal.dot(&ray.direction);
+            if speed.abs() < f32::EPSILON {
+                if distance > 0.0 {
+                    return None; // Parallel and outside this face
+                }
+                continue;
+            }
+
+            let t = -distance / speed;
+            if speed < 0.0 {
+                if t > t_enter {
+                    t_enter = t;
+                    enter_normal = normal;
+                }
+            } else if t < t_exit {
+                t_exit = t;
+                exit_normal = normal;
+            }
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+
+        // Closest positive intersection (the exit when starting inside)
+        if t_enter >= 0.0 {
+            Some((t_enter, ray.point_at(t_enter), enter_normal))
+        } else if t_exit >= 0.0 && t_exit.is_finite() {
+            Some((t_exit, ray.point_at(t_exit), exit_normal))
+        } else {
+            None
+        }
+    }
+}
+
+/// Hull face during construction
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Vec3,
+    offset: f32,
+}
+
+impl HullFace {
+    fn new(points: &[Vec3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let normal = (b - a).cross(&(c - a)).normalize();
+        Self { vertices, normal, offset: normal.dot(&a) }
+    }
+
+    fn distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(&point) - self.offset
+    }
+}
+
+fn face_normal(vertices: &[Vec3], face: &[u32; 3]) -> Vec3 {
+    let [a, b, c] = face.map(|i| vertices[i as usize]);
+    (b - a).cross(&(c - a)).normalize()
+}
+
+/// Incremental quickhull: start from a tetrahedron of extreme points, then add
+/// every outside point, replacing the faces it can see with a fan to their horizon
+fn quickhull(points: &[Vec3]) -> Result<(Vec<Vec3>, Vec<[u32; 3]>), ConvexHullError> {
+    if points.len() < 4 {
+        return Err(ConvexHullError::Degenerate(format!("need at least 4 points, got {}", points.len())));
+    }
+
+    // Tolerance relative to the size of the point cloud
+    let (min, max) = points.iter().fold(
+        (points[0], points[0]),
+        |(min, max), p| (min.inf(p), max.sup(p)),
+    );
+    let epsilon = (max - min).magnitude() * 1.0e-5;
+
+    // Initial tetrahedron: the two most distant axis extremes, the point
+    // farthest from their line, and the point farthest from that plane
+    let extremes: Vec<usize> = (0..3)
+        .flat_map(|axis| {
+            let by_axis = |&i: &usize, &j: &usize| points[i][axis].total_cmp(&points[j][axis]);
+            [
+                (0..points.len()).min_by(by_axis).unwrap(),
+                (0..points.len()).max_by(by_axis).unwrap(),
+            ]
+        })
+        .collect();
+    let mut first = (extremes[0], extremes[1]);
+    for &i in &extremes {
+        for &j in &extremes {
+            if (points[i] - points[j]).magnitude_squared() > (points[first.0] - points[first.1]).magnitude_squared() {
+                first = (i, j);
+            }
+        }
+    }
+    let (i0, i1) = first;
+    let line = (points[i1] - points[i0]).normalize();
+    let line_distance = |i: &usize| (points[*i] - points[i0]).cross(&line).magnitude();
+    let i2 = (0..points.len()).max_by(|a, b| line_distance(a).total_cmp(&line_distance(b))).unwrap();
+    if line_distance(&i2) <= epsilon {
+        return Err(ConvexHullError::Degenerate("points are collinear".to_string()));
+    }
+    let plane = (points[i1] - points[i0]).cross(&(points[i2] - points[i0])).normalize();
+    let plane_distance = |i: &usize| plane.dot(&(points[*i] - points[i0])).abs();
+    let i3 = (0..points.len()).max_by(|a, b| plane_distance(a).total_cmp(&plane_distance(b))).unwrap();
+    if plane_distance(&i3) <= epsilon {
+        return Err(ConvexHullError::Degenerate("points are coplanar".to_string()));
+    }
+
+    let interior = (points[i0] + points[i1] + points[i2] + points[i3]) / 4.0;
+    let mut faces: Vec<HullFace> = [[i0, i1, i2], [i0, i3, i1], [i1, i3, i2], [i2, i3, i0]]
+        .into_iter()
+        .map(|[a, b, c]| {
+            let face = HullFace::new(points, [a, b, c]);
+            if face.distance(interior) > 0.0 { HullFace::new(points, [a, c, b]) } else { face }
+        })
+        .collect();
+
+    // Farthest points first, so most interior points are skipped cheaply later
+    let mut order: Vec<usize> = (0..points.len()).filter(|i| ![i0, i1, i2, i3].contains(i)).collect();
+    order.sort_by(|&a, &b| {
+        (points[b] - interior).magnitude_squared().total_cmp(&(points[a] - interior).magnitude_squared())
+    });
+
+    for index in order {
+        let point = points[index];
+        let (visible, hidden): (Vec<HullFace>, Vec<HullFace>) = faces
+            .into_iter()
+            .partition(|face| face.distance(point) > epsilon);
+        faces = hidden;
+        if visible.is_empty() {
+            continue; // Inside (or on) the current hull
+        }
+
+        // Horizon: edges of visible faces whose twin belongs to a hidden face
+        let visible_edges: HashSet<(usize, usize)> = visible
+            .iter()
+            .flat_map(|face| {
+                let [a, b, c] = face.vertices;
+                [(a, b), (b, c), (c, a)]
+            })
+            .collect();
+        for &(a, b) in &visible_edges {
+            if !visible_edges.contains(&(b, a)) {
+                faces.push(HullFace::new(points, [a, b, index]));
+            }
+        }
+    }
+
+    // Keep only the points used by faces
+    let mut remap: HashMap<usize, u32> = HashMap::new();
+    let mut vertices = Vec::new();
+    let faces = faces
+        .iter()
+        .map(|face| {
+            face.vertices.map(|i| {
+                *remap.entry(i).or_insert_with(|| {
+                    vertices.push(points[i]);
+                    (vertices.len() - 1) as u32
+                })
+            })
+        })
+        .collect();
+
+    Ok((vertices, faces))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_points() -> Vec<Vec3> {
+        let mut points: Vec<Vec3> = (0..8)
+            .map(|i| Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            ))
+            .collect();
+        // Interior and face-center points must not end up on the hull
+        points.push(Vec3::new(0.2, -0.3, 0.1));
+        points.push(Vec3::new(0.0, 1.0, 0.0));
+        points.push(Vec3::new(0.5, 0.5, -1.0));
+        points
+    }
+
+    #[test]
+    fn test_hull_of_cube_points() {
+        let hull = ConvexHullTemplate::from_points(&cube_points()).unwrap();
+        assert_eq!(hull.vertices.len(), 8);
+        assert_eq!(hull.faces.len(), 12);
+        assert!((hull.local_bounding_radius - 3.0f32.sqrt()).abs() < 1e-5);
+        assert_eq!(hull.edges().len(), 12);
+
+        // Every face points away from the center
+        for face in &hull.faces {
+            assert!(face_normal(&hull.vertices, face).dot(&hull.vertices[face[0] as usize]) > 0.0);
+        }
+
+        let flat = [Vec3::zeros(), Vec3::x(), Vec3::y(), Vec3::new(1.0, 1.0, 0.0)];
+        assert!(matches!(ConvexHullTemplate::from_points(&flat), Err(ConvexHullError::Degenerate(_))));
+    }
+
+    #[test]
+    fn test_hull_ray_intersection() {
+        let hull = ConvexHullTemplate::from_points(&cube_points())
+            .unwrap()
+            .to_world_space(&Mat4::new_translation(&Vec3::new(0.0, 0.0, 5.0)), Vec3::new(0.0, 0.0, 5.0), 1.0);
+
+        let (distance, point, normal) = hull.intersect_ray(&Ray::new(Vec3::zeros(), Vec3::z())).unwrap();
+        assert!((distance - 4.0).abs() < 1e-5);
+        assert!((point - Vec3::new(0.0, 0.0, 4.0)).magnitude() < 1e-5);
+        assert!((normal + Vec3::z()).magnitude() < 1e-5);
+
+        assert!(hull.intersect_ray(&Ray::new(Vec3::zeros(), Vec3::x())).is_none());
+    }
+
+    #[test]
+    fn test_hull_collides_with_every_convex_shape() {
+        use super::super::CollisionShape;
+        use crate::foundation::math::Quat;
+
+        let hull = CollisionShape::convex_hull(&cube_points()).unwrap();
+        let others = [
+            CollisionShape::sphere(1.0),
+            CollisionShape::capsule(1.0, 1.0),
+            CollisionShape::cuboid(Vec3::new(1.0, 1.0, 1.0)),
+            CollisionShape::convex_hull(&cube_points()).unwrap(),
+        ];
+        let world = |shape: &CollisionShape, x: f32| shape.to_world_space(Vec3::new(x, 0.0, 0.0), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+
+        let this = world(&hull, 0.0);
+        for other in &others {
+            let overlapping = world(other, 1.8);
+            assert!(this.intersects(&overlapping) && overlapping.intersects(&this), "{:?}", other);
+            assert!((this.penetration_depth(&overlapping) - 0.2).abs() < 1e-2, "{:?}", other);
+            let manifold = this.manifold(&overlapping).unwrap();
+            assert!((manifold.normal - Vec3::x()).magnitude() < 1e-2, "{:?}: normal {:?}", other, manifold.normal);
+
+            let separated = world(other, 2.3);
+            assert!(!this.intersects(&separated), "{:?}", other);
+            assert_eq!(this.penetration_depth(&separated), 0.0);
+            assert!(this.manifold(&separated).is_none());
+        }
+    }
+}
@@ -4,11 +4,14 @@
 //! and world-space transformations for testing.
 
 use crate::foundation::math::Vec3;
+use serde::{Serialize, Deserialize};
 use super::primitives::{Triangle, Ray, BoundingSphere};
+use super::contact::{Contact, ContactManifold, ContactPoint};
+use super::gjk::{self, SupportMap};
 
 /// A collision mesh template stored in MODEL SPACE (local coordinates)
 /// GEA 13.3.4: "Collision shapes should be stored in model space and transformed on-the-fly"
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollisionMeshTemplate {
     /// Triangles in MODEL SPACE (local coordinates, never modified)
     pub local_triangles: Vec<Triangle>,
@@ -146,6 +149,137 @@ impl WorldSpaceCollisionMesh {
         None
     }
 
+    /// Deepest contact between a sphere and the mesh surface
+    /// 
+    /// The normal points from the sphere towards the mesh.
+    pub fn sphere_contact(&self, sphere: &BoundingSphere) -> Option<Contact> {
+        let bounding_sphere = BoundingSphere::new(self.center, self.bounding_radius);
+        if !bounding_sphere.intersects(sphere) {
+            return None;
+        }
+        
+        let mut deepest: Option<Contact> = None;
+        for triangle in &self.triangles {
+            if triangle.distance_to_point(sphere.center).abs() > sphere.radius {
+                continue;
+            }
+            
+            let closest = triangle.closest_point(sphere.center);
+            let offset = closest - sphere.center;
+            let distance = offset.magnitude();
+            if distance > sphere.radius || deepest.is_some_and(|contact| contact.depth >= sphere.radius - distance) {
+                continue;
+            }
+            
+            // Sphere center on the surface: push out along the face normal
+            let normal = if distance > f32::EPSILON { offset / distance } else { -triangle.normal() };
+            deepest = Some(Contact::new(closest, normal, sphere.radius - distance));
+        }
+        
+        deepest
+    }
+
+    /// Contact manifold between two meshes, normal pointing from `self` towards `other`
+    /// 
+    /// Contact points are where the edges of each mesh cross the other's
+    /// triangles. The normal is the face normal of the overlapping triangles
+    /// (this mesh's faces, or `other`'s reversed) with the least overlap, as in
+    /// the separating axis test, and each point's depth is the overlap of its
+    /// triangle pair along that normal.
+    pub fn mesh_manifold(&self, other: &WorldSpaceCollisionMesh) -> Option<ContactManifold> {
+        let sphere_a = BoundingSphere::new(self.center, self.bounding_radius);
+        let sphere_b = BoundingSphere::new(other.center, other.bounding_radius);
+        if !sphere_a.intersects(&sphere_b) {
+            return None;
+        }
+        
+        let mut pairs = Vec::new();
+        for tri_a in &self.triangles {
+            for tri_b in &other.triangles {
+                if tri_a.intersects_triangle(tri_b) {
+                    pairs.push((tri_a, tri_b));
+                }
+            }
+        }
+        if pairs.is_empty() {
+            return None;
+        }
+        
+        // Overlap along an axis between the triangles of each mesh
+        let overlap = |axis: Vec3, a: &[&Triangle], b: &[&Triangle]| {
+            let max_a = a.iter().flat_map(|t| [t.v0, t.v1, t.v2]).map(|v| v.dot(&axis)).fold(f32::MIN, f32::max);
+            let min_b = b.iter().flat_map(|t| [t.v0, t.v1, t.v2]).map(|v| v.dot(&axis)).fold(f32::MAX, f32::min);
+            max_a - min_b
+        };
+        
+        let involved_a: Vec<&Triangle> = pairs.iter().map(|(a, _)| *a).collect();
+        let involved_b: Vec<&Triangle> = pairs.iter().map(|(_, b)| *b).collect();
+        let towards_other = other.center - self.center;
+        let normal = involved_a.iter().map(|t| t.normal())
+            .chain(involved_b.iter().map(|t| -t.normal()))
+            .filter(|n| n.iter().all(|c| c.is_finite()))
+            .map(|n| (n, overlap(n, &involved_a, &involved_b)))
+            .min_by(|(_, x), (_, y)| x.total_cmp(y))
+            .map(|(n, _)| n)
+            .unwrap_or_else(|| {
+                // Degenerate triangles only: fall back to the center direction
+                if towards_other.magnitude_squared() > f32::EPSILON { towards_other.normalize() } else { Vec3::y() }
+            });
+        
+        let mut points = Vec::new();
+        for (tri_a, tri_b) in pairs {
+            let depth = overlap(normal, &[tri_a], &[tri_b]).max(0.0);
+            
+            let crossings: Vec<Vec3> = edges(tri_a)
+                .into_iter()
+                .filter_map(|(start, end)| tri_b.intersect_segment(start, end))
+                .chain(edges(tri_b).into_iter().filter_map(|(start, end)| tri_a.intersect_segment(start, end)))
+                .collect();
+            
+            if crossings.is_empty() {
+                // Touching coplanar faces: no edge crosses, use the closest point
+                points.push(ContactPoint { point: tri_b.closest_point(tri_a.centroid()), depth });
+            } else {
+                points.extend(crossings.into_iter().map(|point| ContactPoint { point, depth }));
+            }
+        }
+        
+        Some(ContactManifold::new(normal, points))
+    }
+
+    /// Contact manifold between the mesh and a convex shape, normal pointing from the mesh
+    /// 
+    /// Each triangle near the shape is tested with GJK/EPA. The deepest
+    /// contact sets the normal; contacts from triangles facing roughly the
+    /// same way are kept with their depth projected onto it.
+    pub fn convex_manifold(&self, shape: &dyn SupportMap, bounds: &BoundingSphere) -> Option<ContactManifold> {
+        let bounding_sphere = BoundingSphere::new(self.center, self.bounding_radius);
+        if !bounding_sphere.intersects(bounds) {
+            return None;
+        }
+        
+        let manifolds: Vec<ContactManifold> = self.triangles
+            .iter()
+            .filter(|triangle| {
+                (triangle.closest_point(bounds.center) - bounds.center).magnitude_squared() <= bounds.radius * bounds.radius
+            })
+            .filter_map(|triangle| gjk::contact_manifold(triangle, shape))
+            .collect();
+        let normal = manifolds
+            .iter()
+            .max_by(|a, b| a.max_depth().total_cmp(&b.max_depth()))?
+            .normal;
+        
+        let points = manifolds
+            .iter()
+            .filter(|manifold| manifold.normal.dot(&normal) > 0.7)
+            .flat_map(|manifold| {
+                let alignment = manifold.normal.dot(&normal);
+                manifold.points.iter().map(move |p| ContactPoint { point: p.point, depth: p.depth * alignment })
+            });
+        Some(ContactManifold::new(normal, points))
+    }
+
     /// Test mesh-mesh intersection
     pub fn intersects_mesh(&self, other: &WorldSpaceCollisionMesh) -> bool {
         // First check bounding spheres
@@ -168,3 +302,12 @@ impl WorldSpaceCollisionMesh {
         false
     }
 }
+
+/// The three edges of a triangle as (start, end) pairs
+fn edges(triangle: &Triangle) -> [(Vec3, Vec3); 3] {
+    [
+        (triangle.v0, triangle.v1),
+        (triangle.v1, triangle.v2),
+        (triangle.v2, triangle.v0),
+    ]
+}
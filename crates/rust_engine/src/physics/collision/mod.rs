@@ -12,21 +12,34 @@
 //!
 //! # Module Organization
 //!
-//! - [`primitives`] - Basic geometric primitives (rays, spheres, triangles)
+//! - [`primitives`] - Basic geometric primitives (rays, spheres, capsules, boxes, triangles)
 //! - [`mesh`] - Complex mesh-based collision geometry
+//! - [`hull`] - Convex hulls built from model vertices
+//! - [`gjk`] - GJK/EPA narrow phase for convex shapes
 //! - [`shape`] - High-level ECS-friendly collision shapes
+//! - [`contact`] - Contact points and manifolds produced by the narrow phase
+//! - [`ccd`] - Swept-sphere time of impact for fast-moving colliders
 //!
 //! # Key Types
 //!
 //! - [`CollisionShape`] - Model-space shape attached to entities (ECS component data)
 //! - [`WorldSpaceShape`] - Temporary world-space shape for collision testing
-//! - [`Ray`], [`BoundingSphere`], [`Triangle`] - Primitive geometric types
+//! - [`ContactManifold`] - Contact points and normal between two intersecting shapes
+//! - [`Ray`], [`BoundingSphere`], [`Capsule`], [`OrientedBox`], [`Triangle`] - Primitive geometric types
 
 pub mod primitives;
 pub mod mesh;
+pub mod hull;
+pub mod gjk;
 pub mod shape;
+pub mod contact;
+pub mod ccd;
 
 // Re-export commonly used types
-pub use primitives::{Ray, RayHit, BoundingSphere, Triangle};
+pub use primitives::{Ray, RayHit, BoundingSphere, Capsule, OrientedBox, Triangle};
 pub use mesh::{CollisionMeshTemplate, WorldSpaceCollisionMesh};
+pub use hull::{ConvexHullError, ConvexHullTemplate, WorldSpaceConvexHull};
+pub use gjk::SupportMap;
 pub use shape::{CollisionShape, WorldSpaceShape};
+pub use contact::{Contact, ContactManifold, ContactPoint, MAX_MANIFOLD_POINTS};
+pub use ccd::{sweep_sphere, TimeOfImpact};
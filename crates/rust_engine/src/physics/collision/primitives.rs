@@ -3,8 +3,10 @@
 //! Provides basic geometric primitives (rays, spheres, triangles) with
 //! efficient intersection testing algorithms.
 
-use crate::foundation::math::Vec3;
+use crate::foundation::math::{Quat, Vec3};
 use crate::ecs::Entity;
+use super::contact::Contact;
+use serde::{Serialize, Deserialize};
 
 /// A ray for ray casting and picking
 #[derive(Debug, Clone, Copy)]
@@ -76,6 +78,21 @@ impl BoundingSphere {
         }
     }
 
+    /// Contact with another sphere, normal pointing from this sphere to `other`
+    pub fn contact(&self, other: &BoundingSphere) -> Option<Contact> {
+        let offset = other.center - self.center;
+        let distance = offset.magnitude();
+        let radius_sum = self.radius + other.radius;
+        if distance > radius_sum {
+            return None;
+        }
+        
+        // Concentric spheres have no preferred direction; pick +Y
+        let normal = if distance > f32::EPSILON { offset / distance } else { Vec3::y() };
+        let depth = radius_sum - distance;
+        Some(Contact::new(self.center + normal * (self.radius - depth * 0.5), normal, depth))
+    }
+
     /// Test ray intersection with this sphere
     /// Returns (distance, hit_point, normal) if hit, None otherwise
     pub fn intersect_ray(&self, ray: &Ray) -> Option<(f32, Vec3, Vec3)> {
@@ -116,8 +133,174 @@ impl BoundingSphere {
     }
 }
 
-/// A triangle for collision detection
+/// A capsule: all points within `radius` of the segment from `start` to `end`
 #[derive(Debug, Clone, Copy)]
+pub struct Capsule {
+    /// First end of the capsule spine in world space
+    pub start: Vec3,
+    /// Second end of the capsule spine in world space
+    pub end: Vec3,
+    /// Radius around the spine
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Creates a new capsule
+    pub fn new(start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    /// Center of the spine
+    pub fn center(&self) -> Vec3 {
+        (self.start + self.end) * 0.5
+    }
+
+    /// Test ray intersection with this capsule
+    /// Returns (distance, hit_point, normal) if hit, None otherwise
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(f32, Vec3, Vec3)> {
+        let axis = self.end - self.start;
+        let length_sq = axis.magnitude_squared();
+        
+        // End caps (also covers degenerate capsules)
+        let mut closest = BoundingSphere::new(self.start, self.radius).intersect_ray(ray);
+        if let Some(hit) = BoundingSphere::new(self.end, self.radius).intersect_ray(ray) {
+            if closest.is_none_or(|(t, _, _)| hit.0 < t) {
+                closest = Some(hit);
+            }
+        }
+        if length_sq < f32::EPSILON {
+            return closest;
+        }
+        
+        // Cylinder body: solve |(m + t*d) x axis|^2 = r^2 |axis|^2 with m = origin - start
+        let m = ray.origin - self.start;
+        let d = ray.direction;
+        let md = m.dot(&axis);
+        let nd = d.dot(&axis);
+        let a = length_sq * d.dot(&d) - nd * nd;
+        let b = length_sq * m.dot(&d) - md * nd;
+        let c = length_sq * (m.dot(&m) - self.radius * self.radius) - md * md;
+        // Half-b form of the quadratic: a*t^2 + 2b*t + c = 0
+        let discriminant = b.powi(2) - a * c;
+        if a.abs() < f32::EPSILON || discriminant < 0.0 {
+            return closest; // Parallel to the spine or missing the body
+        }
+        
+        let sqrt_discriminant = discriminant.sqrt();
+        for t in [(-b - sqrt_discriminant) / a, (-b + sqrt_discriminant) / a] {
+            let along = (md + t * nd) / length_sq;
+            if t < 0.0 || !(0.0..=1.0).contains(&along) {
+                continue;
+            }
+            if closest.is_none_or(|(best, _, _)| t < best) {
+                let hit_point = ray.point_at(t);
+                let normal = (hit_point - (self.start + axis * along)).normalize();
+                closest = Some((t, hit_point, normal));
+            }
+            break;
+        }
+        
+        closest
+    }
+}
+
+/// An oriented bounding box (OBB)
+#[derive(Debug, Clone, Copy)]
+pub struct OrientedBox {
+    /// Center position in world space
+    pub center: Vec3,
+    /// Half-extents along the box's local axes
+    pub half_extents: Vec3,
+    /// Orientation of the box's local axes
+    pub rotation: Quat,
+}
+
+impl OrientedBox {
+    /// Creates a new oriented box
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Self { center, half_extents, rotation }
+    }
+
+    /// The box's local X, Y and Z axes in world space
+    pub fn axes(&self) -> [Vec3; 3] {
+        [
+            self.rotation * Vec3::x(),
+            self.rotation * Vec3::y(),
+            self.rotation * Vec3::z(),
+        ]
+    }
+
+    /// The eight corners in world space
+    pub fn corners(&self) -> [Vec3; 8] {
+        let [x, y, z] = self.axes();
+        let e = self.half_extents;
+        std::array::from_fn(|i| {
+            let sx = if i & 1 == 0 { -e.x } else { e.x };
+            let sy = if i & 2 == 0 { -e.y } else { e.y };
+            let sz = if i & 4 == 0 { -e.z } else { e.z };
+            self.center + x * sx + y * sy + z * sz
+        })
+    }
+
+    /// Test ray intersection with this box (slab test in box space)
+    /// Returns (distance, hit_point, normal) if hit, None otherwise
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(f32, Vec3, Vec3)> {
+        let inverse = self.rotation.inverse();
+        let origin = inverse * (ray.origin - self.center);
+        let direction = inverse * ray.direction;
+        
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+        let mut enter_axis = (0, 0.0);
+        let mut exit_axis = (0, 0.0);
+        for axis in 0..3 {
+            let extent = self.half_extents[axis];
+            if direction[axis].abs() < f32::EPSILON {
+                // Parallel to this slab: miss unless already between its planes
+                if origin[axis].abs() > extent {
+                    return None;
+                }
+                continue;
+            }
+            
+            let inverse_direction = 1.0 / direction[axis];
+            let mut near = (-extent - origin[axis]) * inverse_direction;
+            let mut far = (extent - origin[axis]) * inverse_direction;
+            let mut sign = -1.0;
+            if near > far {
+                std::mem::swap(&mut near, &mut far);
+                sign = 1.0;
+            }
+            if near > t_enter {
+                t_enter = near;
+                enter_axis = (axis, sign);
+            }
+            if far < t_exit {
+                t_exit = far;
+                exit_axis = (axis, -sign);
+            }
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+        
+        // Closest positive intersection (the exit when starting inside)
+        let (t, (axis, sign)) = if t_enter >= 0.0 {
+            (t_enter, enter_axis)
+        } else if t_exit >= 0.0 {
+            (t_exit, exit_axis)
+        } else {
+            return None;
+        };
+        
+        let mut local_normal = Vec3::zeros();
+        local_normal[axis] = sign;
+        Some((t, ray.point_at(t), self.rotation * local_normal))
+    }
+}
+
+/// A triangle for collision detection
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Triangle {
     /// Triangle vertices in world space
     pub v0: Vec3,
@@ -194,6 +377,15 @@ impl Triangle {
         }
     }
 
+    /// Point where the segment from `start` to `end` crosses the triangle, if any
+    pub fn intersect_segment(&self, start: Vec3, end: Vec3) -> Option<Vec3> {
+        // Unnormalized direction so t is the fraction of the segment
+        let ray = Ray { origin: start, direction: end - start };
+        self.intersect_ray(&ray)
+            .filter(|(t, _, _)| *t <= 1.0)
+            .map(|(t, _, _)| ray.point_at(t))
+    }
+
     /// Get the closest point on the triangle to a given point
     pub fn closest_point(&self, point: Vec3) -> Vec3 {
         // Project point onto triangle plane
@@ -260,9 +452,10 @@ impl Triangle {
     
     /// Test if this triangle intersects another triangle
     /// Uses proper Separating Axis Theorem (SAT)
-    /// Tests 11 potential separating axes:
+    /// Tests 17 potential separating axes:
     /// - 2 face normals (one per triangle)
     /// - 9 edge-edge cross products
+    /// - 6 in-plane edge normals (for coplanar triangles)
     pub fn intersects_triangle(&self, other: &Triangle) -> bool {
         const EPSILON: f32 = 0.000001;
         
@@ -328,6 +521,19 @@ impl Triangle {
             }
         }
         
+        // Test 12-17: In-plane edge normals (only separate coplanar triangles,
+        // where every axis above is parallel to the shared normal)
+        for edge in &edges1 {
+            if !test_axis(self, other, n1.cross(edge)) {
+                return false;
+            }
+        }
+        for edge in &edges2 {
+            if !test_axis(self, other, n2.cross(edge)) {
+                return false;
+            }
+        }
+        
         // No separating axis found = triangles intersect
         true
     }
@@ -2,19 +2,44 @@
 //!
 //! Provides ECS-friendly collision shapes that store data in model space
 //! and transform to world space on-demand during collision tests.
+//!
+//! Spheres, capsules, boxes and convex hulls are convex and are tested
+//! against each other with GJK/EPA; triangle meshes are tested per triangle.
 
-use crate::foundation::math::Vec3;
-use super::primitives::{BoundingSphere, Ray};
+use crate::foundation::math::{Mat4, Quat, Vec3};
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use super::primitives::{BoundingSphere, Capsule, OrientedBox, Ray};
 use super::mesh::{CollisionMeshTemplate, WorldSpaceCollisionMesh};
+use super::hull::{ConvexHullError, ConvexHullTemplate, WorldSpaceConvexHull};
+use super::contact::{Contact, ContactManifold};
+use super::gjk::{self, SupportMap};
 
 /// Collision shape types (stored in MODEL SPACE)
 /// GEA 13.3.4: "Store collision shapes in model space, transform on-the-fly during tests"
-#[derive(Debug, Clone)]
+/// 
+/// Sphere, capsule and box dimensions are in world units (not scaled by the
+/// transform); meshes and convex hulls are scaled like the model they wrap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CollisionShape {
     /// A spherical collision shape (radius only, position from TransformComponent)
     Sphere(f32),
     /// A triangle mesh collision shape template (model space, transformed on-demand)
     Mesh(CollisionMeshTemplate),
+    /// A capsule along the local Y axis (spine from -half_height to +half_height)
+    Capsule {
+        /// Half the length of the spine (excluding the end caps)
+        half_height: f32,
+        /// Radius around the spine
+        radius: f32,
+    },
+    /// An oriented box (rotation from TransformComponent)
+    Box {
+        /// Half-extents along the local axes
+        half_extents: Vec3,
+    },
+    /// A convex hull template (model space, transformed on-demand)
+    ConvexHull(ConvexHullTemplate),
 }
 
 impl CollisionShape {
@@ -31,6 +56,26 @@ impl CollisionShape {
         Self::Sphere(radius)
     }
 
+    /// Creates a capsule along the local Y axis
+    pub fn capsule(half_height: f32, radius: f32) -> Self {
+        Self::Capsule { half_height, radius }
+    }
+
+    /// Creates an oriented box with the given half-extents
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self::Box { half_extents }
+    }
+
+    /// Creates a convex hull around MODEL SPACE points
+    pub fn convex_hull(points: &[Vec3]) -> Result<Self, ConvexHullError> {
+        ConvexHullTemplate::from_points(points).map(Self::ConvexHull)
+    }
+
+    /// Creates a convex hull around the vertices of an OBJ model
+    pub fn convex_hull_from_obj<P: AsRef<Path>>(path: P) -> Result<Self, ConvexHullError> {
+        ConvexHullTemplate::from_obj(path).map(Self::ConvexHull)
+    }
+
     /// Creates a mesh collision shape from MODEL SPACE vertices and indices
     /// Vertices should be in local coordinates (relative to origin)
     pub fn mesh_from_model(vertices: &[Vec3], indices: &[u32]) -> Self {
@@ -50,6 +95,9 @@ impl CollisionShape {
         match self {
             Self::Sphere(radius) => *radius,
             Self::Mesh(template) => template.local_bounding_radius,
+            Self::Capsule { half_height, radius } => half_height + radius,
+            Self::Box { half_extents } => half_extents.magnitude(),
+            Self::ConvexHull(template) => template.local_bounding_radius,
         }
     }
     
@@ -58,26 +106,37 @@ impl CollisionShape {
     pub fn to_world_space(
         &self,
         position: Vec3,
-        rotation: crate::foundation::math::Quat,
+        rotation: Quat,
         scale: Vec3,
     ) -> WorldSpaceShape {
+        // Build transformation matrix: TRS order
+        let model_matrix = || {
+            let translation = Mat4::new_translation(&position);
+            let rotation_mat = rotation.to_homogeneous();
+            let scale_mat = Mat4::new_nonuniform_scaling(&scale);
+            translation * rotation_mat * scale_mat
+        };
+        let scale_factor = scale.x.max(scale.y).max(scale.z);
+        
         match self {
             Self::Sphere(radius) => {
                 // Sphere radius is already in world-space units (not scaled by transform)
                 WorldSpaceShape::Sphere(BoundingSphere::new(position, *radius))
             }
             Self::Mesh(template) => {
-                // Build transformation matrix: TRS order
-                use crate::foundation::math::Mat4;
-                let translation = Mat4::new_translation(&position);
-                let rotation_mat = rotation.to_homogeneous();
-                let scale_mat = Mat4::new_nonuniform_scaling(&scale);
-                let matrix = translation * rotation_mat * scale_mat;
-                
-                let scale_factor = scale.x.max(scale.y).max(scale.z);
-                let world_mesh = template.to_world_space(&matrix, position, scale_factor);
+                let world_mesh = template.to_world_space(&model_matrix(), position, scale_factor);
                 WorldSpaceShape::Mesh(world_mesh)
             }
+            Self::Capsule { half_height, radius } => {
+                let spine = rotation * Vec3::new(0.0, *half_height, 0.0);
+                WorldSpaceShape::Capsule(Capsule::new(position - spine, position + spine, *radius))
+            }
+            Self::Box { half_extents } => {
+                WorldSpaceShape::Box(OrientedBox::new(position, *half_extents, rotation))
+            }
+            Self::ConvexHull(template) => {
+                WorldSpaceShape::ConvexHull(template.to_world_space(&model_matrix(), position, scale_factor))
+            }
         }
     }
 }
@@ -89,6 +148,12 @@ pub enum WorldSpaceShape {
     Sphere(BoundingSphere),
     /// World-space mesh
     Mesh(WorldSpaceCollisionMesh),
+    /// World-space capsule
+    Capsule(Capsule),
+    /// World-space oriented box
+    Box(OrientedBox),
+    /// World-space convex hull
+    ConvexHull(WorldSpaceConvexHull),
 }
 
 impl WorldSpaceShape {
@@ -97,6 +162,9 @@ impl WorldSpaceShape {
         match self {
             Self::Sphere(sphere) => sphere.center,
             Self::Mesh(mesh) => mesh.center,
+            Self::Capsule(capsule) => capsule.center(),
+            Self::Box(obb) => obb.center,
+            Self::ConvexHull(hull) => hull.center,
         }
     }
     
@@ -105,16 +173,30 @@ impl WorldSpaceShape {
         match self {
             Self::Sphere(sphere) => *sphere,
             Self::Mesh(mesh) => BoundingSphere::new(mesh.center, mesh.bounding_radius),
+            Self::Capsule(capsule) => BoundingSphere::new(
+                capsule.center(),
+                (capsule.end - capsule.start).magnitude() * 0.5 + capsule.radius,
+            ),
+            Self::Box(obb) => BoundingSphere::new(obb.center, obb.half_extents.magnitude()),
+            Self::ConvexHull(hull) => BoundingSphere::new(hull.center, hull.bounding_radius),
+        }
+    }
+
+    /// Support mapping for the GJK/EPA tests (None for non-convex meshes)
+    pub fn support_map(&self) -> Option<&dyn SupportMap> {
+        match self {
+            Self::Sphere(sphere) => Some(sphere),
+            Self::Mesh(_) => None,
+            Self::Capsule(capsule) => Some(capsule),
+            Self::Box(obb) => Some(obb),
+            Self::ConvexHull(hull) => Some(hull),
         }
     }
 
     /// Test ray intersection with this collision shape
     /// Returns distance to hit if intersected, None otherwise
     pub fn intersect_ray(&self, ray: &Ray) -> Option<f32> {
-        match self {
-            Self::Sphere(sphere) => sphere.intersect_ray(ray).map(|(distance, _, _)| distance),
-            Self::Mesh(mesh) => mesh.intersect_ray(ray).map(|(distance, _, _)| distance),
-        }
+        self.intersect_ray_detailed(ray).map(|(distance, _, _)| distance)
     }
 
     /// Test ray intersection with this collision shape (detailed)
@@ -123,6 +205,9 @@ impl WorldSpaceShape {
         match self {
             Self::Sphere(sphere) => sphere.intersect_ray(ray),
             Self::Mesh(mesh) => mesh.intersect_ray(ray),
+            Self::Capsule(capsule) => capsule.intersect_ray(ray),
+            Self::Box(obb) => obb.intersect_ray(ray),
+            Self::ConvexHull(hull) => hull.intersect_ray(ray),
         }
     }
 
@@ -142,9 +227,58 @@ impl WorldSpaceShape {
             (Self::Mesh(a), Self::Mesh(b)) => {
                 a.intersects_mesh(b)
             }
+            
+            // Mesh-Convex intersection (per triangle)
+            (Self::Mesh(_), _) | (_, Self::Mesh(_)) => self.manifold(other).is_some(),
+            
+            // Convex-Convex intersection (GJK)
+            _ => match (self.support_map(), other.support_map()) {
+                (Some(a), Some(b)) => {
+                    self.bounding_sphere().intersects(&other.bounding_sphere()) && gjk::intersects(a, b)
+                }
+                _ => false,
+            },
         }
     }
 
+    /// Contact manifold with another shape, normal pointing from this shape to `other`
+    /// 
+    /// Spheres touch at a single point; flat contacts between meshes, boxes
+    /// and hulls can have up to `MAX_MANIFOLD_POINTS` points spanning the
+    /// overlapping region.
+    pub fn manifold(&self, other: &WorldSpaceShape) -> Option<ContactManifold> {
+        match (self, other) {
+            (Self::Sphere(a), Self::Sphere(b)) => a.contact(b).map(ContactManifold::from),
+            
+            (Self::Sphere(sphere), Self::Mesh(mesh)) => mesh.sphere_contact(sphere).map(ContactManifold::from),
+            (Self::Mesh(mesh), Self::Sphere(sphere)) => {
+                mesh.sphere_contact(sphere).map(|contact| ContactManifold::from(contact.flipped()))
+            }
+            
+            (Self::Mesh(a), Self::Mesh(b)) => a.mesh_manifold(b),
+            
+            (Self::Mesh(mesh), convex) => {
+                mesh.convex_manifold(convex.support_map()?, &convex.bounding_sphere())
+            }
+            (convex, Self::Mesh(mesh)) => {
+                mesh.convex_manifold(convex.support_map()?, &convex.bounding_sphere())
+                    .map(|manifold| manifold.flipped())
+            }
+            
+            (a, b) => {
+                if !a.bounding_sphere().intersects(&b.bounding_sphere()) {
+                    return None;
+                }
+                gjk::contact_manifold(a.support_map()?, b.support_map()?)
+            }
+        }
+    }
+
+    /// Deepest contact with another shape, normal pointing from this shape to `other`
+    pub fn contact(&self, other: &WorldSpaceShape) -> Option<Contact> {
+        self.manifold(other).and_then(|manifold| manifold.deepest())
+    }
+
     /// Get penetration depth for intersecting shapes (0.0 if not intersecting)
     pub fn penetration_depth(&self, other: &WorldSpaceShape) -> f32 {
         match (self, other) {
@@ -157,12 +291,93 @@ impl WorldSpaceShape {
                     .unwrap_or(0.0)
             }
             
-            (Self::Mesh(a), Self::Mesh(b)) => {
-                // Use bounding sphere penetration as approximation
-                let sphere_a = BoundingSphere::new(a.center, a.bounding_radius);
-                let sphere_b = BoundingSphere::new(b.center, b.bounding_radius);
-                sphere_a.penetration_depth(&sphere_b)
+            _ => {
+                self.manifold(other)
+                    .map(|manifold| manifold.max_depth())
+                    .unwrap_or(0.0)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundation::math::Quat;
+
+    /// Unit cube (half extent 1) with outward facing triangles
+    fn cube() -> CollisionShape {
+        let vertices: Vec<Vec3> = (0..8)
+            .map(|i| Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            ))
+            .collect();
+        let indices = [
+            0, 2, 1, 1, 2, 3, // -Z
+            4, 5, 6, 5, 7, 6, // +Z
+            0, 4, 2, 2, 4, 6, // -X
+            1, 3, 5, 3, 7, 5, // +X
+            0, 1, 4, 1, 5, 4, // -Y
+            2, 6, 3, 3, 6, 7, // +Y
+        ];
+        CollisionShape::mesh_from_model(&vertices, &indices)
+    }
+
+    #[test]
+    fn test_mesh_mesh_manifold_spans_overlapping_faces() {
+        let lower = cube().to_world_space(Vec3::zeros(), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+        let upper = cube().to_world_space(Vec3::new(0.3, 1.8, 0.2), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+
+        let manifold = lower.manifold(&upper).expect("cubes overlap");
+        assert!((manifold.normal - Vec3::y()).magnitude() < 1e-5, "normal {:?}", manifold.normal);
+        assert_eq!(manifold.len(), 4);
+        assert!((manifold.max_depth() - 0.2).abs() < 1e-4);
+        for point in &manifold.points {
+            assert!(point.point.y >= 0.8 - 1e-4 && point.point.y <= 1.0 + 1e-4, "point {:?}", point.point);
+        }
+        assert!((lower.penetration_depth(&upper) - 0.2).abs() < 1e-4);
+
+        // Seen from the other shape the normal flips
+        let reversed = upper.manifold(&lower).unwrap();
+        assert!((reversed.normal + Vec3::y()).magnitude() < 1e-5);
+
+        let apart = cube().to_world_space(Vec3::new(0.0, 2.5, 0.0), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+        assert!(lower.manifold(&apart).is_none());
+    }
+
+    #[test]
+    fn test_sphere_manifolds_have_one_point() {
+        let a = CollisionShape::sphere(1.0).to_world_space(Vec3::zeros(), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+        let b = CollisionShape::sphere(1.0).to_world_space(Vec3::new(1.5, 0.0, 0.0), Quat::identity(), Vec3::new(1.0, 1.0, 1.0));
+
+        let manifold = a.manifold(&b).unwrap();
+        assert_eq!(manifold.len(), 1);
+        assert_eq!(manifold.normal, Vec3::x());
+        assert!((manifold.max_depth() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ray_hits_capsule_and_box() {
+        let unit = Vec3::new(1.0, 1.0, 1.0);
+        let ray = Ray::new(Vec3::new(-10.0, 0.0, 0.0), Vec3::x());
+
+        // Capsule lying along X (rotated from local Y): ray enters the end cap
+        let rotation = Quat::from_axis_angle(&Vec3::z_axis(), std::f32::consts::FRAC_PI_2);
+        let capsule = CollisionShape::capsule(2.0, 0.5).to_world_space(Vec3::zeros(), rotation, unit);
+        let (distance, _, normal) = capsule.intersect_ray_detailed(&ray).unwrap();
+        assert!((distance - 7.5).abs() < 1e-4, "distance {}", distance);
+        assert!((normal + Vec3::x()).magnitude() < 1e-4);
+
+        // Ray from above hits the cylinder body
+        let down = Ray::new(Vec3::new(1.0, 10.0, 0.0), -Vec3::y());
+        assert!((capsule.intersect_ray(&down).unwrap() - 9.5).abs() < 1e-4);
+
+        // Box rotated 45 degrees about Y: the ray hits an edge at sqrt(2)
+        let rotation = Quat::from_axis_angle(&Vec3::y_axis(), std::f32::consts::FRAC_PI_4);
+        let obb = CollisionShape::cuboid(unit).to_world_space(Vec3::zeros(), rotation, unit);
+        assert!((obb.intersect_ray(&ray).unwrap() - (10.0 - 2.0f32.sqrt())).abs() < 1e-4);
+        assert!(obb.intersect_ray(&Ray::new(Vec3::new(-10.0, 1.5, 0.0), Vec3::x())).is_none());
+    }
+}
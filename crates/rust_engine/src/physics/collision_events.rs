@@ -0,0 +1,76 @@
+//! Typed collision and trigger events
+//!
+//! `PhysicsCollisionSystem::send_events` publishes these to `Events<E>` world
+//! resources each frame, so gameplay systems can react to contacts with their
+//! own `EventReader`s instead of polling `CollisionStateComponent`s. Pairs
+//! involving a trigger collider (`ColliderComponent::as_trigger`) produce
+//! trigger events only; all other pairs produce collision events.
+
+use crate::ecs::Entity;
+use super::collision::ContactManifold;
+
+/// Two solid colliders started touching
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionStarted {
+    /// Entity with the smaller ID
+    pub entity_a: Entity,
+    /// Entity with the larger ID
+    pub entity_b: Entity,
+    /// Contact points when the collision started (normal from `entity_a` to `entity_b`)
+    pub manifold: ContactManifold,
+}
+
+impl CollisionStarted {
+    /// The other entity of the pair, if `entity` is part of it
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        other_of(self.entity_a, self.entity_b, entity)
+    }
+}
+
+/// Two solid colliders stopped touching (or one of them was removed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionEnded {
+    /// Entity with the smaller ID
+    pub entity_a: Entity,
+    /// Entity with the larger ID
+    pub entity_b: Entity,
+}
+
+impl CollisionEnded {
+    /// The other entity of the pair, if `entity` is part of it
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        other_of(self.entity_a, self.entity_b, entity)
+    }
+}
+
+/// A collider entered a trigger volume
+///
+/// When two triggers overlap, each gets its own event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerEntered {
+    /// Entity with the trigger collider
+    pub trigger: Entity,
+    /// Entity that entered it
+    pub other: Entity,
+    /// Overlap when it was entered (normal from `trigger` to `other`)
+    pub manifold: ContactManifold,
+}
+
+/// A collider left a trigger volume (or one of them was removed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerExited {
+    /// Entity with the trigger collider
+    pub trigger: Entity,
+    /// Entity that left it
+    pub other: Entity,
+}
+
+fn other_of(entity_a: Entity, entity_b: Entity, entity: Entity) -> Option<Entity> {
+    if entity == entity_a {
+        Some(entity_b)
+    } else if entity == entity_b {
+        Some(entity_a)
+    } else {
+        None
+    }
+}
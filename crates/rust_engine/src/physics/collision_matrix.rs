@@ -0,0 +1,221 @@
+//! Named collision layers with a data-driven can-collide table
+//!
+//! GEA 13.3.8: collision filtering is usually configured by designers rather
+//! than hardcoded. A `CollisionMatrix` names up to 32 layers (bit `i` of a
+//! collider's `layer` is the `i`-th name) and stores which layer pairs may
+//! collide. Loaded through `settings::Config`, e.g. from RON:
+//!
+//! ```ron
+//! (
+//!     layers: ["player", "enemy", "projectile", "environment"],
+//!     collisions: [
+//!         ("player", "enemy"),
+//!         ("player", "environment"),
+//!         ("projectile", "enemy"),
+//!     ],
+//! )
+//! ```
+//!
+//! When present as a world resource, `PhysicsCollisionSystem` only reports
+//! pairs allowed by both the matrix and the colliders' own masks.
+
+use crate::settings::Config;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Maximum number of named layers (one per bit of a `u32` layer mask)
+pub const MAX_COLLISION_LAYERS: usize = 32;
+
+/// Names of the standard layers, in `CollisionLayers` bit order
+const STANDARD_LAYERS: [&str; 8] = [
+    "player",
+    "enemy",
+    "projectile",
+    "environment",
+    "trigger",
+    "debris",
+    "vehicle",
+    "pickup",
+];
+
+/// Collision matrix errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CollisionMatrixError {
+    /// More than `MAX_COLLISION_LAYERS` layers were defined
+    #[error("Collision matrix supports at most {MAX_COLLISION_LAYERS} layers")]
+    TooManyLayers,
+
+    /// The same layer name was defined twice
+    #[error("Duplicate collision layer '{0}'")]
+    DuplicateLayer(String),
+
+    /// A layer name was used without being defined
+    #[error("Unknown collision layer '{0}'")]
+    UnknownLayer(String),
+}
+
+/// Named collision layers and a symmetric can-collide table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "CollisionMatrixData", into = "CollisionMatrixData")]
+pub struct CollisionMatrix {
+    names: Vec<String>,
+    /// Row `i` holds the layers that layer `i` collides with
+    rows: [u32; MAX_COLLISION_LAYERS],
+}
+
+/// Serialized form: layer names and the pairs that collide
+#[derive(Serialize, Deserialize)]
+struct CollisionMatrixData {
+    layers: Vec<String>,
+    #[serde(default)]
+    collisions: Vec<(String, String)>,
+}
+
+impl Default for CollisionMatrix {
+    /// The standard `CollisionLayers` names, every pair colliding
+    fn default() -> Self {
+        let mut matrix = Self::new();
+        for name in STANDARD_LAYERS {
+            matrix.add_layer(name).expect("standard layers are unique");
+        }
+        matrix.rows = [u32::MAX; MAX_COLLISION_LAYERS];
+        matrix
+    }
+}
+
+impl CollisionMatrix {
+    /// Create a matrix without layers
+    pub fn new() -> Self {
+        Self { names: Vec::new(), rows: [0; MAX_COLLISION_LAYERS] }
+    }
+
+    /// Define the next layer, returning its bit
+    pub fn add_layer(&mut self, name: &str) -> Result<u32, CollisionMatrixError> {
+        if self.names.iter().any(|existing| existing == name) {
+            return Err(CollisionMatrixError::DuplicateLayer(name.to_string()));
+        }
+        if self.names.len() == MAX_COLLISION_LAYERS {
+            return Err(CollisionMatrixError::TooManyLayers);
+        }
+        self.names.push(name.to_string());
+        Ok(1 << (self.names.len() - 1))
+    }
+
+    /// Bit of a named layer
+    pub fn layer(&self, name: &str) -> Option<u32> {
+        self.index(name).map(|index| 1 << index)
+    }
+
+    /// Name of the layer at `bit` (a single-bit layer value)
+    pub fn layer_name(&self, bit: u32) -> Option<&str> {
+        if bit.count_ones() != 1 {
+            return None;
+        }
+        self.names.get(bit.trailing_zeros() as usize).map(String::as_str)
+    }
+
+    /// Defined layer names in bit order
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Allow or forbid collisions between two named layers (in both directions)
+    pub fn set_collides(&mut self, a: &str, b: &str, collides: bool) -> Result<(), CollisionMatrixError> {
+        let a = self.index(a).ok_or_else(|| CollisionMatrixError::UnknownLayer(a.to_string()))?;
+        let b = self.index(b).ok_or_else(|| CollisionMatrixError::UnknownLayer(b.to_string()))?;
+        if collides {
+            self.rows[a] |= 1 << b;
+            self.rows[b] |= 1 << a;
+        } else {
+            self.rows[a] &= !(1 << b);
+            self.rows[b] &= !(1 << a);
+        }
+        Ok(())
+    }
+
+    /// Layers a named layer collides with, usable as a collider mask
+    pub fn mask(&self, name: &str) -> Option<u32> {
+        self.index(name).map(|index| self.rows[index])
+    }
+
+    /// Check whether colliders on `layer_a` and `layer_b` may collide
+    ///
+    /// Layers with several bits set collide if any of their bits do.
+    pub fn can_collide(&self, layer_a: u32, layer_b: u32) -> bool {
+        (0..MAX_COLLISION_LAYERS)
+            .filter(|&index| layer_a & (1 << index) != 0)
+            .any(|index| self.rows[index] & layer_b != 0)
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|existing| existing == name)
+    }
+}
+
+impl Config for CollisionMatrix {}
+
+impl TryFrom<CollisionMatrixData> for CollisionMatrix {
+    type Error = CollisionMatrixError;
+
+    fn try_from(data: CollisionMatrixData) -> Result<Self, Self::Error> {
+        let mut matrix = Self::new();
+        for name in &data.layers {
+            matrix.add_layer(name)?;
+        }
+        for (a, b) in &data.collisions {
+            matrix.set_collides(a, b, true)?;
+        }
+        Ok(matrix)
+    }
+}
+
+impl From<CollisionMatrix> for CollisionMatrixData {
+    fn from(matrix: CollisionMatrix) -> Self {
+        let mut collisions = Vec::new();
+        for a in 0..matrix.names.len() {
+            for b in a..matrix.names.len() {
+                if matrix.rows[a] & (1 << b) != 0 {
+                    collisions.push((matrix.names[a].clone(), matrix.names[b].clone()));
+                }
+            }
+        }
+        Self { layers: matrix.names, collisions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::CollisionLayers;
+
+    const TURRET_MATRIX: &str = r#"(
+        layers: ["player", "enemy", "projectile"],
+        collisions: [("projectile", "enemy"), ("player", "enemy")],
+    )"#;
+
+    #[test]
+    fn test_matrix_from_ron_is_symmetric() {
+        let matrix: CollisionMatrix = ron::from_str(TURRET_MATRIX).unwrap();
+
+        assert_eq!(matrix.layer("enemy"), Some(CollisionLayers::ENEMY));
+        assert!(matrix.can_collide(CollisionLayers::PROJECTILE, CollisionLayers::ENEMY));
+        assert!(matrix.can_collide(CollisionLayers::ENEMY, CollisionLayers::PROJECTILE));
+        assert!(!matrix.can_collide(CollisionLayers::PROJECTILE, CollisionLayers::PLAYER));
+        assert!(!matrix.can_collide(CollisionLayers::ENEMY, CollisionLayers::ENEMY));
+        assert_eq!(matrix.mask("enemy"), Some(CollisionLayers::PLAYER | CollisionLayers::PROJECTILE));
+
+        // Round trip through TOML keeps the table
+        let toml = toml::to_string(&matrix).unwrap();
+        assert_eq!(toml::from_str::<CollisionMatrix>(&toml).unwrap(), matrix);
+    }
+
+    #[test]
+    fn test_unknown_layer_is_rejected() {
+        let result = ron::from_str::<CollisionMatrix>(r#"(layers: ["player"], collisions: [("player", "ghost")])"#);
+        assert!(result.is_err());
+
+        let mut matrix = CollisionMatrix::new();
+        matrix.add_layer("player").unwrap();
+        assert_eq!(matrix.add_layer("player"), Err(CollisionMatrixError::DuplicateLayer("player".into())));
+    }
+}
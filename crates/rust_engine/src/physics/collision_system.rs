@@ -9,10 +9,18 @@
 //! ecs/systems/collision_system.rs
 
 use crate::spatial::spatial_query::SpatialQuery;
-use crate::ecs::{Entity, World};
-use crate::ecs::components::TransformComponent;
-use crate::physics::collision::CollisionShape;
+use crate::ecs::{Entity, Events, World};
+use crate::ecs::components::{RigidBodyComponent, RigidBodyType};
+use crate::foundation::math::Vec3;
+use crate::foundation::math::Quat;
+use crate::physics::collision::{
+    sweep_sphere, BoundingSphere, CollisionShape, Contact, ContactManifold, OrientedBox, Ray, RayHit,
+    TimeOfImpact, WorldSpaceShape,
+};
+use crate::scene::AABB;
 use crate::physics::collision_layers::CollisionLayers;
+use crate::physics::collision_events::{CollisionEnded, CollisionStarted, TriggerEntered, TriggerExited};
+use crate::physics::collision_matrix::CollisionMatrix;
 use std::collections::{HashMap, HashSet};
 
 /// Collision pair representing two entities that are colliding
@@ -41,8 +49,37 @@ struct ColliderData {
     shape: CollisionShape,
     layer: u32,
     mask: u32,
-    #[allow(dead_code)]
     is_trigger: bool,
+    /// Sweep this collider between frames to catch fast-moving hits
+    ccd: bool,
+    /// World-space position at the previous detection pass (CCD colliders only)
+    previous_position: Option<Vec3>,
+}
+
+/// Earliest hit of a continuous (CCD) collider along its motion this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CcdHit {
+    /// Entity that was hit
+    pub other: Entity,
+    /// Time of impact along the motion from the previous to the current position
+    pub impact: TimeOfImpact,
+}
+
+/// Colliders whose bodies cannot move this frame
+struct RestingColliders {
+    /// Colliders on sleeping bodies
+    sleeping: HashSet<Entity>,
+    /// Colliders on sleeping or static bodies
+    at_rest: HashSet<Entity>,
+}
+
+impl RestingColliders {
+    /// Neither side can move and at least one is asleep, so last frame's contact still holds
+    fn is_frozen(&self, pair: &CollisionPair) -> bool {
+        self.at_rest.contains(&pair.entity_a)
+            && self.at_rest.contains(&pair.entity_b)
+            && (self.sleeping.contains(&pair.entity_a) || self.sleeping.contains(&pair.entity_b))
+    }
 }
 
 /// Core collision detection system (GEA 13.3)
@@ -59,9 +96,21 @@ pub struct PhysicsCollisionSystem {
     /// Collision pairs from the current frame
     current_pairs: HashSet<CollisionPair>,
     
+    /// Contact manifolds for the current pairs (normal from entity_a to entity_b)
+    manifolds: HashMap<CollisionPair, ContactManifold>,
+    
     /// Collision pairs from the previous frame
     previous_pairs: HashSet<CollisionPair>,
     
+    /// Which side of each current pair is a trigger (pairs without triggers are absent)
+    trigger_pairs: HashMap<CollisionPair, (bool, bool)>,
+    
+    /// Trigger sides of the previous frame's pairs, kept for exit events
+    previous_trigger_pairs: HashMap<CollisionPair, (bool, bool)>,
+    
+    /// Earliest swept hit for each CCD collider this frame
+    ccd_hits: HashMap<Entity, CcdHit>,
+    
     /// Enable debug mode
     pub debug_enabled: bool,
 }
@@ -73,7 +122,11 @@ impl PhysicsCollisionSystem {
             spatial_query,
             colliders: HashMap::new(),
             current_pairs: HashSet::new(),
+            manifolds: HashMap::new(),
             previous_pairs: HashSet::new(),
+            trigger_pairs: HashMap::new(),
+            previous_trigger_pairs: HashMap::new(),
+            ccd_hits: HashMap::new(),
             debug_enabled: false,
         }
     }
@@ -95,12 +148,27 @@ impl PhysicsCollisionSystem {
             layer,
             mask,
             is_trigger,
+            ccd: false,
+            previous_position: None,
         });
         
         // Insert into spatial query with initial position
         self.spatial_query.insert(entity, initial_position, bounding_radius);
     }
     
+    /// Enable or disable continuous collision detection for a collider
+    /// 
+    /// CCD colliders sweep their bounding sphere from last frame's position to
+    /// the current one, so fast objects cannot pass through thin targets.
+    pub fn set_ccd(&mut self, entity: Entity, enabled: bool) {
+        // Sweep from the registered position on the first pass
+        let position = self.spatial_query.get_entity_data(entity).map(|(position, _)| position);
+        if let Some(collider) = self.colliders.get_mut(&entity) {
+            collider.ccd = enabled;
+            collider.previous_position = position.filter(|_| enabled);
+        }
+    }
+    
     /// Unregister a collider (called when ColliderComponent is removed)
     pub fn unregister_collider(&mut self, entity: Entity) {
         self.spatial_query.remove(entity);
@@ -125,25 +193,152 @@ impl PhysicsCollisionSystem {
     pub fn detect_collisions(&mut self, world: &World) -> &HashSet<CollisionPair> {
         // Move current pairs to previous
         std::mem::swap(&mut self.current_pairs, &mut self.previous_pairs);
+        std::mem::swap(&mut self.trigger_pairs, &mut self.previous_trigger_pairs);
         self.current_pairs.clear();
+        self.trigger_pairs.clear();
+        self.ccd_hits.clear();
+        
+        // Drop colliders whose entities were despawned (stale handles)
+        self.remove_despawned_colliders(world);
+        
+        // Contacts between sleeping (or sleeping and static) bodies are kept
+        // from the last frame instead of being tested again
+        let resting = self.resting_colliders(world);
+        let manifolds = std::mem::take(&mut self.manifolds);
+        for (pair, manifold) in manifolds {
+            let registered = self.colliders.contains_key(&pair.entity_a) && self.colliders.contains_key(&pair.entity_b);
+            if registered && resting.is_frozen(&pair) {
+                self.current_pairs.insert(pair);
+                self.manifolds.insert(pair, manifold);
+            }
+        }
         
         // Phase 1: Broad-phase - get potential collision pairs from spatial query
-        let potential_pairs = self.broad_phase();
+        let potential_pairs = self.broad_phase(world.resource::<CollisionMatrix>(), &resting);
         
         // Phase 2: Narrow-phase - test actual shape intersections using transforms
         self.narrow_phase(potential_pairs, world);
         
+        // Phase 3: Sweep CCD colliders along their motion since the last frame
+        self.continuous_phase(world, &resting);
+        
+        // Remember trigger pairs so their exit is reported even if a collider is removed
+        for pair in &self.current_pairs {
+            let sides = (self.is_trigger(pair.entity_a), self.is_trigger(pair.entity_b));
+            if sides.0 || sides.1 {
+                self.trigger_pairs.insert(*pair, sides);
+            }
+        }
+        
         &self.current_pairs
     }
     
+    /// Publish this frame's enter/exit events to the world's event queues
+    /// 
+    /// Call once per `detect_collisions`. Pairs with a trigger collider produce
+    /// `TriggerEntered`/`TriggerExited` (one per trigger in the pair), all others
+    /// `CollisionStarted`/`CollisionEnded`. The `Events` resources are created on
+    /// first use and updated here, so readers must read at least every other frame.
+    pub fn send_events(&self, world: &mut World) {
+        let mut entered: Vec<CollisionPair> = self.get_collision_entered().collect();
+        let mut exited: Vec<CollisionPair> = self.get_collision_exited().collect();
+        entered.sort_by_key(|pair| (pair.entity_a.id(), pair.entity_b.id()));
+        exited.sort_by_key(|pair| (pair.entity_a.id(), pair.entity_b.id()));
+        
+        let mut started = Vec::new();
+        let mut trigger_entered = Vec::new();
+        for pair in entered {
+            // Every current pair has a manifold
+            let Some(manifold) = self.manifolds.get(&pair).cloned() else { continue };
+            match self.trigger_pairs.get(&pair) {
+                Some(&(trigger_a, trigger_b)) => {
+                    if trigger_a {
+                        trigger_entered.push(TriggerEntered { trigger: pair.entity_a, other: pair.entity_b, manifold: manifold.clone() });
+                    }
+                    if trigger_b {
+                        trigger_entered.push(TriggerEntered { trigger: pair.entity_b, other: pair.entity_a, manifold: manifold.flipped() });
+                    }
+                }
+                None => started.push(CollisionStarted { entity_a: pair.entity_a, entity_b: pair.entity_b, manifold }),
+            }
+        }
+        
+        let mut ended = Vec::new();
+        let mut trigger_exited = Vec::new();
+        for pair in exited {
+            match self.previous_trigger_pairs.get(&pair) {
+                Some(&(trigger_a, trigger_b)) => {
+                    if trigger_a {
+                        trigger_exited.push(TriggerExited { trigger: pair.entity_a, other: pair.entity_b });
+                    }
+                    if trigger_b {
+                        trigger_exited.push(TriggerExited { trigger: pair.entity_b, other: pair.entity_a });
+                    }
+                }
+                None => ended.push(CollisionEnded { entity_a: pair.entity_a, entity_b: pair.entity_b }),
+            }
+        }
+        
+        Self::publish(world, started);
+        Self::publish(world, ended);
+        Self::publish(world, trigger_entered);
+        Self::publish(world, trigger_exited);
+    }
+    
+    /// Update an event queue (creating it if needed) and send a frame's events
+    fn publish<E: Send + Sync + 'static>(world: &mut World, events: Vec<E>) {
+        if !world.contains_resource::<Events<E>>() {
+            world.insert_resource(Events::<E>::new());
+        }
+        let queue = world.resource_mut::<Events<E>>().expect("event queue was just inserted");
+        queue.update();
+        queue.send_batch(events);
+    }
+    
+    /// Colliders on sleeping or static rigid bodies
+    fn resting_colliders(&self, world: &World) -> RestingColliders {
+        let mut resting = RestingColliders { sleeping: HashSet::new(), at_rest: HashSet::new() };
+        for &entity in self.colliders.keys() {
+            let Some(body) = world.get_component::<RigidBodyComponent>(entity) else { continue };
+            if body.is_sleeping() {
+                resting.sleeping.insert(entity);
+                resting.at_rest.insert(entity);
+            } else if body.body_type == RigidBodyType::Static {
+                resting.at_rest.insert(entity);
+            }
+        }
+        resting
+    }
+    
+    /// Unregister colliders whose entity is no longer alive in the world
+    fn remove_despawned_colliders(&mut self, world: &World) {
+        let despawned: Vec<Entity> = self.colliders
+            .keys()
+            .filter(|entity| !world.is_alive(**entity))
+            .copied()
+            .collect();
+        
+        for entity in despawned {
+            self.unregister_collider(entity);
+        }
+    }
+    
     /// Broad-phase: Use spatial query to find potential collision pairs
     /// GEA 13.3.1: "The broad phase quickly identifies pairs of objects that
     /// might be colliding using some kind of spatial partitioning scheme."
-    fn broad_phase(&self) -> Vec<CollisionPair> {
+    /// 
+    /// Pairs must pass both the colliders' masks and, when the world has a
+    /// `CollisionMatrix` resource, its can-collide table. Sleeping colliders
+    /// don't query (awake neighbours find them), and frozen pairs are skipped.
+    fn broad_phase(&self, matrix: Option<&CollisionMatrix>, resting: &RestingColliders) -> Vec<CollisionPair> {
         let mut potential_pairs = HashSet::new();
         
         // For each registered collider
         for (&entity, collider) in &self.colliders {
+            if resting.sleeping.contains(&entity) {
+                continue;
+            }
+            
             // Query nearby entities from spatial structure
             let nearby = self.spatial_query.query_nearby(entity);
             
@@ -157,17 +352,15 @@ impl PhysicsCollisionSystem {
                 // Check if the other entity has a collider
                 if let Some(other_collider) = self.colliders.get(&nearby_entity) {
                     // Apply layer filtering (GEA 13.3.8)
-                    if !CollisionLayers::should_collide(
-                        collider.layer,
-                        collider.mask,
-                        other_collider.layer,
-                        other_collider.mask,
-                    ) {
+                    if !Self::layers_collide(collider, other_collider, matrix) {
                         continue;
                     }
                     
                     // Add to potential pairs (automatically handles duplicates via HashSet)
-                    potential_pairs.insert(CollisionPair::new(entity, nearby_entity));
+                    let pair = CollisionPair::new(entity, nearby_entity);
+                    if !resting.is_frozen(&pair) {
+                        potential_pairs.insert(pair);
+                    }
                 }
             }
         }
@@ -175,7 +368,23 @@ impl PhysicsCollisionSystem {
         potential_pairs.into_iter().collect()
     }
     
-    /// Narrow-phase: Test actual shape intersections using TransformComponents
+    /// Whether two registered colliders pass the broad phase's layer filtering
+    /// 
+    /// Uses the collider masks and the world's `CollisionMatrix`, if any.
+    pub fn can_collide(&self, world: &World, a: Entity, b: Entity) -> bool {
+        match (self.colliders.get(&a), self.colliders.get(&b)) {
+            (Some(a), Some(b)) => Self::layers_collide(a, b, world.resource::<CollisionMatrix>()),
+            _ => false,
+        }
+    }
+    
+    /// Layer filtering by collider masks and the optional collision matrix
+    fn layers_collide(a: &ColliderData, b: &ColliderData, matrix: Option<&CollisionMatrix>) -> bool {
+        CollisionLayers::should_collide(a.layer, a.mask, b.layer, b.mask)
+            && matrix.is_none_or(|matrix| matrix.can_collide(a.layer, b.layer))
+    }
+    
+    /// Narrow-phase: Test actual shape intersections using world-space transforms
     /// GEA 13.3.4: "The narrow phase performs detailed shape-to-shape tests"
     fn narrow_phase(&mut self, potential_pairs: Vec<CollisionPair>, world: &World) {
         for pair in potential_pairs {
@@ -190,13 +399,13 @@ impl PhysicsCollisionSystem {
                 None => continue,
             };
             
-            // Get transforms
-            let transform_a = match world.get_component::<TransformComponent>(pair.entity_a) {
+            // Get world-space transforms (colliders may be parented)
+            let transform_a = match world.world_transform(pair.entity_a) {
                 Some(t) => t,
                 None => continue,
             };
             
-            let transform_b = match world.get_component::<TransformComponent>(pair.entity_b) {
+            let transform_b = match world.world_transform(pair.entity_b) {
                 Some(t) => t,
                 None => continue,
             };
@@ -214,32 +423,241 @@ impl PhysicsCollisionSystem {
                 transform_b.scale,
             );
             
-            // Test intersection in world space
-            if world_shape_a.intersects(&world_shape_b) {
+            // Test intersection in world space, keeping the manifold for collision response
+            if let Some(manifold) = world_shape_a.manifold(&world_shape_b) {
                 self.current_pairs.insert(pair);
+                self.manifolds.insert(pair, manifold);
             }
         }
     }
     
-    /// Get entities that entered collision this frame
-    pub fn get_collision_entered(&self) -> Vec<CollisionPair> {
-        self.current_pairs
-            .difference(&self.previous_pairs)
-            .copied()
+    /// Continuous phase: sweep CCD colliders from their previous to current position
+    /// 
+    /// Only the earliest hit along the path is reported. Targets are tested at
+    /// their current pose; a hit that the discrete test missed (the collider
+    /// tunneled through) is added as a collision pair with a touching contact.
+    fn continuous_phase(&mut self, world: &World, resting: &RestingColliders) {
+        let ccd_entities: Vec<Entity> = self.colliders
+            .iter()
+            .filter(|(entity, collider)| collider.ccd && !resting.sleeping.contains(entity))
+            .map(|(&entity, _)| entity)
+            .collect();
+        
+        for entity in ccd_entities {
+            let transform = match world.world_transform(entity) {
+                Some(t) => t,
+                None => continue,
+            };
+            let collider = &self.colliders[&entity];
+            let previous = collider.previous_position;
+            
+            if let Some(previous) = previous {
+                let bounds = collider.shape
+                    .to_world_space(transform.position, transform.rotation, transform.scale)
+                    .bounding_sphere();
+                let end = bounds.center;
+                let start = end - (transform.position - previous);
+                
+                if let Some(hit) = self.sweep(entity, start, end, bounds.radius, world) {
+                    self.record_ccd_hit(entity, hit);
+                }
+            }
+            
+            if let Some(collider) = self.colliders.get_mut(&entity) {
+                collider.previous_position = Some(transform.position);
+            }
+        }
+    }
+    
+    /// Earliest hit of a sphere swept from `start` to `end` against the colliders `entity` can collide with
+    fn sweep(&self, entity: Entity, start: Vec3, end: Vec3, radius: f32, world: &World) -> Option<CcdHit> {
+        let collider = self.colliders.get(&entity)?;
+        let half_length = (end - start).magnitude() * 0.5;
+        let candidates = self.spatial_query.query_sphere((start + end) * 0.5, half_length + radius);
+        
+        let mut earliest: Option<CcdHit> = None;
+        for other in candidates {
+            if other == entity {
+                continue;
+            }
+            let other_collider = match self.colliders.get(&other) {
+                Some(c) => c,
+                None => continue,
+            };
+            if !Self::layers_collide(collider, other_collider, world.resource::<CollisionMatrix>()) {
+                continue;
+            }
+            let transform = match world.world_transform(other) {
+                Some(t) => t,
+                None => continue,
+            };
+            
+            let target = other_collider.shape.to_world_space(
+                transform.position,
+                transform.rotation,
+                transform.scale,
+            );
+            if let Some(impact) = sweep_sphere(start, end, radius, &target) {
+                if earliest.is_none_or(|hit| impact.time < hit.impact.time) {
+                    earliest = Some(CcdHit { other, impact });
+                }
+            }
+        }
+        
+        earliest
+    }
+    
+    /// Store a CCD hit and make sure its pair is reported as colliding
+    fn record_ccd_hit(&mut self, entity: Entity, hit: CcdHit) {
+        let pair = CollisionPair::new(entity, hit.other);
+        if self.current_pairs.insert(pair) {
+            // Normal from entity_a to entity_b, touching at the impact point
+            let normal = if pair.entity_a == entity { hit.impact.normal } else { -hit.impact.normal };
+            self.manifolds.insert(pair, ContactManifold::from(Contact::new(hit.impact.point, normal, 0.0)));
+        }
+        self.ccd_hits.insert(entity, hit);
+    }
+    
+    /// Cast a ray against all colliders on the layers in `mask`, returning the closest hit
+    /// 
+    /// GEA 13.3.7: scene queries such as line-of-sight checks reuse the collision
+    /// world instead of testing every object by hand.
+    pub fn raycast(&self, world: &World, ray: &Ray, max_distance: f32, mask: u32) -> Option<RayHit> {
+        let mut closest: Option<RayHit> = None;
+        self.spatial_query.query_ray_sorted(
+            ray.origin,
+            ray.direction,
+            max_distance,
+            &|entity| self.matches_mask(entity, mask),
+            &mut |entity, entry| {
+                // Bounds entered beyond the closest hit cannot hold a closer one
+                if closest.is_some_and(|hit| entry > hit.distance) {
+                    return false;
+                }
+                let hit = self.world_shape(world, entity).and_then(|shape| shape.intersect_ray_detailed(ray));
+                if let Some((distance, point, normal)) = hit {
+                    if distance <= max_distance && closest.is_none_or(|hit| distance < hit.distance) {
+                        closest = Some(RayHit { entity, distance, point, normal });
+                    }
+                }
+                true
+            },
+        );
+        closest
+    }
+    
+    /// Cast a ray against all colliders on the layers in `mask`, returning every hit sorted by distance
+    pub fn raycast_all(&self, world: &World, ray: &Ray, max_distance: f32, mask: u32) -> Vec<RayHit> {
+        let mut hits: Vec<RayHit> = self.ray_hits(world, ray, max_distance, mask).collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+    
+    /// Sweep a sphere of `radius` along a ray, returning the first collider it touches
+    /// 
+    /// `distance` is how far the sphere's center travelled; `point` and `normal`
+    /// describe the touched surface. `max_distance` must be finite.
+    pub fn sphere_cast(&self, world: &World, ray: &Ray, radius: f32, max_distance: f32, mask: u32) -> Option<RayHit> {
+        let end = ray.point_at(max_distance);
+        let candidates = self.spatial_query.query_sphere(ray.point_at(max_distance * 0.5), max_distance * 0.5 + radius);
+        
+        candidates
+            .into_iter()
+            .filter(|&entity| self.matches_mask(entity, mask))
+            .filter_map(|entity| {
+                let impact = sweep_sphere(ray.origin, end, radius, &self.world_shape(world, entity)?)?;
+                Some(RayHit {
+                    entity,
+                    distance: impact.time * max_distance,
+                    point: impact.point,
+                    normal: -impact.normal,
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+    
+    /// Up to `k` colliders on the layers in `mask` closest to `point`, nearest first
+    /// 
+    /// Distances are to the colliders' bounding spheres, which is enough for
+    /// picking targets; use `overlap_sphere` or a raycast for exact shapes.
+    pub fn nearest(&self, point: Vec3, k: usize, mask: u32) -> Vec<(Entity, f32)> {
+        self.spatial_query.query_k_nearest(point, k, &|entity| self.matches_mask(entity, mask))
+    }
+    
+    /// Colliders on the layers in `mask` overlapping a sphere
+    pub fn overlap_sphere(&self, world: &World, center: Vec3, radius: f32, mask: u32) -> Vec<Entity> {
+        let query = WorldSpaceShape::Sphere(BoundingSphere::new(center, radius));
+        self.overlapping(world, self.spatial_query.query_sphere(center, radius), &query, mask)
+    }
+    
+    /// Colliders on the layers in `mask` overlapping an axis-aligned box
+    pub fn overlap_aabb(&self, world: &World, aabb: &AABB, mask: u32) -> Vec<Entity> {
+        let query = WorldSpaceShape::Box(OrientedBox::new(aabb.center(), aabb.extents(), Quat::identity()));
+        self.overlapping(world, self.spatial_query.query_aabb(aabb), &query, mask)
+    }
+    
+    /// Ray hits against broad-phase candidates, unsorted
+    fn ray_hits<'a>(&'a self, world: &'a World, ray: &'a Ray, max_distance: f32, mask: u32) -> impl Iterator<Item = RayHit> + 'a {
+        self.spatial_query
+            .query_ray(ray.origin, ray.direction)
+            .into_iter()
+            .filter(move |&entity| self.matches_mask(entity, mask))
+            .filter_map(move |entity| {
+                let (distance, point, normal) = self.world_shape(world, entity)?.intersect_ray_detailed(ray)?;
+                (distance <= max_distance).then_some(RayHit { entity, distance, point, normal })
+            })
+    }
+    
+    /// Candidates whose shape intersects the query shape
+    fn overlapping(&self, world: &World, candidates: Vec<Entity>, query: &WorldSpaceShape, mask: u32) -> Vec<Entity> {
+        candidates
+            .into_iter()
+            .filter(|&entity| self.matches_mask(entity, mask))
+            .filter(|&entity| self.world_shape(world, entity).is_some_and(|shape| query.intersects(&shape)))
             .collect()
     }
     
+    /// Whether an entity's collider is on one of the layers in `mask`
+    fn matches_mask(&self, entity: Entity, mask: u32) -> bool {
+        self.colliders.get(&entity).is_some_and(|collider| collider.layer & mask != 0)
+    }
+    
+    /// An entity's collision shape transformed to world space
+    fn world_shape(&self, world: &World, entity: Entity) -> Option<WorldSpaceShape> {
+        let collider = self.colliders.get(&entity)?;
+        let transform = world.world_transform(entity)?;
+        Some(collider.shape.to_world_space(transform.position, transform.rotation, transform.scale))
+    }
+    
+    /// Get entities that entered collision this frame
+    pub fn get_collision_entered(&self) -> impl Iterator<Item = CollisionPair> + '_ {
+        self.current_pairs.difference(&self.previous_pairs).copied()
+    }
+    
     /// Get entities that exited collision this frame
-    pub fn get_collision_exited(&self) -> Vec<CollisionPair> {
-        self.previous_pairs
-            .difference(&self.current_pairs)
-            .copied()
-            .collect()
+    pub fn get_collision_exited(&self) -> impl Iterator<Item = CollisionPair> + '_ {
+        self.previous_pairs.difference(&self.current_pairs).copied()
     }
     
-    /// Get all current collision pairs
-    pub fn get_current_collisions(&self) -> &HashSet<CollisionPair> {
-        &self.current_pairs
+    /// Get all current collision pairs with their contact manifolds
+    /// (normal from `entity_a` to `entity_b`)
+    pub fn get_current_collisions(&self) -> &HashMap<CollisionPair, ContactManifold> {
+        &self.manifolds
+    }
+    
+    /// Get the contact manifold of a current collision pair
+    pub fn get_manifold(&self, pair: &CollisionPair) -> Option<&ContactManifold> {
+        self.manifolds.get(pair)
+    }
+    
+    /// Get the earliest swept hit of a CCD collider this frame
+    pub fn get_ccd_hit(&self, entity: Entity) -> Option<&CcdHit> {
+        self.ccd_hits.get(&entity)
+    }
+    
+    /// Get all swept hits of CCD colliders this frame
+    pub fn ccd_hits(&self) -> &HashMap<Entity, CcdHit> {
+        &self.ccd_hits
     }
     
     /// Query nearby entities for a specific entity
@@ -273,6 +691,16 @@ impl PhysicsCollisionSystem {
         self.colliders.contains_key(&entity)
     }
     
+    /// Check if continuous collision detection is enabled for an entity's collider
+    pub fn is_ccd(&self, entity: Entity) -> bool {
+        self.colliders.get(&entity).is_some_and(|c| c.ccd)
+    }
+    
+    /// Check if an entity's collider is a trigger (reports overlaps without collision response)
+    pub fn is_trigger(&self, entity: Entity) -> bool {
+        self.colliders.get(&entity).is_some_and(|c| c.is_trigger)
+    }
+    
     /// Get the number of registered colliders
     pub fn collider_count(&self) -> usize {
         self.colliders.len()
@@ -283,7 +711,11 @@ impl PhysicsCollisionSystem {
         self.spatial_query.clear();
         self.colliders.clear();
         self.current_pairs.clear();
+        self.manifolds.clear();
         self.previous_pairs.clear();
+        self.trigger_pairs.clear();
+        self.previous_trigger_pairs.clear();
+        self.ccd_hits.clear();
     }
 }
 
@@ -292,8 +724,7 @@ mod tests {
     use super::*;
     use crate::spatial::{Octree, OctreeConfig, OctreeSpatialQuery};
     use crate::scene::AABB;
-    use crate::physics::collision::BoundingSphere;
-    use crate::foundation::math::Vec3;
+    use crate::ecs::components::TransformComponent;
     
     fn create_test_system() -> PhysicsCollisionSystem {
         let bounds = AABB::new(
@@ -311,22 +742,23 @@ mod tests {
         PhysicsCollisionSystem::new(spatial)
     }
     
+    fn spawn_at(world: &mut World, position: Vec3) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(position));
+        entity
+    }
+    
     #[test]
     fn test_collision_detection() {
         let mut system = create_test_system();
         
         // Create two overlapping spheres
-        let entity_a = Entity::new(1, 0);
-        let entity_b = Entity::new(2, 0);
+        let mut world = World::new();
+        let entity_a = spawn_at(&mut world, Vec3::new(0.0, 0.0, 0.0));
+        let entity_b = spawn_at(&mut world, Vec3::new(8.0, 0.0, 0.0));
         
-        let shape_a = CollisionShape::Sphere(BoundingSphere::new(
-            Vec3::new(0.0, 0.0, 0.0),
-            5.0,
-        ));
-        let shape_b = CollisionShape::Sphere(BoundingSphere::new(
-            Vec3::new(8.0, 0.0, 0.0),
-            5.0,
-        ));
+        let shape_a = CollisionShape::sphere(5.0);
+        let shape_b = CollisionShape::sphere(5.0);
         
         system.register_collider(
             entity_a,
@@ -335,6 +767,7 @@ mod tests {
             CollisionLayers::ALL,
             false,
             5.0,
+            Vec3::new(0.0, 0.0, 0.0),
         );
         
         system.register_collider(
@@ -344,10 +777,11 @@ mod tests {
             CollisionLayers::ALL,
             false,
             5.0,
+            Vec3::new(8.0, 0.0, 0.0),
         );
         
         // Detect collisions
-        let collisions = system.detect_collisions();
+        let collisions = system.detect_collisions(&world);
         
         // Should detect collision
         assert_eq!(collisions.len(), 1);
@@ -363,17 +797,12 @@ mod tests {
         let mut system = create_test_system();
         
         // Create two overlapping spheres on different layers
-        let entity_a = Entity::new(1, 0);
-        let entity_b = Entity::new(2, 0);
+        let mut world = World::new();
+        let entity_a = spawn_at(&mut world, Vec3::new(0.0, 0.0, 0.0));
+        let entity_b = spawn_at(&mut world, Vec3::new(8.0, 0.0, 0.0));
         
-        let shape_a = CollisionShape::Sphere(BoundingSphere::new(
-            Vec3::new(0.0, 0.0, 0.0),
-            5.0,
-        ));
-        let shape_b = CollisionShape::Sphere(BoundingSphere::new(
-            Vec3::new(8.0, 0.0, 0.0),
-            5.0,
-        ));
+        let shape_a = CollisionShape::sphere(5.0);
+        let shape_b = CollisionShape::sphere(5.0);
         
         // Entity A is on PLAYER layer, only collides with ENEMY
         system.register_collider(
@@ -383,6 +812,7 @@ mod tests {
             CollisionLayers::ENEMY,
             false,
             5.0,
+            Vec3::new(0.0, 0.0, 0.0),
         );
         
         // Entity B is on ENVIRONMENT layer (not ENEMY)
@@ -393,12 +823,168 @@ mod tests {
             CollisionLayers::ALL,
             false,
             5.0,
+            Vec3::new(8.0, 0.0, 0.0),
         );
         
         // Detect collisions
-        let collisions = system.detect_collisions();
+        let collisions = system.detect_collisions(&world);
         
         // Should NOT detect collision due to layer filtering
         assert_eq!(collisions.len(), 0);
     }
+    
+    #[test]
+    fn test_collision_matrix_resource_filters_pairs() {
+        let mut system = create_test_system();
+        let mut world = World::new();
+        let player = spawn_at(&mut world, Vec3::zeros());
+        let pickup = spawn_at(&mut world, Vec3::new(1.0, 0.0, 0.0));
+        for (entity, layer) in [(player, CollisionLayers::PLAYER), (pickup, CollisionLayers::PICKUP)] {
+            let position = world.get_component::<TransformComponent>(entity).unwrap().position;
+            system.register_collider(entity, CollisionShape::sphere(1.0), layer, CollisionLayers::ALL, false, 1.0, position);
+        }
+        
+        // Masks alone allow the pair
+        assert_eq!(system.detect_collisions(&world).len(), 1);
+        
+        let mut matrix = CollisionMatrix::default();
+        matrix.set_collides("player", "pickup", false).unwrap();
+        world.insert_resource(matrix);
+        assert!(system.detect_collisions(&world).is_empty());
+    }
+    
+    #[test]
+    fn test_enter_and_exit_events_split_solid_and_trigger_pairs() {
+        use crate::ecs::EventReader;
+        
+        let mut system = create_test_system();
+        let mut world = World::new();
+        let a = spawn_at(&mut world, Vec3::zeros());
+        let b = spawn_at(&mut world, Vec3::new(1.5, 0.0, 0.0));
+        let trigger = spawn_at(&mut world, Vec3::new(20.0, 0.0, 0.0));
+        let visitor = spawn_at(&mut world, Vec3::new(21.0, 0.0, 0.0));
+        for entity in [a, b, trigger, visitor] {
+            let position = world.get_component::<TransformComponent>(entity).unwrap().position;
+            system.register_collider(entity, CollisionShape::sphere(1.0), CollisionLayers::ALL, CollisionLayers::ALL, entity == trigger, 1.0, position);
+        }
+        
+        let mut started = EventReader::<CollisionStarted>::new();
+        let mut ended = EventReader::<CollisionEnded>::new();
+        let mut entered = EventReader::<TriggerEntered>::new();
+        let mut exited = EventReader::<TriggerExited>::new();
+        
+        system.detect_collisions(&world);
+        system.send_events(&mut world);
+        
+        let events: Vec<_> = started.read(world.resource::<Events<CollisionStarted>>().unwrap()).cloned().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].other(a), Some(b));
+        assert!((events[0].manifold.max_depth() - 0.5).abs() < 1e-4);
+        
+        let events: Vec<_> = entered.read(world.resource::<Events<TriggerEntered>>().unwrap()).cloned().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].trigger, events[0].other), (trigger, visitor));
+        assert!(events[0].manifold.normal.x > 0.99, "normal should point from the trigger to the visitor");
+        
+        // Staying in contact sends nothing new
+        system.detect_collisions(&world);
+        system.send_events(&mut world);
+        assert_eq!(started.read(world.resource::<Events<CollisionStarted>>().unwrap()).count(), 0);
+        
+        // Separating and removing the trigger both report exits
+        world.get_component_mut::<TransformComponent>(b).unwrap().position.x = 5.0;
+        system.update_collider_position(b, Vec3::new(5.0, 0.0, 0.0), 1.0);
+        system.unregister_collider(trigger);
+        system.detect_collisions(&world);
+        system.send_events(&mut world);
+        
+        let events: Vec<_> = ended.read(world.resource::<Events<CollisionEnded>>().unwrap()).copied().collect();
+        assert_eq!(events, vec![CollisionEnded { entity_a: a, entity_b: b }]);
+        let events: Vec<_> = exited.read(world.resource::<Events<TriggerExited>>().unwrap()).copied().collect();
+        assert_eq!(events, vec![TriggerExited { trigger, other: visitor }]);
+    }
+    
+    #[test]
+    fn test_sleeping_pairs_keep_contacts_without_retesting() {
+        let mut system = create_test_system();
+        let mut world = World::new();
+        let a = spawn_at(&mut world, Vec3::zeros());
+        let b = spawn_at(&mut world, Vec3::new(1.5, 0.0, 0.0));
+        for entity in [a, b] {
+            let position = world.get_component::<TransformComponent>(entity).unwrap().position;
+            system.register_collider(entity, CollisionShape::sphere(1.0), CollisionLayers::ALL, CollisionLayers::ALL, false, 1.0, position);
+            world.add_component(entity, RigidBodyComponent::dynamic(1.0));
+        }
+        assert_eq!(system.detect_collisions(&world).len(), 1);
+        
+        // Both asleep: the pair is kept even though b has (illegally) moved away
+        for entity in [a, b] {
+            world.get_component_mut::<RigidBodyComponent>(entity).unwrap().set_sleep_state(true, 0.0);
+        }
+        world.get_component_mut::<TransformComponent>(b).unwrap().position.x = 10.0;
+        assert_eq!(system.detect_collisions(&world).len(), 1);
+        assert_eq!(system.get_collision_exited().count(), 0);
+        
+        // Once one of them wakes up the pair is tested again
+        world.get_component_mut::<RigidBodyComponent>(b).unwrap().wake_up();
+        assert!(system.detect_collisions(&world).is_empty());
+    }
+    
+    #[test]
+    fn test_scene_queries_respect_layer_mask() {
+        let mut system = create_test_system();
+        let mut world = World::new();
+        
+        // An enemy behind a wall along +X, plus an environment sphere off to the side
+        let wall = spawn_at(&mut world, Vec3::new(5.0, 0.0, 0.0));
+        let enemy = spawn_at(&mut world, Vec3::new(10.0, 0.0, 0.0));
+        let rock = spawn_at(&mut world, Vec3::new(0.0, 6.0, 0.0));
+        system.register_collider(wall, CollisionShape::cuboid(Vec3::new(0.5, 2.0, 2.0)),
+            CollisionLayers::ENVIRONMENT, CollisionLayers::ALL, false, 3.0, Vec3::new(5.0, 0.0, 0.0));
+        system.register_collider(enemy, CollisionShape::sphere(1.0),
+            CollisionLayers::ENEMY, CollisionLayers::ALL, false, 1.0, Vec3::new(10.0, 0.0, 0.0));
+        system.register_collider(rock, CollisionShape::sphere(1.0),
+            CollisionLayers::ENVIRONMENT, CollisionLayers::ALL, false, 1.0, Vec3::new(0.0, 6.0, 0.0));
+        
+        let ray = Ray::new(Vec3::zeros(), Vec3::new(1.0, 0.0, 0.0));
+        
+        // Line of sight is blocked by the wall...
+        let hit = system.raycast(&world, &ray, 100.0, CollisionLayers::ALL).unwrap();
+        assert_eq!(hit.entity, wall);
+        assert!((hit.distance - 4.5).abs() < 1e-4);
+        assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).magnitude() < 1e-4);
+        
+        // ...unless only enemies are queried
+        let hit = system.raycast(&world, &ray, 100.0, CollisionLayers::ENEMY).unwrap();
+        assert_eq!(hit.entity, enemy);
+        assert!((hit.point - Vec3::new(9.0, 0.0, 0.0)).magnitude() < 1e-4);
+        assert!(system.raycast(&world, &ray, 8.0, CollisionLayers::ENEMY).is_none());
+        
+        let all: Vec<Entity> = system.raycast_all(&world, &ray, 100.0, CollisionLayers::ALL)
+            .into_iter()
+            .map(|hit| hit.entity)
+            .collect();
+        assert_eq!(all, vec![wall, enemy]);
+        
+        // A sphere cast above the wall clips the rock's underside first
+        let high = Ray::new(Vec3::new(-3.0, 4.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit = system.sphere_cast(&world, &high, 0.5, 20.0, CollisionLayers::ALL).unwrap();
+        assert_eq!(hit.entity, rock);
+        assert!(hit.normal.y < 0.0);
+        
+        let mut near_origin = system.overlap_sphere(&world, Vec3::new(0.0, 4.0, 0.0), 1.5, CollisionLayers::ALL);
+        near_origin.sort_by_key(|entity| entity.id());
+        assert_eq!(near_origin, vec![rock]);
+        
+        let boxed = system.overlap_aabb(&world, &AABB::new(Vec3::new(4.0, -1.0, -1.0), Vec3::new(11.0, 1.0, 1.0)), CollisionLayers::ENEMY);
+        assert_eq!(boxed, vec![enemy]);
+
+        // Nearest colliders by bounding sphere, skipping other layers
+        let nearest: Vec<Entity> = system.nearest(Vec3::zeros(), 2, CollisionLayers::ENVIRONMENT)
+            .into_iter()
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(nearest, vec![wall, rock]);
+        assert_eq!(system.nearest(Vec3::zeros(), 5, CollisionLayers::ENEMY), vec![(enemy, 9.0)]);
+    }
 }
@@ -0,0 +1,799 @@
+//! Rigid body dynamics and impulse-based collision response
+//!
+//! Based on Game Engine Architecture 3rd Edition, Section 13.4: each step the
+//! physics system integrates forces into velocities, resolves the contacts found
+//! by the collision system with sequential impulses (normal, friction and
+//! restitution), then integrates velocities into positions. Remaining overlap is
+//! removed with a small positional correction so resting bodies don't sink.
+//!
+//! Colliders without a `RigidBodyComponent` take part as static geometry, and
+//! trigger colliders never produce a collision response. Joints
+//! (`JointComponent`) are solved in the same loop as the contacts.
+//!
+//! Dynamic bodies connected by contacts or joints form islands. An island
+//! whose bodies have all rested below the sleep thresholds for
+//! `time_to_sleep` falls asleep: its bodies are treated as static until an
+//! awake body (or a moving kinematic one) touches the island, which wakes it
+//! as a group.
+
+use crate::ecs::{Entity, World};
+use crate::ecs::components::{JointComponent, RigidBodyComponent, RigidBodyType, TransformComponent};
+use crate::foundation::math::{Mat3, Quat, Vec3};
+use crate::physics::collision_system::{CollisionPair, PhysicsCollisionSystem};
+use crate::physics::joints::{self, JointRow, SolverJoint};
+use std::collections::{HashMap, HashSet};
+
+/// Physics solver settings
+#[derive(Debug, Clone)]
+pub struct PhysicsConfig {
+    /// Gravity acceleration applied to dynamic bodies
+    pub gravity: Vec3,
+    /// Sequential impulse iterations per step
+    pub solver_iterations: u32,
+    /// Closing speeds below this don't bounce (avoids jitter when resting)
+    pub restitution_threshold: f32,
+    /// Overlap allowed before positional correction kicks in
+    pub penetration_slop: f32,
+    /// Fraction of the overlap removed per step
+    pub position_correction: f32,
+    /// Largest positional correction per contact pair and step
+    pub max_position_correction: f32,
+    /// Fraction of joint drift removed per step
+    pub joint_correction: f32,
+    /// Linear speed below which a body counts as resting
+    pub sleep_linear_velocity: f32,
+    /// Angular speed below which a body counts as resting
+    pub sleep_angular_velocity: f32,
+    /// Seconds an island must rest before it falls asleep
+    pub time_to_sleep: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            gravity: Vec3::zeros(), // Space: no gravity by default
+            solver_iterations: 8,
+            restitution_threshold: 0.5,
+            penetration_slop: 0.01,
+            position_correction: 0.4,
+            max_position_correction: 0.2,
+            joint_correction: 0.2,
+            sleep_linear_velocity: 0.05,
+            sleep_angular_velocity: 0.05,
+            time_to_sleep: 0.5,
+        }
+    }
+}
+
+/// Statistics from the last physics step
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhysicsStats {
+    /// Rigid bodies simulated
+    pub bodies: usize,
+    /// Contact points resolved by the solver
+    pub contacts: usize,
+    /// Joints solved
+    pub joints: usize,
+    /// Dynamic bodies simulated this step
+    pub awake: usize,
+    /// Dynamic bodies asleep after this step
+    pub sleeping: usize,
+    /// Islands of awake dynamic bodies
+    pub islands: usize,
+}
+
+/// Solver copy of a rigid body
+pub(super) struct BodyState {
+    entity: Entity,
+    pub(super) position: Vec3,
+    pub(super) rotation: Quat,
+    pub(super) linear_velocity: Vec3,
+    pub(super) angular_velocity: Vec3,
+    pub(super) inverse_mass: f32,
+    pub(super) inverse_inertia: Mat3, // World space
+    restitution: f32,
+    friction: f32,
+    moves: bool,
+    dynamic: bool,
+    can_sleep: bool,
+    sleeping: bool,
+    sleep_timer: f32,
+}
+
+/// Contact constraint between two bodies (None = static collider)
+struct ContactConstraint {
+    body_a: Option<usize>,
+    body_b: Option<usize>,
+    normal: Vec3,         // From a to b
+    tangents: [Vec3; 2],
+    offset_a: Vec3,       // Contact point relative to body a
+    offset_b: Vec3,
+    depth: f32,
+    correction_share: f32, // 1 / points in the manifold, so it is corrected once
+    normal_mass: f32,
+    tangent_mass: [f32; 2],
+    velocity_bias: f32,   // Target separating speed (restitution)
+    friction: f32,
+    normal_impulse: f32,
+    tangent_impulse: [f32; 2],
+}
+
+/// Rigid body simulation step (GEA 13.4)
+///
+/// Run it after `PhysicsCollisionSystem::detect_collisions` (or
+/// `EcsCollisionSystem::update`) so the contacts match the current positions.
+pub struct PhysicsSystem {
+    config: PhysicsConfig,
+    stats: PhysicsStats,
+}
+
+impl PhysicsSystem {
+    /// Create a physics system with default settings
+    pub fn new() -> Self {
+        Self::with_config(PhysicsConfig::default())
+    }
+
+    /// Create a physics system with custom settings
+    pub fn with_config(config: PhysicsConfig) -> Self {
+        Self { config, stats: PhysicsStats::default() }
+    }
+
+    /// Solver settings
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    /// Mutable solver settings
+    pub fn config_mut(&mut self) -> &mut PhysicsConfig {
+        &mut self.config
+    }
+
+    /// Statistics from the last step
+    pub fn stats(&self) -> PhysicsStats {
+        self.stats
+    }
+
+    /// Advance all rigid bodies by `delta_time`, resolving the collision system's contacts
+    pub fn step(&mut self, world: &mut World, collisions: &PhysicsCollisionSystem, delta_time: f32) {
+        if delta_time <= 0.0 {
+            return;
+        }
+
+        self.integrate_forces(world, delta_time);
+
+        let mut bodies = self.gather_bodies(world);
+        let index: HashMap<Entity, usize> = bodies.iter().enumerate().map(|(i, body)| (body.entity, i)).collect();
+        let solver_joints = Self::gather_joints(world, &bodies, &index);
+        let islands = Self::build_islands(&bodies, &index, collisions, &solver_joints);
+        Self::wake_islands(&mut bodies, &islands, &index, collisions);
+        let connected: HashSet<CollisionPair> = world.query::<&JointComponent>()
+            .into_iter()
+            .filter(|(_, joint)| !joint.collide_connected)
+            .filter_map(|(_, joint)| joint.body_b.map(|body_b| CollisionPair::new(joint.body_a, body_b)))
+            .collect();
+        let mut constraints = self.build_constraints(&bodies, &index, collisions, &connected);
+
+        let correction_rate = self.config.joint_correction / delta_time;
+        let mut rows: Vec<JointRow> = solver_joints.iter()
+            .flat_map(|joint| joints::build_rows(joint, &mut bodies, correction_rate, delta_time))
+            .collect();
+
+        for _ in 0..self.config.solver_iterations {
+            for row in &mut rows {
+                row.solve(&mut bodies);
+            }
+            for constraint in &mut constraints {
+                Self::solve_constraint(constraint, &mut bodies);
+            }
+        }
+
+        for body in bodies.iter_mut().filter(|body| body.moves) {
+            body.position += body.linear_velocity * delta_time;
+            body.rotation = Quat::from_scaled_axis(body.angular_velocity * delta_time) * body.rotation;
+        }
+        self.correct_positions(&constraints, &mut bodies);
+
+        let awake = bodies.iter().filter(|body| body.dynamic && !body.sleeping).count();
+        let awake_islands: HashSet<usize> = bodies.iter().enumerate()
+            .filter(|(_, body)| body.dynamic && !body.sleeping)
+            .map(|(i, _)| islands[i])
+            .collect();
+        self.update_sleep(&mut bodies, &islands, &solver_joints, delta_time);
+
+        self.stats = PhysicsStats {
+            bodies: bodies.len(),
+            contacts: constraints.len(),
+            joints: solver_joints.len(),
+            awake,
+            sleeping: bodies.iter().filter(|body| body.dynamic && body.sleeping).count(),
+            islands: awake_islands.len(),
+        };
+        Self::write_back(world, &bodies);
+    }
+
+    /// Apply gravity, accumulated forces and damping to dynamic body velocities
+    fn integrate_forces(&self, world: &mut World, delta_time: f32) {
+        for (_, (transform, body)) in world.query_mut::<(&TransformComponent, &mut RigidBodyComponent)>() {
+            let body = body.into_inner();
+            if !body.is_dynamic() || body.is_sleeping() {
+                body.clear_forces();
+                continue;
+            }
+
+            let (force, torque) = body.accumulated_force();
+            let inverse_inertia = body.world_inverse_inertia(&transform.rotation);
+            body.linear_velocity += (self.config.gravity * body.gravity_scale + force * body.inverse_mass()) * delta_time;
+            body.angular_velocity += inverse_inertia * torque * delta_time;
+            body.linear_velocity *= (1.0 - body.linear_damping * delta_time).max(0.0);
+            body.angular_velocity *= (1.0 - body.angular_damping * delta_time).max(0.0);
+            body.clear_forces();
+        }
+    }
+
+    fn gather_bodies(&self, world: &World) -> Vec<BodyState> {
+        world.query::<(&TransformComponent, &RigidBodyComponent)>()
+            .into_iter()
+            .map(|(entity, (transform, body))| BodyState {
+                entity,
+                position: transform.position,
+                rotation: transform.rotation,
+                linear_velocity: body.linear_velocity,
+                angular_velocity: body.angular_velocity,
+                inverse_mass: body.inverse_mass(),
+                inverse_inertia: body.world_inverse_inertia(&transform.rotation),
+                restitution: body.restitution,
+                friction: body.friction,
+                moves: body.body_type != RigidBodyType::Static,
+                dynamic: body.is_dynamic(),
+                can_sleep: body.can_sleep,
+                // Setting a sleeping body's velocity directly wakes it
+                sleeping: body.is_sleeping() && self.is_resting(body.linear_velocity, body.angular_velocity),
+                sleep_timer: body.sleep_timer(),
+            })
+            .collect()
+    }
+
+    fn is_resting(&self, linear_velocity: Vec3, angular_velocity: Vec3) -> bool {
+        linear_velocity.magnitude() < self.config.sleep_linear_velocity
+            && angular_velocity.magnitude() < self.config.sleep_angular_velocity
+    }
+
+    /// Island of each body: dynamic bodies linked by contacts or joints share one
+    fn build_islands(
+        bodies: &[BodyState],
+        index: &HashMap<Entity, usize>,
+        collisions: &PhysicsCollisionSystem,
+        joints: &[SolverJoint],
+    ) -> Vec<usize> {
+        let mut parent: Vec<usize> = (0..bodies.len()).collect();
+        let mut link = |a: usize, b: usize| {
+            if bodies[a].dynamic && bodies[b].dynamic {
+                let (root_a, root_b) = (find_root(&mut parent, a), find_root(&mut parent, b));
+                parent[root_a] = root_b;
+            }
+        };
+
+        for pair in collisions.get_current_collisions().keys() {
+            if collisions.is_trigger(pair.entity_a) || collisions.is_trigger(pair.entity_b) {
+                continue;
+            }
+            if let (Some(&a), Some(&b)) = (index.get(&pair.entity_a), index.get(&pair.entity_b)) {
+                link(a, b);
+            }
+        }
+        for joint in joints {
+            if let Some(body_b) = joint.body_b {
+                link(joint.body_a, body_b);
+            }
+        }
+
+        (0..bodies.len()).map(|i| find_root(&mut parent, i)).collect()
+    }
+
+    /// Wake every island with an awake body or touched by a moving kinematic body,
+    /// then freeze the bodies that stay asleep
+    fn wake_islands(bodies: &mut [BodyState], islands: &[usize], index: &HashMap<Entity, usize>, collisions: &PhysicsCollisionSystem) {
+        let mut awake: HashSet<usize> = bodies.iter().enumerate()
+            .filter(|(_, body)| body.dynamic && !body.sleeping)
+            .map(|(i, _)| islands[i])
+            .collect();
+
+        for pair in collisions.get_current_collisions().keys() {
+            if let (Some(&a), Some(&b)) = (index.get(&pair.entity_a), index.get(&pair.entity_b)) {
+                for (body, other) in [(a, b), (b, a)] {
+                    let other_state = &bodies[other];
+                    let kinematic_moving = other_state.moves && !other_state.dynamic
+                        && (other_state.linear_velocity != Vec3::zeros() || other_state.angular_velocity != Vec3::zeros());
+                    if bodies[body].dynamic && kinematic_moving {
+                        awake.insert(islands[body]);
+                    }
+                }
+            }
+        }
+
+        for (i, body) in bodies.iter_mut().enumerate().filter(|(_, body)| body.dynamic && body.sleeping) {
+            if awake.contains(&islands[i]) {
+                body.sleeping = false;
+                body.sleep_timer = 0.0;
+            } else {
+                body.inverse_mass = 0.0;
+                body.inverse_inertia = Mat3::zeros();
+                body.linear_velocity = Vec3::zeros();
+                body.angular_velocity = Vec3::zeros();
+                body.moves = false;
+            }
+        }
+    }
+
+    /// Advance rest timers and put islands that have all rested long enough to sleep
+    fn update_sleep(&self, bodies: &mut [BodyState], islands: &[usize], joints: &[SolverJoint], delta_time: f32) {
+        let mut restless: HashSet<usize> = HashSet::new();
+        for (i, body) in bodies.iter_mut().enumerate().filter(|(_, body)| body.dynamic && !body.sleeping) {
+            let resting = body.can_sleep && self.is_resting(body.linear_velocity, body.angular_velocity);
+            body.sleep_timer = if resting { body.sleep_timer + delta_time } else { 0.0 };
+            if body.sleep_timer < self.config.time_to_sleep {
+                restless.insert(islands[i]);
+            }
+        }
+        // Motors keep driving their bodies, so they keep the island awake
+        for joint in joints.iter().filter(|joint| joint.motor.is_some()) {
+            restless.insert(islands[joint.body_a]);
+            restless.extend(joint.body_b.map(|body_b| islands[body_b]));
+        }
+
+        for (i, body) in bodies.iter_mut().enumerate().filter(|(_, body)| body.dynamic && !body.sleeping) {
+            if !restless.contains(&islands[i]) {
+                body.sleeping = true;
+                body.linear_velocity = Vec3::zeros();
+                body.angular_velocity = Vec3::zeros();
+            }
+        }
+    }
+
+    /// Collect joints whose bodies are simulated, capturing new joints' local frames
+    fn gather_joints(world: &mut World, bodies: &[BodyState], index: &HashMap<Entity, usize>) -> Vec<SolverJoint> {
+        let mut joints = Vec::new();
+        for (_, mut joint) in world.query_mut::<&mut JointComponent>() {
+            let Some(&body_a) = index.get(&joint.body_a) else { continue };
+            let body_b = match joint.body_b {
+                Some(entity) => match index.get(&entity) {
+                    Some(&body_b) => Some(body_b),
+                    None => continue,
+                },
+                None => None,
+            };
+            let frames = match joint.frames {
+                Some(frames) => frames,
+                None => {
+                    let frames = joints::capture_frames(&joint, bodies, body_a, body_b);
+                    joint.frames = Some(frames);
+                    frames
+                }
+            };
+            joints.push(SolverJoint::new(&joint, body_a, body_b, frames));
+        }
+        joints
+    }
+
+    fn build_constraints(
+        &self,
+        bodies: &[BodyState],
+        index: &HashMap<Entity, usize>,
+        collisions: &PhysicsCollisionSystem,
+        connected: &HashSet<CollisionPair>,
+    ) -> Vec<ContactConstraint> {
+        // Solve in a stable order so identical scenes give identical results
+        let mut manifolds: Vec<_> = collisions.get_current_collisions().iter().collect();
+        manifolds.sort_by_key(|(pair, _)| (pair.entity_a.id(), pair.entity_b.id()));
+
+        let mut constraints = Vec::new();
+        for (pair, manifold) in manifolds {
+            if collisions.is_trigger(pair.entity_a) || collisions.is_trigger(pair.entity_b) || connected.contains(pair) {
+                continue;
+            }
+            let body_a = index.get(&pair.entity_a).copied();
+            let body_b = index.get(&pair.entity_b).copied();
+            let dynamic = |body: Option<usize>| body.is_some_and(|i| bodies[i].inverse_mass > 0.0);
+            if !dynamic(body_a) && !dynamic(body_b) {
+                continue;
+            }
+
+            // A static collider without a body takes the other body's material
+            let (restitution, friction) = match (body_a, body_b) {
+                (Some(a), Some(b)) => (
+                    bodies[a].restitution.max(bodies[b].restitution),
+                    (bodies[a].friction * bodies[b].friction).sqrt(),
+                ),
+                (Some(i), None) | (None, Some(i)) => (bodies[i].restitution, bodies[i].friction),
+                (None, None) => unreachable!("at least one body is dynamic"),
+            };
+
+            let normal = manifold.normal;
+            let tangents = tangent_basis(normal);
+            let correction_share = 1.0 / manifold.len() as f32;
+
+            for contact in manifold.contacts() {
+                let offset_a = body_a.map_or(Vec3::zeros(), |a| contact.point - bodies[a].position);
+                let offset_b = body_b.map_or(Vec3::zeros(), |b| contact.point - bodies[b].position);
+
+                let mut constraint = ContactConstraint {
+                    body_a,
+                    body_b,
+                    normal,
+                    tangents,
+                    offset_a,
+                    offset_b,
+                    depth: contact.depth,
+                    correction_share,
+                    normal_mass: 0.0,
+                    tangent_mass: [0.0; 2],
+                    velocity_bias: 0.0,
+                    friction,
+                    normal_impulse: 0.0,
+                    tangent_impulse: [0.0; 2],
+                };
+                constraint.normal_mass = effective_mass(&constraint, bodies, normal);
+                constraint.tangent_mass = tangents.map(|tangent| effective_mass(&constraint, bodies, tangent));
+
+                // Bounce only when closing fast enough
+                let closing_speed = relative_velocity(&constraint, bodies).dot(&normal);
+                if closing_speed < -self.config.restitution_threshold {
+                    constraint.velocity_bias = -restitution * closing_speed;
+                }
+                constraints.push(constraint);
+            }
+        }
+        constraints
+    }
+
+    /// One sequential impulse pass over a contact: friction, then the normal impulse
+    fn solve_constraint(constraint: &mut ContactConstraint, bodies: &mut [BodyState]) {
+        for axis in 0..2 {
+            let tangent = constraint.tangents[axis];
+            let speed = relative_velocity(constraint, bodies).dot(&tangent);
+            let max_friction = constraint.friction * constraint.normal_impulse;
+            let accumulated = (constraint.tangent_impulse[axis] - speed * constraint.tangent_mass[axis])
+                .clamp(-max_friction, max_friction);
+            let impulse = accumulated - constraint.tangent_impulse[axis];
+            constraint.tangent_impulse[axis] = accumulated;
+            apply_impulse(constraint, bodies, tangent * impulse);
+        }
+
+        let speed = relative_velocity(constraint, bodies).dot(&constraint.normal);
+        let accumulated = (constraint.normal_impulse + (constraint.velocity_bias - speed) * constraint.normal_mass).max(0.0);
+        let impulse = accumulated - constraint.normal_impulse;
+        constraint.normal_impulse = accumulated;
+        apply_impulse(constraint, bodies, constraint.normal * impulse);
+    }
+
+    /// Push overlapping bodies apart along the contact normal, weighted by inverse mass
+    fn correct_positions(&self, constraints: &[ContactConstraint], bodies: &mut [BodyState]) {
+        for constraint in constraints {
+            let inverse_mass_a = constraint.body_a.map_or(0.0, |a| bodies[a].inverse_mass);
+            let inverse_mass_b = constraint.body_b.map_or(0.0, |b| bodies[b].inverse_mass);
+            let total = inverse_mass_a + inverse_mass_b;
+            let correction = ((constraint.depth - self.config.penetration_slop).max(0.0) * self.config.position_correction)
+                .min(self.config.max_position_correction) * constraint.correction_share;
+            if total <= 0.0 || correction <= 0.0 {
+                continue;
+            }
+
+            let push = constraint.normal * (correction / total);
+            if let Some(a) = constraint.body_a {
+                bodies[a].position -= push * inverse_mass_a;
+            }
+            if let Some(b) = constraint.body_b {
+                bodies[b].position += push * inverse_mass_b;
+            }
+        }
+    }
+
+    /// Copy simulated state back to the components
+    ///
+    /// Sleeping bodies' transforms are left untouched (bodies falling asleep
+    /// drop their last sub-threshold motion), so they don't show up as changed
+    /// and the collision system doesn't re-sync them.
+    fn write_back(world: &mut World, bodies: &[BodyState]) {
+        for state in bodies.iter().filter(|state| state.moves) {
+            if let Some(body) = world.get_component_mut::<RigidBodyComponent>(state.entity) {
+                body.linear_velocity = state.linear_velocity;
+                body.angular_velocity = state.angular_velocity;
+                body.set_sleep_state(state.sleeping, state.sleep_timer);
+            }
+            if state.sleeping {
+                continue;
+            }
+            if let Some(transform) = world.get_component_mut::<TransformComponent>(state.entity) {
+                transform.position = state.position;
+                transform.rotation = state.rotation;
+            }
+        }
+    }
+}
+
+impl Default for PhysicsSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Root of `i`'s set in a union-find forest (with path halving)
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Two unit vectors perpendicular to `normal` and each other
+pub(super) fn tangent_basis(normal: Vec3) -> [Vec3; 2] {
+    let reference = if normal.x.abs() < 0.9 { Vec3::x() } else { Vec3::y() };
+    let first = normal.cross(&reference).normalize();
+    [first, normal.cross(&first)]
+}
+
+/// Velocity of body b relative to body a at the contact point
+fn relative_velocity(constraint: &ContactConstraint, bodies: &[BodyState]) -> Vec3 {
+    let point_velocity = |body: Option<usize>, offset: Vec3| {
+        body.map_or(Vec3::zeros(), |i| bodies[i].linear_velocity + bodies[i].angular_velocity.cross(&offset))
+    };
+    point_velocity(constraint.body_b, constraint.offset_b) - point_velocity(constraint.body_a, constraint.offset_a)
+}
+
+/// Inverse of the impulse needed to change the relative velocity along `direction` by 1
+fn effective_mass(constraint: &ContactConstraint, bodies: &[BodyState], direction: Vec3) -> f32 {
+    let term = |body: Option<usize>, offset: Vec3| {
+        body.map_or(0.0, |i| {
+            let body = &bodies[i];
+            let angular = (body.inverse_inertia * offset.cross(&direction)).cross(&offset);
+            body.inverse_mass + angular.dot(&direction)
+        })
+    };
+    let k = term(constraint.body_a, constraint.offset_a) + term(constraint.body_b, constraint.offset_b);
+    if k > 0.0 { 1.0 / k } else { 0.0 }
+}
+
+/// Apply `impulse` to body b and its opposite to body a
+fn apply_impulse(constraint: &ContactConstraint, bodies: &mut [BodyState], impulse: Vec3) {
+    if let Some(a) = constraint.body_a {
+        let body = &mut bodies[a];
+        body.linear_velocity -= impulse * body.inverse_mass;
+        body.angular_velocity -= body.inverse_inertia * constraint.offset_a.cross(&impulse);
+    }
+    if let Some(b) = constraint.body_b {
+        let body = &mut bodies[b];
+        body.linear_velocity += impulse * body.inverse_mass;
+        body.angular_velocity += body.inverse_inertia * constraint.offset_b.cross(&impulse);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::ColliderComponent;
+    use crate::physics::collision::CollisionShape;
+    use crate::physics::CollisionLayers;
+    use crate::scene::AABB;
+    use crate::spatial::{Octree, OctreeConfig, OctreeSpatialQuery};
+
+    fn collision_system() -> PhysicsCollisionSystem {
+        let bounds = AABB::new(Vec3::new(-100.0, -100.0, -100.0), Vec3::new(100.0, 100.0, 100.0));
+        let config = OctreeConfig { max_entities_per_node: 4, max_depth: 5, min_node_size: 5.0 };
+        PhysicsCollisionSystem::new(Box::new(OctreeSpatialQuery::new(Octree::new(bounds, config))))
+    }
+
+    fn spawn_sphere(world: &mut World, collisions: &mut PhysicsCollisionSystem, position: Vec3, body: Option<RigidBodyComponent>) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(position));
+        let collider = ColliderComponent::new(CollisionShape::sphere(1.0));
+        collisions.register_collider(entity, collider.shape.clone(), CollisionLayers::ALL, CollisionLayers::ALL, false, 1.0, position);
+        world.add_component(entity, collider);
+        if let Some(body) = body {
+            world.add_component(entity, body);
+        }
+        entity
+    }
+
+    fn run(world: &mut World, collisions: &mut PhysicsCollisionSystem, physics: &mut PhysicsSystem, steps: usize) {
+        for _ in 0..steps {
+            let positions: Vec<_> = world.query::<&TransformComponent>().into_iter().map(|(e, t)| (e, t.position)).collect();
+            for (entity, position) in positions {
+                collisions.update_collider_position(entity, position, 1.0);
+            }
+            collisions.detect_collisions(world);
+            physics.step(world, collisions, 1.0 / 60.0);
+        }
+    }
+
+    #[test]
+    fn test_equal_mass_elastic_collision_swaps_velocities() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::new();
+        let a = spawn_sphere(&mut world, &mut collisions, Vec3::new(-2.0, 0.0, 0.0),
+            Some(RigidBodyComponent::dynamic(1.0).with_restitution(1.0).with_velocity(Vec3::new(5.0, 0.0, 0.0))));
+        let b = spawn_sphere(&mut world, &mut collisions, Vec3::new(2.0, 0.0, 0.0),
+            Some(RigidBodyComponent::dynamic(1.0).with_restitution(1.0).with_velocity(Vec3::new(-5.0, 0.0, 0.0))));
+
+        run(&mut world, &mut collisions, &mut physics, 60);
+
+        let velocity_a = world.get_component::<RigidBodyComponent>(a).unwrap().linear_velocity;
+        let velocity_b = world.get_component::<RigidBodyComponent>(b).unwrap().linear_velocity;
+        assert!((velocity_a.x + 5.0).abs() < 0.01, "a should bounce back, got {:?}", velocity_a);
+        assert!((velocity_b.x - 5.0).abs() < 0.01, "b should bounce back, got {:?}", velocity_b);
+
+        let distance = (world.get_component::<TransformComponent>(b).unwrap().position
+            - world.get_component::<TransformComponent>(a).unwrap().position).magnitude();
+        assert!(distance > 2.0, "spheres should have separated, distance {}", distance);
+    }
+
+    #[test]
+    fn test_body_bounces_off_static_collider() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::new();
+        let wall = spawn_sphere(&mut world, &mut collisions, Vec3::new(0.0, 0.0, 0.0), None);
+        let ball = spawn_sphere(&mut world, &mut collisions, Vec3::new(0.0, 3.0, 0.0),
+            Some(RigidBodyComponent::dynamic(2.0).with_restitution(0.5).with_velocity(Vec3::new(0.0, -6.0, 0.0))));
+
+        run(&mut world, &mut collisions, &mut physics, 30);
+
+        let velocity = world.get_component::<RigidBodyComponent>(ball).unwrap().linear_velocity;
+        assert!((velocity.y - 3.0).abs() < 0.01, "ball should rebound at half speed, got {:?}", velocity);
+        assert_eq!(world.get_component::<TransformComponent>(wall).unwrap().position, Vec3::zeros());
+        assert!(world.get_component::<TransformComponent>(ball).unwrap().position.y > 2.0);
+    }
+
+    #[test]
+    fn test_forces_and_gravity_integrate_into_velocity() {
+        let mut world = World::new();
+        let collisions = collision_system();
+        let mut physics = PhysicsSystem::with_config(PhysicsConfig { gravity: Vec3::new(0.0, -10.0, 0.0), ..Default::default() });
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::identity());
+        let mut body = RigidBodyComponent::dynamic(2.0);
+        body.apply_force(Vec3::new(4.0, 0.0, 0.0));
+        world.add_component(entity, body);
+
+        physics.step(&mut world, &collisions, 0.5);
+
+        let body = world.get_component::<RigidBodyComponent>(entity).unwrap();
+        assert_eq!(body.linear_velocity, Vec3::new(1.0, -5.0, 0.0));
+        assert_eq!(body.accumulated_force(), (Vec3::zeros(), Vec3::zeros()));
+        assert_eq!(world.get_component::<TransformComponent>(entity).unwrap().position, Vec3::new(0.5, -2.5, 0.0));
+        assert_eq!(physics.stats().bodies, 1);
+    }
+
+    #[test]
+    fn test_resting_body_sleeps_and_island_wakes_on_contact() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::with_config(PhysicsConfig { gravity: Vec3::new(0.0, -10.0, 0.0), ..Default::default() });
+        spawn_sphere(&mut world, &mut collisions, Vec3::zeros(), None);
+        let resting = spawn_sphere(&mut world, &mut collisions, Vec3::new(0.0, 1.99, 0.0), Some(RigidBodyComponent::dynamic(1.0)));
+
+        run(&mut world, &mut collisions, &mut physics, 60);
+        assert!(world.get_component::<RigidBodyComponent>(resting).unwrap().is_sleeping());
+        assert_eq!((physics.stats().awake, physics.stats().sleeping, physics.stats().islands), (0, 1, 0));
+        let asleep_at = world.get_component::<TransformComponent>(resting).unwrap().position;
+
+        run(&mut world, &mut collisions, &mut physics, 30);
+        assert_eq!(world.get_component::<TransformComponent>(resting).unwrap().position, asleep_at);
+
+        // A falling body joins the sleeper's island and wakes it
+        let falling = spawn_sphere(&mut world, &mut collisions, asleep_at + Vec3::new(0.0, 2.5, 0.0),
+            Some(RigidBodyComponent::dynamic(1.0).with_velocity(Vec3::new(0.0, -3.0, 0.0))));
+        let mut woken = false;
+        for _ in 0..20 {
+            run(&mut world, &mut collisions, &mut physics, 1);
+            if physics.stats().awake == 2 {
+                woken = true;
+                break;
+            }
+        }
+        assert!(woken, "landing on the sleeping body should wake it");
+        assert_eq!(physics.stats().islands, 1);
+        assert!(!world.get_component::<RigidBodyComponent>(resting).unwrap().is_sleeping());
+        assert!(!world.get_component::<RigidBodyComponent>(falling).unwrap().is_sleeping());
+
+        // Applying a force also wakes a body
+        let mut body = RigidBodyComponent::dynamic(1.0);
+        body.set_sleep_state(true, 1.0);
+        body.apply_force(Vec3::x());
+        assert!(!body.is_sleeping());
+    }
+
+    fn spawn_body(world: &mut World, position: Vec3, body: RigidBodyComponent) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(entity, TransformComponent::from_position(position));
+        world.add_component(entity, body);
+        entity
+    }
+
+    fn spawn_joint(world: &mut World, joint: JointComponent) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(entity, joint);
+        entity
+    }
+
+    #[test]
+    fn test_ball_socket_pendulum_keeps_its_length() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::with_config(PhysicsConfig { gravity: Vec3::new(0.0, -10.0, 0.0), ..Default::default() });
+        let bob = spawn_body(&mut world, Vec3::new(2.0, 0.0, 0.0), RigidBodyComponent::dynamic(1.0));
+        spawn_joint(&mut world, JointComponent::ball_socket(bob, None, Vec3::zeros()));
+
+        let mut lowest = 0.0f32;
+        for _ in 0..120 {
+            run(&mut world, &mut collisions, &mut physics, 1);
+            let position = world.get_component::<TransformComponent>(bob).unwrap().position;
+            assert!((position.magnitude() - 2.0).abs() < 0.05, "pendulum stretched to {:?}", position);
+            lowest = lowest.min(position.y);
+        }
+
+        // It has swung through the bottom of the arc
+        assert!(lowest < -1.9, "pendulum should swing down, lowest point {}", lowest);
+        assert_eq!(physics.stats().joints, 1);
+    }
+
+    #[test]
+    fn test_hinge_motor_respects_limits() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::new();
+        let base = spawn_body(&mut world, Vec3::zeros(), RigidBodyComponent::fixed());
+        let barrel = spawn_body(&mut world, Vec3::new(1.0, 0.0, 0.0), RigidBodyComponent::dynamic(1.0));
+        let joint = spawn_joint(&mut world, JointComponent::hinge(base, Some(barrel), Vec3::zeros(), Vec3::y())
+            .with_motor(2.0, 100.0)
+            .with_limits(-0.5, 0.5));
+
+        run(&mut world, &mut collisions, &mut physics, 10);
+        let body = world.get_component::<RigidBodyComponent>(barrel).unwrap();
+        assert!((body.angular_velocity.y - 2.0).abs() < 0.05, "motor should drive the hinge, got {:?}", body.angular_velocity);
+        assert!(body.angular_velocity.xz().magnitude() < 0.01);
+
+        run(&mut world, &mut collisions, &mut physics, 120);
+        let transform = world.get_component::<TransformComponent>(barrel).unwrap();
+        let angle = transform.rotation.angle();
+        assert!(angle < 0.55, "hinge should stop at its limit, angle {}", angle);
+        assert!(angle > 0.45, "motor should hold the hinge at its limit, angle {}", angle);
+        assert!((transform.position.magnitude() - 1.0).abs() < 0.02, "barrel should stay on the pivot, got {:?}", transform.position);
+        assert!(world.get_component::<JointComponent>(joint).unwrap().frames.is_some());
+    }
+
+    #[test]
+    fn test_slider_limits_travel_and_removes_sideways_motion() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::new();
+        let piston = spawn_body(&mut world, Vec3::zeros(),
+            RigidBodyComponent::dynamic(1.0).with_velocity(Vec3::new(3.0, 2.0, 0.0)).with_angular_velocity(Vec3::new(0.0, 0.0, 1.0)));
+        spawn_joint(&mut world, JointComponent::slider(piston, None, Vec3::zeros(), Vec3::x()).with_limits(-1.0, 1.0));
+
+        run(&mut world, &mut collisions, &mut physics, 60);
+
+        let transform = world.get_component::<TransformComponent>(piston).unwrap();
+        assert!(transform.position.x < 1.05 && transform.position.x > 0.9, "slider should stop at its limit, got {:?}", transform.position);
+        assert!(transform.position.y.abs() < 0.05, "slider should not move sideways, got {:?}", transform.position);
+        assert!(transform.rotation.angle() < 0.05);
+    }
+
+    #[test]
+    fn test_jointed_bodies_ignore_each_others_contacts() {
+        let mut world = World::new();
+        let mut collisions = collision_system();
+        let mut physics = PhysicsSystem::new();
+        let a = spawn_sphere(&mut world, &mut collisions, Vec3::zeros(), Some(RigidBodyComponent::dynamic(1.0)));
+        let b = spawn_sphere(&mut world, &mut collisions, Vec3::new(1.5, 0.0, 0.0), Some(RigidBodyComponent::dynamic(1.0)));
+        spawn_joint(&mut world, JointComponent::fixed(a, Some(b), Vec3::new(0.75, 0.0, 0.0)));
+
+        run(&mut world, &mut collisions, &mut physics, 10);
+
+        // The overlapping spheres stay welded instead of being pushed apart
+        assert_eq!(physics.stats().contacts, 0);
+        assert_eq!(world.get_component::<TransformComponent>(a).unwrap().position, Vec3::zeros());
+        assert_eq!(world.get_component::<TransformComponent>(b).unwrap().position, Vec3::new(1.5, 0.0, 0.0));
+    }
+}
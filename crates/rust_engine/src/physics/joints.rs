@@ -0,0 +1,312 @@
+//! Joint constraints solved alongside contacts
+//!
+//! GEA 13.4: each joint is broken down into one-dimensional velocity
+//! constraints ("rows"): three for a shared point, one or three for locked
+//! rotation axes, and optional limit and motor rows on the free coordinate.
+//! The physics system solves them in the same sequential impulse loop as the
+//! contacts, and feeds positional drift back as a velocity bias so the joint
+//! pulls itself back together over a few steps.
+
+use crate::debug::DebugDrawSystem;
+use crate::ecs::{Entity, World};
+use crate::ecs::components::{
+    JointComponent, JointFrames, JointKind, JointLimits, JointMotor, JointSpring, TransformComponent,
+};
+use crate::foundation::math::{Quat, Vec3, Vec4};
+use super::dynamics::{tangent_basis, BodyState};
+
+/// Length of the hinge/slider axis drawn by `draw_joints`
+const DEBUG_AXIS_LENGTH: f32 = 1.0;
+
+/// Lines from each body to its anchor
+const DEBUG_BODY_COLOR: Vec4 = Vec4::new(0.6, 0.6, 0.6, 1.0);
+
+/// Line between the anchors (cable of a distance joint, drift otherwise)
+const DEBUG_LINK_COLOR: Vec4 = Vec4::new(1.0, 0.8, 0.0, 1.0);
+
+/// Hinge and slider axes
+const DEBUG_AXIS_COLOR: Vec4 = Vec4::new(0.0, 0.8, 1.0, 1.0);
+
+/// A joint whose bodies were found this step, with its captured frames
+pub(super) struct SolverJoint {
+    pub(super) kind: JointKind,
+    pub(super) body_a: usize,
+    pub(super) body_b: Option<usize>,
+    pub(super) frames: JointFrames,
+    pub(super) limits: Option<JointLimits>,
+    pub(super) motor: Option<JointMotor>,
+    pub(super) spring: Option<JointSpring>,
+}
+
+impl SolverJoint {
+    pub(super) fn new(joint: &JointComponent, body_a: usize, body_b: Option<usize>, frames: JointFrames) -> Self {
+        Self {
+            kind: joint.kind,
+            body_a,
+            body_b,
+            frames,
+            limits: joint.limits,
+            motor: joint.motor,
+            spring: joint.spring,
+        }
+    }
+}
+
+/// One-dimensional velocity constraint between two bodies (None = world)
+pub(super) struct JointRow {
+    body_a: Option<usize>,
+    body_b: Option<usize>,
+    linear: Vec3,      // Applied to b, opposite to a
+    angular_a: Vec3,
+    angular_b: Vec3,
+    mass: f32,
+    target_speed: f32, // Desired constraint speed (drift bias or motor speed)
+    impulse: f32,      // Accumulated over the iterations
+    min_impulse: f32,
+    max_impulse: f32,
+}
+
+impl JointRow {
+    fn new(bodies: &[BodyState], body_a: Option<usize>, body_b: Option<usize>, linear: Vec3, angular_a: Vec3, angular_b: Vec3) -> Self {
+        let term = |body: Option<usize>, angular: Vec3| {
+            body.map_or(0.0, |i| bodies[i].inverse_mass * linear.norm_squared() + angular.dot(&(bodies[i].inverse_inertia * angular)))
+        };
+        let k = term(body_a, angular_a) + term(body_b, angular_b);
+        Self {
+            body_a,
+            body_b,
+            linear,
+            angular_a,
+            angular_b,
+            mass: if k > 0.0 { 1.0 / k } else { 0.0 },
+            target_speed: 0.0,
+            impulse: 0.0,
+            min_impulse: f32::NEG_INFINITY,
+            max_impulse: f32::INFINITY,
+        }
+    }
+
+    fn with_target(mut self, target_speed: f32) -> Self {
+        self.target_speed = target_speed;
+        self
+    }
+
+    fn with_bounds(mut self, min_impulse: f32, max_impulse: f32) -> Self {
+        self.min_impulse = min_impulse;
+        self.max_impulse = max_impulse;
+        self
+    }
+
+    /// Rate of change of the constraint (b relative to a)
+    fn speed(&self, bodies: &[BodyState]) -> f32 {
+        let speed = |body: Option<usize>, angular: Vec3| {
+            body.map_or(0.0, |i| self.linear.dot(&bodies[i].linear_velocity) + angular.dot(&bodies[i].angular_velocity))
+        };
+        speed(self.body_b, self.angular_b) - speed(self.body_a, self.angular_a)
+    }
+
+    fn apply(&self, bodies: &mut [BodyState], impulse: f32) {
+        if let Some(a) = self.body_a {
+            let body = &mut bodies[a];
+            body.linear_velocity -= self.linear * (impulse * body.inverse_mass);
+            body.angular_velocity -= body.inverse_inertia * self.angular_a * impulse;
+        }
+        if let Some(b) = self.body_b {
+            let body = &mut bodies[b];
+            body.linear_velocity += self.linear * (impulse * body.inverse_mass);
+            body.angular_velocity += body.inverse_inertia * self.angular_b * impulse;
+        }
+    }
+
+    /// One sequential impulse pass, clamping the accumulated impulse to the row's bounds
+    pub(super) fn solve(&mut self, bodies: &mut [BodyState]) {
+        let accumulated = (self.impulse + (self.target_speed - self.speed(bodies)) * self.mass)
+            .clamp(self.min_impulse, self.max_impulse);
+        let impulse = accumulated - self.impulse;
+        self.impulse = accumulated;
+        self.apply(bodies, impulse);
+    }
+}
+
+/// Convert a joint's world-space anchors and axis into the bodies' local frames
+pub(super) fn capture_frames(joint: &JointComponent, bodies: &[BodyState], body_a: usize, body_b: Option<usize>) -> JointFrames {
+    let (position_a, rotation_a) = pose(bodies, Some(body_a));
+    let (position_b, rotation_b) = pose(bodies, body_b);
+    JointFrames {
+        anchor_a: rotation_a.inverse() * (joint.anchor_a - position_a),
+        anchor_b: rotation_b.inverse() * (joint.anchor_b - position_b),
+        axis_a: rotation_a.inverse() * joint.axis,
+        axis_b: rotation_b.inverse() * joint.axis,
+        rest_rotation: rotation_a.inverse() * rotation_b,
+        rest_length: (joint.anchor_b - joint.anchor_a).magnitude(),
+    }
+}
+
+/// Build the solver rows for a joint from the current body poses
+///
+/// Springs are applied here as a single explicit impulse rather than solved.
+/// `correction_rate` converts positional drift into a corrective speed.
+pub(super) fn build_rows(joint: &SolverJoint, bodies: &mut [BodyState], correction_rate: f32, delta_time: f32) -> Vec<JointRow> {
+    let (a, b) = (Some(joint.body_a), joint.body_b);
+    let frames = &joint.frames;
+    let (position_a, rotation_a) = pose(bodies, a);
+    let (position_b, rotation_b) = pose(bodies, b);
+    let anchor_a = position_a + rotation_a * frames.anchor_a;
+    let anchor_b = position_b + rotation_b * frames.anchor_b;
+    let offset_a = anchor_a - position_a;
+    let offset_b = anchor_b - position_b;
+    let separation = anchor_b - anchor_a;
+    let axis = (rotation_a * frames.axis_a).normalize();
+    // Rotation of b away from where it would be if welded to a
+    let drift = rotation_b * (rotation_a * frames.rest_rotation).inverse();
+    let correct = |error: f32| -correction_rate * error;
+
+    if let (JointKind::Distance, Some(spring)) = (joint.kind, joint.spring) {
+        let length = separation.magnitude();
+        if let Some(direction) = separation.try_normalize(1e-6) {
+            let row = JointRow::new(bodies, a, b, direction, offset_a.cross(&direction), offset_b.cross(&direction));
+            let force = spring.stiffness * (length - spring.rest_length) + spring.damping * row.speed(bodies);
+            row.apply(bodies, -force * delta_time);
+        }
+    }
+
+    let bodies = &*bodies;
+    let point = |offset_a: Vec3, direction: Vec3| {
+        JointRow::new(bodies, a, b, direction, offset_a.cross(&direction), offset_b.cross(&direction))
+    };
+    let angular = |direction: Vec3| JointRow::new(bodies, a, b, Vec3::zeros(), direction, direction);
+
+    let mut rows = Vec::new();
+    let lock_point = |rows: &mut Vec<JointRow>| {
+        for direction in [Vec3::x(), Vec3::y(), Vec3::z()] {
+            rows.push(point(offset_a, direction).with_target(correct(separation.dot(&direction))));
+        }
+    };
+    let lock_rotation = |rows: &mut Vec<JointRow>| {
+        let error = drift.scaled_axis();
+        for direction in [Vec3::x(), Vec3::y(), Vec3::z()] {
+            rows.push(angular(direction).with_target(correct(error.dot(&direction))));
+        }
+    };
+
+    match joint.kind {
+        JointKind::Fixed => {
+            lock_point(&mut rows);
+            lock_rotation(&mut rows);
+        }
+        JointKind::BallSocket => lock_point(&mut rows),
+        JointKind::Hinge => {
+            lock_point(&mut rows);
+            // Keep b's copy of the axis aligned with a's
+            let misalignment = axis.cross(&(rotation_b * frames.axis_b));
+            for direction in tangent_basis(axis) {
+                rows.push(angular(direction).with_target(correct(misalignment.dot(&direction))));
+            }
+            free_axis_rows(joint, &mut rows, || angular(axis), twist_angle(drift, axis), correction_rate, delta_time);
+        }
+        JointKind::Slider => {
+            lock_rotation(&mut rows);
+            // Measure from a's center to b's anchor so the rows stay valid as the bodies separate
+            let reach = anchor_b - position_a;
+            for direction in tangent_basis(axis) {
+                rows.push(point(reach, direction).with_target(correct(separation.dot(&direction))));
+            }
+            free_axis_rows(joint, &mut rows, || point(reach, axis), separation.dot(&axis), correction_rate, delta_time);
+        }
+        JointKind::Distance => {
+            let length = separation.magnitude();
+            let direction = separation.try_normalize(1e-6).unwrap_or(axis);
+            // Without limits or a spring the joint is a rigid rod
+            let limits = joint.limits.or(match joint.spring {
+                Some(_) => None,
+                None => Some(JointLimits { min: frames.rest_length, max: frames.rest_length }),
+            });
+            if let Some(limits) = limits {
+                limit_rows(&mut rows, || point(offset_a, direction), length, limits, correction_rate);
+            }
+        }
+    }
+
+    rows.retain(|row| row.mass > 0.0);
+    rows
+}
+
+/// Motor and limit rows on a hinge or slider's free coordinate
+fn free_axis_rows(
+    joint: &SolverJoint,
+    rows: &mut Vec<JointRow>,
+    row: impl Fn() -> JointRow,
+    value: f32,
+    correction_rate: f32,
+    delta_time: f32,
+) {
+    if let Some(motor) = joint.motor {
+        let max_impulse = motor.max_force * delta_time;
+        rows.push(row().with_target(motor.target_speed).with_bounds(-max_impulse, max_impulse));
+    }
+    if let Some(limits) = joint.limits {
+        limit_rows(rows, row, value, limits, correction_rate);
+    }
+}
+
+/// Push a one-sided row when `value` is outside `limits` (two-sided when the range is empty)
+fn limit_rows(rows: &mut Vec<JointRow>, row: impl Fn() -> JointRow, value: f32, limits: JointLimits, correction_rate: f32) {
+    if limits.max - limits.min <= f32::EPSILON {
+        rows.push(row().with_target(-correction_rate * (value - limits.min)));
+    } else if value <= limits.min {
+        rows.push(row().with_target(-correction_rate * (value - limits.min)).with_bounds(0.0, f32::INFINITY));
+    } else if value >= limits.max {
+        rows.push(row().with_target(-correction_rate * (value - limits.max)).with_bounds(f32::NEG_INFINITY, 0.0));
+    }
+}
+
+/// Angle of `rotation` about `axis` (swing-twist decomposition)
+fn twist_angle(rotation: Quat, axis: Vec3) -> f32 {
+    let quaternion = rotation.quaternion();
+    let sign = if quaternion.w < 0.0 { -1.0 } else { 1.0 };
+    2.0 * (sign * quaternion.imag().dot(&axis)).atan2(sign * quaternion.w)
+}
+
+/// Position and rotation of a joint body (the world origin for `None`)
+fn pose(bodies: &[BodyState], body: Option<usize>) -> (Vec3, Quat) {
+    body.map_or((Vec3::zeros(), Quat::identity()), |i| (bodies[i].position, bodies[i].rotation))
+}
+
+/// Draw every joint with `DebugDrawSystem::draw_line`
+///
+/// Shows each body's link to its anchor, the line between the two anchors (a
+/// distance joint's cable, or drift for the other kinds) and hinge/slider axes.
+pub fn draw_joints(world: &World, debug: &mut DebugDrawSystem) {
+    let pose = |entity: Entity| world.get_component::<TransformComponent>(entity).map(|t| (t.position, t.rotation));
+
+    for (_, joint) in world.query::<&JointComponent>() {
+        let Some((position_a, rotation_a)) = pose(joint.body_a) else { continue };
+        let (position_b, rotation_b) = match joint.body_b {
+            Some(entity) => match pose(entity) {
+                Some(pose) => pose,
+                None => continue,
+            },
+            None => (Vec3::zeros(), Quat::identity()),
+        };
+
+        let (anchor_a, anchor_b, axis) = match &joint.frames {
+            Some(frames) => (
+                position_a + rotation_a * frames.anchor_a,
+                position_b + rotation_b * frames.anchor_b,
+                rotation_a * frames.axis_a,
+            ),
+            None => (joint.anchor_a, joint.anchor_b, joint.axis),
+        };
+
+        debug.draw_line(position_a, anchor_a, DEBUG_BODY_COLOR, 0.0);
+        if joint.body_b.is_some() {
+            debug.draw_line(position_b, anchor_b, DEBUG_BODY_COLOR, 0.0);
+        }
+        debug.draw_line(anchor_a, anchor_b, DEBUG_LINK_COLOR, 0.0);
+
+        if matches!(joint.kind, JointKind::Hinge | JointKind::Slider) {
+            let half = axis * (DEBUG_AXIS_LENGTH * 0.5);
+            debug.draw_line(anchor_a - half, anchor_a + half, DEBUG_AXIS_COLOR, 0.0);
+        }
+    }
+}
@@ -1,36 +1,33 @@
 //! Physics module for collision detection and response
 //!
 //! Provides collision detection using spatial partitioning (octree/quadtree)
-//! and basic collision response for common shapes.
+//! and impulse-based rigid body collision response.
 
 pub mod collision;
+pub mod collision_events;
 pub mod collision_layers;
+pub mod collision_matrix;
 pub mod collision_system;
+pub mod dynamics;
+pub mod joints;
 
 pub use collision::{
     CollisionShape,
     BoundingSphere,
+    Capsule,
+    OrientedBox,
+    ConvexHullError,
     Ray,
     RayHit,
     Triangle,
+    Contact,
+    ContactManifold,
+    ContactPoint,
+    TimeOfImpact,
 };
+pub use collision_events::{CollisionStarted, CollisionEnded, TriggerEntered, TriggerExited};
 pub use collision_layers::CollisionLayers;
-pub use collision_system::{PhysicsCollisionSystem, CollisionPair};
-
-/// Physics system placeholder
-pub struct PhysicsSystem {
-    _enabled: bool,
-}
-
-impl PhysicsSystem {
-    /// Create a new physics system
-    pub fn new() -> Self {
-        Self { _enabled: false }
-    }
-}
-
-impl Default for PhysicsSystem {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+pub use collision_matrix::{CollisionMatrix, CollisionMatrixError};
+pub use collision_system::{PhysicsCollisionSystem, CollisionPair, CcdHit};
+pub use dynamics::{PhysicsSystem, PhysicsConfig, PhysicsStats};
+pub use joints::draw_joints;
@@ -74,8 +74,8 @@ pub struct VulkanRenderer {
     // UI input processing
     ui_input_processor: UIInputProcessor,
     
-    // Event system for UI interactions
-    event_system: crate::events::EventSystem,
+    // Typed events for UI interactions
+    events: crate::events::EventBus,
     
     // UI overlay rendering (Vulkan-specific resources) - pub(crate) for ui_backend module
     pub(crate) ui_pipeline: Option<vk::Pipeline>,
@@ -166,7 +166,7 @@ impl VulkanRenderer {
             next_object_id: 1,
             instance_renderer: None, // Will be initialized when dynamic system is enabled
             ui_input_processor: UIInputProcessor::new(800.0, 600.0), // Updated on first frame
-            event_system: crate::events::EventSystem::new(),
+            events: crate::events::EventBus::new(),
             ui_pipeline: None, // Lazy initialization on first UI render
             ui_pipeline_layout: None,
             ui_vertex_buffer: None,
@@ -270,19 +270,19 @@ impl VulkanRenderer {
         self.ui_input_processor.begin_frame();
     }
     
-    /// Register an event handler for UI events
-    pub fn register_event_handler(&mut self, event_type: crate::events::EventType, handler: Box<dyn crate::events::EventHandler>) {
-        self.event_system.register_handler(event_type, handler);
+    /// Get the UI event bus
+    pub fn events(&self) -> &crate::events::EventBus {
+        &self.events
     }
     
-    /// Dispatch all pending UI events
-    pub fn dispatch_events(&mut self) {
-        self.event_system.dispatch();
+    /// Get the UI event bus mutably
+    pub fn events_mut(&mut self) -> &mut crate::events::EventBus {
+        &mut self.events
     }
     
-    /// Update event system time
-    pub fn update_event_time(&mut self, time: f64) {
-        self.event_system.update_time(time);
+    /// Swap the UI event buffers (call once per frame)
+    pub fn update_events(&mut self) {
+        self.events.update();
     }
     
     // === Internal Resource Management (Hidden from Applications) ===
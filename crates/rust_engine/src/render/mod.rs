@@ -1030,6 +1030,10 @@ impl GraphicsEngine {
                     ) {
                         log::warn!("Failed to update entity {:?} material: {}", obj.entity, e);
                     }
+                    // Show again if it was culled last frame
+                    if let Err(e) = self.pool_manager.as_mut().unwrap().set_instance_visible(mesh_type, handle, true) {
+                        log::warn!("Failed to show entity {:?}: {}", obj.entity, e);
+                    }
                 } else {
                     // New entity - use the mesh_type stored in RenderableObject
                     let mesh_type = obj.mesh_type;
@@ -1071,6 +1075,10 @@ impl GraphicsEngine {
                     ) {
                         log::warn!("Failed to update entity {:?} material: {}", obj.entity, e);
                     }
+                    // Show again if it was culled last frame
+                    if let Err(e) = self.pool_manager.as_mut().unwrap().set_instance_visible(mesh_type, handle, true) {
+                        log::warn!("Failed to show entity {:?}: {}", obj.entity, e);
+                    }
                 } else {
                     // New entity - use the mesh_type stored in RenderableObject
                     let mesh_type = obj.mesh_type;
@@ -1093,9 +1101,20 @@ impl GraphicsEngine {
             }
         }
         
-        // Free handles for entities that no longer exist
+        // Culled entities keep their handles (hidden) so they don't need to be
+        // reallocated when they come back into view
+        let culled_entities: HashSet<_> = render_queue.culled_entities().iter().copied().collect();
+        for entity in &culled_entities {
+            if let Some(&(mesh_type, handle)) = self.entity_handles.get(entity) {
+                if let Err(e) = self.pool_manager.as_mut().unwrap().set_instance_visible(mesh_type, handle, false) {
+                    log::warn!("Failed to hide culled entity {:?}: {}", entity, e);
+                }
+            }
+        }
+        
+        // Free handles for entities no longer in the scene (destroyed or hidden)
         let entities_to_remove: Vec<_> = self.entity_handles.keys()
-            .filter(|entity| !active_entities.contains(entity))
+            .filter(|entity| !active_entities.contains(entity) && !culled_entities.contains(entity))
             .copied()
             .collect();
         
@@ -193,6 +193,9 @@ pub struct DynamicRenderData {
     /// Render layer for sorting (0-255, higher = renders later)
     /// Default is 0 for normal objects. Use 255 for skyboxes.
     pub render_layer: u8,
+    
+    /// Whether the object is drawn; hidden objects keep their slot
+    pub visible: bool,
 }
 
 impl Default for DynamicRenderData {
@@ -204,6 +207,7 @@ impl Default for DynamicRenderData {
             generation: 0,
             state: ResourceState::Available,
             render_layer: 0,
+            visible: true,
         }
     }
 }
@@ -403,6 +407,7 @@ impl DynamicObjectManager {
                 object.generation = generation;
                 object.state = ResourceState::Active;
                 object.render_layer = params.render_layer; // Set render layer from params
+                object.visible = true;
             }
             
             let handle = DynamicObjectHandle::new(index as u32, generation);
@@ -481,6 +486,20 @@ impl DynamicObjectManager {
         }
     }
 
+    /// Show or hide an object without releasing its slot
+    ///
+    /// Hidden objects are skipped when rendering, e.g. while frustum culled.
+    pub fn set_instance_visible(&mut self, handle: DynamicObjectHandle, visible: bool) -> Result<(), DynamicObjectError> {
+        if let Some(object) = self.object_pool.get_mut_with_handle(handle) {
+            object.visible = visible;
+            Ok(())
+        } else {
+            Err(DynamicObjectError::InvalidHandle {
+                handle,
+                reason: "Object not found or generation mismatch".to_string(),
+            })
+        }
+    }
     
     /// Get active object count
     pub fn get_object(&self, handle: DynamicObjectHandle) -> Result<&DynamicRenderData, DynamicObjectError> {
@@ -608,7 +627,7 @@ impl DynamicObjectManager {
     
     /// Get active objects as HashMap for rendering
     ///
-    /// Creates a HashMap mapping active object handles to their render data.
+    /// Creates a HashMap mapping active, visible object handles to their render data.
     /// This is used by the rendering system for batch operations.
     /// 
     /// WARNING: This method clones all render data - use for_each_active_object for better performance
@@ -619,7 +638,7 @@ impl DynamicObjectManager {
         let mut active_map = std::collections::HashMap::new();
         
         for &handle in &self.active_objects {
-            if let Some(render_data) = self.object_pool.get_with_handle(handle) {
+            if let Some(render_data) = self.object_pool.get_with_handle(handle).filter(|data| data.visible) {
                 active_map.insert(handle, render_data.clone());
             }
         }
@@ -627,7 +646,7 @@ impl DynamicObjectManager {
         active_map
     }
     
-    /// Iterate over active objects without cloning data
+    /// Iterate over active, visible objects without cloning data
     ///
     /// More efficient than get_active_objects_map() as it avoids HashMap creation
     /// and render data cloning. Use this for performance-critical rendering paths.
@@ -639,7 +658,7 @@ impl DynamicObjectManager {
         F: FnMut(DynamicObjectHandle, &DynamicRenderData),
     {
         for &handle in &self.active_objects {
-            if let Some(render_data) = self.object_pool.get_with_handle(handle) {
+            if let Some(render_data) = self.object_pool.get_with_handle(handle).filter(|data| data.visible) {
                 callback(handle, render_data);
             }
         }
@@ -760,4 +779,25 @@ mod tests {
         
         assert_eq!(manager.active_count(), 0);
     }
+    
+    #[test]
+    fn test_hidden_objects_keep_their_slot() {
+        let mut manager = DynamicObjectManager::new(10);
+        
+        let params = DynamicSpawnParams::from_matrix(
+            Mat4::identity(),
+            create_test_material(),
+        );
+        
+        let handle = manager.spawn_object(params).expect("Should spawn object");
+        manager.set_instance_visible(handle, false).expect("Should hide object");
+        manager.update();
+        
+        // Still allocated, but not handed to the renderer
+        assert_eq!(manager.active_count(), 1);
+        assert!(manager.get_active_objects_map().is_empty());
+        
+        manager.set_instance_visible(handle, true).expect("Should show object");
+        assert!(manager.get_active_objects_map().contains_key(&handle));
+    }
 }
@@ -849,6 +849,30 @@ impl MeshPoolManager {
         }
     }
 
+    /// Show or hide a specific dynamic object without freeing it
+    ///
+    /// Hidden objects keep their handle and instance slot but are not drawn,
+    /// so objects that are culled for a few frames need no reallocation.
+    ///
+    /// # Arguments
+    /// * `mesh_type` - The type of mesh (determines which pool to search)
+    /// * `handle` - Unique handle identifying the object
+    /// * `visible` - Whether the object should be drawn
+    pub fn set_instance_visible(
+        &mut self,
+        mesh_type: MeshType,
+        handle: DynamicObjectHandle,
+        visible: bool,
+    ) -> Result<(), DynamicObjectError> {
+        if let Some(pool_resources) = self.pools.get_mut(&mesh_type) {
+            pool_resources.manager.set_instance_visible(handle, visible)
+        } else {
+            Err(DynamicObjectError::ResourceCreationFailed(
+                format!("No pool found for mesh type: {:?}", mesh_type)
+            ))
+        }
+    }
+
 }
 
 impl Default for MeshPoolManager {
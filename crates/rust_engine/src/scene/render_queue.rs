@@ -3,6 +3,7 @@
 //! Collects renderable objects and organizes them for efficient rendering.
 //! Following Game Engine Architecture Chapter 11.3 - Render Queues.
 
+use crate::ecs::Entity;
 use crate::scene::RenderableObject;
 use crate::render::resources::materials::MaterialId;
 use std::collections::HashMap;
@@ -51,6 +52,9 @@ pub struct RenderQueue {
     /// render/mod.rs only calls opaque_batches() and never calls transparent_batches().
     /// This is a partially implemented feature. See TODO in docs/API_REFACTORING_PLAN.md.
     transparent_batches: Vec<RenderBatch>,
+    
+    /// Entities that still exist but were frustum culled this frame
+    culled: Vec<Entity>,
 }
 
 impl RenderQueue {
@@ -59,9 +63,18 @@ impl RenderQueue {
         Self {
             opaque_batches: Vec::new(),
             transparent_batches: Vec::new(),
+            culled: Vec::new(),
         }
     }
     
+    /// Record the entities culled from this queue
+    ///
+    /// Unlike despawned or hidden entities, these keep their GPU resources.
+    pub fn with_culled(mut self, culled: Vec<Entity>) -> Self {
+        self.culled = culled;
+        self
+    }
+    
     /// Build a render queue from a list of renderable objects
     pub fn from_objects(objects: &[RenderableObject]) -> Self {
         let mut queue = Self::new();
@@ -134,6 +147,11 @@ impl RenderQueue {
         self.opaque_object_count() + self.transparent_object_count()
     }
     
+    /// Entities left out of the queue only because they were culled
+    pub fn culled_entities(&self) -> &[Entity] {
+        &self.culled
+    }
+    
     /// Get total number of batches
     pub fn batch_count(&self) -> usize {
         self.opaque_batches.len() + self.transparent_batches.len()
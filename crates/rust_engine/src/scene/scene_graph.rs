@@ -4,7 +4,8 @@
 //! Following Game Engine Architecture Chapter 11.2.7.4 - Scene Graphs.
 
 use crate::ecs::Entity;
-use crate::foundation::math::{Vec3, Mat4};
+use crate::foundation::math::{Vec3, Vec4, Mat4};
+use crate::spatial::{DynamicAabbTree, SpatialQuery};
 
 /// Axis-Aligned Bounding Box for spatial queries
 #[derive(Debug, Clone, Copy)]
@@ -52,11 +53,55 @@ impl AABB {
         self.min.y <= other.max.y && self.max.y >= other.min.y &&
         self.min.z <= other.max.z && self.max.z >= other.min.z
     }
-    
+
+    /// Create the AABB enclosing a sphere
+    pub fn from_sphere(center: Vec3, radius: f32) -> Self {
+        Self::from_center_extents(center, Vec3::new(radius, radius, radius))
+    }
+
+    /// Smallest AABB enclosing both this AABB and another
+    pub fn union(&self, other: &AABB) -> AABB {
+        Self {
+            min: self.min.inf(&other.min),
+            max: self.max.sup(&other.max),
+        }
+    }
+
+    /// Check if this AABB fully encloses another AABB
+    pub fn contains(&self, other: &AABB) -> bool {
+        self.min.x <= other.min.x && self.max.x >= other.max.x &&
+        self.min.y <= other.min.y && self.max.y >= other.max.y &&
+        self.min.z <= other.min.z && self.max.z >= other.max.z
+    }
+
+    /// Total area of the six faces (the cost metric used when building BVHs)
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    /// Check if this AABB intersects a sphere
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        let closest = center.sup(&self.min).inf(&self.max);
+        (closest - center).magnitude_squared() <= radius * radius
+    }
+
+    /// Distance from a point to the closest point of this AABB (0 inside)
+    pub fn distance_to_point(&self, point: Vec3) -> f32 {
+        (point.sup(&self.min).inf(&self.max) - point).magnitude()
+    }
+
     /// Test ray intersection with this AABB using slab method
     /// Returns the distance to the entry point if the ray intersects, None otherwise
     /// Based on "An Efficient and Robust Ray–Box Intersection Algorithm"
     pub fn intersect_ray(&self, ray_origin: Vec3, ray_dir: Vec3) -> Option<f32> {
+        self.ray_span(ray_origin, ray_dir).map(|(entry, _)| entry)
+    }
+
+    /// Ray parameters where the ray enters and leaves this AABB
+    ///
+    /// The entry is clamped to 0 when the origin is inside the box.
+    pub fn ray_span(&self, ray_origin: Vec3, ray_dir: Vec3) -> Option<(f32, f32)> {
         let inv_dir = Vec3::new(
             if ray_dir.x != 0.0 { 1.0 / ray_dir.x } else { f32::INFINITY },
             if ray_dir.y != 0.0 { 1.0 / ray_dir.y } else { f32::INFINITY },
@@ -75,8 +120,8 @@ impl AABB {
         
         // Ray intersects if tmax >= tmin and tmax >= 0
         if tmax >= tmin && tmax >= 0.0 {
-            // Return entry point distance (or 0 if we're inside the box)
-            Some(tmin.max(0.0))
+            // Entry point distance (or 0 if we're inside the box)
+            Some((tmin.max(0.0), tmax))
         } else {
             None
         }
@@ -99,19 +144,39 @@ impl Frustum {
     /// Extract frustum planes from a view-projection matrix
     ///
     /// This uses the Gribb-Hartmann method to extract frustum planes
-    /// from the combined view-projection matrix.
-    pub fn from_matrix(_vp_matrix: &Mat4) -> Self {
-        // TODO: Implement Gribb-Hartmann frustum extraction
-        // For now, return a frustum with degenerate planes (no culling)
-        let degenerate_plane = Plane {
-            normal: Vec3::new(0.0, 0.0, 0.0),
-            distance: 0.0,
-        };
+    /// from the combined view-projection matrix (`Camera::get_view_projection_matrix`).
+    /// Clip space follows Vulkan conventions: `-w <= x, y <= w` with Y pointing
+    /// down, and depth `0 <= z <= w`. A reverse-Z projection uses the same
+    /// depth range with near and far swapped, so the extracted volume is the
+    /// same; only the `near`/`far` labels trade places.
+    pub fn from_matrix(vp_matrix: &Mat4) -> Self {
+        let row = |i: usize| -> Vec4 { vp_matrix.row(i).transpose() };
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
         Self {
-            planes: [degenerate_plane; 6],
+            planes: [
+                Plane::from_coefficients(w + x), // left:   x >= -w
+                Plane::from_coefficients(w - x), // right:  x <= w
+                Plane::from_coefficients(w + y), // top:    y >= -w (Vulkan Y points down)
+                Plane::from_coefficients(w - y), // bottom: y <= w
+                Plane::from_coefficients(z),     // near:   z >= 0
+                Plane::from_coefficients(w - z), // far:    z <= w
+            ],
         }
     }
     
+    /// Check if a point is inside the frustum
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|plane| plane.distance_to_point(point) >= 0.0)
+    }
+    
+    /// Check if a sphere is inside or intersects the frustum
+    ///
+    /// Conservative near the frustum's corners: a sphere just outside two
+    /// planes at once may still be reported as visible.
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes.iter().all(|plane| plane.distance_to_point(center) >= -radius)
+    }
+    
     /// Check if an AABB is inside or intersects the frustum
     pub fn intersects_aabb(&self, aabb: &AABB) -> bool {
         // For each plane, check if the AABB is completely outside
@@ -148,6 +213,19 @@ impl Plane {
         Self { normal: normal.normalize(), distance }
     }
     
+    /// Create a plane from `ax + by + cz + d` coefficients, normalizing all four
+    ///
+    /// Degenerate coefficients (zero normal) give a plane that contains everything.
+    pub fn from_coefficients(coefficients: Vec4) -> Self {
+        let normal = coefficients.xyz();
+        let length = normal.magnitude();
+        if length > f32::EPSILON {
+            Self { normal: normal / length, distance: coefficients.w / length }
+        } else {
+            Self { normal: Vec3::zeros(), distance: 0.0 }
+        }
+    }
+    
     /// Calculate signed distance from plane to point
     pub fn distance_to_point(&self, point: Vec3) -> f32 {
         self.normal.dot(&point) + self.distance
@@ -255,9 +333,115 @@ impl SceneGraph for SimpleListGraph {
     }
 }
 
+/// Scene graph backed by a dynamic AABB tree
+///
+/// Entities are stored by the bounding sphere of their AABB, so frustum and
+/// radius queries only visit subtrees whose bounds pass the test. Suits
+/// scenes with many objects where most are off-screen at any time.
+#[derive(Debug, Default)]
+pub struct BvhSceneGraph {
+    tree: DynamicAabbTree,
+}
+
+impl BvhSceneGraph {
+    /// Create a new empty scene graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    fn sphere(bounds: &AABB) -> (Vec3, f32) {
+        (bounds.center(), bounds.extents().magnitude())
+    }
+}
+
+impl SceneGraph for BvhSceneGraph {
+    fn add(&mut self, entity: Entity, bounds: AABB) {
+        let (center, radius) = Self::sphere(&bounds);
+        self.tree.insert(entity, center, radius);
+    }
+    
+    fn remove(&mut self, entity: Entity) {
+        self.tree.remove(entity);
+    }
+    
+    fn update(&mut self, entity: Entity, bounds: AABB) {
+        let (center, radius) = Self::sphere(&bounds);
+        self.tree.update(entity, center, radius);
+    }
+    
+    fn query_visible(&self, frustum: &Frustum) -> Vec<Entity> {
+        self.tree.query_bounds(
+            |bounds| frustum.intersects_aabb(bounds),
+            |center, radius| frustum.intersects_sphere(center, radius),
+        )
+    }
+    
+    fn query_radius(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        self.tree.query_sphere(center, radius)
+    }
+    
+    fn entity_count(&self) -> usize {
+        self.tree.entity_count()
+    }
+    
+    fn clear(&mut self) {
+        self.tree.clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::foundation::math::Mat4Ext;
+    
+    /// View-projection of a camera at the origin looking down -Z
+    fn test_view_projection() -> Mat4 {
+        let view = Mat4::look_at(Vec3::zeros(), Vec3::new(0.0, 0.0, -1.0), Vec3::y());
+        let projection = Mat4::perspective(90f32.to_radians(), 1.0, 0.1, 100.0);
+        projection * Mat4::vulkan_coordinate_transform() * view
+    }
+    
+    #[test]
+    fn test_frustum_from_camera_matrix() {
+        let frustum = Frustum::from_matrix(&test_view_projection());
+        
+        assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -10.0)));
+        assert!(frustum.contains_point(Vec3::new(9.0, -9.0, -10.0)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 10.0)), "behind the camera");
+        assert!(!frustum.contains_point(Vec3::new(11.0, 0.0, -10.0)), "right of the 90 degree fov");
+        assert!(!frustum.contains_point(Vec3::new(0.0, 11.0, -10.0)), "above the 90 degree fov");
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -0.05)), "before the near plane");
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -101.0)), "past the far plane");
+        
+        // Planes are normalized, so sphere tests use real distances
+        assert!(frustum.intersects_sphere(Vec3::new(12.0, 0.0, -10.0), 1.5));
+        assert!(!frustum.intersects_sphere(Vec3::new(12.0, 0.0, -10.0), 1.0));
+        assert!(frustum.intersects_aabb(&AABB::from_sphere(Vec3::new(0.0, 0.0, -102.0), 3.0)));
+        assert!(!frustum.intersects_aabb(&AABB::from_sphere(Vec3::new(0.0, 0.0, 20.0), 3.0)));
+    }
+    
+    #[test]
+    fn test_bvh_scene_graph_culls_to_frustum() {
+        use crate::ecs::World;
+        
+        let mut graph = BvhSceneGraph::new();
+        let mut world = World::new();
+        let ahead = world.create_entity();
+        let behind = world.create_entity();
+        let edge = world.create_entity();
+        graph.add(ahead, AABB::from_sphere(Vec3::new(0.0, 0.0, -20.0), 1.0));
+        graph.add(behind, AABB::from_sphere(Vec3::new(0.0, 0.0, 20.0), 1.0));
+        graph.add(edge, AABB::from_sphere(Vec3::new(21.0, 0.0, -20.0), 2.0));
+        
+        let frustum = Frustum::from_matrix(&test_view_projection());
+        let mut visible = graph.query_visible(&frustum);
+        visible.sort_by_key(|entity| entity.id());
+        assert_eq!(visible, vec![ahead, edge]);
+        
+        graph.update(ahead, AABB::from_sphere(Vec3::new(0.0, 0.0, 30.0), 1.0));
+        assert_eq!(graph.query_visible(&frustum), vec![edge]);
+        assert_eq!(graph.query_radius(Vec3::new(0.0, 0.0, 25.0), 4.0).len(), 2);
+    }
     
     #[test]
     fn test_aabb_contains_point() {
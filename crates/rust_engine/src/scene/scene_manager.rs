@@ -9,11 +9,12 @@
 //! 3. Performs visibility culling
 //! 4. Generates optimized render queues
 
-use crate::ecs::{World, Entity};
-use crate::ecs::components::{TransformComponent, RenderableComponent};
-use crate::scene::{SceneGraph, SimpleListGraph, RenderableObject, RenderQueue, AABB};
+use crate::ecs::{World, Entity, Query, Changed, RemovedComponents};
+use crate::ecs::components::{TransformComponent, RenderableComponent, GlobalTransform};
+use crate::scene::{SceneGraph, BvhSceneGraph, RenderableObject, RenderQueue, AABB, Frustum};
 use crate::foundation::math::{Vec3, Transform};
 use crate::render::primitives::Mesh;
+use crate::render::Camera;
 use crate::render::resources::materials::Material;
 use crate::render::systems::dynamic::MeshType;
 use crate::render::GraphicsEngine;
@@ -26,7 +27,7 @@ pub struct SceneConfig {
     /// Enable dirty tracking (only sync changed entities)
     pub enable_dirty_tracking: bool,
     
-    /// Enable spatial culling
+    /// Enable frustum culling in `build_render_queue` (once a view frustum is set)
     pub enable_culling: bool,
     
     /// Default AABB extents for entities without explicit bounds
@@ -43,6 +44,17 @@ impl Default for SceneConfig {
     }
 }
 
+/// Visibility counts from the most recent `build_render_queue`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SceneStats {
+    /// Renderable objects in the scene
+    pub total: usize,
+    /// Objects inside the view frustum (added to the render queue)
+    pub visible: usize,
+    /// Objects rejected by frustum culling
+    pub culled: usize,
+}
+
 /// Scene Manager - coordinates ECS, rendering, and spatial queries
 ///
 /// This is the central hub for rendering. It bridges the gap between
@@ -62,6 +74,27 @@ pub struct SceneManager {
     
     /// Active camera entity (for culling)
     active_camera: Option<Entity>,
+    
+    /// View frustum used to cull the render queue
+    view_frustum: Option<Frustum>,
+    
+    /// Visibility counts from the last render queue build
+    stats: Mutex<SceneStats>,
+    
+    /// Change detection: transforms added/mutated since the last sync
+    changed_transforms: Query<&'static TransformComponent, Changed<TransformComponent>>,
+    
+    /// Change detection: world transforms of hierarchy members updated by propagation
+    changed_global_transforms: Query<&'static GlobalTransform, Changed<GlobalTransform>>,
+    
+    /// Change detection: renderables added/mutated since the last sync
+    changed_renderables: Query<&'static RenderableComponent, Changed<RenderableComponent>>,
+    
+    /// Change detection: transforms removed (or entities despawned) since the last sync
+    removed_transforms: RemovedComponents<TransformComponent>,
+    
+    /// Change detection: renderables removed (or entities despawned) since the last sync
+    removed_renderables: RemovedComponents<RenderableComponent>,
 }
 
 impl SceneManager {
@@ -74,10 +107,17 @@ impl SceneManager {
     pub fn with_config(config: SceneConfig) -> Self {
         Self {
             config,
-            scene_graph: Arc::new(RwLock::new(Box::new(SimpleListGraph::new()))),
+            scene_graph: Arc::new(RwLock::new(Box::new(BvhSceneGraph::new()))),
             renderable_cache: Arc::new(RwLock::new(HashMap::new())),
             dirty_entities: Arc::new(Mutex::new(HashSet::new())),
             active_camera: None,
+            view_frustum: None,
+            stats: Mutex::new(SceneStats::default()),
+            changed_transforms: Query::new(),
+            changed_global_transforms: Query::new(),
+            changed_renderables: Query::new(),
+            removed_transforms: RemovedComponents::new(),
+            removed_renderables: RemovedComponents::new(),
         }
     }
     
@@ -86,6 +126,23 @@ impl SceneManager {
         self.active_camera = Some(camera_entity);
     }
     
+    /// Set the frustum used to cull the render queue (`None` disables culling)
+    pub fn set_view_frustum(&mut self, frustum: Option<Frustum>) {
+        self.view_frustum = frustum;
+    }
+    
+    /// Cull against the camera's current view frustum
+    ///
+    /// Call whenever the camera moves, before `build_render_queue`.
+    pub fn set_camera(&mut self, camera: &Camera) {
+        self.view_frustum = Some(Frustum::from_matrix(&camera.get_view_projection_matrix()));
+    }
+    
+    /// Visibility counts from the last `build_render_queue`
+    pub fn stats(&self) -> SceneStats {
+        *self.stats.lock().unwrap()
+    }
+    
     /// Mark an entity as dirty (needs sync from ECS)
     pub fn mark_entity_dirty(&self, entity: Entity) {
         if self.config.enable_dirty_tracking {
@@ -97,22 +154,30 @@ impl SceneManager {
     /// Sync ECS world to scene (update renderable cache)
     ///
     /// This is the key method that bridges ECS → Renderer.
-    /// Call once per frame after ECS systems have updated.
+    /// Call once per frame after ECS systems (and transform propagation) have updated.
     ///
-    /// Uses automatic change detection from ECS World to only sync
-    /// entities with modified Transform or Renderable components.
+    /// Uses `Changed`/`RemovedComponents` change detection to only sync
+    /// entities whose Transform or Renderable components were added, mutated
+    /// or removed since the previous sync.
     pub fn sync_from_world(&mut self, world: &mut World) {
-        let mut cache = self.renderable_cache.write().unwrap();
-        let mut graph = self.scene_graph.write().unwrap();
-        
         // Get entities that changed (Transform or Renderable components)
-        let changed_entities = if self.config.enable_dirty_tracking {
-            world.get_changed_renderable_entities()
+        let changed_entities: Vec<Entity> = if self.config.enable_dirty_tracking {
+            let mut seen = HashSet::new();
+            self.changed_transforms.iter(world).into_iter().map(|(entity, _)| entity)
+                .chain(self.changed_global_transforms.iter(world).into_iter().map(|(entity, _)| entity))
+                .chain(self.changed_renderables.iter(world).into_iter().map(|(entity, _)| entity))
+                .chain(self.removed_transforms.read(world))
+                .chain(self.removed_renderables.read(world))
+                .filter(|entity| seen.insert(*entity))
+                .collect()
         } else {
             // If dirty tracking disabled, sync all entities
             world.entities().cloned().collect()
         };
         
+        let mut cache = self.renderable_cache.write().unwrap();
+        let mut graph = self.scene_graph.write().unwrap();
+        
         // Sync each changed entity
         for entity in &changed_entities {
             // Check if entity still exists in ECS
@@ -127,8 +192,8 @@ impl SceneManager {
                 continue;
             }
             
-            // Get Transform and Renderable components
-            let transform = world.get_component::<TransformComponent>(*entity);
+            // Get world-space transform (children are relative to their parent) and Renderable
+            let transform = world.world_transform(*entity);
             let renderable = world.get_component::<RenderableComponent>(*entity);
             
             match (transform, renderable) {
@@ -155,7 +220,7 @@ impl SceneManager {
                         // This will be handled during rendering for now
                         
                         // Update in scene graph
-                        let bounds = self.compute_bounds(&t.position);
+                        let bounds = self.compute_bounds(&t, &r.mesh);
                         graph.update(*entity, bounds);
                     } else {
                         // Create new renderable
@@ -173,7 +238,7 @@ impl SceneManager {
                         cache.insert(*entity, obj);
                         
                         // Add to scene graph
-                        let bounds = self.compute_bounds(&t.position);
+                        let bounds = self.compute_bounds(&t, &r.mesh);
                         graph.add(*entity, bounds);
                     }
                 }
@@ -185,26 +250,40 @@ impl SceneManager {
                 }
             }
         }
-        
-        // Clear change tracking in ECS for synced entities
-        if self.config.enable_dirty_tracking {
-            for entity in changed_entities {
-                world.clear_entity_changes(entity);
-            }
-        }
     }
     
     /// Build render queue for current frame
     ///
-    /// Queries visible objects (with optional frustum culling) and
-    /// organizes them into batches by material.
+    /// Queries visible objects from the scene graph (when culling is enabled
+    /// and a view frustum has been set) and organizes them into batches by
+    /// material. Culled objects are left out of the batches and listed in
+    /// `RenderQueue::culled_entities` instead.
     pub fn build_render_queue(&self) -> RenderQueue {
         let cache = self.renderable_cache.read().unwrap();
         
-        // For now, include all cached objects (culling TODO)
-        let objects: Vec<RenderableObject> = cache.values().cloned().collect();
+        let (objects, culled): (Vec<RenderableObject>, Vec<Entity>) = match &self.view_frustum {
+            Some(frustum) if self.config.enable_culling => {
+                let graph = self.scene_graph.read().unwrap();
+                let visible: HashSet<Entity> = graph.query_visible(frustum).into_iter().collect();
+                let objects = visible.iter()
+                    .filter_map(|entity| cache.get(entity).cloned())
+                    .collect();
+                let culled = cache.keys()
+                    .filter(|entity| !visible.contains(entity))
+                    .copied()
+                    .collect();
+                (objects, culled)
+            }
+            _ => (cache.values().cloned().collect(), Vec::new()),
+        };
         
-        RenderQueue::from_objects(&objects)
+        *self.stats.lock().unwrap() = SceneStats {
+            total: cache.len(),
+            visible: objects.len(),
+            culled: culled.len(),
+        };
+        
+        RenderQueue::from_objects(&objects).with_culled(culled)
     }
     
     /// Get number of entities in the scene
@@ -228,9 +307,21 @@ impl SceneManager {
         graph.clear();
     }
     
-    /// Compute AABB bounds for an entity (simplified for now)
-    fn compute_bounds(&self, position: &Vec3) -> AABB {
-        AABB::from_center_extents(*position, self.config.default_extents)
+    /// Compute world-space AABB bounds for an entity
+    ///
+    /// Uses the mesh's bounding sphere (about its origin) scaled by the largest
+    /// scale axis, which stays conservative under any rotation. Meshes without
+    /// vertices fall back to `default_extents`.
+    fn compute_bounds(&self, transform: &TransformComponent, mesh: &Mesh) -> AABB {
+        let local_radius_sq = mesh.vertices.iter()
+            .map(|vertex| Vec3::from(vertex.position).magnitude_squared())
+            .fold(0.0f32, f32::max);
+        if local_radius_sq <= 0.0 {
+            return AABB::from_center_extents(transform.position, self.config.default_extents);
+        }
+        
+        let scale = transform.scale.abs().max();
+        AABB::from_sphere(transform.position, local_radius_sq.sqrt() * scale)
     }
     
     // ========================================================================
@@ -392,11 +483,9 @@ impl SceneManager {
         graphics_engine: &mut GraphicsEngine,
         entity: Entity,
     ) {
-        // Phase 1: Remove components to mark entity as inactive
+        // Phase 1: Despawn to remove all components and invalidate the handle
         // This prevents it from being rendered even if cleanup fails
-        world.remove_component::<TransformComponent>(entity);
-        world.remove_component::<RenderableComponent>(entity);
-        world.remove_component::<crate::ecs::components::LightComponent>(entity);
+        world.despawn(entity);
         
         // Phase 2: Release GPU resources via GraphicsEngine (can fail gracefully)
         // GraphicsEngine tracks entity→(mesh_type, handle) mapping internally
@@ -504,6 +593,39 @@ mod tests {
         assert_eq!(queue.batch_count(), 2); // 2 different materials
     }
     
+    #[test]
+    fn test_build_render_queue_culls_to_camera() {
+        let mut world = World::new();
+        let mut scene_manager = SceneManager::new();
+        
+        // Camera at the origin looking down -Z; one cube in front, two behind
+        for z in [-10.0, 10.0, 20.0] {
+            let entity = world.create_entity();
+            world.add_component(entity, TransformComponent::from_position(Vec3::new(0.0, 0.0, z)));
+            world.add_component(entity, RenderableComponent::new(
+                create_test_material(),
+                crate::render::primitives::Mesh::cube(),
+                MeshType::Cube,
+            ));
+        }
+        scene_manager.sync_from_world(&mut world);
+        
+        // No frustum yet: everything is queued
+        let queue = scene_manager.build_render_queue();
+        assert_eq!(queue.total_object_count(), 3);
+        assert!(queue.culled_entities().is_empty());
+        assert_eq!(scene_manager.stats().culled, 0);
+        
+        let mut camera = Camera::perspective(Vec3::zeros(), 60.0, 1.0, 0.1, 100.0);
+        camera.look_at(Vec3::new(0.0, 0.0, -1.0), Vec3::y());
+        scene_manager.set_camera(&camera);
+        
+        let queue = scene_manager.build_render_queue();
+        assert_eq!(queue.total_object_count(), 1);
+        assert_eq!(queue.culled_entities().len(), 2);
+        assert_eq!(scene_manager.stats(), SceneStats { total: 3, visible: 1, culled: 2 });
+    }
+    
     #[test]
     fn test_automatic_change_detection_integration() {
         let mut world = World::new();
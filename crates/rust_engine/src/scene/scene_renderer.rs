@@ -91,10 +91,10 @@ impl SceneRenderer {
     ///
     /// This is the main rendering entry point. It:
     /// 1. Syncs ECS world to Scene Manager (if auto_sync enabled)
-    /// 2. Builds optimized render queue from Scene Manager
-    /// 3. Performs frustum culling (if enabled)
-    /// 4. Records GPU commands via VulkanRenderer
-    /// 5. Submits and presents the frame
+    /// 2. Builds optimized render queue from Scene Manager, frustum culled
+    ///    against the current view-projection (if enabled)
+    /// 3. Records GPU commands via VulkanRenderer
+    /// 4. Submits and presents the frame
     ///
     /// # Arguments
     /// * `world` - The ECS world to sync from
@@ -112,47 +112,24 @@ impl SceneRenderer {
             scene_manager.sync_from_world(world);
         }
         
-        // Step 2: Build optimized render queue
+        // Step 2: Build optimized render queue, culled by the scene graph
+        let frustum = self.config.enable_frustum_culling
+            .then(|| Frustum::from_matrix(&self.view_projection));
+        scene_manager.set_view_frustum(frustum);
         let render_queue = scene_manager.build_render_queue();
         
-        // Step 3: Perform frustum culling (if enabled)
-        let culled_queue = if self.config.enable_frustum_culling {
-            self.cull_render_queue(&render_queue)
-        } else {
-            render_queue
-        };
-        
-        // Step 4: Limit object count for performance
-        let limited_queue = self.limit_render_queue(culled_queue);
+        // Step 3: Limit object count for performance
+        let limited_queue = self.limit_render_queue(render_queue);
         
-        // Step 5: Record and submit GPU commands
+        // Step 4: Record and submit GPU commands
         self.execute_render_queue(&limited_queue)?;
         
-        // Step 6: Clear dirty flags after successful render
+        // Step 5: Clear dirty flags after successful render
         self.clear_dirty_flags(scene_manager);
         
         Ok(())
     }
     
-    /// Perform frustum culling on the render queue
-    ///
-    /// Removes objects outside the view frustum to avoid rendering
-    /// geometry that won't be visible.
-    fn cull_render_queue(&self, _queue: &RenderQueue) -> RenderQueue {
-        // Extract frustum from view-projection matrix
-        let _frustum = Frustum::from_matrix(&self.view_projection);
-        
-        // TODO: Implement actual culling by testing object bounds against frustum
-        // For now, return unmodified queue
-        // In a full implementation:
-        // 1. Get AABB for each object from scene graph
-        // 2. Test AABB against frustum planes
-        // 3. Keep only objects that pass the test
-        
-        // Placeholder: Just clone the queue
-        RenderQueue::new() // Simplified for Phase 3
-    }
-    
     /// Limit render queue to max_objects_per_frame
     fn limit_render_queue(&self, queue: RenderQueue) -> RenderQueue {
         // TODO: Implement proper limiting by truncating batches
@@ -0,0 +1,696 @@
+//! Dynamic AABB tree (bounding volume hierarchy)
+//!
+//! GEA 13.3.2: a bounding volume hierarchy groups nearby objects under
+//! enclosing volumes so a query can reject whole groups at once. This is the
+//! incremental variant used by most physics engines: each leaf stores a "fat"
+//! AABB (the entity's bounds grown by a margin), so an entity that moves a
+//! little stays inside its leaf and the tree is left untouched. Only when it
+//! leaves its fat bounds is the leaf removed and reinserted, refitting and
+//! rebalancing the ancestors on its path.
+//!
+//! Works well for clustered scenes and widely varying object sizes (fleets,
+//! stations next to fighters) and needs no world bounds up front.
+
+use crate::ecs::Entity;
+use crate::foundation::math::Vec3;
+use crate::scene::AABB;
+use crate::spatial::spatial_query::ray_sphere_entry;
+use crate::spatial::{EntityFilter, SpatialQuery};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Index marking a missing parent or child
+const NULL: usize = usize::MAX;
+
+/// Configuration for dynamic AABB tree behavior
+#[derive(Debug, Clone)]
+pub struct AabbTreeConfig {
+    /// Distance leaf bounds are grown by on every side
+    ///
+    /// Larger margins mean fewer reinsertions for moving entities but more
+    /// false positives in queries.
+    pub fat_margin: f32,
+}
+
+impl Default for AabbTreeConfig {
+    fn default() -> Self {
+        Self { fat_margin: 1.0 }
+    }
+}
+
+/// Tree node; leaves hold one entity, internal nodes exactly two children
+#[derive(Debug, Clone)]
+struct TreeNode {
+    /// Fat bounds for leaves, union of the children otherwise
+    aabb: AABB,
+    parent: usize,
+    left: usize,
+    right: usize,
+    /// Leaves have height 0
+    height: i32,
+    entity: Option<Entity>,
+    /// Exact bounding sphere of the leaf's entity
+    position: Vec3,
+    radius: f32,
+}
+
+impl TreeNode {
+    fn is_leaf(&self) -> bool {
+        self.left == NULL
+    }
+}
+
+/// Node waiting in a best-first traversal, ordered so the heap pops the smallest key
+///
+/// The key is a lower bound for everything below the node: the distance to
+/// its bounds for internal nodes, the exact distance for leaves.
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    key: f32,
+    index: usize,
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+/// Dynamic AABB tree implementing `SpatialQuery`
+#[derive(Debug, Clone)]
+pub struct DynamicAabbTree {
+    config: AabbTreeConfig,
+    /// Node arena; freed slots are recycled through `free`
+    nodes: Vec<TreeNode>,
+    free: Vec<usize>,
+    root: usize,
+    /// Leaf node of each entity
+    leaves: HashMap<Entity, usize>,
+}
+
+impl Default for DynamicAabbTree {
+    fn default() -> Self {
+        Self::new(AabbTreeConfig::default())
+    }
+}
+
+impl DynamicAabbTree {
+    /// Create an empty tree
+    pub fn new(config: AabbTreeConfig) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NULL,
+            leaves: HashMap::new(),
+        }
+    }
+
+    /// Get the tree configuration
+    pub fn config(&self) -> &AabbTreeConfig {
+        &self.config
+    }
+
+    /// Height of the tree (0 for a single leaf or an empty tree)
+    pub fn height(&self) -> i32 {
+        if self.root == NULL { 0 } else { self.nodes[self.root].height }
+    }
+
+    /// Bounds of every node, with its depth (for visualization)
+    pub fn node_bounds(&self) -> Vec<(AABB, u32)> {
+        let mut bounds = Vec::new();
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push((self.root, 0));
+        }
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            bounds.push((node.aabb, depth));
+            if !node.is_leaf() {
+                stack.push((node.left, depth + 1));
+                stack.push((node.right, depth + 1));
+            }
+        }
+        bounds
+    }
+
+    /// Query with custom tests, e.g. against a view frustum
+    ///
+    /// `node_test` is applied to the bounds of every visited node (and must
+    /// be conservative), `sphere_test` to the bounding sphere of each
+    /// candidate entity.
+    pub fn query_bounds(
+        &self,
+        node_test: impl Fn(&AABB) -> bool,
+        sphere_test: impl Fn(Vec3, f32) -> bool,
+    ) -> Vec<Entity> {
+        let mut results = Vec::new();
+        self.visit(node_test, |leaf| {
+            if sphere_test(leaf.position, leaf.radius) {
+                results.extend(leaf.entity);
+            }
+        });
+        results
+    }
+
+    fn fat_aabb(&self, position: Vec3, radius: f32) -> AABB {
+        AABB::from_sphere(position, radius + self.config.fat_margin)
+    }
+
+    fn allocate(&mut self, node: TreeNode) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) {
+        self.nodes[index].entity = None;
+        self.free.push(index);
+    }
+
+    /// Point `parent`'s link to `old` at `new` instead (or the root if there is no parent)
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if parent == NULL {
+            self.root = new;
+        } else if self.nodes[parent].left == old {
+            self.nodes[parent].left = new;
+        } else {
+            self.nodes[parent].right = new;
+        }
+    }
+
+    /// Insert a leaf, picking the sibling with the lowest surface area cost
+    fn insert_leaf(&mut self, leaf: usize) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf].parent = NULL;
+            return;
+        }
+
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = self.root;
+        while !self.nodes[index].is_leaf() {
+            let node = &self.nodes[index];
+            let area = node.aabb.surface_area();
+            let combined_area = node.aabb.union(&leaf_aabb).surface_area();
+
+            // Cost of making the leaf a sibling of this node, and the cost
+            // every level below pays for growing this node
+            let cost = 2.0 * combined_area;
+            let inheritance = 2.0 * (combined_area - area);
+
+            let child_cost = |child: usize| {
+                let child = &self.nodes[child];
+                let grown = child.aabb.union(&leaf_aabb).surface_area();
+                if child.is_leaf() {
+                    grown + inheritance
+                } else {
+                    grown - child.aabb.surface_area() + inheritance
+                }
+            };
+            let cost_left = child_cost(node.left);
+            let cost_right = child_cost(node.right);
+
+            if cost < cost_left && cost < cost_right {
+                break;
+            }
+            index = if cost_left < cost_right { node.left } else { node.right };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(TreeNode {
+            aabb: self.nodes[sibling].aabb.union(&leaf_aabb),
+            parent: old_parent,
+            left: sibling,
+            right: leaf,
+            height: self.nodes[sibling].height + 1,
+            entity: None,
+            position: Vec3::zeros(),
+            radius: 0.0,
+        });
+        self.replace_child(old_parent, sibling, new_parent);
+        self.nodes[sibling].parent = new_parent;
+        self.nodes[leaf].parent = new_parent;
+
+        self.refit(new_parent);
+    }
+
+    /// Detach a leaf, splicing its sibling into the parent's place
+    fn remove_leaf(&mut self, leaf: usize) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent;
+        let grandparent = self.nodes[parent].parent;
+        let sibling = if self.nodes[parent].left == leaf {
+            self.nodes[parent].right
+        } else {
+            self.nodes[parent].left
+        };
+
+        self.replace_child(grandparent, parent, sibling);
+        self.nodes[sibling].parent = grandparent;
+        self.release(parent);
+        self.refit(grandparent);
+    }
+
+    /// Rebalance and recompute bounds from `index` up to the root
+    fn refit(&mut self, mut index: usize) {
+        while index != NULL {
+            index = self.balance(index);
+            self.recompute(index);
+            index = self.nodes[index].parent;
+        }
+    }
+
+    fn recompute(&mut self, index: usize) {
+        let (left, right) = (self.nodes[index].left, self.nodes[index].right);
+        self.nodes[index].aabb = self.nodes[left].aabb.union(&self.nodes[right].aabb);
+        self.nodes[index].height = 1 + self.nodes[left].height.max(self.nodes[right].height);
+    }
+
+    /// Rotate the taller child of `index` above it if the subtree is unbalanced
+    ///
+    /// Returns the node now at `index`'s old position.
+    fn balance(&mut self, index: usize) -> usize {
+        let node = &self.nodes[index];
+        if node.is_leaf() || node.height < 2 {
+            return index;
+        }
+        let (left, right) = (node.left, node.right);
+        let balance = self.nodes[right].height - self.nodes[left].height;
+        if balance > 1 {
+            self.rotate_up(index, right, left)
+        } else if balance < -1 {
+            self.rotate_up(index, left, right)
+        } else {
+            index
+        }
+    }
+
+    /// Promote `up` (a child of `index`) into `index`'s place
+    ///
+    /// `index` keeps `other` and takes the shorter of `up`'s children; `up`
+    /// keeps the taller one.
+    fn rotate_up(&mut self, index: usize, up: usize, other: usize) -> usize {
+        let (f, g) = (self.nodes[up].left, self.nodes[up].right);
+        let (keep, give) = if self.nodes[f].height > self.nodes[g].height { (f, g) } else { (g, f) };
+
+        let parent = self.nodes[index].parent;
+        self.replace_child(parent, index, up);
+        self.nodes[up].parent = parent;
+        self.nodes[up].left = index;
+        self.nodes[up].right = keep;
+
+        self.nodes[index].parent = up;
+        self.nodes[index].left = other;
+        self.nodes[index].right = give;
+        self.nodes[give].parent = index;
+
+        self.recompute(index);
+        self.recompute(up);
+        up
+    }
+
+    /// Visit leaves in increasing order of `key`, stopping when `on_leaf` returns false
+    ///
+    /// `key` returns `None` to prune a node; for internal nodes it must not
+    /// exceed the key of any leaf below them.
+    fn visit_best_first(
+        &self,
+        key: impl Fn(&TreeNode) -> Option<f32>,
+        mut on_leaf: impl FnMut(&TreeNode, f32) -> bool,
+    ) {
+        if self.root == NULL {
+            return;
+        }
+        let mut heap = BinaryHeap::new();
+        if let Some(root_key) = key(&self.nodes[self.root]) {
+            heap.push(Pending { key: root_key, index: self.root });
+        }
+        while let Some(Pending { key: node_key, index }) = heap.pop() {
+            let node = &self.nodes[index];
+            if node.is_leaf() {
+                if !on_leaf(node, node_key) {
+                    return;
+                }
+                continue;
+            }
+            for child in [node.left, node.right] {
+                if let Some(child_key) = key(&self.nodes[child]) {
+                    heap.push(Pending { key: child_key, index: child });
+                }
+            }
+        }
+    }
+
+    /// Visit every leaf whose fat bounds pass `node_test`
+    fn visit(&self, node_test: impl Fn(&AABB) -> bool, mut on_leaf: impl FnMut(&TreeNode)) {
+        if self.root == NULL {
+            return;
+        }
+        let mut stack = vec![self.root];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node_test(&node.aabb) {
+                continue;
+            }
+            if node.is_leaf() {
+                on_leaf(node);
+            } else {
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+    }
+}
+
+impl SpatialQuery for DynamicAabbTree {
+    fn insert(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        if self.leaves.contains_key(&entity) {
+            self.update(entity, position, radius);
+            return;
+        }
+        let leaf = self.allocate(TreeNode {
+            aabb: self.fat_aabb(position, radius),
+            parent: NULL,
+            left: NULL,
+            right: NULL,
+            height: 0,
+            entity: Some(entity),
+            position,
+            radius,
+        });
+        self.insert_leaf(leaf);
+        self.leaves.insert(entity, leaf);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(leaf) = self.leaves.remove(&entity) {
+            self.remove_leaf(leaf);
+            self.release(leaf);
+        }
+    }
+
+    fn update(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        let Some(&leaf) = self.leaves.get(&entity) else {
+            self.insert(entity, position, radius);
+            return;
+        };
+
+        self.nodes[leaf].position = position;
+        self.nodes[leaf].radius = radius;
+
+        // Still inside the fat bounds: nothing in the tree changes
+        if self.nodes[leaf].aabb.contains(&AABB::from_sphere(position, radius)) {
+            return;
+        }
+
+        self.remove_leaf(leaf);
+        self.nodes[leaf].aabb = self.fat_aabb(position, radius);
+        self.insert_leaf(leaf);
+    }
+
+    fn query_nearby(&self, entity: Entity) -> Vec<Entity> {
+        match self.get_entity_data(entity) {
+            Some((position, radius)) => self.query_sphere(position, radius),
+            None => Vec::new(),
+        }
+    }
+
+    fn query_sphere(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        let mut results = Vec::new();
+        self.visit(
+            |aabb| aabb.intersects_sphere(center, radius),
+            |leaf| {
+                let combined_radius = radius + leaf.radius;
+                if (leaf.position - center).magnitude_squared() <= combined_radius * combined_radius {
+                    results.extend(leaf.entity);
+                }
+            },
+        );
+        results
+    }
+
+    fn query_aabb(&self, aabb: &AABB) -> Vec<Entity> {
+        let mut results = Vec::new();
+        self.visit(
+            |bounds| bounds.intersects(aabb),
+            |leaf| {
+                if aabb.intersects(&AABB::from_sphere(leaf.position, leaf.radius)) {
+                    results.extend(leaf.entity);
+                }
+            },
+        );
+        results
+    }
+
+    fn query_ray(&self, origin: Vec3, direction: Vec3) -> Vec<Entity> {
+        let mut results = Vec::new();
+        self.visit(
+            |aabb| aabb.intersect_ray(origin, direction).is_some(),
+            |leaf| {
+                if ray_reaches_sphere(origin, direction, leaf.position, leaf.radius) {
+                    results.extend(leaf.entity);
+                }
+            },
+        );
+        results
+    }
+
+    fn query_k_nearest(&self, point: Vec3, k: usize, filter: EntityFilter<'_>) -> Vec<(Entity, f32)> {
+        let mut results = Vec::with_capacity(k.min(self.leaves.len()));
+        if k == 0 {
+            return results;
+        }
+        self.visit_best_first(
+            |node| match node.entity {
+                Some(entity) if filter(entity) => {
+                    Some(((node.position - point).magnitude() - node.radius).max(0.0))
+                }
+                Some(_) => None,
+                None => Some(node.aabb.distance_to_point(point)),
+            },
+            |leaf, distance| {
+                results.extend(leaf.entity.map(|entity| (entity, distance)));
+                results.len() < k
+            },
+        );
+        results
+    }
+
+    fn query_ray_sorted(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: EntityFilter<'_>,
+        visit: &mut dyn FnMut(Entity, f32) -> bool,
+    ) {
+        let Some(direction) = direction.try_normalize(f32::EPSILON) else {
+            return;
+        };
+        self.visit_best_first(
+            |node| {
+                let entry = match node.entity {
+                    Some(entity) if filter(entity) => {
+                        ray_sphere_entry(origin, direction, node.position, node.radius)?
+                    }
+                    Some(_) => return None,
+                    None => node.aabb.intersect_ray(origin, direction)?,
+                };
+                (entry <= max_distance).then_some(entry)
+            },
+            |leaf, entry| leaf.entity.is_none_or(|entity| visit(entity, entry)),
+        );
+    }
+
+    fn get_entity_data(&self, entity: Entity) -> Option<(Vec3, f32)> {
+        self.leaves.get(&entity).map(|&leaf| (self.nodes[leaf].position, self.nodes[leaf].radius))
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.leaves.clear();
+        self.root = NULL;
+    }
+
+    fn entity_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Check whether a ray (not necessarily normalized) passes within `radius` of `center`
+pub(super) fn ray_reaches_sphere(origin: Vec3, direction: Vec3, center: Vec3, radius: f32) -> bool {
+    let to_center = center - origin;
+    let length_sq = direction.magnitude_squared();
+    let t = if length_sq > 0.0 { (to_center.dot(&direction) / length_sq).max(0.0) } else { 0.0 };
+    (to_center - direction * t).magnitude_squared() <= radius * radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic scattered positions for comparing against brute force
+    fn scatter(count: u32) -> Vec<(Entity, Vec3, f32)> {
+        let mut seed = 12345u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32
+        };
+        (0..count)
+            .map(|id| {
+                let position = Vec3::new(next(), next(), next()) * 100.0 - Vec3::new(50.0, 50.0, 50.0);
+                (Entity::new(id, 0), position, 0.5 + next() * 3.0)
+            })
+            .collect()
+    }
+
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort_by_key(|entity| entity.id());
+        entities
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let bodies = scatter(300);
+        let mut tree = DynamicAabbTree::default();
+        for &(entity, position, radius) in &bodies {
+            tree.insert(entity, position, radius);
+        }
+        assert_eq!(tree.entity_count(), 300);
+        // Balancing keeps the tree logarithmic even for scattered insertion orders
+        assert!(tree.height() < 20, "height {}", tree.height());
+
+        let center = Vec3::new(5.0, -3.0, 10.0);
+        let expected: Vec<_> = bodies.iter()
+            .filter(|(_, position, radius)| (position - center).magnitude() <= 15.0 + radius)
+            .map(|(entity, _, _)| *entity)
+            .collect();
+        assert_eq!(sorted(tree.query_sphere(center, 15.0)), sorted(expected));
+
+        let (entity, position, radius) = bodies[7];
+        let nearby = tree.query_nearby(entity);
+        assert!(nearby.contains(&entity));
+        for (other, other_position, other_radius) in &bodies {
+            let touching = (position - other_position).magnitude() <= radius + other_radius;
+            assert_eq!(nearby.contains(other), touching);
+        }
+    }
+
+    #[test]
+    fn test_small_moves_stay_in_fat_bounds() {
+        let mut tree = DynamicAabbTree::new(AabbTreeConfig { fat_margin: 2.0 });
+        let a = Entity::new(1, 0);
+        let b = Entity::new(2, 0);
+        tree.insert(a, Vec3::zeros(), 1.0);
+        tree.insert(b, Vec3::new(20.0, 0.0, 0.0), 1.0);
+        let fat = tree.nodes[tree.leaves[&a]].aabb;
+
+        // Within the margin only the stored sphere changes
+        tree.update(a, Vec3::new(1.5, 0.0, 0.0), 1.0);
+        assert_eq!(tree.nodes[tree.leaves[&a]].aabb.min, fat.min);
+        assert_eq!(tree.get_entity_data(a), Some((Vec3::new(1.5, 0.0, 0.0), 1.0)));
+        assert!(tree.query_sphere(Vec3::zeros(), 0.2).is_empty());
+
+        // Leaving the fat bounds reinserts the leaf
+        tree.update(a, Vec3::new(19.0, 0.0, 0.0), 1.0);
+        assert!(tree.nodes[tree.leaves[&a]].aabb.contains_point(Vec3::new(19.0, 0.0, 0.0)));
+        assert_eq!(sorted(tree.query_nearby(b)), vec![a, b]);
+
+        tree.remove(a);
+        tree.remove(b);
+        assert_eq!(tree.entity_count(), 0);
+        assert_eq!(tree.height(), 0);
+    }
+
+    #[test]
+    fn test_ray_and_aabb_queries() {
+        let mut tree = DynamicAabbTree::default();
+        let on_ray = Entity::new(1, 0);
+        let off_ray = Entity::new(2, 0);
+        let behind = Entity::new(3, 0);
+        tree.insert(on_ray, Vec3::new(10.0, 0.5, 0.0), 1.0);
+        tree.insert(off_ray, Vec3::new(10.0, 5.0, 0.0), 1.0);
+        tree.insert(behind, Vec3::new(-10.0, 0.0, 0.0), 1.0);
+
+        assert_eq!(tree.query_ray(Vec3::zeros(), Vec3::new(2.0, 0.0, 0.0)), vec![on_ray]);
+
+        let region = AABB::new(Vec3::new(8.0, 3.0, -1.0), Vec3::new(12.0, 7.0, 1.0));
+        assert_eq!(tree.query_aabb(&region), vec![off_ray]);
+    }
+
+    #[test]
+    fn test_best_first_queries_match_brute_force() {
+        let mut bodies = scatter(300);
+        // Guarantee a few odd entities along the cast ray
+        for (id, x) in [(1001, -20.0), (1003, 0.0), (1005, 20.0)] {
+            bodies.push((Entity::new(id, 0), Vec3::new(x, 0.0, 0.0), 1.0));
+        }
+        let mut tree = DynamicAabbTree::default();
+        for &(entity, position, radius) in &bodies {
+            tree.insert(entity, position, radius);
+        }
+        let odd = |entity: Entity| entity.id() % 2 == 1;
+
+        let point = Vec3::new(-7.0, 12.0, 3.0);
+        let mut expected: Vec<_> = bodies.iter()
+            .filter(|(entity, _, _)| odd(*entity))
+            .map(|(entity, position, radius)| (*entity, ((position - point).magnitude() - radius).max(0.0)))
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        expected.truncate(5);
+        assert_eq!(tree.query_k_nearest(point, 5, &odd), expected);
+
+        // Front to back, stopping after three entries
+        let origin = Vec3::new(-60.0, 0.0, 0.0);
+        let mut entries = Vec::new();
+        tree.query_ray_sorted(origin, Vec3::new(2.0, 0.0, 0.0), 200.0, &odd, &mut |entity, entry| {
+            entries.push((entity, entry));
+            entries.len() < 3
+        });
+        let direction = Vec3::x();
+        let mut expected: Vec<_> = bodies.iter()
+            .filter(|(entity, _, _)| odd(*entity))
+            .filter_map(|&(entity, position, radius)| Some((entity, ray_sphere_entry(origin, direction, position, radius)?)))
+            .collect();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        expected.truncate(3);
+        assert_eq!(entries, expected);
+    }
+}
@@ -0,0 +1,408 @@
+//! Hashed uniform grid
+//!
+//! GEA 13.3.2: space is divided into equal cells and each entity is listed in
+//! every cell its bounds touch, so a query only looks at the cells it covers.
+//! Cells live in a hash map keyed by their integer coordinates, which keeps
+//! the grid sparse and unbounded.
+//!
+//! Works best when objects have similar sizes and are spread out (asteroid
+//! fields, debris). Entities covering more than `max_cells_per_entity` cells
+//! are kept in a separate list that every query checks.
+
+use crate::ecs::Entity;
+use crate::foundation::math::Vec3;
+use crate::scene::AABB;
+use crate::spatial::SpatialQuery;
+use crate::spatial::aabb_tree::ray_reaches_sphere;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+/// Integer coordinates of a grid cell
+type Cell = [i32; 3];
+
+/// Configuration for hashed grid behavior
+#[derive(Debug, Clone)]
+pub struct HashGridConfig {
+    /// Edge length of a cell; roughly the diameter of a typical entity works well
+    pub cell_size: f32,
+
+    /// Entities covering more cells than this go into the oversized list
+    pub max_cells_per_entity: usize,
+}
+
+impl Default for HashGridConfig {
+    fn default() -> Self {
+        Self {
+            cell_size: 10.0,
+            max_cells_per_entity: 64,
+        }
+    }
+}
+
+/// Stored sphere and the cells it was filed under
+#[derive(Debug, Clone, Copy)]
+struct GridEntry {
+    position: Vec3,
+    radius: f32,
+    /// Inclusive cell range, `None` for oversized entities
+    cells: Option<(Cell, Cell)>,
+}
+
+/// Hashed uniform grid implementing `SpatialQuery`
+#[derive(Debug, Clone)]
+pub struct HashGrid {
+    config: HashGridConfig,
+    cells: HashMap<Cell, Vec<Entity>>,
+    entries: HashMap<Entity, GridEntry>,
+    oversized: Vec<Entity>,
+    /// Bounds of everything inserted since the last clear (limits ray marching)
+    bounds: Option<AABB>,
+}
+
+impl Default for HashGrid {
+    fn default() -> Self {
+        Self::new(HashGridConfig::default())
+    }
+}
+
+impl HashGrid {
+    /// Create an empty grid
+    pub fn new(config: HashGridConfig) -> Self {
+        Self {
+            config: HashGridConfig {
+                cell_size: config.cell_size.max(f32::EPSILON),
+                ..config
+            },
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            oversized: Vec::new(),
+            bounds: None,
+        }
+    }
+
+    /// Get the grid configuration
+    pub fn config(&self) -> &HashGridConfig {
+        &self.config
+    }
+
+    /// Number of cells holding at least one entity
+    pub fn occupied_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Bounds of every occupied cell (for visualization)
+    pub fn cell_bounds(&self) -> Vec<AABB> {
+        let size = self.config.cell_size;
+        self.cells.keys()
+            .map(|cell| {
+                let min = Vec3::new(cell[0] as f32, cell[1] as f32, cell[2] as f32) * size;
+                AABB::new(min, min + Vec3::new(size, size, size))
+            })
+            .collect()
+    }
+
+    fn cell_of(&self, point: Vec3) -> Cell {
+        let size = self.config.cell_size;
+        [
+            (point.x / size).floor() as i32,
+            (point.y / size).floor() as i32,
+            (point.z / size).floor() as i32,
+        ]
+    }
+
+    fn cell_range(&self, aabb: &AABB) -> (Cell, Cell) {
+        (self.cell_of(aabb.min), self.cell_of(aabb.max))
+    }
+
+    fn add_to_cells(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        let aabb = AABB::from_sphere(position, radius);
+        self.bounds = Some(self.bounds.map_or(aabb, |bounds| bounds.union(&aabb)));
+
+        let (min, max) = self.cell_range(&aabb);
+        let cells = if range_len(min, max) > self.config.max_cells_per_entity {
+            self.oversized.push(entity);
+            None
+        } else {
+            for_each_cell(min, max, |cell| self.cells.entry(cell).or_default().push(entity));
+            Some((min, max))
+        };
+        self.entries.insert(entity, GridEntry { position, radius, cells });
+    }
+
+    fn remove_from_cells(&mut self, entity: Entity, entry: &GridEntry) {
+        match entry.cells {
+            Some((min, max)) => for_each_cell(min, max, |cell| {
+                if let Some(list) = self.cells.get_mut(&cell) {
+                    list.retain(|&other| other != entity);
+                    if list.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }),
+            None => self.oversized.retain(|&other| other != entity),
+        }
+    }
+
+    /// Visit each entity filed in cells overlapping `[min, max]` once
+    ///
+    /// An entity is reported only from the first cell of the overlap between
+    /// its own range and the query range, so no deduplication set is needed.
+    fn visit_range(&self, min: Cell, max: Cell, mut on_entity: impl FnMut(Entity, &GridEntry)) {
+        let mut visit_cell = |cell: Cell, list: &Vec<Entity>| {
+            for &entity in list {
+                let entry = &self.entries[&entity];
+                if let Some((entity_min, _)) = entry.cells {
+                    let first = [
+                        entity_min[0].max(min[0]),
+                        entity_min[1].max(min[1]),
+                        entity_min[2].max(min[2]),
+                    ];
+                    if first == cell {
+                        on_entity(entity, entry);
+                    }
+                }
+            }
+        };
+
+        // Large queries walk the occupied cells instead of the (mostly empty) range
+        if range_len(min, max) > self.cells.len() {
+            for (cell, list) in &self.cells {
+                if (0..3).all(|axis| cell[axis] >= min[axis] && cell[axis] <= max[axis]) {
+                    visit_cell(*cell, list);
+                }
+            }
+        } else {
+            for_each_cell(min, max, |cell| {
+                if let Some(list) = self.cells.get(&cell) {
+                    visit_cell(cell, list);
+                }
+            });
+        }
+
+        for &entity in &self.oversized {
+            on_entity(entity, &self.entries[&entity]);
+        }
+    }
+}
+
+impl SpatialQuery for HashGrid {
+    fn insert(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        if let Some(entry) = self.entries.remove(&entity) {
+            self.remove_from_cells(entity, &entry);
+        }
+        self.add_to_cells(entity, position, radius);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(entry) = self.entries.remove(&entity) {
+            self.remove_from_cells(entity, &entry);
+        }
+    }
+
+    fn update(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        let aabb = AABB::from_sphere(position, radius);
+        let (min, max) = self.cell_range(&aabb);
+        let oversized = range_len(min, max) > self.config.max_cells_per_entity;
+        if let Some(entry) = self.entries.get_mut(&entity) {
+            // Same cells: only the stored sphere changes
+            let unchanged = match entry.cells {
+                Some(range) => range == (min, max),
+                None => oversized,
+            };
+            if unchanged {
+                entry.position = position;
+                entry.radius = radius;
+                self.bounds = Some(self.bounds.map_or(aabb, |bounds| bounds.union(&aabb)));
+                return;
+            }
+        }
+        self.insert(entity, position, radius);
+    }
+
+    fn query_nearby(&self, entity: Entity) -> Vec<Entity> {
+        match self.get_entity_data(entity) {
+            Some((position, radius)) => self.query_sphere(position, radius),
+            None => Vec::new(),
+        }
+    }
+
+    fn query_sphere(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        let (min, max) = self.cell_range(&AABB::from_sphere(center, radius));
+        let mut results = Vec::new();
+        self.visit_range(min, max, |entity, entry| {
+            let combined_radius = radius + entry.radius;
+            if (entry.position - center).magnitude_squared() <= combined_radius * combined_radius {
+                results.push(entity);
+            }
+        });
+        results
+    }
+
+    fn query_aabb(&self, aabb: &AABB) -> Vec<Entity> {
+        let (min, max) = self.cell_range(aabb);
+        let mut results = Vec::new();
+        self.visit_range(min, max, |entity, entry| {
+            if aabb.intersects(&AABB::from_sphere(entry.position, entry.radius)) {
+                results.push(entity);
+            }
+        });
+        results
+    }
+
+    fn query_ray(&self, origin: Vec3, direction: Vec3) -> Vec<Entity> {
+        let mut results: Vec<Entity> = self.oversized.iter()
+            .copied()
+            .filter(|entity| {
+                let entry = &self.entries[entity];
+                ray_reaches_sphere(origin, direction, entry.position, entry.radius)
+            })
+            .collect();
+
+        let Some(bounds) = self.bounds else {
+            return results;
+        };
+        let Some((t_enter, t_exit)) = bounds.ray_span(origin, direction) else {
+            return results;
+        };
+
+        // Amanatides-Woo traversal of the cells the ray crosses inside the bounds
+        let (bounds_min, bounds_max) = self.cell_range(&bounds);
+        let start = self.cell_of(origin + direction * t_enter);
+        let mut cell = [0; 3];
+        let mut step = [0; 3];
+        let mut t_max = [f32::INFINITY; 3];
+        let mut t_delta = [f32::INFINITY; 3];
+        let size = self.config.cell_size;
+        for axis in 0..3 {
+            cell[axis] = start[axis].clamp(bounds_min[axis], bounds_max[axis]);
+            if direction[axis] > 0.0 {
+                step[axis] = 1;
+                t_max[axis] = ((cell[axis] + 1) as f32 * size - origin[axis]) / direction[axis];
+                t_delta[axis] = size / direction[axis];
+            } else if direction[axis] < 0.0 {
+                step[axis] = -1;
+                t_max[axis] = (cell[axis] as f32 * size - origin[axis]) / direction[axis];
+                t_delta[axis] = -size / direction[axis];
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let max_steps = (0..3).map(|axis| (bounds_max[axis] - bounds_min[axis]) as usize + 1).sum::<usize>();
+        for _ in 0..=max_steps {
+            if let Some(list) = self.cells.get(&cell) {
+                for &entity in list {
+                    let entry = &self.entries[&entity];
+                    if seen.insert(entity) && ray_reaches_sphere(origin, direction, entry.position, entry.radius) {
+                        results.push(entity);
+                    }
+                }
+            }
+
+            let axis = if t_max[0] < t_max[1] {
+                if t_max[0] < t_max[2] { 0 } else { 2 }
+            } else if t_max[1] < t_max[2] { 1 } else { 2 };
+            if t_max[axis] > t_exit {
+                break;
+            }
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+        }
+
+        results
+    }
+
+    fn get_entity_data(&self, entity: Entity) -> Option<(Vec3, f32)> {
+        self.entries.get(&entity).map(|entry| (entry.position, entry.radius))
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        self.oversized.clear();
+        self.bounds = None;
+    }
+
+    fn entity_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Number of cells in an inclusive range
+fn range_len(min: Cell, max: Cell) -> usize {
+    (0..3)
+        .map(|axis| (max[axis] as i64 - min[axis] as i64 + 1).max(0) as usize)
+        .fold(1usize, usize::saturating_mul)
+}
+
+fn for_each_cell(min: Cell, max: Cell, mut f: impl FnMut(Cell)) {
+    for x in min[0]..=max[0] {
+        for y in min[1]..=max[1] {
+            for z in min[2]..=max[2] {
+                f([x, y, z]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort_by_key(|entity| entity.id());
+        entities
+    }
+
+    #[test]
+    fn test_entities_spanning_cells_are_reported_once() {
+        let mut grid = HashGrid::new(HashGridConfig { cell_size: 2.0, max_cells_per_entity: 64 });
+        let big = Entity::new(1, 0);
+        let small = Entity::new(2, 0);
+        let far = Entity::new(3, 0);
+        grid.insert(big, Vec3::zeros(), 3.0);
+        grid.insert(small, Vec3::new(3.5, 0.0, 0.0), 0.6);
+        grid.insert(far, Vec3::new(40.0, 0.0, 0.0), 0.5);
+
+        assert_eq!(sorted(grid.query_nearby(small)), vec![big, small]);
+        assert_eq!(sorted(grid.query_sphere(Vec3::zeros(), 10.0)), vec![big, small]);
+        assert_eq!(grid.query_aabb(&AABB::new(Vec3::new(39.0, -1.0, -1.0), Vec3::new(41.0, 1.0, 1.0))), vec![far]);
+
+        // Moving across cells refiles the entity
+        grid.update(far, Vec3::new(1.0, 0.0, 0.0), 0.5);
+        assert_eq!(sorted(grid.query_sphere(Vec3::new(1.0, 0.0, 0.0), 0.1)), vec![big, far]);
+        assert!(grid.query_sphere(Vec3::new(40.0, 0.0, 0.0), 1.0).is_empty());
+
+        grid.remove(big);
+        assert_eq!(grid.entity_count(), 2);
+        assert_eq!(grid.query_sphere(Vec3::new(-2.0, 0.0, 0.0), 0.5), Vec::<Entity>::new());
+    }
+
+    #[test]
+    fn test_oversized_entities_and_rays() {
+        let mut grid = HashGrid::new(HashGridConfig { cell_size: 1.0, max_cells_per_entity: 8 });
+        let planet = Entity::new(1, 0);
+        let rock = Entity::new(2, 0);
+        let off_ray = Entity::new(3, 0);
+        grid.insert(planet, Vec3::new(0.0, 50.0, 0.0), 20.0);
+        grid.insert(rock, Vec3::new(-12.3, 0.4, 0.0), 0.5);
+        grid.insert(off_ray, Vec3::new(-12.0, 3.0, 0.0), 0.5);
+        assert!(grid.occupied_cells() <= 16);
+
+        assert_eq!(grid.query_sphere(Vec3::new(0.0, 31.0, 0.0), 0.5), vec![planet]);
+        assert_eq!(grid.query_ray(Vec3::new(5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)), vec![rock]);
+        assert_eq!(sorted(grid.query_ray(Vec3::new(-12.0, -5.0, 0.0), Vec3::new(0.0, 1.0, 0.0))), vec![planet, rock, off_ray]);
+        assert!(grid.query_ray(Vec3::new(-30.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)).is_empty());
+
+        grid.clear();
+        assert_eq!(grid.entity_count(), 0);
+        assert_eq!(grid.occupied_cells(), 0);
+    }
+}
@@ -3,8 +3,12 @@
 //! Provides efficient spatial indexing for collision detection,
 //! ray casting, and proximity queries in 3D space.
 
+mod aabb_tree;
+mod hash_grid;
 mod octree;
 pub mod spatial_query;
 
-pub use octree::{Octree, OctreeNode, OctreeConfig};
-pub use spatial_query::{SpatialQuery, OctreeSpatialQuery};
+pub use aabb_tree::{DynamicAabbTree, AabbTreeConfig};
+pub use hash_grid::{HashGrid, HashGridConfig};
+pub use octree::{Octree, OctreeNode, OctreeConfig, OctreeNodeStats, OctreeStats, MAX_ROOT_EXTENT};
+pub use spatial_query::{SpatialQuery, OctreeSpatialQuery, EntityFilter};
@@ -2,12 +2,27 @@
 //!
 //! Efficiently divides 3D space into hierarchical regions for fast
 //! spatial queries. Each node subdivides into 8 octants when entity
-//! density exceeds a threshold.
+//! density exceeds a threshold, and merges its children back once they
+//! hold few entities again.
+//!
+//! The initial world bounds are only a starting size: inserting an entity
+//! outside them grows the root (doubling it towards the entity), so ships
+//! that fly off never drop out of collision. Entities that cannot be placed
+//! in the tree at all (non-finite positions, or beyond `MAX_ROOT_EXTENT`)
+//! are kept in an overflow list that every query still checks.
 
 use crate::ecs::Entity;
 use crate::foundation::math::Vec3;
 use crate::scene::AABB;
 
+/// Largest half-size the root may grow to; beyond this f32 positions are too
+/// coarse for partitioning to help, so entities go to the overflow list
+pub const MAX_ROOT_EXTENT: f32 = 1.0e7;
+
+/// Smallest half-size the root starts with; flatter bounds are padded so the
+/// root can still grow by doubling along every axis
+pub const MIN_ROOT_EXTENT: f32 = 0.5;
+
 /// Configuration for octree behavior
 #[derive(Debug, Clone)]
 pub struct OctreeConfig {
@@ -39,6 +54,42 @@ pub struct OctreeEntity {
     pub radius: f32,
 }
 
+/// Occupancy of a single octree node (for visualization)
+#[derive(Debug, Clone, Copy)]
+pub struct OctreeNodeStats {
+    /// World-space bounds of the node
+    pub bounds: AABB,
+    /// Depth in the tree (0 = root)
+    pub depth: u32,
+    /// Whether the node has no children
+    pub is_leaf: bool,
+    /// Entities stored directly in the node
+    pub entities: usize,
+    /// Entities stored in the node and all of its descendants
+    pub subtree_entities: usize,
+}
+
+/// Shape and occupancy summary of an octree
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OctreeStats {
+    /// Total number of nodes
+    pub nodes: usize,
+    /// Number of leaf nodes
+    pub leaves: usize,
+    /// Leaves holding no entities
+    pub empty_leaves: usize,
+    /// Deepest node depth
+    pub max_depth: u32,
+    /// Entities stored in the tree nodes
+    pub entities: usize,
+    /// Entities kept in the overflow list
+    pub overflow: usize,
+    /// Most entities held by a single leaf
+    pub max_leaf_entities: usize,
+    /// Average entities per non-empty leaf
+    pub average_leaf_entities: f32,
+}
+
 /// Single node in the octree hierarchy
 #[derive(Debug, Clone)]
 pub struct OctreeNode {
@@ -197,7 +248,10 @@ impl OctreeNode {
     }
     
     /// Remove an entity from this node
-    pub fn remove(&mut self, entity_id: Entity) -> bool {
+    ///
+    /// Children that end up holding few entities are merged back into their
+    /// parent on the way out.
+    pub fn remove(&mut self, entity_id: Entity, config: &OctreeConfig) -> bool {
         // Check if entity is in this node
         if let Some(index) = self.entities.iter().position(|e| e.id == entity_id) {
             self.entities.swap_remove(index);
@@ -205,19 +259,52 @@ impl OctreeNode {
         }
         
         // Check children
+        let removed = match self.children {
+            Some(ref mut children) => children.iter_mut().any(|child| child.remove(entity_id, config)),
+            None => false,
+        };
+        if removed {
+            self.merge_if_sparse(config);
+        }
+        removed
+    }
+    
+    /// Collapse leaf children back into this node once they hold at most half
+    /// of `max_entities_per_node` (the gap to the split threshold avoids
+    /// thrashing when a ship hovers at a boundary)
+    fn merge_if_sparse(&mut self, config: &OctreeConfig) {
+        let Some(ref children) = self.children else {
+            return;
+        };
+        if children.iter().any(|child| !child.is_leaf()) {
+            return;
+        }
+        let count = self.entities.len() + children.iter().map(|child| child.entities.len()).sum::<usize>();
+        if count > config.max_entities_per_node / 2 {
+            return;
+        }
+        
+        if let Some(children) = self.children.take() {
+            for child in *children {
+                self.entities.extend(child.entities);
+            }
+        }
+    }
+    
+    /// Add `offset` to the depth of this node and all descendants
+    fn shift_depth(&mut self, offset: u32) {
+        self.depth += offset;
         if let Some(ref mut children) = self.children {
             for child in children.iter_mut() {
-                if child.remove(entity_id) {
-                    return true;
-                }
+                child.shift_depth(offset);
             }
         }
-        
-        false
     }
     
-    /// Query all entities within a radius of a point
-    pub fn query_radius(&self, center: Vec3, radius: f32, results: &mut Vec<OctreeEntity>) {
+    /// Query all entities whose bounding spheres overlap a sphere
+    /// max_entity_radius: The maximum entity radius in the entire octree, since entities
+    /// are stored by center and may extend beyond their node
+    pub fn query_radius(&self, center: Vec3, radius: f32, max_entity_radius: f32, results: &mut Vec<OctreeEntity>) {
         // Quick AABB check - if sphere doesn't intersect node bounds, skip
         let closest_point = Vec3::new(
             center.x.clamp(self.bounds.min.x, self.bounds.max.x),
@@ -226,7 +313,8 @@ impl OctreeNode {
         );
         
         let distance_sq = (closest_point - center).magnitude_squared();
-        if distance_sq > radius * radius {
+        let reach = radius + max_entity_radius;
+        if distance_sq > reach * reach {
             return; // Sphere doesn't intersect this node
         }
         
@@ -245,7 +333,7 @@ impl OctreeNode {
         // Recursively check children
         if let Some(ref children) = self.children {
             for child in children.iter() {
-                child.query_radius(center, radius, results);
+                child.query_radius(center, radius, max_entity_radius, results);
             }
         }
     }
@@ -335,6 +423,29 @@ impl OctreeNode {
         
         count
     }
+    
+    /// Append occupancy stats for this node and its descendants (pre-order)
+    ///
+    /// Returns the number of entities in this subtree.
+    pub fn collect_stats(&self, stats: &mut Vec<OctreeNodeStats>) -> usize {
+        let index = stats.len();
+        stats.push(OctreeNodeStats {
+            bounds: self.bounds,
+            depth: self.depth,
+            is_leaf: self.is_leaf(),
+            entities: self.entities.len(),
+            subtree_entities: 0,
+        });
+        
+        let mut count = self.entities.len();
+        if let Some(ref children) = self.children {
+            for child in children.iter() {
+                count += child.collect_stats(stats);
+            }
+        }
+        stats[index].subtree_entities = count;
+        count
+    }
 }
 
 /// Octree spatial partitioning structure
@@ -348,19 +459,56 @@ pub struct Octree {
     
     /// Cached maximum entity radius in the tree (updated on insert/remove)
     max_entity_radius: f32,
+    
+    /// Entities that could not be placed in the tree
+    overflow: Vec<OctreeEntity>,
+    
+    /// Bounds the root starts with (and returns to on `clear`)
+    initial_bounds: AABB,
 }
 
 impl Octree {
     /// Create a new octree with given world bounds
-    pub fn new(world_bounds: AABB, config: OctreeConfig) -> Self {
+    ///
+    /// Axes narrower than `MIN_ROOT_EXTENT` (e.g. a zero-size starting box)
+    /// are padded around their center.
+    pub fn new(mut world_bounds: AABB, config: OctreeConfig) -> Self {
+        let center = world_bounds.center();
+        for axis in 0..3 {
+            if world_bounds.extents()[axis] < MIN_ROOT_EXTENT {
+                world_bounds.min[axis] = center[axis] - MIN_ROOT_EXTENT;
+                world_bounds.max[axis] = center[axis] + MIN_ROOT_EXTENT;
+            }
+        }
         Self {
             root: OctreeNode::new(world_bounds, 0),
             config,
             max_entity_radius: 0.0,
+            overflow: Vec::new(),
+            initial_bounds: world_bounds,
         }
     }
     
+    /// Get the octree configuration
+    pub fn config(&self) -> &OctreeConfig {
+        &self.config
+    }
+    
+    /// Current root bounds (grows as entities are inserted further out)
+    pub fn bounds(&self) -> AABB {
+        self.root.bounds
+    }
+    
+    /// Entities kept outside the tree nodes
+    pub fn overflow(&self) -> &[OctreeEntity] {
+        &self.overflow
+    }
+    
     /// Insert an entity into the octree
+    ///
+    /// The root grows to enclose entities outside it. Returns `false` if the
+    /// entity had to go to the overflow list instead (it is still found by
+    /// queries).
     pub fn insert(&mut self, entity_id: Entity, position: Vec3, radius: f32) -> bool {
         let entity = OctreeEntity {
             id: entity_id,
@@ -373,18 +521,60 @@ impl Octree {
             self.max_entity_radius = radius;
         }
         
-        self.root.insert(entity, &self.config)
+        let finite = position.iter().all(|value| value.is_finite());
+        while finite && !self.root.bounds.contains_point(position) && self.root.bounds.extents().max() < MAX_ROOT_EXTENT {
+            self.grow_towards(position);
+        }
+        
+        if finite && self.root.insert(entity, &self.config) {
+            true
+        } else {
+            self.overflow.push(entity);
+            false
+        }
+    }
+    
+    /// Double the root towards `position`, keeping the old root as one octant
+    fn grow_towards(&mut self, position: Vec3) {
+        let old_bounds = self.root.bounds;
+        let size = old_bounds.max - old_bounds.min;
+        let mut bounds = old_bounds;
+        let mut octant = 0;
+        for axis in 0..3 {
+            if position[axis] < old_bounds.min[axis] {
+                // Grow downwards: the old root becomes the upper half
+                bounds.min[axis] -= size[axis];
+                octant |= 1 << axis;
+            } else {
+                bounds.max[axis] += size[axis];
+            }
+        }
+        
+        let mut old_root = std::mem::replace(&mut self.root, OctreeNode::new(bounds, 0));
+        old_root.shift_depth(1);
+        self.root.subdivide();
+        if let Some(ref mut children) = self.root.children {
+            children[octant] = old_root;
+        }
     }
     
     /// Remove an entity from the octree
     pub fn remove(&mut self, entity_id: Entity) -> bool {
-        self.root.remove(entity_id)
+        if let Some(index) = self.overflow.iter().position(|e| e.id == entity_id) {
+            self.overflow.swap_remove(index);
+            return true;
+        }
+        self.root.remove(entity_id, &self.config)
     }
     
-    /// Query all entities within a radius of a point
+    /// Query all entities whose bounding spheres overlap a sphere
     pub fn query_radius(&self, center: Vec3, radius: f32) -> Vec<OctreeEntity> {
         let mut results = Vec::new();
-        self.root.query_radius(center, radius, &mut results);
+        self.root.query_radius(center, radius, self.max_entity_radius, &mut results);
+        results.extend(self.overflow.iter().filter(|entity| {
+            let combined_radius = radius + entity.radius;
+            (entity.position - center).magnitude_squared() <= combined_radius * combined_radius
+        }));
         results
     }
     
@@ -395,6 +585,7 @@ impl Octree {
         // Use cached max entity radius (O(1) instead of O(n))
         let mut results = Vec::new();
         self.root.query_ray(ray_origin, ray_dir, self.max_entity_radius, &mut results);
+        results.extend_from_slice(&self.overflow);
         results
     }
     
@@ -417,6 +608,7 @@ impl Octree {
     /// Find an entity in the octree and return its data
     pub fn find_entity(&self, entity_id: Entity) -> Option<OctreeEntity> {
         self.root.find_entity(entity_id)
+            .or_else(|| self.overflow.iter().find(|e| e.id == entity_id).copied())
     }
     
     /// Get all leaf nodes (for visualization)
@@ -433,15 +625,50 @@ impl Octree {
         nodes
     }
     
+    /// Occupancy of every node (pre-order, root first)
+    pub fn node_stats(&self) -> Vec<OctreeNodeStats> {
+        let mut stats = Vec::new();
+        self.root.collect_stats(&mut stats);
+        stats
+    }
+    
+    /// Summary of the tree's shape and occupancy
+    pub fn stats(&self) -> OctreeStats {
+        let mut stats = OctreeStats {
+            overflow: self.overflow.len(),
+            ..OctreeStats::default()
+        };
+        let mut occupied_leaves = 0;
+        for node in self.node_stats() {
+            stats.nodes += 1;
+            stats.max_depth = stats.max_depth.max(node.depth);
+            stats.entities += node.entities;
+            if node.is_leaf {
+                stats.leaves += 1;
+                stats.max_leaf_entities = stats.max_leaf_entities.max(node.entities);
+                if node.entities == 0 {
+                    stats.empty_leaves += 1;
+                } else {
+                    occupied_leaves += 1;
+                }
+            }
+        }
+        if occupied_leaves > 0 {
+            stats.average_leaf_entities = stats.entities as f32 / occupied_leaves as f32;
+        }
+        stats
+    }
+    
     /// Get total entity count
     pub fn entity_count(&self) -> usize {
-        self.root.count_entities()
+        self.root.count_entities() + self.overflow.len()
     }
     
-    /// Clear the octree
+    /// Clear the octree, shrinking the root back to the initial world bounds
     pub fn clear(&mut self) {
-        self.root = OctreeNode::new(self.root.bounds, 0);
+        self.root = OctreeNode::new(self.initial_bounds, 0);
         self.max_entity_radius = 0.0;
+        self.overflow.clear();
     }
 }
 
@@ -499,4 +726,105 @@ mod tests {
         let results = octree.query_radius(Vec3::new(0.0, 0.0, 0.0), 10.0);
         assert_eq!(results.len(), 2); // Should find entity1 and entity2
     }
+    
+    #[test]
+    fn test_large_entities_found_from_neighbouring_nodes() {
+        let bounds = AABB::new(Vec3::new(-100.0, -100.0, -100.0), Vec3::new(100.0, 100.0, 100.0));
+        let config = OctreeConfig { max_entities_per_node: 1, max_depth: 4, min_node_size: 1.0 };
+        let mut octree = Octree::new(bounds, config);
+        let mut world = World::new();
+        
+        // The big entity's center lies in a different octant than the query point
+        let big = world.create_entity();
+        let small = world.create_entity();
+        octree.insert(big, Vec3::new(-5.0, -5.0, -5.0), 20.0);
+        octree.insert(small, Vec3::new(60.0, 60.0, 60.0), 1.0);
+        
+        let results: Vec<_> = octree.query_radius(Vec3::new(5.0, 5.0, 5.0), 1.0).into_iter().map(|e| e.id).collect();
+        assert_eq!(results, vec![big]);
+    }
+    
+    #[test]
+    fn test_root_grows_for_out_of_bounds_entities() {
+        let bounds = AABB::new(Vec3::new(-50.0, -50.0, -50.0), Vec3::new(50.0, 50.0, 50.0));
+        let config = OctreeConfig { max_entities_per_node: 2, max_depth: 6, min_node_size: 1.0 };
+        let mut octree = Octree::new(bounds, config);
+        let mut world = World::new();
+        
+        let inside: Vec<_> = (0..4).map(|i| {
+            let entity = world.create_entity();
+            octree.insert(entity, Vec3::new(i as f32 * 10.0, 0.0, 0.0), 1.0);
+            entity
+        }).collect();
+        
+        let far = world.create_entity();
+        assert!(octree.insert(far, Vec3::new(-180.0, 300.0, 20.0), 1.0));
+        assert!(octree.bounds().contains_point(Vec3::new(-180.0, 300.0, 20.0)));
+        assert!(octree.overflow().is_empty());
+        assert_eq!(octree.entity_count(), 5);
+        
+        // Existing entities are still found after the root grew around them
+        let near_origin: Vec<_> = octree.query_radius(Vec3::zeros(), 15.0).into_iter().map(|e| e.id).collect();
+        assert!(near_origin.contains(&inside[0]) && near_origin.contains(&inside[1]));
+        assert_eq!(octree.query_nearby(far), vec![far]);
+        
+        // Unplaceable positions go to the overflow list but stay queryable
+        let lost = world.create_entity();
+        assert!(!octree.insert(lost, Vec3::new(f32::NAN, 0.0, 0.0), 1.0));
+        assert_eq!(octree.stats().overflow, 1);
+        assert!(octree.find_entity(lost).is_some());
+        assert!(octree.remove(lost));
+        
+        octree.clear();
+        assert_eq!(octree.bounds().max, bounds.max);
+    }
+    
+    #[test]
+    fn test_zero_extent_root_can_grow() {
+        let origin = Vec3::new(5.0, 5.0, 5.0);
+        let mut octree = Octree::new(AABB::new(origin, origin), OctreeConfig::default());
+        let mut world = World::new();
+        assert!(octree.bounds().extents().min() >= MIN_ROOT_EXTENT);
+        
+        // Would spin forever doubling a zero-size root
+        let far = world.create_entity();
+        assert!(octree.insert(far, Vec3::new(-40.0, 120.0, 5.0), 1.0));
+        assert!(octree.overflow().is_empty());
+        assert!(octree.bounds().contains_point(origin));
+        assert_eq!(octree.query_nearby(far), vec![far]);
+        
+        // A root flat along one axis grows along it too
+        let mut flat = Octree::new(AABB::new(Vec3::new(-10.0, 0.0, -10.0), Vec3::new(10.0, 0.0, 10.0)), OctreeConfig::default());
+        let above = world.create_entity();
+        assert!(flat.insert(above, Vec3::new(0.0, 30.0, 0.0), 1.0));
+        assert!(flat.overflow().is_empty());
+    }
+    
+    #[test]
+    fn test_emptied_nodes_merge() {
+        let bounds = AABB::new(Vec3::new(-100.0, -100.0, -100.0), Vec3::new(100.0, 100.0, 100.0));
+        let config = OctreeConfig { max_entities_per_node: 4, max_depth: 5, min_node_size: 1.0 };
+        let mut octree = Octree::new(bounds, config);
+        let mut world = World::new();
+        
+        let entities: Vec<_> = (0..20).map(|i| {
+            let entity = world.create_entity();
+            let offset = i as f32 * 9.0 - 90.0;
+            octree.insert(entity, Vec3::new(offset, offset * 0.5, -offset), 1.0);
+            entity
+        }).collect();
+        let full = octree.stats();
+        assert!(full.nodes > 1);
+        assert_eq!(full.entities, 20);
+        assert!(full.max_leaf_entities <= 4);
+        
+        for &entity in &entities[2..] {
+            assert!(octree.remove(entity));
+        }
+        
+        let stats = octree.stats();
+        assert_eq!(stats.nodes, 1);
+        assert_eq!(stats.entities, 2);
+        assert_eq!(octree.node_stats()[0].subtree_entities, 2);
+    }
 }
@@ -5,7 +5,12 @@
 //! objects that cannot possibly be colliding."
 //!
 //! This abstraction allows swapping different spatial partitioning schemes
-//! (octree, grid, BVH, etc.) without changing the collision system.
+//! without changing the collision system. Implementations:
+//! - `OctreeSpatialQuery`: fixed world bounds, adaptive subdivision
+//! - `DynamicAabbTree`: unbounded BVH with fat leaves, suits clustered scenes
+//! - `HashGrid`: unbounded sparse grid, suits evenly spread, similar-sized objects
+//!
+//! `benches/spatial_query.rs` compares them on typical workloads.
 
 use crate::ecs::Entity;
 use crate::foundation::math::Vec3;
@@ -14,6 +19,18 @@ use crate::spatial::Octree;
 use std::collections::HashMap;
 use std::any::Any;
 
+/// Predicate deciding which entities a query may return
+///
+/// Lets callers skip other layers, dead entities and so on while the query
+/// runs, without collecting and filtering a candidate list afterwards.
+pub type EntityFilter<'a> = &'a dyn Fn(Entity) -> bool;
+
+/// Search radius the default `query_k_nearest` starts from
+const K_NEAREST_START_RADIUS: f32 = 16.0;
+
+/// Search radius past which the default `query_k_nearest` gives up growing
+const K_NEAREST_MAX_RADIUS: f32 = 1.0e8;
+
 /// Abstract interface for spatial partitioning used in broad-phase collision detection
 /// 
 /// GEA 13.3.2: "The broad phase quickly identifies pairs of objects that might
@@ -38,6 +55,79 @@ pub trait SpatialQuery: Send + Sync {
     /// Query entities within an AABB
     fn query_aabb(&self, aabb: &AABB) -> Vec<Entity>;
     
+    /// Query entities whose bounds may be crossed by a ray (conservative)
+    fn query_ray(&self, origin: Vec3, direction: Vec3) -> Vec<Entity>;
+    
+    /// Up to `k` entities passing `filter` closest to `point`, nearest first
+    /// 
+    /// Distances are measured to the surface of each bounding sphere (0 when
+    /// the point is inside it). The default implementation grows a sphere
+    /// query until it holds `k` matches.
+    fn query_k_nearest(&self, point: Vec3, k: usize, filter: EntityFilter<'_>) -> Vec<(Entity, f32)> {
+        let total = self.entity_count();
+        if k == 0 || total == 0 {
+            return Vec::new();
+        }
+        
+        let mut radius = K_NEAREST_START_RADIUS;
+        loop {
+            let candidates = self.query_sphere(point, radius);
+            // Every entity whose surface is within `radius` is a candidate, so
+            // once k of them match nothing outside can be closer
+            let exhaustive = candidates.len() >= total || radius >= K_NEAREST_MAX_RADIUS;
+            let mut nearest: Vec<(Entity, f32)> = candidates
+                .into_iter()
+                .filter(|&entity| filter(entity))
+                .filter_map(|entity| {
+                    let (position, entity_radius) = self.get_entity_data(entity)?;
+                    Some((entity, ((position - point).magnitude() - entity_radius).max(0.0)))
+                })
+                .collect();
+            
+            if nearest.len() >= k || exhaustive {
+                nearest.sort_by(|a, b| a.1.total_cmp(&b.1));
+                nearest.truncate(k);
+                return nearest;
+            }
+            radius *= 4.0;
+        }
+    }
+    
+    /// Visit entities passing `filter` whose bounds a ray enters within `max_distance`, front to back
+    /// 
+    /// `visit` receives each entity with the distance along the normalized ray
+    /// to its bounding sphere (0 when the origin is inside) and returns
+    /// `false` to stop early, e.g. once an exact hit closer than the next
+    /// sphere has been found. The default implementation sorts `query_ray`.
+    fn query_ray_sorted(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        filter: EntityFilter<'_>,
+        visit: &mut dyn FnMut(Entity, f32) -> bool,
+    ) {
+        let Some(direction) = direction.try_normalize(f32::EPSILON) else {
+            return;
+        };
+        let mut hits: Vec<(Entity, f32)> = self.query_ray(origin, direction)
+            .into_iter()
+            .filter(|&entity| filter(entity))
+            .filter_map(|entity| {
+                let (position, radius) = self.get_entity_data(entity)?;
+                let entry = ray_sphere_entry(origin, direction, position, radius)?;
+                (entry <= max_distance).then_some((entity, entry))
+            })
+            .collect();
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        
+        for (entity, entry) in hits {
+            if !visit(entity, entry) {
+                return;
+            }
+        }
+    }
+    
     /// Get entity's current position and radius (if it exists)
     fn get_entity_data(&self, entity: Entity) -> Option<(Vec3, f32)>;
     
@@ -54,6 +144,24 @@ pub trait SpatialQuery: Send + Sync {
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
+/// Distance along a normalized ray to where it enters a sphere
+/// 
+/// Returns 0 when the origin is inside the sphere and `None` when the ray
+/// misses it or the sphere is behind the origin.
+pub(crate) fn ray_sphere_entry(origin: Vec3, direction: Vec3, center: Vec3, radius: f32) -> Option<f32> {
+    let to_center = center - origin;
+    let distance_sq = to_center.magnitude_squared();
+    if distance_sq <= radius * radius {
+        return Some(0.0);
+    }
+    let along = to_center.dot(&direction);
+    if along < 0.0 {
+        return None;
+    }
+    let discriminant = radius * radius - (distance_sq - along * along);
+    (discriminant >= 0.0).then(|| along - discriminant.sqrt())
+}
+
 /// Octree-based implementation of SpatialQuery
 /// 
 /// Wraps the existing Octree implementation to provide the SpatialQuery interface
@@ -135,6 +243,13 @@ impl SpatialQuery for OctreeSpatialQuery {
             .collect()
     }
     
+    fn query_ray(&self, origin: Vec3, direction: Vec3) -> Vec<Entity> {
+        self.octree.query_ray(origin, direction)
+            .into_iter()
+            .map(|octree_entity| octree_entity.id)
+            .collect()
+    }
+    
     fn get_entity_data(&self, entity: Entity) -> Option<(Vec3, f32)> {
         self.entity_cache.get(&entity).copied()
     }
@@ -186,4 +301,45 @@ mod tests {
         spatial.remove(entity);
         assert_eq!(spatial.entity_count(), 0);
     }
+    
+    #[test]
+    fn test_default_k_nearest_and_sorted_ray() {
+        let bounds = AABB::new(
+            Vec3::new(-500.0, -500.0, -500.0),
+            Vec3::new(500.0, 500.0, 500.0),
+        );
+        let mut spatial = OctreeSpatialQuery::new(Octree::new(bounds, OctreeConfig::default()));
+        
+        let near = Entity::new(1, 0);
+        let dead = Entity::new(2, 0);
+        let large = Entity::new(3, 0);
+        let far = Entity::new(4, 0);
+        spatial.insert(near, Vec3::new(5.0, 0.0, 0.0), 1.0);
+        spatial.insert(dead, Vec3::new(3.0, 0.0, 0.0), 1.0);
+        spatial.insert(large, Vec3::new(40.0, 0.0, 0.0), 30.0);
+        spatial.insert(far, Vec3::new(300.0, 0.0, 0.0), 1.0);
+        
+        let alive = |entity: Entity| entity != dead;
+        // Distances are to the sphere surfaces and the filtered entity is skipped
+        assert_eq!(spatial.query_k_nearest(Vec3::zeros(), 2, &alive), vec![(near, 4.0), (large, 10.0)]);
+        // Growing the search radius finds entities far outside the first query
+        let nearest = spatial.query_k_nearest(Vec3::zeros(), 10, &alive);
+        assert_eq!(nearest.len(), 3);
+        assert_eq!(nearest[2], (far, 299.0));
+        
+        let mut visited = Vec::new();
+        spatial.query_ray_sorted(Vec3::zeros(), Vec3::new(2.0, 0.0, 0.0), 100.0, &alive, &mut |entity, entry| {
+            visited.push((entity, entry));
+            true
+        });
+        assert_eq!(visited, vec![(near, 4.0), (large, 10.0)]);
+        
+        // Returning false stops the traversal at the first entity
+        let mut count = 0;
+        spatial.query_ray_sorted(Vec3::zeros(), Vec3::x(), f32::INFINITY, &|_| true, &mut |_, _| {
+            count += 1;
+            false
+        });
+        assert_eq!(count, 1);
+    }
 }
@@ -3,4 +3,4 @@
 pub mod processor;
 
 // Re-export commonly used types
-pub use processor::{UIInputProcessor, UIInputEvent, MouseButton};
+pub use processor::{UIInputProcessor, UIInputEvent, MouseButton, ButtonClicked, ButtonHoverChanged};
@@ -3,7 +3,7 @@
 //! Handles UI-specific input processing separate from rendering.
 
 use crate::ui::widgets::{UIButton, ButtonState};
-use crate::events::{Event, EventType, EventArg, EventSystem};
+use crate::events::EventBus;
 use crate::input::collision;
 
 /// Mouse button identifier
@@ -34,6 +34,22 @@ pub enum UIInputEvent {
     },
 }
 
+/// Sent on the UI event bus when a button is clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonClicked {
+    /// Button identifier (`UIButton::on_click_id`)
+    pub button_id: u32,
+}
+
+/// Sent on the UI event bus when the mouse enters or leaves a button
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonHoverChanged {
+    /// Button identifier (`UIButton::on_click_id`)
+    pub button_id: u32,
+    /// Is button hovered
+    pub hovered: bool,
+}
+
 /// UI input processor
 ///
 /// Handles mouse input and button state management separate from rendering.
@@ -107,9 +123,12 @@ impl UIInputProcessor {
     
     /// Process input for a button, updating its state
     ///
+    /// Clicks and hover changes are also sent to `events` as `ButtonClicked`
+    /// and `ButtonHoverChanged`.
+    ///
     /// # Returns
     /// Optional event if button was clicked or hover changed
-    pub fn process_button(&self, button: &mut UIButton, event_system: &mut EventSystem, timestamp: f64) -> Option<UIInputEvent> {
+    pub fn process_button(&self, button: &mut UIButton, events: &mut EventBus) -> Option<UIInputEvent> {
         if !button.enabled || !button.element.visible {
             button.state = ButtonState::Disabled;
             return None;
@@ -141,11 +160,7 @@ impl UIInputProcessor {
                 if self.left_button_released_this_frame && old_state == ButtonState::Pressed {
                     if let Some(id) = button.on_click_id {
                         ui_event = Some(UIInputEvent::ButtonClicked { button_id: id });
-                        
-                        // Send to event system
-                        let event = Event::new(EventType::ButtonClicked, timestamp)
-                            .with_arg("button_id", EventArg::ButtonId(id));
-                        event_system.send(event);
+                        events.send(ButtonClicked { button_id: id });
                     }
                 }
             }
@@ -162,12 +177,7 @@ impl UIInputProcessor {
                                 button_id: id, 
                                 hovered: true 
                             });
-                            
-                            // Send to event system
-                            let event = Event::new(EventType::ButtonHoverChanged, timestamp)
-                                .with_arg("button_id", EventArg::ButtonId(id))
-                                .with_arg("hovered", EventArg::Hovered(true));
-                            event_system.send(event);
+                            events.send(ButtonHoverChanged { button_id: id, hovered: true });
                         }
                     }
                 }
@@ -179,12 +189,7 @@ impl UIInputProcessor {
                                 button_id: id, 
                                 hovered: false 
                             });
-                            
-                            // Send to event system
-                            let event = Event::new(EventType::ButtonHoverChanged, timestamp)
-                                .with_arg("button_id", EventArg::ButtonId(id))
-                                .with_arg("hovered", EventArg::Hovered(false));
-                            event_system.send(event);
+                            events.send(ButtonHoverChanged { button_id: id, hovered: false });
                         }
                     }
                 }
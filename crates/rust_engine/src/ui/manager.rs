@@ -7,7 +7,7 @@ use super::backend::UIRenderBackend;
 use crate::render::systems::text::FontAtlas;
 use crate::ui::rendering::{UIRenderer, UIRenderCommand, UIRenderData, RenderQuad, RenderText};
 use crate::ui::input::{UIInputProcessor, MouseButton};
-use crate::events::EventSystem;
+use crate::events::EventBus;
 use crate::foundation::math::Vec2;
 use std::collections::HashMap;
 
@@ -32,17 +32,15 @@ pub struct UIManager {
     /// Input processor for mouse/keyboard
     input_processor: UIInputProcessor,
     
-    /// Event system for UI interactions
-    event_system: EventSystem,
+    /// Typed events for UI interactions (`ButtonClicked`, `ButtonHoverChanged`)
+    events: EventBus,
     
     /// Font atlas for text rendering (optional - managed by backend)
     font_atlas: Option<FontAtlas>,
     
     /// Current screen size
     screen_size: (f32, f32),
-    
-    /// Frame counter for timestamps
-    frame_counter: u64,
+
 }
 
 impl UIManager {
@@ -53,10 +51,9 @@ impl UIManager {
             next_id: 0,
             ui_renderer: UIRenderer::new(),
             input_processor: UIInputProcessor::new(800.0, 600.0), // Default screen size
-            event_system: EventSystem::new(),
+            events: EventBus::new(),
             font_atlas: None,
             screen_size: (800.0, 600.0),
-            frame_counter: 0,
         }
     }
     
@@ -141,23 +138,25 @@ impl UIManager {
         self.screen_size
     }
     
-    /// Get event system reference
-    pub fn event_system(&self) -> &EventSystem {
-        &self.event_system
+    /// Get the UI event bus (read with an `EventReader<ButtonClicked>` etc.)
+    pub fn events(&self) -> &EventBus {
+        &self.events
     }
     
-    /// Get event system mutable reference
-    pub fn event_system_mut(&mut self) -> &mut EventSystem {
-        &mut self.event_system
+    /// Get the UI event bus mutably
+    pub fn events_mut(&mut self) -> &mut EventBus {
+        &mut self.events
     }
     
     /// Update UI state (call once per frame before rendering)
+    /// 
+    /// Swaps the event bus buffers first: events sent during this update
+    /// stay readable through the next frame.
     pub fn update(&mut self, _delta_time: f32) {
-        self.frame_counter += 1;
+        self.events.update();
         self.input_processor.begin_frame();
         
         // Process button input
-        let timestamp = self.frame_counter as f64;
         let button_ids: Vec<UINodeId> = self.nodes.iter()
             .filter_map(|(id, node)| {
                 if matches!(node, UINode::Button(_)) {
@@ -170,7 +169,7 @@ impl UIManager {
         
         for id in button_ids {
             if let Some(UINode::Button(button)) = self.nodes.get_mut(&id) {
-                self.input_processor.process_button(button, &mut self.event_system, timestamp);
+                self.input_processor.process_button(button, &mut self.events);
             }
         }
         
@@ -408,11 +407,6 @@ impl UIManager {
         
         Ok(())
     }
-    
-    /// Dispatch all pending events
-    pub fn dispatch_events(&mut self) {
-        self.event_system.dispatch();
-    }
 }
 
 impl Default for UIManager {
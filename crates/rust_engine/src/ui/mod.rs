@@ -30,10 +30,10 @@ pub use rendering::{
 };
 
 // Re-export input types
-pub use input::{UIInputProcessor, UIInputEvent, MouseButton};
+pub use input::{UIInputProcessor, UIInputEvent, MouseButton, ButtonClicked, ButtonHoverChanged};
 
 // Re-export events
-pub use crate::events::EventSystem;
+pub use crate::events::{EventBus, EventReader};
 
 /// Unique identifier for UI elements
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -8,9 +8,10 @@
 use rust_engine::assets::{ObjLoader, MaterialBuilder};
 use rust_engine::audio::{AudioSystem, SoundHandle};
 use rust_engine::ecs::{
-    World, Entity, LightFactory, LightingSystem as EcsLightingSystem, 
-    TransformComponent, LightComponent, LifecycleComponent,
+    World, Entity, LightFactory, LightingSystem as EcsLightingSystem, SystemScheduler,
+    TransformComponent, LightComponent, LifecycleComponent, EventReader,
     components::RenderableComponent,
+    systems::TransformPropagationSystem,
 };
 use rust_engine::scene::SceneManager;
 use rust_engine::render::{
@@ -37,6 +38,21 @@ const MAX_TEAPOTS: usize = 5;   // Maximum number of monkeys before oldest are d
 const MAX_SPACESHIPS: usize = 10; // Maximum number of spaceships
 const MAX_LIGHTS: usize = 12;    // Maximum number of lights before oldest are despawned
 
+// UI button ids (`UIButton::on_click_id`), reported in `ButtonClicked` events
+const BUTTON_BEEP: u32 = 1;
+const BUTTON_HIGH_BEEP: u32 = 2;
+const BUTTON_LOW_BEEP: u32 = 3;
+const BUTTON_SPAM_TEST: u32 = 4;
+const BUTTON_MASTER_VOL_UP: u32 = 5;
+const BUTTON_MASTER_VOL_DOWN: u32 = 6;
+const BUTTON_SFX_VOL_UP: u32 = 7;
+const BUTTON_SFX_VOL_DOWN: u32 = 8;
+const BUTTON_MUSIC_VOL_UP: u32 = 9;
+const BUTTON_MUSIC_VOL_DOWN: u32 = 10;
+const BUTTON_PLAY_MUSIC: u32 = 11;
+const BUTTON_PAUSE_MUSIC: u32 = 12;
+const BUTTON_STOP_MUSIC: u32 = 13;
+
 #[derive(Clone)]
 struct TeapotInstance {
     entity: Option<Entity>,
@@ -99,7 +115,7 @@ pub struct DynamicTeapotApp {
     
     // Traditional lighting system (keep for compatibility)
     world: World,
-    lighting_system: EcsLightingSystem,
+    scheduler: SystemScheduler, // Transform propagation and lighting
     
     // Dynamic content tracking
     light_instances: Vec<LightInstance>,
@@ -185,23 +201,9 @@ pub struct DynamicTeapotApp {
     // Audio system
     audio: Option<AudioSystem>,
     last_beep_sound: Option<SoundHandle>,
-    button_clicked_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    high_beep_clicked_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    low_beep_clicked_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    spam_test_clicked_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    
-    // Volume control flags
-    master_vol_up_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    master_vol_down_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    sfx_vol_up_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    sfx_vol_down_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    music_vol_up_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    music_vol_down_flag: std::rc::Rc<std::cell::RefCell<bool>>,
     
-    // Music control flags
-    play_music_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    pause_music_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-    stop_music_flag: std::rc::Rc<std::cell::RefCell<bool>>,
+    // Reader for UI button clicks
+    button_clicks: EventReader<rust_engine::ui::ButtonClicked>,
 }
 
 impl DynamicTeapotApp {
@@ -264,7 +266,12 @@ impl DynamicTeapotApp {
         
         // Initialize traditional ECS for lighting
         let mut world = World::new();
-        let lighting_system = EcsLightingSystem::new();
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.add_system(EcsLightingSystem::new().with_ambient(
+            Vec3::new(0.15, 0.12, 0.18), // Ambient color
+            0.1,                         // Ambient intensity
+        ));
         
         // Store material IDs for teapot creation
         let material_ids: Vec<MaterialId> = (0..teapot_materials.len())
@@ -350,7 +357,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(1),
+            on_click_id: Some(BUTTON_BEEP),
         };
         let test_button_id = ui_manager.add_button(button);
         
@@ -374,7 +381,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(2),
+            on_click_id: Some(BUTTON_HIGH_BEEP),
         };
         let high_beep_button_id = ui_manager.add_button(high_button);
         
@@ -398,7 +405,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(3),
+            on_click_id: Some(BUTTON_LOW_BEEP),
         };
         let low_beep_button_id = ui_manager.add_button(low_button);
         
@@ -422,7 +429,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(4),
+            on_click_id: Some(BUTTON_SPAM_TEST),
         };
         let spam_test_button_id = ui_manager.add_button(spam_button);
         
@@ -464,7 +471,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(5),
+            on_click_id: Some(BUTTON_MASTER_VOL_UP),
         };
         let master_vol_up_id = ui_manager.add_button(master_vol_up_button);
         
@@ -487,7 +494,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(6),
+            on_click_id: Some(BUTTON_MASTER_VOL_DOWN),
         };
         let master_vol_down_id = ui_manager.add_button(master_vol_down_button);
         
@@ -527,7 +534,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(7),
+            on_click_id: Some(BUTTON_SFX_VOL_UP),
         };
         let sfx_vol_up_id = ui_manager.add_button(sfx_vol_up_button);
         
@@ -550,7 +557,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(8),
+            on_click_id: Some(BUTTON_SFX_VOL_DOWN),
         };
         let sfx_vol_down_id = ui_manager.add_button(sfx_vol_down_button);
         
@@ -590,7 +597,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(9),
+            on_click_id: Some(BUTTON_MUSIC_VOL_UP),
         };
         let music_vol_up_id = ui_manager.add_button(music_vol_up_button);
         
@@ -613,7 +620,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(10),
+            on_click_id: Some(BUTTON_MUSIC_VOL_DOWN),
         };
         let music_vol_down_id = ui_manager.add_button(music_vol_down_button);
         
@@ -656,7 +663,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(11),
+            on_click_id: Some(BUTTON_PLAY_MUSIC),
         };
         let play_music_button_id = ui_manager.add_button(play_music_button);
         
@@ -680,7 +687,7 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(12),
+            on_click_id: Some(BUTTON_PAUSE_MUSIC),
         };
         let pause_music_button_id = ui_manager.add_button(pause_music_button);
         
@@ -704,131 +711,10 @@ impl DynamicTeapotApp {
             border_color: Vec4::new(0.8, 0.8, 0.8, 1.0),
             border_width: 2.0,
             enabled: true,
-            on_click_id: Some(13),
+            on_click_id: Some(BUTTON_STOP_MUSIC),
         };
         let stop_music_button_id = ui_manager.add_button(stop_music_button);
         
-        // Register button click event handler WITH UIManager's EventSystem
-        // (NOT GraphicsEngine - UIManager has its own EventSystem!)
-        log::info!("Registering UI event handlers...");
-        use rust_engine::events::{Event, EventHandler, EventType};
-        
-        struct ButtonClickHandler {
-            button_id: u32,
-            clicked_flag: std::rc::Rc<std::cell::RefCell<bool>>,
-        }
-        impl EventHandler for ButtonClickHandler {
-            fn on_event(&mut self, event: &Event) -> bool {
-                if let Some(event_button_id) = event.get_button_id() {
-                    if event_button_id == self.button_id {
-                        log::info!("🎉 Button {} was clicked!", event_button_id);
-                        *self.clicked_flag.borrow_mut() = true;
-                        return true; // Consume the event
-                    }
-                }
-                false
-            }
-        }
-        
-        // Create shared flags for button clicks
-        let button_clicked_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let high_beep_clicked_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let low_beep_clicked_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let spam_test_clicked_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        
-        // Volume control flags
-        let master_vol_up_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let master_vol_down_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let sfx_vol_up_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let sfx_vol_down_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let music_vol_up_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let music_vol_down_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        
-        // Music control flags
-        let play_music_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let pause_music_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        let stop_music_flag = std::rc::Rc::new(std::cell::RefCell::new(false));
-        
-        // Register handlers for each button
-        ui_manager.event_system_mut().register_handler(
-            EventType::ButtonClicked,
-            Box::new(ButtonClickHandler {
-                button_id: 1,
-                clicked_flag: button_clicked_flag.clone(),
-            })
-        );
-        
-        ui_manager.event_system_mut().register_handler(
-            EventType::ButtonClicked,
-            Box::new(ButtonClickHandler {
-                button_id: 2,
-                clicked_flag: high_beep_clicked_flag.clone(),
-            })
-        );
-        
-        ui_manager.event_system_mut().register_handler(
-            EventType::ButtonClicked,
-            Box::new(ButtonClickHandler {
-                button_id: 3,
-                clicked_flag: low_beep_clicked_flag.clone(),
-            })
-        );
-        
-        ui_manager.event_system_mut().register_handler(
-            EventType::ButtonClicked,
-            Box::new(ButtonClickHandler {
-                button_id: 4,
-                clicked_flag: spam_test_clicked_flag.clone(),
-            })
-        );
-        
-        // Register volume control button handlers
-        for (button_id, flag) in [
-            (5, master_vol_up_flag.clone()),
-            (6, master_vol_down_flag.clone()),
-            (7, sfx_vol_up_flag.clone()),
-            (8, sfx_vol_down_flag.clone()),
-            (9, music_vol_up_flag.clone()),
-            (10, music_vol_down_flag.clone()),
-        ] {
-            ui_manager.event_system_mut().register_handler(
-                EventType::ButtonClicked,
-                Box::new(ButtonClickHandler {
-                    button_id,
-                    clicked_flag: flag,
-                })
-            );
-        }
-        
-        // Register music control button handlers
-        for (button_id, flag) in [
-            (11, play_music_flag.clone()),
-            (12, pause_music_flag.clone()),
-            (13, stop_music_flag.clone()),
-        ] {
-            ui_manager.event_system_mut().register_handler(
-                EventType::ButtonClicked,
-                Box::new(ButtonClickHandler {
-                    button_id,
-                    clicked_flag: flag,
-                })
-            );
-        }
-        
-        // Register hover event handler for debugging
-        struct HoverHandler;
-        impl EventHandler for HoverHandler {
-            fn on_event(&mut self, _event: &Event) -> bool {
-                // Silently handle hover events without logging
-                false // Don't consume hover events
-            }
-        }
-        ui_manager.event_system_mut().register_handler(
-            EventType::ButtonHoverChanged,
-            Box::new(HoverHandler)
-        );
-        log::info!("UI event handlers registered with UIManager's EventSystem");
-        
         log::info!("UI Manager initialized with panel, FPS label, and button");
         
         let now = Instant::now();
@@ -839,7 +725,7 @@ impl DynamicTeapotApp {
             camera,
             scene_manager,
             world,
-            lighting_system,
+            scheduler,
             light_instances,
             teapot_mesh: None,
             sphere_mesh: None,
@@ -916,23 +802,9 @@ impl DynamicTeapotApp {
             // Audio system
             audio: AudioSystem::new().ok(),
             last_beep_sound: None,
-            button_clicked_flag: button_clicked_flag.clone(),
-            high_beep_clicked_flag: high_beep_clicked_flag.clone(),
-            low_beep_clicked_flag: low_beep_clicked_flag.clone(),
-            spam_test_clicked_flag: spam_test_clicked_flag.clone(),
-            
-            // Volume control flags
-            master_vol_up_flag,
-            master_vol_down_flag,
-            sfx_vol_up_flag,
-            sfx_vol_down_flag,
-            music_vol_up_flag,
-            music_vol_down_flag,
-            
-            // Music control flags
-            play_music_flag,
-            pause_music_flag,
-            stop_music_flag,
+            
+            // UI events
+            button_clicks: EventReader::new(),
         }
     }
     
@@ -2315,12 +2187,11 @@ impl DynamicTeapotApp {
         }
     }
     
-    fn build_multi_light_environment_from_entities(&mut self) -> &MultiLightEnvironment {
-        let multi_light_env = self.lighting_system.build_multi_light_environment(
-            &self.world,
-            Vec3::new(0.15, 0.12, 0.18), // Ambient color
-            0.1                           // Ambient intensity
-        );
+    fn build_multi_light_environment_from_entities(&self) -> MultiLightEnvironment {
+        // Gathered by the scheduler's lighting system
+        let multi_light_env = self.world.resource::<MultiLightEnvironment>()
+            .cloned()
+            .unwrap_or_else(MultiLightEnvironment::new);
         
         log::trace!("MultiLight Environment - Directional: {}, Point: {}, Spot: {}", 
                    multi_light_env.header.directional_light_count,
@@ -2353,6 +2224,9 @@ impl DynamicTeapotApp {
         // Update UI state
         self.ui_manager.update(delta_time);
         
+        // Propagate transforms and gather lights; ends the ECS frame
+        self.scheduler.execute_frame(&mut self.world, delta_time);
+        
         // ✅ PHASE 4 COMPLETE: Sync ECS → Scene Manager
         self.scene_manager.sync_from_world(&mut self.world);
         let render_queue = self.scene_manager.build_render_queue();
@@ -2362,7 +2236,7 @@ impl DynamicTeapotApp {
         self.graphics_engine.render_entities_from_queue(&render_queue)?;
         
         // Build multi-light environment from entity system
-        let multi_light_env = self.build_multi_light_environment_from_entities().clone();
+        let multi_light_env = self.build_multi_light_environment_from_entities();
         
         // Export UI data for rendering
         let ui_data = self.ui_manager.get_render_data();
@@ -2378,11 +2252,17 @@ impl DynamicTeapotApp {
             &mut self.window,
         )?;
         
-        // Dispatch UI events
-        self.ui_manager.dispatch_events();
+        // Buttons clicked during this frame's UI update
+        let clicked: Vec<u32> = self.ui_manager.events()
+            .read(&mut self.button_clicks)
+            .map(|click| click.button_id)
+            .collect();
+        for button_id in &clicked {
+            log::info!("🎉 Button {} was clicked!", button_id);
+        }
         
         // Check for button click and play audio
-        if *self.button_clicked_flag.borrow() {
+        if clicked.contains(&BUTTON_BEEP) {
             if let Some(audio) = &mut self.audio {
                 let (active, max) = audio.get_voice_stats();
                 log::info!("🎵 Normal beep button - voice stats: {}/{} voices active", active, max);
@@ -2400,11 +2280,10 @@ impl DynamicTeapotApp {
                     Err(e) => log::warn!("⚠️ Failed to play normal beep: {} (Hit instance limit - max 4 per sound)", e),
                 }
             }
-            *self.button_clicked_flag.borrow_mut() = false; // Reset flag
         }
         
         // Check for high beep button
-        if *self.high_beep_clicked_flag.borrow() {
+        if clicked.contains(&BUTTON_HIGH_BEEP) {
             if let Some(audio) = &mut self.audio {
                 let (active, max) = audio.get_voice_stats();
                 log::info!("🎵 High beep button - voice stats: {}/{} voices active", active, max);
@@ -2422,11 +2301,10 @@ impl DynamicTeapotApp {
                     Err(e) => log::warn!("⚠️ Failed to play high beep: {} (Hit instance limit - max 4 per sound)", e),
                 }
             }
-            *self.high_beep_clicked_flag.borrow_mut() = false;
         }
         
         // Check for low beep button
-        if *self.low_beep_clicked_flag.borrow() {
+        if clicked.contains(&BUTTON_LOW_BEEP) {
             if let Some(audio) = &mut self.audio {
                 let (active, max) = audio.get_voice_stats();
                 log::info!("🎵 Low beep button - voice stats: {}/{} voices active", active, max);
@@ -2444,11 +2322,10 @@ impl DynamicTeapotApp {
                     Err(e) => log::warn!("⚠️ Failed to play low beep: {} (Hit instance limit - max 4 per sound)", e),
                 }
             }
-            *self.low_beep_clicked_flag.borrow_mut() = false;
         }
         
         // Check for spam test button (plays 5 sounds rapidly to test voice limiting)
-        if *self.spam_test_clicked_flag.borrow() {
+        if clicked.contains(&BUTTON_SPAM_TEST) {
             if let Some(audio) = &mut self.audio {
                 let (active, max) = audio.get_voice_stats();
                 log::info!("🎵🎵🎵 SPAM TEST - Playing 5 beeps rapidly! Voice stats: {}/{}", active, max);
@@ -2469,7 +2346,6 @@ impl DynamicTeapotApp {
                 let (active_after, max) = audio.get_voice_stats();
                 log::info!("🎵 After spam test - voice stats: {}/{} voices active", active_after, max);
             }
-            *self.spam_test_clicked_flag.borrow_mut() = false;
         }
         
         // Handle volume control buttons
@@ -2477,64 +2353,58 @@ impl DynamicTeapotApp {
             const VOLUME_STEP: f32 = 0.1;
             
             // Master volume controls
-            if *self.master_vol_up_flag.borrow() {
+            if clicked.contains(&BUTTON_MASTER_VOL_UP) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::Master);
                 let new_volume = (current + VOLUME_STEP).min(1.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::Master, new_volume);
                 self.ui_manager.update_text(self.master_vol_label_id, format!("Master: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 Master volume: {}%", (new_volume * 100.0) as u32);
-                *self.master_vol_up_flag.borrow_mut() = false;
             }
-            if *self.master_vol_down_flag.borrow() {
+            if clicked.contains(&BUTTON_MASTER_VOL_DOWN) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::Master);
                 let new_volume = (current - VOLUME_STEP).max(0.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::Master, new_volume);
                 self.ui_manager.update_text(self.master_vol_label_id, format!("Master: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 Master volume: {}%", (new_volume * 100.0) as u32);
-                *self.master_vol_down_flag.borrow_mut() = false;
             }
             
             // SFX volume controls
-            if *self.sfx_vol_up_flag.borrow() {
+            if clicked.contains(&BUTTON_SFX_VOL_UP) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::SFX);
                 let new_volume = (current + VOLUME_STEP).min(1.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::SFX, new_volume);
                 self.ui_manager.update_text(self.sfx_vol_label_id, format!("SFX: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 SFX volume: {}%", (new_volume * 100.0) as u32);
-                *self.sfx_vol_up_flag.borrow_mut() = false;
             }
-            if *self.sfx_vol_down_flag.borrow() {
+            if clicked.contains(&BUTTON_SFX_VOL_DOWN) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::SFX);
                 let new_volume = (current - VOLUME_STEP).max(0.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::SFX, new_volume);
                 self.ui_manager.update_text(self.sfx_vol_label_id, format!("SFX: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 SFX volume: {}%", (new_volume * 100.0) as u32);
-                *self.sfx_vol_down_flag.borrow_mut() = false;
             }
             
             // Music volume controls
-            if *self.music_vol_up_flag.borrow() {
+            if clicked.contains(&BUTTON_MUSIC_VOL_UP) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::Music);
                 let new_volume = (current + VOLUME_STEP).min(1.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::Music, new_volume);
                 self.ui_manager.update_text(self.music_vol_label_id, format!("Music: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 Music volume: {}%", (new_volume * 100.0) as u32);
-                *self.music_vol_up_flag.borrow_mut() = false;
             }
-            if *self.music_vol_down_flag.borrow() {
+            if clicked.contains(&BUTTON_MUSIC_VOL_DOWN) {
                 let current = audio.get_group_volume(rust_engine::audio::mixer::VolumeGroup::Music);
                 let new_volume = (current - VOLUME_STEP).max(0.0);
                 audio.set_group_volume(rust_engine::audio::mixer::VolumeGroup::Music, new_volume);
                 self.ui_manager.update_text(self.music_vol_label_id, format!("Music: {}%", (new_volume * 100.0) as u32));
                 log::info!("🔊 Music volume: {}%", (new_volume * 100.0) as u32);
-                *self.music_vol_down_flag.borrow_mut() = false;
             }
         }
         
         // Handle music control buttons
         if let Some(audio) = &mut self.audio {
             // Start/Stop toggle button
-            if *self.play_music_flag.borrow() {
+            if clicked.contains(&BUTTON_PLAY_MUSIC) {
                 if self.music_is_playing {
                     // Stop music
                     match audio.stop_music(Some(0.5)) {
@@ -2564,11 +2434,10 @@ impl DynamicTeapotApp {
                         }
                     }
                 }
-                *self.play_music_flag.borrow_mut() = false;
             }
             
             // Pause/Resume music
-            if *self.pause_music_flag.borrow() {
+            if clicked.contains(&BUTTON_PAUSE_MUSIC) {
                 if audio.is_music_playing() {
                     match audio.pause_music() {
                         Ok(_) => {
@@ -2587,11 +2456,10 @@ impl DynamicTeapotApp {
                         Err(e) => log::warn!("⚠️ Failed to resume music: {}", e),
                     }
                 }
-                *self.pause_music_flag.borrow_mut() = false;
             }
             
             // Loop toggle button
-            if *self.stop_music_flag.borrow() {
+            if clicked.contains(&BUTTON_STOP_MUSIC) {
                 self.music_loop_enabled = !self.music_loop_enabled;
                 
                 // Update button text to reflect new state
@@ -2606,7 +2474,6 @@ impl DynamicTeapotApp {
                 }
                 
                 log::info!("🔁 Music loop: {}", if self.music_loop_enabled { "ON" } else { "OFF" });
-                *self.stop_music_flag.borrow_mut() = false;
             }
             
             // Update music status label based on current state
@@ -2663,7 +2530,7 @@ impl DynamicTeapotApp {
                 
                 // Count teapot entities using ECS query
                 use rust_engine::render::systems::dynamic::MeshType;
-                let teapot_count = self.world.query::<RenderableComponent>()
+                let teapot_count = self.world.query::<&RenderableComponent>()
                     .iter()
                     .filter(|(_, renderable)| matches!(renderable.mesh_type, MeshType::Teapot))
                     .count();
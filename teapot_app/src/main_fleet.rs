@@ -5,14 +5,15 @@
 use rust_engine::assets::{ObjLoader, MaterialBuilder};
 use rust_engine::render::resources::materials::{Material, MaterialType, UnlitMaterialParams};
 use rust_engine::ecs::{
-    World, Entity, LightFactory, LightingSystem as EcsLightingSystem,
+    World, Entity, LightFactory, LightingSystem as EcsLightingSystem, SystemScheduler,
 };
 use rust_engine::ecs::components::{TransformComponent, LightComponent, RenderableComponent};
-use rust_engine::ecs::systems::TrailSystem;
+use rust_engine::ecs::systems::{TrailSystem, TransformPropagationSystem};
 use rust_engine::scene::SceneManager;
 use rust_engine::render::{
     Camera,
     Mesh,
+    MultiLightEnvironment,
     GraphicsEngine,
     VulkanRendererConfig,
     WindowHandle,
@@ -76,7 +77,7 @@ pub struct DynamicTeapotApp {
     camera: Camera,
     scene_manager: SceneManager,
     world: World,
-    lighting_system: EcsLightingSystem,
+    scheduler: SystemScheduler, // Transform propagation and lighting
     trail_system: TrailSystem,
     
     frigate_mesh: Option<Mesh>,
@@ -179,9 +180,11 @@ impl DynamicTeapotApp {
         // Initialize SceneManager
         let scene_manager = SceneManager::new();
         
-        // Initialize ECS for lighting
+        // Initialize ECS
         let mut world = World::new();
-        let lighting_system = EcsLightingSystem::new();
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.add_system(EcsLightingSystem::new().with_ambient(Vec3::new(0.15, 0.12, 0.18), 0.1));
         
         // Create dim yellow sunlight in front of frigates, pointing down and back
         let _sunlight_entity = Some(Self::create_sunlight(&mut world));
@@ -240,7 +243,7 @@ impl DynamicTeapotApp {
             camera,
             scene_manager,
             world,
-            lighting_system,
+            scheduler,
             trail_system: TrailSystem::new(),
             frigate_mesh: None,
             spaceship_mesh: None,
@@ -1504,12 +1507,19 @@ impl DynamicTeapotApp {
         
         // Sync all entity transforms to scene manager for rendering (always run, even when paused)
         // This ensures skybox and any other entities stay properly synced
+        self.scheduler.execute_frame(&mut self.world, delta_time); // Ends the ECS frame
         self.scene_manager.sync_from_world(&mut self.world);
     }
     
     fn render_frame(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Update FPS text
-        let fps_text = format!("FPS: {:.1}", self.current_fps);
+        // Update FPS text (with frustum culling counts from the previous frame)
+        let scene_stats = self.scene_manager.stats();
+        let fps_text = format!(
+            "FPS: {:.1}  Visible: {}/{}",
+            self.current_fps,
+            scene_stats.visible,
+            scene_stats.total,
+        );
         self.ui_manager.update_text(self.fps_label_id, fps_text);
         
         // Update UI state
@@ -1550,18 +1560,18 @@ impl DynamicTeapotApp {
         
         // CRITICAL: Sync entities to render queue and allocate/update GPU handles
         // This must be called before render_frame() to ensure entities have GPU resources
+        // Objects outside the camera frustum are culled from the queue
+        self.scene_manager.set_camera(&self.camera);
         let render_queue = self.scene_manager.build_render_queue();
         self.graphics_engine.render_entities_from_queue(&render_queue)?;
         
         // Generate billboard trail quads
         let billboard_quads = self.trail_system.generate_billboard_quads(&self.world).clone();
         
-        // Build multi-light environment from entity system
-        let multi_light_env = self.lighting_system.build_multi_light_environment(
-            &self.world,
-            Vec3::new(0.15, 0.12, 0.18),
-            0.1
-        ).clone();
+        // Multi-light environment gathered by the scheduler's lighting system
+        let multi_light_env = self.world.resource::<MultiLightEnvironment>()
+            .cloned()
+            .unwrap_or_else(MultiLightEnvironment::new);
         
         // Export UI data for rendering
         let ui_data = self.ui_manager.get_render_data();
@@ -1577,9 +1587,6 @@ impl DynamicTeapotApp {
             &mut self.window,
         )?;
         
-        // Dispatch UI events
-        self.ui_manager.dispatch_events();
-        
         Ok(())
     }
 }
@@ -7,16 +7,16 @@
 //! - Real-time octree subdivision based on entity density
 
 use rust_engine::spatial::{Octree, OctreeConfig, OctreeSpatialQuery};
-use rust_engine::physics::{CollisionShape, CollisionLayers};
+use rust_engine::physics::{CollisionShape, CollisionLayers, PhysicsSystem};
 use rust_engine::scene::{AABB, SceneManager};
 use rust_engine::assets::ObjLoader;
-use rust_engine::ecs::{World, Entity, LightingSystem as EcsLightingSystem};
-use rust_engine::ecs::components::{TransformComponent, PickableComponent, SelectionComponent, ColliderComponent, CollisionStateComponent};
-use rust_engine::ecs::systems::{PickingSystem, EcsCollisionSystem};
+use rust_engine::ecs::{World, Entity, LightingSystem as EcsLightingSystem, SystemScheduler};
+use rust_engine::ecs::components::{TransformComponent, PickableComponent, SelectionComponent, ColliderComponent, CollisionStateComponent, RigidBodyComponent};
+use rust_engine::ecs::systems::{PickingSystem, EcsCollisionSystem, TransformPropagationSystem};
 use rust_engine::render::{
     Camera, Mesh, GraphicsEngine, VulkanRendererConfig,
     WindowHandle, systems::dynamic::MeshType, Vertex, RenderFrameData,
-    FontAtlas,
+    FontAtlas, MultiLightEnvironment,
 };
 use rust_engine::render::resources::materials::{Material, UnlitMaterialParams, StandardMaterialParams};
 use rust_engine::foundation::math::Vec3;
@@ -53,7 +53,6 @@ enum CollisionShapeType {
 
 struct Ship {
     entity: Entity,
-    velocity: Vec3,
     size: f32,  // Visual scale
     collision_radius: f32,  // Actual collision bounding sphere radius
     original_color: Vec3,  // Store original color to restore after collision
@@ -67,10 +66,12 @@ pub struct OctreeVisualizationApp {
     camera: Camera,
     scene_manager: SceneManager,
     world: World,
-    lighting_system: EcsLightingSystem,
     
-    // ECS Collision system (owns the octree)
-    ecs_collision_system: EcsCollisionSystem,
+    // Collision (owns the octree), transform propagation and lighting
+    scheduler: SystemScheduler,
+    
+    // Rigid body dynamics (ships bounce off each other)
+    physics: PhysicsSystem,
     
     // Ships
     ships: Vec<Ship>,
@@ -121,6 +122,7 @@ pub struct OctreeVisualizationApp {
     // UI
     ui_manager: rust_engine::ui::UIManager,
     fps_label_id: rust_engine::ui::UINodeId,
+    octree_stats_label_id: rust_engine::ui::UINodeId,
     
     // Time
     start_time: Instant,
@@ -253,9 +255,8 @@ impl OctreeVisualizationApp {
         // Create ECS world and scene manager
         let world = World::new();
         let scene_manager = SceneManager::new();
-        let lighting_system = EcsLightingSystem::new();
         
-        // Create octree with fixed bounds
+        // Create octree with initial bounds (the root grows if ships leave them)
         let octree_bounds = AABB::new(
             Vec3::new(-OCTREE_SIZE / 2.0, -OCTREE_SIZE / 2.0, -OCTREE_SIZE / 2.0),
             Vec3::new(OCTREE_SIZE / 2.0, OCTREE_SIZE / 2.0, OCTREE_SIZE / 2.0),
@@ -273,6 +274,11 @@ impl OctreeVisualizationApp {
         let mut ecs_collision_system = EcsCollisionSystem::new(octree_query);
         ecs_collision_system.enable_debug(true); // Enable debug visualization
         
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_exclusive_system(ecs_collision_system);
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.add_system(EcsLightingSystem::new().with_ambient(Vec3::new(0.1, 0.1, 0.1), 0.1));
+        
         // Create wireframe cube mesh for octree visualization
         let wireframe_cube_mesh = Self::create_wireframe_cube();
         
@@ -309,6 +315,23 @@ impl OctreeVisualizationApp {
         };
         let fps_label_id = ui_manager.add_text(fps_text);
         
+        // Octree occupancy stats below the FPS counter
+        let octree_stats_text = UIText {
+            element: UIElement {
+                position: (10.0, 60.0),
+                size: (0.0, 0.0),
+                anchor: Anchor::TopLeft,
+                visible: true,
+                z_order: 1,
+            },
+            text: String::new(),
+            font_size: 18.0,
+            color: Vec4::new(0.8, 0.9, 1.0, 1.0),
+            h_align: HorizontalAlign::Left,
+            v_align: VerticalAlign::Top,
+        };
+        let octree_stats_label_id = ui_manager.add_text(octree_stats_text);
+        
         // Initialize picking system
         let (window_width, window_height) = window.get_size();
         let mut picking_system = PickingSystem::new(window_width as u32, window_height as u32);
@@ -324,8 +347,8 @@ impl OctreeVisualizationApp {
             camera,
             scene_manager,
             world,
-            lighting_system,
-            ecs_collision_system,
+            scheduler,
+            physics: PhysicsSystem::new(),
             ships: Vec::new(),
             small_ship_mesh: None,
             large_ship_mesh: None,
@@ -355,6 +378,7 @@ impl OctreeVisualizationApp {
             current_fps: 60.0,
             ui_manager,
             fps_label_id,
+            octree_stats_label_id,
             start_time: now,
         })
     }
@@ -579,11 +603,11 @@ impl OctreeVisualizationApp {
         self.world.add_component(entity, CollisionStateComponent::default());
         
         // Register with ECS collision system
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        self.world.add_component(entity, Self::ship_body(velocity, collision_radius));
         
         self.ships.push(Ship {
             entity,
-            velocity,
             size: SMALL_SHIP_SIZE,
             collision_radius,
             original_color: color,
@@ -645,11 +669,11 @@ impl OctreeVisualizationApp {
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        self.world.add_component(entity, Self::ship_body(velocity, SMALL_SHIP_SIZE));
         
         self.ships.push(Ship {
             entity,
-            velocity,
             size: SMALL_SHIP_SIZE,
             collision_radius: SMALL_SHIP_SIZE,
             original_color: color,
@@ -722,11 +746,11 @@ impl OctreeVisualizationApp {
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        self.world.add_component(entity, Self::ship_body(velocity, collision_radius));
         
         self.ships.push(Ship {
             entity,
-            velocity,
             size: LARGE_SHIP_SIZE,
             collision_radius,
             original_color: color,
@@ -788,11 +812,11 @@ impl OctreeVisualizationApp {
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        self.world.add_component(entity, Self::ship_body(velocity, LARGE_SHIP_SIZE));
         
         self.ships.push(Ship {
             entity,
-            velocity,
             size: LARGE_SHIP_SIZE,
             collision_radius: LARGE_SHIP_SIZE,
             original_color: color,
@@ -866,11 +890,11 @@ impl OctreeVisualizationApp {
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        self.world.add_component(entity, Self::ship_body(velocity, collision_radius));
         
         self.ships.push(Ship {
             entity,
-            velocity,
             size: MONKEY_SIZE,
             collision_radius,
             original_color: color,
@@ -879,6 +903,16 @@ impl OctreeVisualizationApp {
         });
     }
     
+    /// Rigid body for a ship: mass grows with volume, rotation follows velocity instead of contacts
+    fn ship_body(velocity: Vec3, collision_radius: f32) -> RigidBodyComponent {
+        RigidBodyComponent::dynamic(collision_radius.powi(3))
+            .with_sphere_inertia(collision_radius)
+            .with_restitution(0.8)
+            .with_friction(0.2)
+            .with_velocity(velocity)
+            .with_locked_rotation()
+    }
+    
     /// Calculate rotation quaternion to make ship point in direction of velocity
     /// Assumes ship model's forward direction is +Z axis
     fn rotation_from_velocity(velocity: Vec3) -> rust_engine::foundation::math::Quat {
@@ -985,33 +1019,39 @@ impl OctreeVisualizationApp {
         
         // Only update simulation if not paused
         if !self.paused {
-            // Update ship positions
+            // Integrate ships and resolve last frame's contacts (bounce off each other)
+            self.physics.step(&mut self.world, collision(&self.scheduler).collision_system(), delta_time);
+            
+            // Bounce off walls
             let half_bounds = OCTREE_SIZE / 2.0;
             
-            for ship in &mut self.ships {
+            for ship in &self.ships {
+                let mut velocity = match self.world.get_component::<RigidBodyComponent>(ship.entity) {
+                    Some(body) => body.linear_velocity,
+                    None => continue,
+                };
+                
                 if let Some(transform) = self.world.get_component_mut::<TransformComponent>(ship.entity) {
-                    let mut pos = transform.position;
-                    pos += ship.velocity * delta_time;
-                    
-                    // Bounce off walls
+                    let pos = &mut transform.position;
                     if pos.x < -half_bounds || pos.x > half_bounds {
-                        ship.velocity.x = -ship.velocity.x;
+                        velocity.x = -velocity.x;
                         pos.x = pos.x.clamp(-half_bounds, half_bounds);
                     }
                     if pos.y < -half_bounds || pos.y > half_bounds {
-                        ship.velocity.y = -ship.velocity.y;
+                        velocity.y = -velocity.y;
                         pos.y = pos.y.clamp(-half_bounds, half_bounds);
                     }
                     if pos.z < -half_bounds || pos.z > half_bounds {
-                        ship.velocity.z = -ship.velocity.z;
+                        velocity.z = -velocity.z;
                         pos.z = pos.z.clamp(-half_bounds, half_bounds);
                     }
                     
-                    transform.position = pos;
                     // Update rotation to point in direction of travel
-                    transform.rotation = Self::rotation_from_velocity(ship.velocity);
-                    
-                    // Collision shape automatically uses new transform - no manual update needed!
+                    transform.rotation = Self::rotation_from_velocity(velocity);
+                }
+                
+                if let Some(body) = self.world.get_component_mut::<RigidBodyComponent>(ship.entity) {
+                    body.linear_velocity = velocity;
                 }
             }
             
@@ -1020,9 +1060,9 @@ impl OctreeVisualizationApp {
             self.rebuild_octree();
         }
         
-        // Use new ECS collision system (replaces old detect_and_handle_collisions)
+        // Run collision detection, transform propagation and lighting; ends the ECS frame
         let delta_time = 1.0 / 60.0; // Approximate frame time
-        self.ecs_collision_system.update(&mut self.world, delta_time);
+        self.scheduler.execute_frame(&mut self.world, delta_time);
         
         // Update ship colors based on collision state from ECS collision system
         self.update_ship_colors_from_collision_state();
@@ -1049,7 +1089,7 @@ impl OctreeVisualizationApp {
     
     fn rebuild_octree(&mut self) {
         // Clear collision system's octree via SpatialQuery interface
-        let spatial_query = self.ecs_collision_system.spatial_query_mut();
+        let spatial_query = collision_mut(&mut self.scheduler).spatial_query_mut();
         spatial_query.clear();
         
         // Reinsert all ships with consistent collision radius
@@ -1085,7 +1125,7 @@ impl OctreeVisualizationApp {
         
         // Use picking system's internal update to handle mouse state
         // Downcast to OctreeSpatialQuery to get the octree
-        let spatial_query = self.ecs_collision_system.spatial_query();
+        let spatial_query = collision(&self.scheduler).spatial_query();
         let octree_query = spatial_query.as_any()
             .downcast_ref::<rust_engine::spatial::OctreeSpatialQuery>()
             .expect("SpatialQuery must be OctreeSpatialQuery");
@@ -1120,7 +1160,7 @@ impl OctreeVisualizationApp {
         };
         
         // Get octree
-        let spatial_query = self.ecs_collision_system.spatial_query();
+        let spatial_query = collision(&self.scheduler).spatial_query();
         let octree_query = spatial_query.as_any()
             .downcast_ref::<rust_engine::spatial::OctreeSpatialQuery>()
             .expect("SpatialQuery must be OctreeSpatialQuery");
@@ -1262,13 +1302,13 @@ impl OctreeVisualizationApp {
         let selected = self.picking_system.get_selected();
         
         // Tell collision system which entity to visualize
-        self.ecs_collision_system.set_debug_entity(selected);
+        collision_mut(&mut self.scheduler).set_debug_entity(selected);
     }
     
     /// Render debug collision spheres from the collision system's debug visualizer
     fn render_debug_collision_spheres(&mut self) {
         // Get debug shapes from the collision system's visualizer
-        if let Some(viz) = self.ecs_collision_system.debug_visualizer() {
+        if let Some(viz) = collision(&self.scheduler).debug_visualizer() {
             let shapes = viz.get_shapes();
             
             // Find the broad-phase sphere (if any)
@@ -1414,12 +1454,26 @@ impl OctreeVisualizationApp {
         
         // Get all leaf nodes from collision system's octree
         // Downcast to OctreeSpatialQuery to access octree
-        let spatial_query = self.ecs_collision_system.spatial_query();
+        let spatial_query = collision(&self.scheduler).spatial_query();
         let octree_query = spatial_query.as_any()
             .downcast_ref::<rust_engine::spatial::OctreeSpatialQuery>()
             .expect("SpatialQuery must be OctreeSpatialQuery");
         let octree = octree_query.octree();
-        let leaves = octree.get_all_leaves();
+        let capacity = octree.config().max_entities_per_node.max(1) as f32;
+        let stats = octree.stats();
+        let leaves: Vec<_> = octree.node_stats().into_iter().filter(|node| node.is_leaf).collect();
+        
+        let stats_text = format!(
+            "Octree: {} nodes, {} leaves ({} empty), depth {}, max {} / avg {:.1} per leaf, {} overflow",
+            stats.nodes,
+            stats.leaves,
+            stats.empty_leaves,
+            stats.max_depth,
+            stats.max_leaf_entities,
+            stats.average_leaf_entities,
+            stats.overflow,
+        );
+        self.ui_manager.update_text(self.octree_stats_label_id, stats_text);
         
         // Create visualization cube for each leaf node
         for leaf in leaves {
@@ -1432,11 +1486,13 @@ impl OctreeVisualizationApp {
             // Then * 0.95 to create small gaps between adjacent cubes
             let scale = extents * 0.95;
             
-            // Color based on entity count
-            let color = if leaf.entities.is_empty() {
+            // Color based on occupancy relative to the split threshold
+            let color = if leaf.entities == 0 {
                 Vec3::new(0.0, 0.8, 1.0)  // Cyan for empty
             } else {
-                Vec3::new(1.0, 0.5, 0.0)  // Orange for occupied
+                // Orange for lightly occupied, through to red for full leaves
+                let fill = (leaf.entities as f32 / capacity).min(1.0);
+                Vec3::new(1.0, 0.6 * (1.0 - fill), 0.0)
             };
             
             let material = Material::transparent_unlit(UnlitMaterialParams {
@@ -1482,12 +1538,10 @@ impl OctreeVisualizationApp {
         
         self.graphics_engine.render_entities_from_queue(&render_queue)?;
         
-        // Build lighting environment
-        let multi_light_env = self.lighting_system.build_multi_light_environment(
-            &self.world,
-            Vec3::new(0.1, 0.1, 0.1),
-            0.1,
-        ).clone();
+        // Lighting environment gathered by the scheduler's lighting system
+        let multi_light_env = self.world.resource::<MultiLightEnvironment>()
+            .cloned()
+            .unwrap_or_else(MultiLightEnvironment::new);
         
         // Get UI render data and add selection box if dragging
         let mut ui_data = self.ui_manager.get_render_data();
@@ -1639,6 +1693,16 @@ impl OctreeVisualizationApp {
     }
 }
 
+/// Collision system registered with the scheduler
+fn collision(scheduler: &SystemScheduler) -> &EcsCollisionSystem {
+    scheduler.exclusive_system().expect("collision system is registered")
+}
+
+/// Collision system registered with the scheduler, mutably
+fn collision_mut(scheduler: &mut SystemScheduler) -> &mut EcsCollisionSystem {
+    scheduler.exclusive_system_mut().expect("collision system is registered")
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::Builder::from_default_env()
         .filter_level(log::LevelFilter::Info)
@@ -1654,7 +1718,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
     println!("Watch the octree dynamically subdivide as ships move!");
     println!("  Cyan cubes = empty octree nodes");
-    println!("  Orange cubes = nodes containing ships");
+    println!("  Orange to red cubes = nodes containing ships (red = full)");
     println!();
     
     let app = OctreeVisualizationApp::new()?;
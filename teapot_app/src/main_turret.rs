@@ -6,16 +6,18 @@ use rust_engine::assets::{ObjLoader, MaterialBuilder, ImageData};
 use rust_engine::render::resources::materials::{Material, UnlitMaterialParams};
 use rust_engine::render::TextureType;
 use rust_engine::ecs::{
-    World, Entity, LightFactory, LightingSystem as EcsLightingSystem,
+    World, Entity, EventReader, Events, LightFactory, LightingSystem as EcsLightingSystem, SystemScheduler,
 };
-use rust_engine::ecs::components::{TransformComponent, ColliderComponent, CollisionStateComponent};
-use rust_engine::ecs::systems::EcsCollisionSystem;
-use rust_engine::physics::{CollisionShape, CollisionLayers};
+use rust_engine::ecs::components::{TransformComponent, ColliderComponent, CollisionStateComponent, RigidBodyComponent};
+use rust_engine::ecs::systems::{EcsCollisionSystem, TransformPropagationSystem};
+use rust_engine::physics::{CollisionShape, CollisionLayers, CollisionMatrix, CollisionStarted, PhysicsSystem, Ray};
+use rust_engine::settings::Config;
 use rust_engine::spatial::{Octree, OctreeConfig, OctreeSpatialQuery};
 use rust_engine::scene::{SceneManager, AABB};
 use rust_engine::render::{
     Camera,
     Mesh,
+    MultiLightEnvironment,
     GraphicsEngine,
     VulkanRendererConfig,
     WindowHandle,
@@ -35,8 +37,11 @@ const TURRET_ROTATION_SPEED: f32 = 1.0;   // Radians per second turret rotation
 const FIRE_INTERVAL: f32 = 2.0;            // Seconds between bursts
 const BURST_SIZE: usize = 5;               // Number of shots per burst
 const BURST_SHOT_INTERVAL: f32 = 0.15;     // Seconds between shots in a burst
-const TURRET_ALIGNMENT_THRESHOLD: f32 = 0.05;  // Radians - how aligned turret must be to fire (~2.8 degrees)
-// Barrel mount point relative to base: Vec3::new(0.0, 0.5, -1.2)
+const TURRET_AIM_TOLERANCE: f32 = 0.3;     // Radius of the sphere cast along the barrel when checking aim
+const TURRET_RANGE: f32 = 200.0;           // Maximum distance of the aim check
+const ASTEROID_COUNT: usize = 8;           // Asteroids drifting across the frigate's orbit
+const ASTEROID_RESPAWN_DISTANCE: f32 = 100.0; // Asteroids left this far behind are respawned ahead
+// Barrel is parented to the base; muzzle light is parented to the barrel tip
 
 struct TurretBase {
     entity: Entity,
@@ -81,10 +86,11 @@ pub struct TurretDemoApp {
     camera: Camera,
     scene_manager: SceneManager,
     world: World,
-    lighting_system: EcsLightingSystem,
     
-    // ECS Collision system (owns the octree)
-    ecs_collision_system: EcsCollisionSystem,
+    // Collision (owns the octree), transform propagation and lighting
+    scheduler: SystemScheduler,
+    physics: PhysicsSystem, // Asteroids bounce off each other and the frigate
+    collision_reader: EventReader<CollisionStarted>,
     
     // Meshes
     turret_base_mesh: Option<Mesh>,
@@ -106,6 +112,7 @@ pub struct TurretDemoApp {
     standalone_turret_barrel: Option<Entity>,
     monkey_entity: Option<Entity>,
     targets: Vec<Target>,
+    asteroids: Vec<Entity>,
     projectiles: Vec<Projectile>,
     explosions: Vec<Explosion>,
     sunlight_entity: Option<Entity>,
@@ -200,9 +207,16 @@ impl TurretDemoApp {
         // Initialize SceneManager
         let scene_manager = SceneManager::new();
         
-        // Initialize ECS for lighting
+        // Initialize ECS
         let mut world = World::new();
-        let lighting_system = EcsLightingSystem::new();
+        
+        // Layer interactions are data-driven; fall back to "everything collides"
+        let collision_matrix = CollisionMatrix::load_from_file("resources/config/turret_collision.ron")
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load collision matrix: {}", e);
+                CollisionMatrix::default()
+            });
+        world.insert_resource(collision_matrix);
         
         // Create octree with bounds covering the entire scene
         let octree_bounds = AABB::new(
@@ -219,7 +233,10 @@ impl TurretDemoApp {
         // Create ECS collision system with its own octree
         let octree = Octree::new(octree_bounds, octree_config);
         let octree_query = Box::new(OctreeSpatialQuery::new(octree));
-        let ecs_collision_system = EcsCollisionSystem::new(octree_query);
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_exclusive_system(EcsCollisionSystem::new(octree_query));
+        scheduler.add_system(TransformPropagationSystem::new());
+        scheduler.add_system(EcsLightingSystem::new()); // No ambient light in space
         
         // Create sunlight
         let sunlight_entity = Some(Self::create_sunlight(&mut world));
@@ -275,8 +292,8 @@ impl TurretDemoApp {
             camera,
             scene_manager,
             world,
-            lighting_system,
-            ecs_collision_system,
+            scheduler,
+            physics: PhysicsSystem::new(),
             turret_base_mesh: None,
             turret_barrel_mesh: None,
             sphere_mesh: None,
@@ -291,7 +308,9 @@ impl TurretDemoApp {
             standalone_turret_base: None,
             standalone_turret_barrel: None,
             monkey_entity: None,
+            collision_reader: EventReader::new(),
             targets: Vec::new(),
+            asteroids: Vec::new(),
             projectiles: Vec::new(),
             explosions: Vec::new(),
             sunlight_entity,
@@ -679,6 +698,11 @@ impl TurretDemoApp {
         // Spawn some targets
         self.spawn_targets()?;
         
+        // Spawn asteroids in the frigate's path
+        for _ in 0..ASTEROID_COUNT {
+            self.spawn_asteroid();
+        }
+        
         Ok(())
     }
 
@@ -789,14 +813,33 @@ impl TurretDemoApp {
             scale: Vec3::new(3.0, 3.0, 3.0),
         };
         
+        let frigate_mesh = self.frigate_mesh.as_ref().unwrap().clone();
+        let frigate_scale = frigate_transform.scale.x;
         let frigate_entity = self.scene_manager.create_renderable_entity(
             &mut self.world,
-            self.frigate_mesh.as_ref().unwrap().clone(),
+            frigate_mesh.clone(),
             MeshType::Frigate,
             frigate_material,
             frigate_transform
         );
         
+        // Convex hull around the MODEL SPACE vertices; the transform scales it
+        let model_vertices: Vec<Vec3> = frigate_mesh.vertices.iter()
+            .map(|v| Vec3::new(v.position[0], v.position[1], v.position[2]))
+            .collect();
+        let hull_shape = CollisionShape::convex_hull(&model_vertices)
+            .map_err(|e| format!("Failed to build frigate hull: {}", e))?;
+        let mut collider = ColliderComponent::new(hull_shape)
+            .with_layer(CollisionLayers::ENVIRONMENT);
+        collider.bounding_radius *= frigate_scale; // Broad phase expects world-space radius
+        
+        self.world.add_component(frigate_entity, collider.clone());
+        self.world.add_component(frigate_entity, CollisionStateComponent::default());
+        collision_mut(&mut self.scheduler).register_collider(frigate_entity, &collider, &self.world);
+        
+        // Scripted orbit: pushes asteroids aside but is not pushed back
+        self.world.add_component(frigate_entity, RigidBodyComponent::kinematic());
+        
         self.frigate_entity = Some(frigate_entity);
         log::info!("Spawned frigate in orbit");
         Ok(())
@@ -849,15 +892,9 @@ impl TurretDemoApp {
         });
         
         // Create turret barrel entity as child
-        // Barrel is positioned relative to base (slightly up and forward)
-        let barrel_local_position = Vec3::new(0.0, 0.5, -1.2);  // Keep in sync with update_scene!
-        let barrel_world_position = base_position + frigate_rotation * barrel_local_position;
-        
-        let barrel_transform = Transform {
-            position: barrel_world_position,
-            rotation: frigate_rotation,  // Use frigate's rotation instead of identity
-            scale: Vec3::new(1.0, 1.0, 1.0),
-        };
+        // Barrel transform is local to the base (slightly up and forward)
+        let barrel_local_position = Vec3::new(0.0, 0.5, -1.2);
+        let barrel_transform = Transform::from_position(barrel_local_position);
         
         let turret_barrel_material = MaterialBuilder::new()
             .base_color_rgb(0.3, 0.3, 0.35)
@@ -873,13 +910,17 @@ impl TurretDemoApp {
             turret_barrel_material,
             barrel_transform
         );
+        self.world.set_parent(barrel_entity, base_entity)?;
         
-        // Create muzzle flash light at barrel tip
+        // Create muzzle flash light at barrel tip (child of the barrel)
         let muzzle_light_entity = self.world.create_entity();
         let barrel_tip_offset = Vec3::new(0.0, 0.0, -2.0); // Barrel tip in local space
-        let muzzle_light_pos = barrel_world_position + (frigate_rotation * barrel_tip_offset);
+        self.world.add_component(muzzle_light_entity, TransformComponent::from_position(barrel_tip_offset));
+        self.world.set_parent(muzzle_light_entity, barrel_entity)?;
+        let muzzle_light_pos = self.world.world_transform(muzzle_light_entity)
+            .map(|t| t.position)
+            .unwrap_or(base_position);
         
-        self.world.add_component(muzzle_light_entity, TransformComponent::from_position(muzzle_light_pos));
         self.world.add_component(muzzle_light_entity, LightFactory::point(
             muzzle_light_pos,
             Vec3::new(1.0, 0.7, 0.3), // Orange light
@@ -972,11 +1013,11 @@ impl TurretDemoApp {
             // Target sphere has scale 0.5, so radius is 0.5
             let sphere_shape = CollisionShape::sphere(0.5);
             let collider = ColliderComponent::new(sphere_shape)
-                .with_layers(CollisionLayers::ENEMY, CollisionLayers::PROJECTILE);
+                .with_layer(CollisionLayers::ENEMY);
             
             self.world.add_component(entity, collider.clone());
             self.world.add_component(entity, CollisionStateComponent::default());
-            self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+            collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
             
             self.targets.push(Target {
                 entity,
@@ -1052,11 +1093,11 @@ impl TurretDemoApp {
         // Add collision components
         let sphere_shape = CollisionShape::sphere(0.5);
         let collider = ColliderComponent::new(sphere_shape)
-            .with_layers(CollisionLayers::ENEMY, CollisionLayers::PROJECTILE);
+            .with_layer(CollisionLayers::ENEMY);
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
         
         self.targets.push(Target {
             entity,
@@ -1067,6 +1108,93 @@ impl TurretDemoApp {
         log::info!("Spawned replacement target, total targets: {}", self.targets.len());
     }
     
+    /// Random position ahead of the frigate on its orbit and a slow drift velocity
+    fn asteroid_spawn_state(&self, rng: &mut impl rand::Rng) -> (Vec3, Vec3) {
+        let angle = self.orbit_angle + rng.gen_range(0.3..0.5); // 50-80 units ahead, clear of the hull
+        let radius = self.orbit_radius + rng.gen_range(-6.0..6.0);
+        let position = Vec3::new(
+            radius * angle.cos(),
+            rng.gen_range(-4.0..4.0),
+            radius * angle.sin(),
+        );
+        let velocity = Vec3::new(
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-0.3..0.3),
+            rng.gen_range(-1.0..1.0),
+        ).normalize() * rng.gen_range(1.0..3.0);
+        (position, velocity)
+    }
+    
+    fn spawn_asteroid(&mut self) {
+        use rand::Rng;
+        use rust_engine::foundation::math::Transform;
+        let mut rng = rand::thread_rng();
+        
+        let (position, velocity) = self.asteroid_spawn_state(&mut rng);
+        let radius = rng.gen_range(0.8..2.0);
+        let grey = rng.gen_range(0.3..0.5);
+        
+        let asteroid_material = MaterialBuilder::new()
+            .base_color_rgb(grey, grey * 0.95, grey * 0.9)
+            .metallic(0.0)
+            .roughness(0.9)
+            .name("Asteroid Instance")
+            .build();
+        
+        // Sphere mesh has radius 1, so the scale is the asteroid radius
+        let transform = Transform {
+            position,
+            rotation: Quat::identity(),
+            scale: Vec3::new(radius, radius, radius),
+        };
+        
+        let entity = self.scene_manager.create_renderable_entity(
+            &mut self.world,
+            self.sphere_mesh.as_ref().unwrap().clone(),
+            MeshType::Sphere,
+            asteroid_material,
+            transform
+        );
+        
+        let collider = ColliderComponent::new(CollisionShape::sphere(radius))
+            .with_layer(CollisionLayers::DEBRIS);
+        self.world.add_component(entity, collider.clone());
+        self.world.add_component(entity, CollisionStateComponent::default());
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
+        
+        // Rock density: mass grows with volume
+        self.world.add_component(entity, RigidBodyComponent::dynamic(radius.powi(3))
+            .with_sphere_inertia(radius)
+            .with_restitution(0.6)
+            .with_friction(0.4)
+            .with_velocity(velocity)
+            .with_angular_velocity(velocity.cross(&Vec3::y()) * 0.2));
+        
+        self.asteroids.push(entity);
+    }
+    
+    /// Move asteroids the frigate has left behind back into its path
+    fn recycle_asteroids(&mut self, frigate_position: Vec3) {
+        let mut rng = rand::thread_rng();
+        for i in 0..self.asteroids.len() {
+            let entity = self.asteroids[i];
+            let far_behind = self.world.get_component::<TransformComponent>(entity)
+                .is_some_and(|transform| (transform.position - frigate_position).magnitude() > ASTEROID_RESPAWN_DISTANCE);
+            if !far_behind {
+                continue;
+            }
+            
+            let (position, velocity) = self.asteroid_spawn_state(&mut rng);
+            if let Some(transform) = self.world.get_component_mut::<TransformComponent>(entity) {
+                transform.position = position;
+            }
+            if let Some(body) = self.world.get_component_mut::<RigidBodyComponent>(entity) {
+                body.linear_velocity = velocity;
+                body.wake_up();
+            }
+        }
+    }
+    
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         log::info!("Starting turret demo...");
         self.initialize()?;
@@ -1213,10 +1341,14 @@ impl TurretDemoApp {
         // Pause check - only affects simulation, not camera
         if self.paused {
             // Still sync entities even when paused for camera-dependent rendering
+            self.scheduler.execute_frame(&mut self.world, 0.0);
             self.scene_manager.sync_from_world(&mut self.world);
             return;
         }
         
+        // Integrate asteroids and resolve last frame's contacts (asteroids and frigate)
+        self.physics.step(&mut self.world, collision(&self.scheduler).collision_system(), delta_time);
+        
         // Restore automatic orbit angle update
         self.orbit_angle += self.orbit_speed * delta_time;
         
@@ -1237,6 +1369,15 @@ impl TurretDemoApp {
                 transform.rotation = frigate_rotation;
             }
             
+            // Orbital velocity lets the solver push asteroids out of the frigate's way
+            if let Some(body) = self.world.get_component_mut::<RigidBodyComponent>(frigate_entity) {
+                let orbit_tangent = Vec3::new(-self.orbit_angle.sin(), 0.0, self.orbit_angle.cos());
+                body.linear_velocity = orbit_tangent * self.orbit_radius * self.orbit_speed;
+                body.angular_velocity = Vec3::new(0.0, -self.orbit_speed, 0.0);
+            }
+            
+            self.recycle_asteroids(frigate_position);
+            
             // Update turret base position to follow frigate (rotation will be updated after targeting)
             let turret_offset_from_frigate = Vec3::new(0.0, 4.5, 0.0);  // Moved down 0.5 units
             let turret_base_world_pos = frigate_position + (frigate_rotation * turret_offset_from_frigate);
@@ -1245,7 +1386,19 @@ impl TurretDemoApp {
                 turret_base.position = turret_base_world_pos;
             }
         }
-        
+
+        // Track the enemy closest to the turret
+        if let Some(turret_base) = self.turret_base.as_ref() {
+            let nearest = collision(&self.scheduler)
+                .collision_system()
+                .nearest(turret_base.position, 1, CollisionLayers::ENEMY);
+            if let Some(index) = nearest.first().and_then(|&(entity, _)| {
+                self.targets.iter().position(|target| target.entity == entity)
+            }) {
+                self.current_target_index = index;
+            }
+        }
+
         // Update targets (linear motion)
         use rand::Rng;
         let mut rng = rand::thread_rng();
@@ -1386,7 +1539,7 @@ impl TurretDemoApp {
         
         // Update barrel recoil and position
         if let Some(turret_barrel) = self.turret_barrel.as_mut() {
-            if let Some(turret_base) = self.turret_base.as_ref() {
+            if self.turret_base.is_some() {
                 // Animate muzzle flash (on during fire, off otherwise)
                 if turret_barrel.flicker_timer > 0.0 {
                     turret_barrel.flicker_timer -= delta_time;
@@ -1403,20 +1556,8 @@ impl TurretDemoApp {
                     turret_barrel.recoil_offset = turret_barrel.recoil_offset.max(0.0);
                 }
                 
-                // Get frigate rotation first to avoid borrowing conflicts
-                let frigate_rotation = if let Some(frigate_entity) = self.frigate_entity {
-                    self.world.get_component::<TransformComponent>(frigate_entity)
-                        .map(|t| t.rotation)
-                        .unwrap_or(Quat::identity())
-                } else {
-                    Quat::identity()
-                };
-                
-                // Calculate barrel world position with pitch rotation around pivot point
-                // Combine frigate rotation with turret targeting rotation
-                let turret_targeting_rotation = Quat::from_euler_angles(0.0, turret_base.rotation_y, 0.0);
-                let base_rotation = frigate_rotation * turret_targeting_rotation;
-                
+                // Barrel transform is local to the base; propagation applies the
+                // base's frigate + targeting rotation and position
                 // Pivot point is inside the turret base where barrel mounts
                 let barrel_pivot_local = Vec3::new(0.0, 0.5, -0.5);
                 
@@ -1432,31 +1573,15 @@ impl TurretDemoApp {
                 let recoil_vector = barrel_pitch_rotation * Vec3::new(0.0, 0.0, turret_barrel.recoil_offset);
                 let barrel_offset_with_recoil = barrel_offset_pitched + recoil_vector;
                 
-                // Combine barrel position: pivot + pitched barrel offset
+                // Barrel position relative to base: pivot + pitched barrel offset
                 let barrel_local_total = barrel_pivot_local + barrel_offset_with_recoil;
                 
-                // Apply base rotation and add to base world position
-                let rotated_barrel_offset = base_rotation * barrel_local_total;
-                let barrel_world_position = turret_base.position + rotated_barrel_offset;
-                
-                // Barrel rotation is base yaw + barrel pitch
-                let barrel_rotation = base_rotation * barrel_pitch_rotation;
-                
                 if let Some(transform) = self.world.get_component_mut::<TransformComponent>(turret_barrel.entity) {
-                    transform.position = barrel_world_position;
-                    transform.rotation = barrel_rotation;
-                }
-                
-                // Update muzzle light position and intensity to track barrel tip
-                let barrel_tip_local = Vec3::new(0.0, 0.0, -2.0); // Barrel tip in barrel's local space
-                let barrel_tip_world = barrel_world_position + (barrel_rotation * barrel_tip_local);
-                
-                if let Some(light_transform) = self.world.get_component_mut::<TransformComponent>(turret_barrel.muzzle_light) {
-                    light_transform.position = barrel_tip_world;
+                    transform.position = barrel_local_total;
+                    transform.rotation = barrel_pitch_rotation;
                 }
                 
                 if let Some(light_component) = self.world.get_component_mut::<rust_engine::ecs::components::LightComponent>(turret_barrel.muzzle_light) {
-                    light_component.position = barrel_tip_world;
                     light_component.intensity = turret_barrel.muzzle_flash_intensity;
                 }
             }
@@ -1489,23 +1614,18 @@ impl TurretDemoApp {
         // Remove old explosions
         self.explosions.retain(|e| e.age < e.max_age);
         
-        // Run collision detection
-        self.ecs_collision_system.update(&mut self.world, delta_time);
-        
-        // Check for collisions between projectiles and targets
+        // Check for collisions between projectiles and targets (detected by last frame's systems)
         let mut targets_to_remove = Vec::new();
         
-        for (proj_idx, projectile) in self.projectiles.iter().enumerate() {
-            if let Some(collision_state) = self.world.get_component::<CollisionStateComponent>(projectile.entity) {
-                if collision_state.is_colliding() {
-                    // Check which target was hit
-                    for (target_idx, target) in self.targets.iter().enumerate() {
-                        if collision_state.colliding_with.contains(&target.entity) {
-                            log::info!("Projectile hit target!");
-                            targets_to_remove.push(target_idx);
-                            projectiles_to_remove.push(proj_idx);
-                            break;
-                        }
+        if let Some(events) = self.world.resource::<Events<CollisionStarted>>() {
+            for started in self.collision_reader.read(events) {
+                // Check whether a projectile hit a target
+                for (proj_idx, projectile) in self.projectiles.iter().enumerate() {
+                    let Some(other) = started.other(projectile.entity) else { continue };
+                    if let Some(target_idx) = self.targets.iter().position(|target| target.entity == other) {
+                        log::info!("Projectile hit target!");
+                        targets_to_remove.push(target_idx);
+                        projectiles_to_remove.push(proj_idx);
                     }
                 }
             }
@@ -1518,7 +1638,7 @@ impl TurretDemoApp {
         for &idx in targets_to_remove.iter().rev() {
             let target = self.targets.remove(idx);
             // Unregister from collision system
-            self.ecs_collision_system.unregister_collider(target.entity);
+            collision_mut(&mut self.scheduler).unregister_collider(target.entity);
             // Despawn entity
             self.scene_manager.destroy_entity(&mut self.world, &mut self.graphics_engine, target.entity);
         }
@@ -1533,9 +1653,9 @@ impl TurretDemoApp {
         for &idx in projectiles_to_remove.iter().rev() {
             let projectile = self.projectiles.remove(idx);
             // Unregister from collision system
-            self.ecs_collision_system.unregister_collider(projectile.entity);
-            // Remove transform component to mark as inactive (entity cleanup will happen later)
-            self.world.remove_component::<TransformComponent>(projectile.entity);
+            collision_mut(&mut self.scheduler).unregister_collider(projectile.entity);
+            // Despawn entity (removes all components and invalidates the handle)
+            self.world.despawn(projectile.entity);
         }
         
         // Burst firing system
@@ -1573,34 +1693,42 @@ impl TurretDemoApp {
             self.ui_manager.update_text(self.fps_label_id, fps_text);
         }
         
+        // Detect collisions, resolve child transforms (barrel on base, muzzle
+        // light on barrel) and gather lights; ends the ECS frame
+        self.scheduler.execute_frame(&mut self.world, delta_time);
+        
         // CRITICAL: Sync entities to scene manager for rendering
         // This updates the render cache from ECS components
         self.scene_manager.sync_from_world(&mut self.world);
     }
     
-    /// Check if turret is aligned with current target within threshold
-    fn is_turret_aligned(&self) -> bool {
-        // No targets means we can't be aligned
-        if self.targets.is_empty() || self.turret_base.is_none() || self.turret_barrel.is_none() {
-            return false;
-        }
+    /// Barrel tip position and firing direction in world space
+    fn barrel_muzzle(&self) -> Option<(Vec3, Vec3)> {
+        // Get barrel world transform (the barrel is a child of the base)
+        let transform = self.world.world_transform(self.turret_barrel.as_ref()?.entity)?;
         
-        let turret_base = self.turret_base.as_ref().unwrap();
-        let turret_barrel = self.turret_barrel.as_ref().unwrap();
+        // Barrel tip in local space (from simple_turret_barrel.obj: furthest point is z=-2.0)
+        let barrel_tip_world = transform.position + (transform.rotation * Vec3::new(0.0, 0.0, -2.0));
         
-        // Check both yaw and pitch alignment
-        let yaw_diff = (turret_base.target_rotation_y - turret_base.rotation_y).abs();
-        let pitch_diff = (turret_barrel.target_pitch - turret_barrel.pitch).abs();
-        
-        // Normalize yaw difference to [-PI, PI] range
-        let yaw_diff_normalized = if yaw_diff > std::f32::consts::PI {
-            2.0 * std::f32::consts::PI - yaw_diff
-        } else {
-            yaw_diff
+        // Firing direction (forward from barrel)
+        let forward = transform.rotation * Vec3::new(0.0, 0.0, -1.0);
+        Some((barrel_tip_world, forward))
+    }
+    
+    /// Check if the barrel's line of fire reaches the current target
+    fn is_turret_aligned(&self) -> bool {
+        let Some(target) = self.targets.get(self.current_target_index) else {
+            return false;
+        };
+        let Some((barrel_tip, forward)) = self.barrel_muzzle() else {
+            return false;
         };
         
-        // Both yaw and pitch must be within threshold
-        yaw_diff_normalized <= TURRET_ALIGNMENT_THRESHOLD && pitch_diff <= TURRET_ALIGNMENT_THRESHOLD
+        // Sweep a small sphere along the barrel; the first enemy it touches must be our target
+        collision(&self.scheduler)
+            .collision_system()
+            .sphere_cast(&self.world, &Ray::new(barrel_tip, forward), TURRET_AIM_TOLERANCE, TURRET_RANGE, CollisionLayers::ENEMY)
+            .is_some_and(|hit| hit.entity == target.entity)
     }
     
     fn fire_turret(&mut self) {
@@ -1609,32 +1737,20 @@ impl TurretDemoApp {
             turret_barrel.is_recoiling = true;
             turret_barrel.flicker_timer = 0.05; // Short flash - 50ms per shot
             turret_barrel.muzzle_flash_intensity = 20.0; // Bright initial flash
+        }
+        
+        if let Some((barrel_tip_world, forward)) = self.barrel_muzzle() {
+            // Spawn projectile from barrel tip
+            self.spawn_projectile(barrel_tip_world, forward);
             
-            // Get barrel transform
-            if let Some(transform) = self.world.get_component::<TransformComponent>(turret_barrel.entity) {
-                let barrel_rotation = transform.rotation;
-                
-                // Barrel tip in local space (from simple_turret_barrel.obj: furthest point is z=-2.0)
-                let barrel_tip_local = Vec3::new(0.0, 0.0, -2.0);
-                
-                // Transform barrel tip to world space
-                let barrel_tip_world = transform.position + (barrel_rotation * barrel_tip_local);
-                
-                // Calculate firing direction (forward from barrel)
-                let forward = barrel_rotation * Vec3::new(0.0, 0.0, -1.0);
-                
-                // Spawn projectile from barrel tip
-                self.spawn_projectile(barrel_tip_world, forward);
-                
-                // Spawn muzzle flash explosion at barrel tip
-                self.explosions.push(Explosion {
-                    position: barrel_tip_world,
-                    age: 0.0,
-                    max_age: 0.3,  // 0.3 second flash
-                });
-                
-                log::info!("Turret fired projectile from barrel tip {:?} in direction {:?}", barrel_tip_world, forward);
-            }
+            // Spawn muzzle flash explosion at barrel tip
+            self.explosions.push(Explosion {
+                position: barrel_tip_world,
+                age: 0.0,
+                max_age: 0.3,  // 0.3 second flash
+            });
+            
+            log::info!("Turret fired projectile from barrel tip {:?} in direction {:?}", barrel_tip_world, forward);
         }
     }
     
@@ -1653,14 +1769,16 @@ impl TurretDemoApp {
         });
         
         // Add collision components for octree tracking
-        // Projectile sphere has radius 0.2
+        // Projectile sphere has radius 0.2; it travels several radii per frame,
+        // so sweep it between frames to avoid tunneling through targets
         let sphere_shape = CollisionShape::sphere(0.2);
         let collider = ColliderComponent::new(sphere_shape)
-            .with_layers(CollisionLayers::PROJECTILE, CollisionLayers::ENEMY);
+            .with_layer(CollisionLayers::PROJECTILE)
+            .with_ccd(true);
         
         self.world.add_component(entity, collider.clone());
         self.world.add_component(entity, CollisionStateComponent::default());
-        self.ecs_collision_system.register_collider(entity, &collider, &self.world);
+        collision_mut(&mut self.scheduler).register_collider(entity, &collider, &self.world);
         
         // Track projectile
         self.projectiles.push(Projectile {
@@ -1675,13 +1793,6 @@ impl TurretDemoApp {
         let render_queue = self.scene_manager.build_render_queue();
         self.graphics_engine.render_entities_from_queue(&render_queue)?;
         
-        // Build lighting environment
-        let multi_light_env = self.lighting_system.build_multi_light_environment(
-            &self.world,
-            Vec3::new(0.0, 0.0, 0.0),  // No ambient light in space
-            0.0
-        ).clone();
-        
         // Generate billboard quads for projectiles using BillboardBullet pool
         let mut bullet_billboards = Vec::new();
         for projectile in &self.projectiles {
@@ -1712,11 +1823,10 @@ impl TurretDemoApp {
         
         // Set camera and lights
         self.graphics_engine.set_camera(&self.camera);
-        let multi_light_env = self.lighting_system.build_multi_light_environment(
-            &self.world,
-            Vec3::new(0.0, 0.0, 0.0),
-            0.0
-        ).clone();
+        // Lighting environment gathered by the scheduler's lighting system
+        let multi_light_env = self.world.resource::<MultiLightEnvironment>()
+            .cloned()
+            .unwrap_or_else(MultiLightEnvironment::new);
         self.graphics_engine.set_multi_light_environment(&multi_light_env);
         
         // Render typed billboards
@@ -1737,9 +1847,6 @@ impl TurretDemoApp {
         // End frame
         self.graphics_engine.end_dynamic_frame(&mut self.window)?;
         
-        // Dispatch UI events
-        self.ui_manager.dispatch_events();
-        
         Ok(())
     }
     
@@ -1768,13 +1875,8 @@ impl TurretDemoApp {
             base_transform
         );
         
-        // Create standalone turret barrel
-        let barrel_position = standalone_position + Vec3::new(0.0, 0.5, -1.2);
-        let barrel_transform = Transform {
-            position: barrel_position,
-            rotation: Quat::identity(),
-            scale: Vec3::new(1.0, 1.0, 1.0),
-        };
+        // Create standalone turret barrel (local to the base)
+        let barrel_transform = Transform::from_position(Vec3::new(0.0, 0.5, -1.2));
         
         let turret_barrel_material = MaterialBuilder::new()
             .base_color_rgb(0.3, 0.3, 0.35)
@@ -1790,6 +1892,7 @@ impl TurretDemoApp {
             turret_barrel_material,
             barrel_transform
         );
+        self.world.set_parent(barrel_entity, base_entity)?;
         
         self.standalone_turret_base = Some(base_entity);
         self.standalone_turret_barrel = Some(barrel_entity);
@@ -1832,6 +1935,16 @@ impl TurretDemoApp {
     }
 }
 
+/// Collision system registered with the scheduler
+fn collision(scheduler: &SystemScheduler) -> &EcsCollisionSystem {
+    scheduler.exclusive_system().expect("collision system is registered")
+}
+
+/// Collision system registered with the scheduler, mutably
+fn collision_mut(scheduler: &mut SystemScheduler) -> &mut EcsCollisionSystem {
+    scheduler.exclusive_system_mut().expect("collision system is registered")
+}
+
 impl Drop for TurretDemoApp {
     fn drop(&mut self) {
         // Cleanup handled by renderer's drop implementation